@@ -7,11 +7,12 @@
 #![no_std]
 
 use core::convert::Infallible;
-use core::ops::Deref;
+use core::ops::{Deref, DerefMut};
 
-use sel4_driver_interfaces::block::GetBlockDeviceLayout;
+use sel4_driver_interfaces::HandleInterrupt;
+use sel4_driver_interfaces::block::{BlockIO, GetBlockDeviceLayout};
 use virtio_drivers::device::blk::{SECTOR_SIZE, VirtIOBlk};
-use virtio_drivers::{Hal, transport::Transport};
+use virtio_drivers::{Error, Hal, transport::Transport};
 
 pub struct GetBlockDeviceLayoutWrapper<T>(pub T);
 
@@ -28,3 +29,46 @@ impl<H: Hal, T: Transport, U: Deref<Target = VirtIOBlk<H, T>>> GetBlockDeviceLay
         Ok(self.0.deref().capacity())
     }
 }
+
+/// Blocking access to a [`VirtIOBlk`], for drivers which do not need to overlap requests.
+pub struct BlockIOWrapper<T>(pub T);
+
+impl<H: Hal, T: Transport, U: Deref<Target = VirtIOBlk<H, T>>> GetBlockDeviceLayout
+    for BlockIOWrapper<U>
+{
+    type Error = Error;
+
+    fn get_block_size(&mut self) -> Result<usize, Self::Error> {
+        Ok(SECTOR_SIZE)
+    }
+
+    fn get_num_blocks(&mut self) -> Result<u64, Self::Error> {
+        Ok(self.0.deref().capacity())
+    }
+}
+
+impl<H: Hal, T: Transport, U: DerefMut<Target = VirtIOBlk<H, T>>> BlockIO for BlockIOWrapper<U> {
+    fn read_blocks(&mut self, start_block: u64, buf: &mut [u8]) -> Result<(), Self::Error> {
+        self.0.deref_mut().read_blocks(block_id(start_block)?, buf)
+    }
+
+    fn write_blocks(&mut self, start_block: u64, buf: &[u8]) -> Result<(), Self::Error> {
+        self.0.deref_mut().write_blocks(block_id(start_block)?, buf)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.0.deref_mut().flush()
+    }
+}
+
+impl<H: Hal, T: Transport, U: DerefMut<Target = VirtIOBlk<H, T>>> HandleInterrupt
+    for BlockIOWrapper<U>
+{
+    fn handle_interrupt(&mut self) {
+        self.0.deref_mut().ack_interrupt();
+    }
+}
+
+fn block_id(start_block: u64) -> Result<usize, Error> {
+    start_block.try_into().map_err(|_| Error::InvalidParam)
+}
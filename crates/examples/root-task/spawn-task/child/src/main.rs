@@ -12,9 +12,9 @@ mod runtime;
 fn main() -> ! {
     sel4::debug_println!("In child task");
 
-    sel4::cap::Notification::from_bits(1).signal();
+    sel4::cap::Notification::from_bits(5).signal();
 
-    sel4::cap::Tcb::from_bits(2).tcb_suspend().unwrap();
+    sel4::cap::Tcb::from_bits(1).tcb_suspend().unwrap();
 
     unreachable!()
 }
@@ -4,15 +4,18 @@
 # SPDX-License-Identifier: BSD-2-Clause
 #
 
-{ mk, localCrates }:
+{ mk, localCrates, versions }:
 
 mk {
   package.name = "lionsos";
   dependencies = {
+    async-unsync = { version = versions.async-unsync; default-features = false; };
     inherit (localCrates)
       lionsos-sys
       sddf
       sddf-sys
+      sel4-abstract-allocator
+      sel4-shared-memory
     ;
   };
 }
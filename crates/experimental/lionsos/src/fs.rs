@@ -0,0 +1,634 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+//! An async client for the LionsOS file system protocol.
+//!
+//! Commands are enqueued on the command queue with buffers (paths and data) staged in the share
+//! region, and complete when the server enqueues a matching completion. [`FsClient::poll`] must be
+//! called whenever the server notifies the client, after which futures whose commands have
+//! completed will be woken. For example, with `sel4-async-single-threaded-executor`, a component's
+//! `notified` handler would call [`FsClient::poll`] and then `LocalPool::run_all_until_stalled`.
+//!
+//! Dropping a command's future before it completes does not cancel the command. Its buffers
+//! remain allocated until the server completes it.
+
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::alloc::Layout;
+use core::cell::RefCell;
+use core::future::Future;
+use core::mem;
+use core::ops::{BitOr, Range};
+use core::pin::Pin;
+use core::ptr;
+use core::task::{Context, Poll, Waker};
+
+use async_unsync::semaphore::{Permit, Semaphore};
+
+use lionsos_sys::*;
+use sel4_abstract_allocator::{AbstractAllocator, AbstractAllocatorAllocation};
+use sel4_shared_memory::SharedMemoryRef;
+
+pub use lionsos_sys::fs_stat_t as Stat;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The server failed the command with this status, one of [`lionsos_sys::fs_status`].
+    Status(u64),
+    /// There was not enough free space in the share region for the command's buffers.
+    ShareAllocationError,
+    PeerMisbehavior,
+}
+
+impl Error {
+    fn check_status(status: u64) -> Result<(), Self> {
+        if status == fs_status::FS_STATUS_SUCCESS.into() {
+            Ok(())
+        } else {
+            Err(Self::Status(status))
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Fd(u64);
+
+impl Fd {
+    pub const fn from_raw(raw: u64) -> Self {
+        Self(raw)
+    }
+
+    pub const fn into_raw(self) -> u64 {
+        self.0
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DirFd(u64);
+
+impl DirFd {
+    pub const fn from_raw(raw: u64) -> Self {
+        Self(raw)
+    }
+
+    pub const fn into_raw(self) -> u64 {
+        self.0
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct OpenFlags(u64);
+
+impl OpenFlags {
+    pub const READ_ONLY: Self = Self(fs_open_flags::FS_OPEN_FLAGS_READ_ONLY as u64);
+    pub const WRITE_ONLY: Self = Self(fs_open_flags::FS_OPEN_FLAGS_WRITE_ONLY as u64);
+    pub const READ_WRITE: Self = Self(fs_open_flags::FS_OPEN_FLAGS_READ_WRITE as u64);
+    pub const CREATE: Self = Self(fs_open_flags::FS_OPEN_FLAGS_CREATE as u64);
+}
+
+impl BitOr for OpenFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+pub struct FsClient<A: AbstractAllocator, F> {
+    shared: Rc<Shared<A, F>>,
+}
+
+struct Shared<A: AbstractAllocator, F> {
+    // One permit for each command which may be in flight. This bounds the occupancy of both
+    // queues.
+    slots: Semaphore,
+    inner: RefCell<Inner<A, F>>,
+}
+
+struct Inner<A: AbstractAllocator, F> {
+    command_queue: *mut fs_queue_t,
+    completion_queue: *mut fs_queue_t,
+    share: SharedMemoryRef<'static, [u8]>,
+    share_allocator: A,
+    notify: F,
+    next_id: u64,
+    commands: BTreeMap<u64, Command<A>>,
+}
+
+struct Command<A: AbstractAllocator> {
+    state: CommandState,
+    allocations: Vec<A::Allocation>,
+}
+
+enum CommandState {
+    Pending { waker: Option<Waker> },
+    Complete { cmpl: fs_cmpl_t },
+    Abandoned,
+}
+
+impl<A: AbstractAllocator, F: FnMut()> FsClient<A, F> {
+    /// `notify` is called after commands are enqueued, and should notify the server.
+    ///
+    /// # Safety
+    ///
+    /// `command_queue` and `completion_queue` must point to the client ends of a connection to a
+    /// LionsOS file system server, and `share` must be the connection's share region. These must
+    /// remain valid for the lifetime of this client, and no other client may use them.
+    pub unsafe fn new(
+        command_queue: *mut fs_queue_t,
+        completion_queue: *mut fs_queue_t,
+        share: SharedMemoryRef<'static, [u8]>,
+        share_allocator: A,
+        notify: F,
+    ) -> Self {
+        Self {
+            shared: Rc::new(Shared {
+                slots: Semaphore::new(FS_QUEUE_CAPACITY.try_into().unwrap()),
+                inner: RefCell::new(Inner {
+                    command_queue,
+                    completion_queue,
+                    share,
+                    share_allocator,
+                    notify,
+                    next_id: 0,
+                    commands: BTreeMap::new(),
+                }),
+            }),
+        }
+    }
+
+    /// Processes completions, waking the futures of completed commands. Returns whether there were
+    /// any.
+    pub fn poll(&self) -> Result<bool, Error> {
+        let mut inner = self.shared.inner.borrow_mut();
+        let n = unsafe { fs_queue_length_consumer(inner.completion_queue) };
+        for i in 0..n {
+            let cmpl = unsafe {
+                fs_queue_idx_filled(inner.completion_queue, i)
+                    .cast::<fs_cmpl_t>()
+                    .read()
+            };
+            if let Err(err) = self.complete(&mut inner, cmpl) {
+                // Leave the offending completion in the queue, but not those before it, which
+                // have already been acted upon.
+                unsafe {
+                    fs_queue_publish_consumption(inner.completion_queue, i);
+                }
+                return Err(err);
+            }
+        }
+        unsafe {
+            fs_queue_publish_consumption(inner.completion_queue, n);
+        }
+        Ok(n != 0)
+    }
+
+    fn complete(&self, inner: &mut Inner<A, F>, cmpl: fs_cmpl_t) -> Result<(), Error> {
+        let command = inner
+            .commands
+            .get_mut(&cmpl.id)
+            .ok_or(Error::PeerMisbehavior)?;
+        match &mut command.state {
+            CommandState::Pending { waker } => {
+                if let Some(waker) = waker.take() {
+                    waker.wake();
+                }
+                command.state = CommandState::Complete { cmpl };
+            }
+            CommandState::Abandoned => {
+                let command = inner.commands.remove(&cmpl.id).unwrap();
+                inner.free(command.allocations);
+                self.shared.slots.add_permits(1);
+            }
+            CommandState::Complete { .. } => return Err(Error::PeerMisbehavior),
+        }
+        Ok(())
+    }
+
+    pub async fn initialise(&self) -> Result<(), Error> {
+        self.call(
+            self.reserve().await,
+            fs_cmd_::FS_CMD_INITIALISE,
+            Default::default(),
+            Vec::new(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn deinitialise(&self) -> Result<(), Error> {
+        self.call(
+            self.reserve().await,
+            fs_cmd_::FS_CMD_DEINITIALISE,
+            Default::default(),
+            Vec::new(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn open(&self, path: &str, flags: OpenFlags) -> Result<Fd, Error> {
+        let permit = self.reserve().await;
+        let (path, allocation) = self.stage(path.as_bytes())?;
+        let params = fs_cmd_params_t {
+            file_open: fs_cmd_params_file_open_t {
+                path,
+                flags: flags.0,
+            },
+        };
+        let cmpl = self
+            .call(
+                permit,
+                fs_cmd_::FS_CMD_FILE_OPEN,
+                params,
+                [allocation].into(),
+            )
+            .await?;
+        Ok(Fd(unsafe { cmpl.data.file_open.fd }))
+    }
+
+    pub async fn close(&self, fd: Fd) -> Result<(), Error> {
+        let params = fs_cmd_params_t {
+            file_close: fs_cmd_params_file_close_t { fd: fd.0 },
+        };
+        self.call(
+            self.reserve().await,
+            fs_cmd_::FS_CMD_FILE_CLOSE,
+            params,
+            Vec::new(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the number of bytes read into `buf`, which is less than its length at the end of
+    /// the file.
+    pub async fn read(&self, fd: Fd, offset: u64, buf: &mut [u8]) -> Result<usize, Error> {
+        let permit = self.reserve().await;
+        let (share_buf, allocation) = self.allocate(Layout::for_value(buf))?;
+        let params = fs_cmd_params_t {
+            file_read: fs_cmd_params_file_read_t {
+                fd: fd.0,
+                offset,
+                buf: share_buf,
+            },
+        };
+        let cmpl = self
+            .call_keeping_allocations(
+                permit,
+                fs_cmd_::FS_CMD_FILE_READ,
+                params,
+                [allocation].into(),
+            )
+            .await?;
+        let len = usize::try_from(unsafe { cmpl.cmpl.data.file_read.len_read })
+            .ok()
+            .filter(|len| *len <= buf.len())
+            .ok_or(Error::PeerMisbehavior);
+        if let Ok(len) = len {
+            self.copy_out(cmpl.allocations[0].range(), &mut buf[..len]);
+        }
+        self.shared.inner.borrow_mut().free(cmpl.allocations);
+        len
+    }
+
+    /// Returns the number of bytes written from `buf`.
+    pub async fn write(&self, fd: Fd, offset: u64, buf: &[u8]) -> Result<usize, Error> {
+        let permit = self.reserve().await;
+        let (share_buf, allocation) = self.stage(buf)?;
+        let params = fs_cmd_params_t {
+            file_write: fs_cmd_params_file_write_t {
+                fd: fd.0,
+                offset,
+                buf: share_buf,
+            },
+        };
+        let cmpl = self
+            .call(
+                permit,
+                fs_cmd_::FS_CMD_FILE_WRITE,
+                params,
+                [allocation].into(),
+            )
+            .await?;
+        usize::try_from(unsafe { cmpl.data.file_write.len_written })
+            .ok()
+            .filter(|len| *len <= buf.len())
+            .ok_or(Error::PeerMisbehavior)
+    }
+
+    pub async fn size(&self, fd: Fd) -> Result<u64, Error> {
+        let params = fs_cmd_params_t {
+            file_size: fs_cmd_params_file_size_t { fd: fd.0 },
+        };
+        let cmpl = self
+            .call(
+                self.reserve().await,
+                fs_cmd_::FS_CMD_FILE_SIZE,
+                params,
+                Vec::new(),
+            )
+            .await?;
+        Ok(unsafe { cmpl.data.file_size.size })
+    }
+
+    pub async fn stat(&self, path: &str) -> Result<Stat, Error> {
+        let permit = self.reserve().await;
+        let (path, path_allocation) = self.stage(path.as_bytes())?;
+        let (share_buf, buf_allocation) = match self.allocate(Layout::new::<Stat>()) {
+            Ok(x) => x,
+            Err(err) => {
+                self.shared
+                    .inner
+                    .borrow_mut()
+                    .free([path_allocation].into());
+                return Err(err);
+            }
+        };
+        let params = fs_cmd_params_t {
+            stat: fs_cmd_params_stat_t {
+                path,
+                buf: share_buf,
+            },
+        };
+        let cmpl = self
+            .call_keeping_allocations(
+                permit,
+                fs_cmd_::FS_CMD_STAT,
+                params,
+                [path_allocation, buf_allocation].into(),
+            )
+            .await?;
+        let mut bytes = [0; mem::size_of::<Stat>()];
+        self.copy_out(cmpl.allocations[1].range(), &mut bytes);
+        self.shared.inner.borrow_mut().free(cmpl.allocations);
+        // `fs_stat_t` consists only of integers.
+        Ok(unsafe { ptr::read_unaligned(bytes.as_ptr().cast()) })
+    }
+
+    pub async fn open_dir(&self, path: &str) -> Result<DirFd, Error> {
+        let permit = self.reserve().await;
+        let (path, allocation) = self.stage(path.as_bytes())?;
+        let params = fs_cmd_params_t {
+            dir_open: fs_cmd_params_dir_open_t { path },
+        };
+        let cmpl = self
+            .call(
+                permit,
+                fs_cmd_::FS_CMD_DIR_OPEN,
+                params,
+                [allocation].into(),
+            )
+            .await?;
+        Ok(DirFd(unsafe { cmpl.data.dir_open.fd }))
+    }
+
+    /// Returns the name of the next entry in the directory, or `None` at its end.
+    pub async fn read_dir(&self, dir: DirFd) -> Result<Option<Vec<u8>>, Error> {
+        let permit = self.reserve().await;
+        let max_len = usize::try_from(FS_MAX_NAME_LENGTH).unwrap();
+        let (share_buf, allocation) = self.allocate(Layout::array::<u8>(max_len).unwrap())?;
+        let params = fs_cmd_params_t {
+            dir_read: fs_cmd_params_dir_read_t {
+                fd: dir.0,
+                buf: share_buf,
+            },
+        };
+        let cmpl = match self
+            .call_keeping_allocations(
+                permit,
+                fs_cmd_::FS_CMD_DIR_READ,
+                params,
+                [allocation].into(),
+            )
+            .await
+        {
+            Err(Error::Status(status))
+                if status == fs_status::FS_STATUS_END_OF_DIRECTORY.into() =>
+            {
+                return Ok(None);
+            }
+            r => r?,
+        };
+        let name = usize::try_from(unsafe { cmpl.cmpl.data.dir_read.path_len })
+            .ok()
+            .filter(|len| *len <= max_len)
+            .ok_or(Error::PeerMisbehavior)
+            .map(|len| {
+                let mut name = alloc::vec![0; len];
+                self.copy_out(cmpl.allocations[0].range(), &mut name);
+                name
+            });
+        self.shared.inner.borrow_mut().free(cmpl.allocations);
+        name.map(Some)
+    }
+
+    pub async fn close_dir(&self, dir: DirFd) -> Result<(), Error> {
+        let params = fs_cmd_params_t {
+            dir_close: fs_cmd_params_dir_close_t { fd: dir.0 },
+        };
+        self.call(
+            self.reserve().await,
+            fs_cmd_::FS_CMD_DIR_CLOSE,
+            params,
+            Vec::new(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    fn allocate(&self, layout: Layout) -> Result<(fs_buffer_t, A::Allocation), Error> {
+        let allocation = self
+            .shared
+            .inner
+            .borrow_mut()
+            .share_allocator
+            .allocate(layout)
+            .map_err(|_| Error::ShareAllocationError)?;
+        let buf = fs_buffer_t {
+            offset: allocation.range().start.try_into().unwrap(),
+            size: layout.size().try_into().unwrap(),
+        };
+        Ok((buf, allocation))
+    }
+
+    fn stage(&self, bytes: &[u8]) -> Result<(fs_buffer_t, A::Allocation), Error> {
+        let (buf, allocation) = self.allocate(Layout::for_value(bytes))?;
+        self.shared
+            .inner
+            .borrow_mut()
+            .share
+            .as_mut_ptr()
+            .index(allocation.range().start..allocation.range().start + bytes.len())
+            .copy_from_slice(bytes);
+        Ok((buf, allocation))
+    }
+
+    fn copy_out(&self, range: Range<usize>, dst: &mut [u8]) {
+        let range = range.start..range.start + dst.len();
+        self.shared
+            .inner
+            .borrow()
+            .share
+            .as_ptr()
+            .index(range)
+            .copy_into_slice(dst);
+    }
+
+    /// Waits for a command slot. Commands' buffers should only be allocated after this, so that
+    /// they are not leaked if the caller's future is dropped while waiting.
+    async fn reserve(&self) -> Permit<'_> {
+        self.shared.slots.acquire().await.unwrap()
+    }
+
+    async fn call(
+        &self,
+        permit: Permit<'_>,
+        ty: fs_cmd_::Type,
+        params: fs_cmd_params_t,
+        allocations: Vec<A::Allocation>,
+    ) -> Result<fs_cmpl_t, Error> {
+        let completed = self
+            .call_keeping_allocations(permit, ty, params, allocations)
+            .await?;
+        self.shared.inner.borrow_mut().free(completed.allocations);
+        Ok(completed.cmpl)
+    }
+
+    /// On success, the caller is responsible for freeing the returned allocations.
+    async fn call_keeping_allocations(
+        &self,
+        permit: Permit<'_>,
+        ty: fs_cmd_::Type,
+        params: fs_cmd_params_t,
+        allocations: Vec<A::Allocation>,
+    ) -> Result<Completed<A>, Error> {
+        let id = self.submit(permit, ty, params, allocations);
+        let completed = CommandFuture {
+            client: self,
+            id,
+            poll_returned_ready: false,
+        }
+        .await;
+        if let Err(err) = Error::check_status(completed.cmpl.status) {
+            self.shared.inner.borrow_mut().free(completed.allocations);
+            return Err(err);
+        }
+        Ok(completed)
+    }
+
+    fn submit(
+        &self,
+        permit: Permit<'_>,
+        ty: fs_cmd_::Type,
+        params: fs_cmd_params_t,
+        allocations: Vec<A::Allocation>,
+    ) -> u64 {
+        // Returned once the completion has been consumed.
+        permit.forget();
+        let mut inner = self.shared.inner.borrow_mut();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        let cmd = fs_cmd_t {
+            id,
+            type_: ty.into(),
+            params,
+        };
+        unsafe {
+            assert!(fs_queue_length_producer(inner.command_queue) < FS_QUEUE_CAPACITY.into());
+            fs_queue_idx_empty(inner.command_queue, 0)
+                .cast::<fs_cmd_t>()
+                .write(cmd);
+            fs_queue_publish_production(inner.command_queue, 1);
+        }
+        inner.commands.insert(
+            id,
+            Command {
+                state: CommandState::Pending { waker: None },
+                allocations,
+            },
+        );
+        (inner.notify)();
+        id
+    }
+}
+
+impl<A: AbstractAllocator, F> Clone for FsClient<A, F> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<A: AbstractAllocator, F> Inner<A, F> {
+    fn free(&mut self, allocations: Vec<A::Allocation>) {
+        for allocation in allocations {
+            self.share_allocator.deallocate(allocation);
+        }
+    }
+}
+
+struct Completed<A: AbstractAllocator> {
+    cmpl: fs_cmpl_t,
+    allocations: Vec<A::Allocation>,
+}
+
+struct CommandFuture<'a, A: AbstractAllocator, F> {
+    client: &'a FsClient<A, F>,
+    id: u64,
+    poll_returned_ready: bool,
+}
+
+impl<A: AbstractAllocator, F> Future for CommandFuture<'_, A, F> {
+    type Output = Completed<A>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        assert!(!self.poll_returned_ready);
+        let mut inner = self.client.shared.inner.borrow_mut();
+        let command = inner.commands.get_mut(&self.id).unwrap();
+        match &mut command.state {
+            CommandState::Pending { waker } => {
+                waker.replace(cx.waker().clone());
+                Poll::Pending
+            }
+            CommandState::Complete { cmpl } => {
+                let cmpl = *cmpl;
+                let command = inner.commands.remove(&self.id).unwrap();
+                drop(inner);
+                self.client.shared.slots.add_permits(1);
+                self.poll_returned_ready = true;
+                Poll::Ready(Completed {
+                    cmpl,
+                    allocations: command.allocations,
+                })
+            }
+            CommandState::Abandoned => unreachable!(),
+        }
+    }
+}
+
+impl<A: AbstractAllocator, F> Drop for CommandFuture<'_, A, F> {
+    fn drop(&mut self) {
+        if !self.poll_returned_ready {
+            let mut inner = self.client.shared.inner.borrow_mut();
+            let command = inner.commands.get_mut(&self.id).unwrap();
+            match command.state {
+                CommandState::Pending { .. } => {
+                    command.state = CommandState::Abandoned;
+                }
+                CommandState::Complete { .. } => {
+                    let command = inner.commands.remove(&self.id).unwrap();
+                    inner.free(command.allocations);
+                    drop(inner);
+                    self.client.shared.slots.add_permits(1);
+                }
+                CommandState::Abandoned => unreachable!(),
+            }
+        }
+    }
+}
@@ -5,3 +5,7 @@
 //
 
 #![no_std]
+
+extern crate alloc;
+
+pub mod fs;
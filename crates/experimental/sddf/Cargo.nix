@@ -9,8 +9,6 @@
 mk {
   package.name = "sddf";
   dependencies = {
-    inherit (localCrates)
-      sddf-sys
-    ;
+    sddf-sys = localCrates.sddf-sys // { optional = true; };
   };
 }
@@ -0,0 +1,21 @@
+#
+# Copyright 2026, Colias Group, LLC
+#
+# SPDX-License-Identifier: BSD-2-Clause
+#
+
+{ mk, versions, localCrates, smoltcpWith }:
+
+mk {
+  package.name = "sddf-driver-adapters";
+  dependencies = {
+    inherit (versions) log embedded-hal-nb;
+    smoltcp = smoltcpWith [];
+    inherit (localCrates)
+      sddf
+      sel4-driver-interfaces
+      sel4-microkit
+      sel4-shared-memory
+    ;
+  };
+}
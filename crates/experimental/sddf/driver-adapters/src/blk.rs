@@ -0,0 +1,187 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+//! An sDDF block driver for implementors of [`BlockIO`].
+//!
+//! Requests are handled synchronously and in order, so barriers are trivially satisfied.
+
+use core::convert::Infallible;
+
+use sddf::blk::{
+    DriverConfig, QueueHandle, Request, RequestCode, Response, ResponseStatus, StorageInfo,
+    TRANSFER_SIZE,
+};
+use sel4_driver_interfaces::HandleInterrupt;
+use sel4_driver_interfaces::block::BlockIO;
+use sel4_microkit::{Channel, ChannelSet, Handler, MessageInfo};
+
+use crate::DataRegion;
+
+pub struct HandlerImpl<Driver> {
+    driver: Driver,
+    device: Channel,
+    queue: QueueHandle,
+    virt: Channel,
+    data: DataRegion,
+    /// Device blocks per sDDF transfer.
+    blocks_per_transfer: u64,
+    /// In units of [`TRANSFER_SIZE`].
+    capacity: u64,
+}
+
+impl<Driver> HandlerImpl<Driver>
+where
+    Driver: BlockIO + HandleInterrupt,
+{
+    /// `data` is the virtualiser's data region, which the virtualiser refers to by I/O address in
+    /// the requests it passes to this driver.
+    ///
+    /// This fills in the storage information for the virtualiser and marks it as ready.
+    ///
+    /// # Safety
+    ///
+    /// `config` must be this component's configuration, as written by the sDDF metaprogram. See
+    /// [`sddf::blk::ConnectionResource::queue_handle`].
+    pub unsafe fn new(
+        mut driver: Driver,
+        device: Channel,
+        config: &DriverConfig,
+        data: DataRegion,
+    ) -> Result<Self, Driver::Error> {
+        assert!(config.check_magic());
+        let block_size = driver.get_block_size()?;
+        assert_eq!(TRANSFER_SIZE % block_size, 0);
+        let blocks_per_transfer = u64::try_from(TRANSFER_SIZE / block_size).unwrap();
+        let capacity = driver.get_num_blocks()? / blocks_per_transfer;
+        let queue = unsafe { config.virt.queue_handle() };
+        let storage_info = config.virt.storage_info.ptr::<StorageInfo>();
+        unsafe {
+            (*storage_info).read_only = false;
+            (*storage_info).sector_size = block_size.try_into().unwrap();
+            (*storage_info).block_size = 1;
+            (*storage_info).queue_depth = queue.capacity().try_into().unwrap();
+            (*storage_info).capacity = capacity;
+            (*storage_info).set_ready();
+        }
+        Ok(Self {
+            driver,
+            device,
+            queue,
+            virt: Channel::new(config.virt.id.into()),
+            data,
+            blocks_per_transfer,
+            capacity,
+        })
+    }
+
+    fn handle_requests(&mut self) {
+        let mut responded = false;
+        while !self.queue.is_full_resp() {
+            let req = match self.queue.dequeue_req() {
+                Ok(Some(req)) => req,
+                Ok(None) => break,
+                Err(err) => {
+                    log::warn!("{err:?}");
+                    break;
+                }
+            };
+            let status = self.handle_request(&req);
+            let success_count = if status == ResponseStatus::Ok {
+                req.count
+            } else {
+                0
+            };
+            self.queue
+                .enqueue_resp(Response {
+                    status,
+                    success_count,
+                    id: req.id,
+                })
+                .unwrap();
+            responded = true;
+        }
+        if responded {
+            self.virt.notify();
+        }
+    }
+
+    fn handle_request(&mut self, req: &Request) -> ResponseStatus {
+        match req.code {
+            RequestCode::Read | RequestCode::Write => {
+                if req
+                    .block_number
+                    .checked_add(req.count.into())
+                    .is_none_or(|end| end > self.capacity)
+                {
+                    return ResponseStatus::InvalidParameter;
+                }
+                // Transfer through a bounce buffer, one transfer at a time, rather than handing
+                // the device a reference to shared memory.
+                let mut buf = [0; TRANSFER_SIZE];
+                for i in 0..req.count {
+                    let Some(io_addr) = u64::from(i)
+                        .checked_mul(u64::try_from(TRANSFER_SIZE).unwrap())
+                        .zip(u64::try_from(req.io_or_offset).ok())
+                        .and_then(|(offset, base)| base.checked_add(offset))
+                    else {
+                        return ResponseStatus::InvalidParameter;
+                    };
+                    let start_block = (req.block_number + u64::from(i)) * self.blocks_per_transfer;
+                    let Some(region) = self.data.index(io_addr, TRANSFER_SIZE) else {
+                        return ResponseStatus::InvalidParameter;
+                    };
+                    let result = if req.code == RequestCode::Read {
+                        self.driver
+                            .read_blocks(start_block, &mut buf)
+                            .map(|_| region.copy_from_slice(&buf))
+                    } else {
+                        region.copy_into_slice(&mut buf);
+                        self.driver.write_blocks(start_block, &buf)
+                    };
+                    if let Err(err) = result {
+                        log::warn!("{err:?}");
+                        return ResponseStatus::UnspecifiedError;
+                    }
+                }
+                ResponseStatus::Ok
+            }
+            RequestCode::Flush => match self.driver.flush() {
+                Ok(()) => ResponseStatus::Ok,
+                Err(err) => {
+                    log::warn!("{err:?}");
+                    ResponseStatus::UnspecifiedError
+                }
+            },
+            RequestCode::Barrier => ResponseStatus::Ok,
+        }
+    }
+}
+
+impl<Driver> Handler for HandlerImpl<Driver>
+where
+    Driver: BlockIO + HandleInterrupt,
+{
+    type Error = Infallible;
+
+    fn notified(&mut self, channels: ChannelSet) -> Result<(), Self::Error> {
+        if channels.contains(self.device) {
+            self.driver.handle_interrupt();
+            self.device.irq_ack().unwrap();
+        }
+        if channels.contains(self.virt) {
+            self.handle_requests();
+        }
+        Ok(())
+    }
+
+    fn protected(
+        &mut self,
+        channel: Channel,
+        _msg_info: MessageInfo,
+    ) -> Result<MessageInfo, Self::Error> {
+        panic!("unexpected channel: {channel:?}");
+    }
+}
@@ -0,0 +1,49 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+//! Generic sDDF drivers for implementors of the [`sel4_driver_interfaces`] traits.
+//!
+//! Each module provides a [`sel4_microkit::Handler`] which speaks the corresponding sDDF protocol
+//! to a virtualiser (or, in the case of the timer, directly to clients), so that these drivers can
+//! stand in for C sDDF drivers.
+
+#![no_std]
+
+use core::ops::Range;
+
+use sel4_shared_memory::{SharedMemoryPtr, SharedMemoryRef};
+
+pub mod blk;
+pub mod net;
+pub mod serial;
+pub mod timer;
+
+/// A data region shared with a virtualiser, which refers to buffers within it by I/O address.
+///
+/// Unlike most C sDDF drivers, which pass I/O addresses straight to their devices, these drivers
+/// copy data between their devices and buffers, so they must have the region mapped.
+pub struct DataRegion {
+    region: SharedMemoryRef<'static, [u8]>,
+    io_addr: u64,
+}
+
+impl DataRegion {
+    pub fn new(region: SharedMemoryRef<'static, [u8]>, io_addr: u64) -> Self {
+        Self { region, io_addr }
+    }
+
+    /// Returns `None` if `[io_addr, io_addr + len)` is not within this region.
+    pub fn index(&mut self, io_addr: u64, len: usize) -> Option<SharedMemoryPtr<'_, [u8]>> {
+        let range = self.range(io_addr, len)?;
+        Some(self.region.as_mut_ptr().index(range))
+    }
+
+    fn range(&self, io_addr: u64, len: usize) -> Option<Range<usize>> {
+        let start = usize::try_from(io_addr.checked_sub(self.io_addr)?).ok()?;
+        let end = start.checked_add(len)?;
+        (end <= self.region.as_ptr().len()).then_some(start..end)
+    }
+}
@@ -0,0 +1,176 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+//! An sDDF network driver for implementors of [`smoltcp::phy::Device`].
+
+use core::convert::Infallible;
+
+use smoltcp::{
+    phy::{self, RxToken, TxToken},
+    time::Instant,
+};
+
+use sddf::network::{BuffDesc, DriverConfig, QueueHandle};
+use sel4_driver_interfaces::HandleInterrupt;
+use sel4_microkit::{Channel, ChannelSet, Handler, MessageInfo};
+
+use crate::DataRegion;
+
+pub struct HandlerImpl<Device> {
+    dev: Device,
+    device: Channel,
+    rx: Queues,
+    tx: Queues,
+}
+
+struct Queues {
+    queue: QueueHandle,
+    channel: Channel,
+    data: DataRegion,
+}
+
+impl<Device> HandlerImpl<Device>
+where
+    Device: phy::Device + HandleInterrupt,
+{
+    /// `rx_data` and `tx_data` are the virtualisers' data regions, which the virtualisers refer to
+    /// by I/O address in the buffer descriptors they pass to this driver.
+    ///
+    /// # Safety
+    ///
+    /// `config` must be this component's configuration, as written by the sDDF metaprogram. See
+    /// [`sddf::network::ConnectionResource::queue_handle`].
+    pub unsafe fn new(
+        dev: Device,
+        device: Channel,
+        config: &DriverConfig,
+        rx_data: DataRegion,
+        tx_data: DataRegion,
+    ) -> Self {
+        assert!(config.check_magic());
+        Self {
+            dev,
+            device,
+            rx: Queues {
+                queue: unsafe { config.virt_rx.queue_handle() },
+                channel: Channel::new(config.virt_rx.id.into()),
+                data: rx_data,
+            },
+            tx: Queues {
+                queue: unsafe { config.virt_tx.queue_handle() },
+                channel: Channel::new(config.virt_tx.id.into()),
+                data: tx_data,
+            },
+        }
+    }
+
+    fn handle_rx(&mut self) {
+        let rx = &mut self.rx;
+        let mut enqueued = false;
+        loop {
+            if rx.queue.is_empty_free() {
+                // Ask the virtualiser to tell us when it returns buffers, and then check again, in
+                // case it did so before seeing the request.
+                rx.queue.request_signal_free();
+                if rx.queue.is_empty_free() {
+                    break;
+                }
+                rx.queue.cancel_signal_free();
+            }
+            let Some((rx_tok, _tx_tok)) = self.dev.receive(Instant::ZERO) else {
+                break;
+            };
+            let mut desc = rx.queue.dequeue_free().unwrap();
+            rx_tok.consume(|rx_buf| {
+                let len = rx_buf.len().min(sddf::network::BUFFER_SIZE);
+                if len < rx_buf.len() {
+                    log::warn!("truncating {} byte packet", rx_buf.len());
+                }
+                match rx.data.index(desc.io_or_offset, len) {
+                    Some(buf) => {
+                        buf.copy_from_slice(&rx_buf[..len]);
+                        desc.len = len.try_into().unwrap();
+                    }
+                    None => {
+                        log::warn!("invalid rx buffer: {desc:x?}");
+                        desc.len = 0;
+                    }
+                }
+            });
+            rx.queue.enqueue_active(desc).unwrap();
+            enqueued = true;
+        }
+        if enqueued && rx.queue.require_signal_active() {
+            rx.queue.cancel_signal_active();
+            rx.channel.notify();
+        }
+    }
+
+    fn handle_tx(&mut self) {
+        let tx = &mut self.tx;
+        let mut returned = false;
+        loop {
+            if tx.queue.is_empty_active() {
+                // Ask the virtualiser to tell us when there is more to transmit, and then check
+                // again, in case it enqueued more before seeing the request.
+                tx.queue.request_signal_active();
+                if tx.queue.is_empty_active() {
+                    break;
+                }
+                tx.queue.cancel_signal_active();
+            }
+            // Leave the buffer in the queue until the device is ready for it.
+            let Some(tx_tok) = self.dev.transmit(Instant::ZERO) else {
+                break;
+            };
+            let desc = tx.queue.dequeue_active().unwrap();
+            let len = usize::from(desc.len);
+            match tx.data.index(desc.io_or_offset, len) {
+                Some(buf) => {
+                    tx_tok.consume(len, |tx_buf| buf.copy_into_slice(tx_buf));
+                }
+                None => {
+                    log::warn!("invalid tx buffer: {desc:x?}");
+                }
+            }
+            tx.queue
+                .enqueue_free(BuffDesc::new(desc.io_or_offset, 0))
+                .unwrap();
+            returned = true;
+        }
+        if returned && tx.queue.require_signal_free() {
+            tx.queue.cancel_signal_free();
+            tx.channel.notify();
+        }
+    }
+}
+
+impl<Device> Handler for HandlerImpl<Device>
+where
+    Device: phy::Device + HandleInterrupt,
+{
+    type Error = Infallible;
+
+    fn notified(&mut self, channels: ChannelSet) -> Result<(), Self::Error> {
+        if channels.contains(self.device) {
+            self.dev.handle_interrupt();
+            self.device.irq_ack().unwrap();
+        }
+        // Receiving may have been blocked on free buffers, and transmitting on the device, so try
+        // both whatever the cause of the notification.
+        self.handle_rx();
+        self.handle_tx();
+        Ok(())
+    }
+
+    fn protected(
+        &mut self,
+        channel: Channel,
+        _msg_info: MessageInfo,
+    ) -> Result<MessageInfo, Self::Error> {
+        panic!("unexpected channel: {channel:?}");
+    }
+}
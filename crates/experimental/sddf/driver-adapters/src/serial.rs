@@ -0,0 +1,155 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+//! An sDDF serial driver for implementors of [`serial::Read<u8>`] and [`serial::Write<u8>`].
+
+use core::convert::Infallible;
+
+use embedded_hal_nb::nb;
+use embedded_hal_nb::serial;
+
+use sddf::serial::{DriverConfig, QueueHandle};
+use sel4_driver_interfaces::HandleInterrupt;
+use sel4_microkit::{Channel, ChannelSet, Handler, MessageInfo};
+
+pub struct HandlerImpl<Driver> {
+    driver: Driver,
+    device: Channel,
+    /// Absent if receiving is disabled in the configuration.
+    rx: Option<(QueueHandle, Channel)>,
+    tx: (QueueHandle, Channel),
+    /// A byte which has been dequeued but which the device was not ready for.
+    pending_tx: Option<u8>,
+}
+
+impl<Driver> HandlerImpl<Driver>
+where
+    Driver: serial::Read<u8> + serial::Write<u8> + HandleInterrupt,
+{
+    /// # Safety
+    ///
+    /// `config` must be this component's configuration, as written by the sDDF metaprogram. See
+    /// [`sddf::serial::ConnectionResource::queue_handle`].
+    pub unsafe fn new(driver: Driver, device: Channel, config: &DriverConfig) -> Self {
+        assert!(config.check_magic());
+        let rx = config.rx_enabled.then(|| {
+            (
+                unsafe { config.rx.queue_handle() },
+                Channel::new(config.rx.id.into()),
+            )
+        });
+        let tx = (
+            unsafe { config.tx.queue_handle() },
+            Channel::new(config.tx.id.into()),
+        );
+        Self {
+            driver,
+            device,
+            rx,
+            tx,
+            pending_tx: None,
+        }
+    }
+
+    fn handle_rx(&mut self) {
+        let Some((queue, channel)) = &mut self.rx else {
+            return;
+        };
+        let mut enqueued = false;
+        loop {
+            if queue.is_full() {
+                // Ask the virtualiser to tell us when there is space again. Until then, the device
+                // buffers what it can.
+                queue.request_producer_signal();
+                break;
+            }
+            match self.driver.read() {
+                Ok(c) => {
+                    queue.enqueue(c).unwrap();
+                    enqueued = true;
+                }
+                Err(nb::Error::WouldBlock) => break,
+                Err(nb::Error::Other(err)) => {
+                    log::debug!("read error: {err:?}");
+                    break;
+                }
+            }
+        }
+        if enqueued && queue.require_consumer_signal() {
+            queue.cancel_consumer_signal();
+            channel.notify();
+        }
+    }
+
+    fn handle_tx(&mut self) {
+        let (queue, channel) = &mut self.tx;
+        let mut dequeued = false;
+        loop {
+            let Some(c) = self.pending_tx.take().or_else(|| {
+                let c = queue.dequeue();
+                dequeued |= c.is_some();
+                c
+            }) else {
+                // Ask the virtualiser to tell us when there is more to transmit, and then check
+                // again, in case it enqueued more before seeing the request.
+                queue.request_consumer_signal();
+                if queue.is_empty() {
+                    break;
+                }
+                queue.cancel_consumer_signal();
+                continue;
+            };
+            match self.driver.write(c) {
+                Ok(()) => {}
+                Err(nb::Error::WouldBlock) => {
+                    // Try again on the next interrupt.
+                    self.pending_tx = Some(c);
+                    break;
+                }
+                Err(nb::Error::Other(err)) => {
+                    log::debug!("write error: {err:?}");
+                }
+            }
+        }
+        if dequeued && queue.require_producer_signal() {
+            queue.cancel_producer_signal();
+            channel.notify();
+        }
+    }
+}
+
+impl<Driver> Handler for HandlerImpl<Driver>
+where
+    Driver: serial::Read<u8> + serial::Write<u8> + HandleInterrupt,
+{
+    type Error = Infallible;
+
+    fn notified(&mut self, channels: ChannelSet) -> Result<(), Self::Error> {
+        if channels.contains(self.device) {
+            self.handle_rx();
+            self.handle_tx();
+            self.driver.handle_interrupt();
+            self.device.irq_ack().unwrap();
+        }
+        if let Some((_, channel)) = &self.rx
+            && channels.contains(*channel)
+        {
+            self.handle_rx();
+        }
+        if channels.contains(self.tx.1) {
+            self.handle_tx();
+        }
+        Ok(())
+    }
+
+    fn protected(
+        &mut self,
+        channel: Channel,
+        _msg_info: MessageInfo,
+    ) -> Result<MessageInfo, Self::Error> {
+        panic!("unexpected channel: {channel:?}");
+    }
+}
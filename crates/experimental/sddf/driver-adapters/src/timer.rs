@@ -0,0 +1,114 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+//! An sDDF timer driver for implementors of [`Timer`].
+//!
+//! Clients are identified by the channel over which they call the driver, and are notified over
+//! that same channel when their timeouts expire. Each client has at most one pending timeout.
+
+#![allow(clippy::useless_conversion)]
+
+use core::convert::Infallible;
+use core::time::Duration;
+
+use sddf::timer::{GET_TIME, SET_TIMEOUT};
+use sel4_driver_interfaces::HandleInterrupt;
+use sel4_driver_interfaces::timer::Timer;
+use sel4_microkit::{
+    Channel, ChannelSet, Handler, MessageInfo, MessageLabel, MessageRegisterValue, get_mr, set_mr,
+};
+
+// Microkit channel indices are less than this.
+const MAX_CHANNELS: usize = 62;
+
+const GET_TIME_LABEL: MessageLabel = GET_TIME as MessageLabel;
+const SET_TIMEOUT_LABEL: MessageLabel = SET_TIMEOUT as MessageLabel;
+
+pub struct HandlerImpl<Driver> {
+    driver: Driver,
+    device: Channel,
+    /// Absolute deadlines, indexed by client channel.
+    timeouts: [Option<Duration>; MAX_CHANNELS],
+}
+
+impl<Driver> HandlerImpl<Driver>
+where
+    Driver: Timer + HandleInterrupt,
+{
+    pub fn new(driver: Driver, device: Channel) -> Self {
+        Self {
+            driver,
+            device,
+            timeouts: [None; MAX_CHANNELS],
+        }
+    }
+
+    fn now(&mut self) -> Duration {
+        self.driver.get_time().unwrap()
+    }
+
+    /// Notifies clients whose timeouts have expired, and arms the device for the earliest of the
+    /// rest.
+    fn process_timeouts(&mut self) {
+        let now = self.now();
+        for (i, timeout) in self.timeouts.iter_mut().enumerate() {
+            if timeout.is_some_and(|deadline| deadline <= now) {
+                *timeout = None;
+                Channel::new(i).notify();
+            }
+        }
+        match self.timeouts.iter().flatten().min() {
+            Some(deadline) => self.driver.set_timeout(*deadline - now).unwrap(),
+            None => self.driver.clear_timeout().unwrap(),
+        }
+    }
+}
+
+impl<Driver> Handler for HandlerImpl<Driver>
+where
+    Driver: Timer + HandleInterrupt,
+{
+    type Error = Infallible;
+
+    fn notified(&mut self, channels: ChannelSet) -> Result<(), Self::Error> {
+        if channels.contains(self.device) {
+            self.driver.handle_interrupt();
+            self.device.irq_ack().unwrap();
+            self.process_timeouts();
+        } else {
+            panic!("unexpected channels: {}", channels.display());
+        }
+        Ok(())
+    }
+
+    fn protected(
+        &mut self,
+        channel: Channel,
+        msg_info: MessageInfo,
+    ) -> Result<MessageInfo, Self::Error> {
+        Ok(match msg_info.label() {
+            GET_TIME_LABEL => {
+                let now = self.now();
+                set_mr(0, duration_to_mr(now));
+                MessageInfo::new(0, 1)
+            }
+            SET_TIMEOUT_LABEL if msg_info.count() == 1 => {
+                let relative = Duration::from_nanos(get_mr(0).into());
+                self.timeouts[channel.index()] = Some(self.now() + relative);
+                self.process_timeouts();
+                MessageInfo::new(0, 0)
+            }
+            _ => {
+                log::warn!("invalid request from {channel:?}: {msg_info:?}");
+                MessageInfo::new(0, 0)
+            }
+        })
+    }
+}
+
+fn duration_to_mr(d: Duration) -> MessageRegisterValue {
+    d.as_nanos().try_into().unwrap_or(MessageRegisterValue::MAX)
+}
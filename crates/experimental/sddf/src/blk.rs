@@ -3,3 +3,461 @@
 //
 // SPDX-License-Identifier: BSD-2-Clause
 //
+
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+use crate::PeerMisbehaviorError;
+use crate::config::{MAGIC_LEN, RegionResource, magic};
+use crate::ring::Ring;
+
+pub const MAGIC: [u8; MAGIC_LEN] = magic(0x5);
+
+/// Unit of transfers, in bytes.
+pub const TRANSFER_SIZE: usize = 4096;
+
+pub const MAX_SERIAL_NUMBER: usize = 63;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RequestCode {
+    Read,
+    Write,
+    Flush,
+    Barrier,
+}
+
+impl RequestCode {
+    fn from_raw(raw: u32) -> Result<Self, PeerMisbehaviorError> {
+        Ok(match raw {
+            0 => Self::Read,
+            1 => Self::Write,
+            2 => Self::Flush,
+            3 => Self::Barrier,
+            _ => return Err(PeerMisbehaviorError::new()),
+        })
+    }
+
+    fn into_raw(self) -> u32 {
+        match self {
+            Self::Read => 0,
+            Self::Write => 1,
+            Self::Flush => 2,
+            Self::Barrier => 3,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub code: RequestCode,
+    /// Offset of the transfer within the data region, or its I/O address.
+    pub io_or_offset: usize,
+    pub block_number: u64,
+    /// In units of [`TRANSFER_SIZE`].
+    pub count: u16,
+    /// Chosen by the client, and echoed in the corresponding [`Response`].
+    pub id: u32,
+}
+
+/// Layout of `blk_req_t`.
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct RawRequest {
+    code: u32,
+    io_or_offset: usize,
+    block_number: u64,
+    count: u16,
+    id: u32,
+}
+
+impl Request {
+    fn from_raw(raw: RawRequest) -> Result<Self, PeerMisbehaviorError> {
+        Ok(Self {
+            code: RequestCode::from_raw(raw.code)?,
+            io_or_offset: raw.io_or_offset,
+            block_number: raw.block_number,
+            count: raw.count,
+            id: raw.id,
+        })
+    }
+
+    fn into_raw(self) -> RawRequest {
+        RawRequest {
+            code: self.code.into_raw(),
+            io_or_offset: self.io_or_offset,
+            block_number: self.block_number,
+            count: self.count,
+            id: self.id,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ResponseStatus {
+    Ok,
+    UnspecifiedError,
+    InvalidParameter,
+    NoDevice,
+}
+
+impl ResponseStatus {
+    fn from_raw(raw: u32) -> Result<Self, PeerMisbehaviorError> {
+        Ok(match raw {
+            0 => Self::Ok,
+            1 => Self::UnspecifiedError,
+            2 => Self::InvalidParameter,
+            3 => Self::NoDevice,
+            _ => return Err(PeerMisbehaviorError::new()),
+        })
+    }
+
+    fn into_raw(self) -> u32 {
+        match self {
+            Self::Ok => 0,
+            Self::UnspecifiedError => 1,
+            Self::InvalidParameter => 2,
+            Self::NoDevice => 3,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: ResponseStatus,
+    /// In units of [`TRANSFER_SIZE`].
+    pub success_count: u16,
+    pub id: u32,
+}
+
+/// Layout of `blk_resp_t`.
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct RawResponse {
+    status: u32,
+    success_count: u16,
+    id: u32,
+}
+
+impl Response {
+    fn from_raw(raw: RawResponse) -> Result<Self, PeerMisbehaviorError> {
+        Ok(Self {
+            status: ResponseStatus::from_raw(raw.status)?,
+            success_count: raw.success_count,
+            id: raw.id,
+        })
+    }
+
+    fn into_raw(self) -> RawResponse {
+        RawResponse {
+            status: self.status.into_raw(),
+            success_count: self.success_count,
+            id: self.id,
+        }
+    }
+}
+
+/// Layout of `blk_req_queue_t`, which is followed by its requests.
+#[repr(C)]
+pub struct RequestQueue {
+    head: AtomicU32,
+    tail: AtomicU32,
+    buffers: [RawRequest; 0],
+}
+
+/// Layout of `blk_resp_queue_t`, which is followed by its responses.
+#[repr(C)]
+pub struct ResponseQueue {
+    head: AtomicU32,
+    tail: AtomicU32,
+    buffers: [RawResponse; 0],
+}
+
+/// A pair of request and response queues.
+///
+/// Clients enqueue requests and dequeue responses, and drivers (or virtualisers, towards their
+/// drivers) dequeue requests and enqueue responses.
+pub struct QueueHandle {
+    req: Ring<RawRequest>,
+    resp: Ring<RawResponse>,
+}
+
+impl QueueHandle {
+    /// # Safety
+    ///
+    /// `request` and `response` must point to block queues, each with `capacity` entries, which
+    /// remain valid for the lifetime of this handle. Only one handle may exist for each end of a
+    /// queue.
+    pub unsafe fn new(
+        request: *mut RequestQueue,
+        response: *mut ResponseQueue,
+        capacity: u32,
+    ) -> Self {
+        unsafe {
+            Self {
+                req: Ring::new(
+                    &raw const (*request).tail,
+                    &raw const (*request).head,
+                    (&raw mut (*request).buffers).cast(),
+                    capacity,
+                ),
+                resp: Ring::new(
+                    &raw const (*response).tail,
+                    &raw const (*response).head,
+                    (&raw mut (*response).buffers).cast(),
+                    capacity,
+                ),
+            }
+        }
+    }
+
+    pub fn capacity(&self) -> u32 {
+        self.req.capacity()
+    }
+
+    pub fn is_empty_req(&self) -> bool {
+        self.req.is_empty()
+    }
+
+    pub fn is_empty_resp(&self) -> bool {
+        self.resp.is_empty()
+    }
+
+    pub fn is_full_req(&self) -> bool {
+        self.req.is_full()
+    }
+
+    pub fn is_full_resp(&self) -> bool {
+        self.resp.is_full()
+    }
+
+    pub fn len_req(&self) -> u32 {
+        self.req.len()
+    }
+
+    pub fn len_resp(&self) -> u32 {
+        self.resp.len()
+    }
+
+    /// Returns the request if the queue is full.
+    pub fn enqueue_req(&mut self, req: Request) -> Result<(), Request> {
+        self.req.enqueue(req.into_raw()).map_err(|_| req)
+    }
+
+    /// Returns the response if the queue is full.
+    pub fn enqueue_resp(&mut self, resp: Response) -> Result<(), Response> {
+        self.resp.enqueue(resp.into_raw()).map_err(|_| resp)
+    }
+
+    pub fn dequeue_req(&mut self) -> Result<Option<Request>, PeerMisbehaviorError> {
+        self.req.dequeue().map(Request::from_raw).transpose()
+    }
+
+    pub fn dequeue_resp(&mut self) -> Result<Option<Response>, PeerMisbehaviorError> {
+        self.resp.dequeue().map(Response::from_raw).transpose()
+    }
+}
+
+/// Layout of `blk_storage_info_t`, which the virtualiser fills in for each client.
+#[repr(C)]
+#[derive(Debug)]
+pub struct StorageInfo {
+    pub serial_number: [u8; MAX_SERIAL_NUMBER + 1],
+    pub read_only: bool,
+    ready: AtomicBool,
+    /// In bytes.
+    pub sector_size: u16,
+    /// In units of [`TRANSFER_SIZE`]. The optimal size for transfers.
+    pub block_size: u16,
+    pub queue_depth: u16,
+    pub cylinders: u16,
+    pub heads: u16,
+    pub blocks: u16,
+    /// In units of [`TRANSFER_SIZE`].
+    pub capacity: u64,
+}
+
+impl StorageInfo {
+    /// The other fields are only meaningful once this is `true`.
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Acquire)
+    }
+
+    /// For the driver, once it has filled in the other fields.
+    pub fn set_ready(&self) {
+        self.ready.store(true, Ordering::Release)
+    }
+}
+
+/// Layout of `blk_connection_resource_t`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct ConnectionResource {
+    pub storage_info: RegionResource,
+    pub req_queue: RegionResource,
+    pub resp_queue: RegionResource,
+    pub num_buffers: u16,
+    pub id: u8,
+}
+
+impl ConnectionResource {
+    /// # Safety
+    ///
+    /// This resource must describe valid queues, as it does when it is part of a configuration
+    /// written by the sDDF metaprogram. See [`QueueHandle::new`].
+    pub unsafe fn queue_handle(&self) -> QueueHandle {
+        unsafe {
+            QueueHandle::new(
+                self.req_queue.ptr(),
+                self.resp_queue.ptr(),
+                self.num_buffers.into(),
+            )
+        }
+    }
+
+    /// # Safety
+    ///
+    /// This resource must describe a valid storage information region, as it does when it is part
+    /// of a configuration written by the sDDF metaprogram.
+    pub unsafe fn storage_info(&self) -> &StorageInfo {
+        unsafe { &*self.storage_info.ptr() }
+    }
+}
+
+/// Layout of `blk_client_config_t`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct ClientConfig {
+    pub magic: [u8; MAGIC_LEN],
+    pub virt: ConnectionResource,
+    pub data: RegionResource,
+}
+
+impl ClientConfig {
+    pub fn check_magic(&self) -> bool {
+        self.magic == MAGIC
+    }
+}
+
+/// Layout of `blk_driver_config_t`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct DriverConfig {
+    pub magic: [u8; MAGIC_LEN],
+    pub virt: ConnectionResource,
+}
+
+impl DriverConfig {
+    pub fn check_magic(&self) -> bool {
+        self.magic == MAGIC
+    }
+}
+
+#[cfg(all(test, feature = "sddf-sys"))]
+mod test {
+    use super::*;
+
+    use sddf_sys::{blk_req_code, blk_resp_status};
+
+    use crate::assert_layout_eq;
+
+    #[test]
+    fn layouts() {
+        assert_layout_eq!(
+            RawRequest,
+            sddf_sys::blk_req_t {
+                code,
+                io_or_offset,
+                block_number,
+                count,
+                id,
+            }
+        );
+        assert_layout_eq!(
+            RawResponse,
+            sddf_sys::blk_resp_t {
+                status,
+                success_count,
+                id,
+            }
+        );
+        assert_layout_eq!(
+            RequestQueue,
+            sddf_sys::blk_req_queue_t {
+                head,
+                tail,
+                buffers
+            }
+        );
+        assert_layout_eq!(
+            ResponseQueue,
+            sddf_sys::blk_resp_queue_t {
+                head,
+                tail,
+                buffers
+            }
+        );
+        assert_layout_eq!(
+            StorageInfo,
+            sddf_sys::blk_storage_info_t {
+                serial_number,
+                read_only,
+                ready,
+                sector_size,
+                block_size,
+                queue_depth,
+                cylinders,
+                heads,
+                blocks,
+                capacity,
+            }
+        );
+        assert_layout_eq!(
+            ConnectionResource,
+            sddf_sys::blk_connection_resource_t {
+                storage_info,
+                req_queue,
+                resp_queue,
+                num_buffers,
+                id,
+            }
+        );
+        assert_layout_eq!(
+            ClientConfig,
+            sddf_sys::blk_client_config_t { magic, virt, data }
+        );
+        assert_layout_eq!(DriverConfig, sddf_sys::blk_driver_config_t { magic, virt });
+    }
+
+    #[test]
+    fn constants() {
+        assert_eq!(TRANSFER_SIZE, sddf_sys::BLK_TRANSFER_SIZE as usize);
+        assert_eq!(MAX_SERIAL_NUMBER, sddf_sys::BLK_MAX_SERIAL_NUMBER as usize);
+        for code in [
+            RequestCode::Read,
+            RequestCode::Write,
+            RequestCode::Flush,
+            RequestCode::Barrier,
+        ] {
+            let expected = match code {
+                RequestCode::Read => blk_req_code::BLK_REQ_READ,
+                RequestCode::Write => blk_req_code::BLK_REQ_WRITE,
+                RequestCode::Flush => blk_req_code::BLK_REQ_FLUSH,
+                RequestCode::Barrier => blk_req_code::BLK_REQ_BARRIER,
+            };
+            assert_eq!(code.into_raw(), expected);
+        }
+        for status in [
+            ResponseStatus::Ok,
+            ResponseStatus::UnspecifiedError,
+            ResponseStatus::InvalidParameter,
+            ResponseStatus::NoDevice,
+        ] {
+            let expected = match status {
+                ResponseStatus::Ok => blk_resp_status::BLK_RESP_OK,
+                ResponseStatus::UnspecifiedError => blk_resp_status::BLK_RESP_ERR_UNSPEC,
+                ResponseStatus::InvalidParameter => blk_resp_status::BLK_RESP_ERR_INVALID_PARAM,
+                ResponseStatus::NoDevice => blk_resp_status::BLK_RESP_ERR_NO_DEVICE,
+            };
+            assert_eq!(status.into_raw(), expected);
+        }
+    }
+}
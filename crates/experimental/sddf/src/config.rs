@@ -0,0 +1,28 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+// Configuration structs are written into components' ELF files by the sDDF metaprogram
+// (`sdfgen`), each into a section named like `.serial_client_config`.
+
+pub const MAGIC_LEN: usize = 5;
+
+pub(crate) const fn magic(device_class: u8) -> [u8; MAGIC_LEN] {
+    [b's', b'D', b'D', b'F', device_class]
+}
+
+/// Layout of `region_resource_t`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct RegionResource {
+    pub vaddr: usize,
+    pub size: u64,
+}
+
+impl RegionResource {
+    pub fn ptr<T>(&self) -> *mut T {
+        self.vaddr as *mut T
+    }
+}
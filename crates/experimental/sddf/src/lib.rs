@@ -4,9 +4,63 @@
 // SPDX-License-Identifier: BSD-2-Clause
 //
 
+//! A native Rust implementation of the sDDF shared memory queues and configuration structs.
+//!
+//! This crate does not depend on the sDDF C headers, but its types are layout-compatible with
+//! their C counterparts, so Rust components can interoperate with C components. With the
+//! `sddf-sys` feature (which requires the headers), this crate's tests check that this is the
+//! case.
+//!
+//! Queue handles are constructed from pointers into shared memory regions (for example, those
+//! described by a component's sDDF configuration). Construction is `unsafe`, as it is the caller's
+//! responsibility to ensure that these pointers are valid. Everything else is safe, even in the
+//! face of a misbehaving peer, which can at worst cause [`PeerMisbehaviorError`]s or garbage data.
+
 #![no_std]
 
+mod config;
+mod ring;
+
 pub mod blk;
 pub mod network;
 pub mod serial;
 pub mod timer;
+
+pub use config::{MAGIC_LEN, RegionResource};
+
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct PeerMisbehaviorError(());
+
+impl PeerMisbehaviorError {
+    fn new() -> Self {
+        Self(())
+    }
+}
+
+#[cfg(all(test, feature = "sddf-sys"))]
+macro_rules! assert_layout_eq {
+    ($native:ty, $sys:ty { $($field:ident),* $(,)? }) => {{
+        assert_eq!(core::mem::size_of::<$native>(), core::mem::size_of::<$sys>());
+        assert_eq!(core::mem::align_of::<$native>(), core::mem::align_of::<$sys>());
+        $(
+            assert_eq!(
+                core::mem::offset_of!($native, $field),
+                core::mem::offset_of!($sys, $field),
+                concat!("offset of ", stringify!($field)),
+            );
+        )*
+    }};
+}
+
+#[cfg(all(test, feature = "sddf-sys"))]
+use assert_layout_eq;
+
+#[cfg(all(test, feature = "sddf-sys"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn layouts() {
+        assert_layout_eq!(RegionResource, sddf_sys::region_resource_t { vaddr, size });
+    }
+}
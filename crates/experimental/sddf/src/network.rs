@@ -3,3 +3,263 @@
 //
 // SPDX-License-Identifier: BSD-2-Clause
 //
+
+use core::sync::atomic::AtomicU32;
+
+use crate::config::{MAGIC_LEN, RegionResource, magic};
+use crate::ring::{Ring, SignalFlag};
+
+pub const MAGIC: [u8; MAGIC_LEN] = magic(0x1);
+
+/// Size of each buffer in a data region.
+pub const BUFFER_SIZE: usize = 2048;
+
+/// A buffer in a network data region. Layout of `net_buff_desc_t`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct BuffDesc {
+    /// Offset of the buffer within the data region, or its I/O address.
+    pub io_or_offset: u64,
+    /// Length of the data in the buffer.
+    pub len: u16,
+}
+
+impl BuffDesc {
+    pub const fn new(io_or_offset: u64, len: u16) -> Self {
+        Self { io_or_offset, len }
+    }
+}
+
+/// Layout of `net_queue_t`, which is followed by its buffer descriptors.
+#[repr(C)]
+pub struct Queue {
+    tail: AtomicU32,
+    head: AtomicU32,
+    consumer_signalled: AtomicU32,
+    buffers: [BuffDesc; 0],
+}
+
+/// A pair of free and active queues of [`BuffDesc`]s.
+///
+/// Buffers circulate between the two ends of the pair: producers of data dequeue from `free` and
+/// enqueue to `active`, and consumers dequeue from `active` and enqueue to `free`.
+pub struct QueueHandle {
+    free: Ring<BuffDesc>,
+    active: Ring<BuffDesc>,
+    free_signal: SignalFlag,
+    active_signal: SignalFlag,
+}
+
+impl QueueHandle {
+    /// # Safety
+    ///
+    /// `free` and `active` must point to network queues, each with `capacity` entries, which remain
+    /// valid for the lifetime of this handle. Only one handle may exist for each end of a queue.
+    pub unsafe fn new(free: *mut Queue, active: *mut Queue, capacity: u32) -> Self {
+        unsafe {
+            Self {
+                free: ring(free, capacity),
+                active: ring(active, capacity),
+                free_signal: SignalFlag::new(&raw const (*free).consumer_signalled),
+                active_signal: SignalFlag::new(&raw const (*active).consumer_signalled),
+            }
+        }
+    }
+
+    pub fn capacity(&self) -> u32 {
+        self.free.capacity()
+    }
+
+    /// Fills the free queue with buffers of [`BUFFER_SIZE`] bytes, starting at `base_addr`. Only
+    /// one end of the queue pair should do this, before either end uses it.
+    pub fn buffers_init(&mut self, base_addr: usize) {
+        for i in 0..usize::try_from(self.capacity()).unwrap() {
+            let io_or_offset = (base_addr + i * BUFFER_SIZE).try_into().unwrap();
+            self.enqueue_free(BuffDesc::new(io_or_offset, 0)).unwrap();
+        }
+    }
+
+    pub fn is_empty_free(&self) -> bool {
+        self.free.is_empty()
+    }
+
+    pub fn is_empty_active(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    pub fn is_full_free(&self) -> bool {
+        self.free.is_full()
+    }
+
+    pub fn is_full_active(&self) -> bool {
+        self.active.is_full()
+    }
+
+    /// Returns the buffer if the queue is full.
+    pub fn enqueue_free(&mut self, buffer: BuffDesc) -> Result<(), BuffDesc> {
+        self.free.enqueue(buffer)
+    }
+
+    /// Returns the buffer if the queue is full.
+    pub fn enqueue_active(&mut self, buffer: BuffDesc) -> Result<(), BuffDesc> {
+        self.active.enqueue(buffer)
+    }
+
+    pub fn dequeue_free(&mut self) -> Option<BuffDesc> {
+        self.free.dequeue()
+    }
+
+    pub fn dequeue_active(&mut self) -> Option<BuffDesc> {
+        self.active.dequeue()
+    }
+
+    /// As the consumer of the free queue, asks the producer to signal after its next enqueue.
+    pub fn request_signal_free(&mut self) {
+        self.free_signal.request()
+    }
+
+    /// As the consumer of the active queue, asks the producer to signal after its next enqueue.
+    pub fn request_signal_active(&mut self) {
+        self.active_signal.request()
+    }
+
+    /// As the producer of the free queue, records that the consumer is about to be signalled.
+    pub fn cancel_signal_free(&mut self) {
+        self.free_signal.cancel()
+    }
+
+    /// As the producer of the active queue, records that the consumer is about to be signalled.
+    pub fn cancel_signal_active(&mut self) {
+        self.active_signal.cancel()
+    }
+
+    /// As the producer of the free queue, whether the consumer has asked to be signalled.
+    pub fn require_signal_free(&self) -> bool {
+        self.free_signal.is_required()
+    }
+
+    /// As the producer of the active queue, whether the consumer has asked to be signalled.
+    pub fn require_signal_active(&self) -> bool {
+        self.active_signal.is_required()
+    }
+}
+
+unsafe fn ring(queue: *mut Queue, capacity: u32) -> Ring<BuffDesc> {
+    unsafe {
+        Ring::new(
+            &raw const (*queue).tail,
+            &raw const (*queue).head,
+            (&raw mut (*queue).buffers).cast(),
+            capacity,
+        )
+    }
+}
+
+/// Layout of `net_connection_resource_t`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct ConnectionResource {
+    pub free_queue: RegionResource,
+    pub active_queue: RegionResource,
+    pub num_buffers: u16,
+    pub id: u8,
+}
+
+impl ConnectionResource {
+    /// # Safety
+    ///
+    /// This resource must describe valid queues, as it does when it is part of a configuration
+    /// written by the sDDF metaprogram. See [`QueueHandle::new`].
+    pub unsafe fn queue_handle(&self) -> QueueHandle {
+        unsafe {
+            QueueHandle::new(
+                self.free_queue.ptr(),
+                self.active_queue.ptr(),
+                self.num_buffers.into(),
+            )
+        }
+    }
+}
+
+/// Layout of `net_client_config_t`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct ClientConfig {
+    pub magic: [u8; MAGIC_LEN],
+    pub rx: ConnectionResource,
+    pub rx_data: RegionResource,
+    pub tx: ConnectionResource,
+    pub tx_data: RegionResource,
+    pub mac_addr: [u8; 6],
+}
+
+impl ClientConfig {
+    pub fn check_magic(&self) -> bool {
+        self.magic == MAGIC
+    }
+}
+
+/// Layout of `net_driver_config_t`. The driver produces into the active queue of `virt_rx`, and
+/// consumes from the active queue of `virt_tx`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct DriverConfig {
+    pub magic: [u8; MAGIC_LEN],
+    pub virt_rx: ConnectionResource,
+    pub virt_tx: ConnectionResource,
+}
+
+impl DriverConfig {
+    pub fn check_magic(&self) -> bool {
+        self.magic == MAGIC
+    }
+}
+
+#[cfg(all(test, feature = "sddf-sys"))]
+mod test {
+    use super::*;
+
+    use crate::assert_layout_eq;
+
+    #[test]
+    fn layouts() {
+        assert_layout_eq!(BuffDesc, sddf_sys::net_buff_desc_t { io_or_offset, len });
+        assert_layout_eq!(
+            Queue,
+            sddf_sys::net_queue_t {
+                tail,
+                head,
+                consumer_signalled,
+                buffers,
+            }
+        );
+        assert_layout_eq!(
+            ConnectionResource,
+            sddf_sys::net_connection_resource_t {
+                free_queue,
+                active_queue,
+                num_buffers,
+                id,
+            }
+        );
+        assert_layout_eq!(
+            ClientConfig,
+            sddf_sys::net_client_config_t {
+                magic,
+                rx,
+                rx_data,
+                tx,
+                tx_data,
+                mac_addr,
+            }
+        );
+        assert_layout_eq!(
+            DriverConfig,
+            sddf_sys::net_driver_config_t {
+                magic,
+                virt_rx,
+                virt_tx,
+            }
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use core::ptr;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+/// One end of an sDDF single-producer single-consumer queue.
+///
+/// As in the C implementation, `tail` and `head` are free-running indices into `buffers`, which
+/// has `capacity` entries. The producer publishes entries with a release store to `tail`, and the
+/// consumer releases them with a release store to `head`.
+pub(crate) struct Ring<T> {
+    tail: *const AtomicU32,
+    head: *const AtomicU32,
+    buffers: *mut T,
+    capacity: u32,
+}
+
+impl<T: Copy> Ring<T> {
+    /// # Safety
+    ///
+    /// The pointers must be valid for the lifetime of this value, and `buffers` must have
+    /// `capacity` entries.
+    pub(crate) unsafe fn new(
+        tail: *const AtomicU32,
+        head: *const AtomicU32,
+        buffers: *mut T,
+        capacity: u32,
+    ) -> Self {
+        assert_ne!(capacity, 0);
+        Self {
+            tail,
+            head,
+            buffers,
+            capacity,
+        }
+    }
+
+    pub(crate) fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    fn tail(&self) -> &AtomicU32 {
+        unsafe { &*self.tail }
+    }
+
+    fn head(&self) -> &AtomicU32 {
+        unsafe { &*self.head }
+    }
+
+    fn slot(&self, index: u32) -> *mut T {
+        unsafe {
+            self.buffers
+                .add((index % self.capacity).try_into().unwrap())
+        }
+    }
+
+    /// Saturates at `capacity` if the peer has corrupted the indices.
+    pub(crate) fn len(&self) -> u32 {
+        let tail = self.tail().load(Ordering::Acquire);
+        let head = self.head().load(Ordering::Acquire);
+        tail.wrapping_sub(head).min(self.capacity)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn is_full(&self) -> bool {
+        self.len() == self.capacity
+    }
+
+    /// Enqueues as many entries from `src` as fit, and returns how many that was.
+    pub(crate) fn enqueue_batch(&mut self, src: &[T]) -> usize {
+        let tail = self.tail().load(Ordering::Relaxed);
+        let n = usize::try_from(self.capacity - self.len())
+            .unwrap()
+            .min(src.len());
+        for (i, x) in src[..n].iter().enumerate() {
+            unsafe {
+                ptr::write_volatile(self.slot(tail.wrapping_add(i as u32)), *x);
+            }
+        }
+        self.tail()
+            .store(tail.wrapping_add(n as u32), Ordering::Release);
+        n
+    }
+
+    /// Dequeues entries into `dst` until either it is full or the queue is empty, and returns how
+    /// many were dequeued.
+    pub(crate) fn dequeue_batch(&mut self, dst: &mut [T]) -> usize {
+        let head = self.head().load(Ordering::Relaxed);
+        let n = usize::try_from(self.len()).unwrap().min(dst.len());
+        for (i, x) in dst[..n].iter_mut().enumerate() {
+            *x = unsafe { ptr::read_volatile(self.slot(head.wrapping_add(i as u32))) };
+        }
+        self.head()
+            .store(head.wrapping_add(n as u32), Ordering::Release);
+        n
+    }
+
+    pub(crate) fn enqueue(&mut self, x: T) -> Result<(), T> {
+        match self.enqueue_batch(&[x]) {
+            1 => Ok(()),
+            _ => Err(x),
+        }
+    }
+
+    pub(crate) fn dequeue(&mut self) -> Option<T> {
+        let head = self.head().load(Ordering::Relaxed);
+        if self.is_empty() {
+            return None;
+        }
+        let x = unsafe { ptr::read_volatile(self.slot(head)) };
+        self.head().store(head.wrapping_add(1), Ordering::Release);
+        Some(x)
+    }
+}
+
+/// A flag by which a consumer asks to be signalled. As in the C implementation, it is `0` while
+/// the consumer is waiting.
+pub(crate) struct SignalFlag(*const AtomicU32);
+
+impl SignalFlag {
+    /// # Safety
+    ///
+    /// The pointer must be valid for the lifetime of this value.
+    pub(crate) unsafe fn new(flag: *const AtomicU32) -> Self {
+        Self(flag)
+    }
+
+    fn flag(&self) -> &AtomicU32 {
+        unsafe { &*self.0 }
+    }
+
+    pub(crate) fn request(&mut self) {
+        self.flag().store(0, Ordering::SeqCst)
+    }
+
+    pub(crate) fn cancel(&mut self) {
+        self.flag().store(1, Ordering::SeqCst)
+    }
+
+    pub(crate) fn is_required(&self) -> bool {
+        self.flag().load(Ordering::SeqCst) == 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn wrap_around() {
+        let tail = AtomicU32::new(u32::MAX - 1);
+        let head = AtomicU32::new(u32::MAX - 1);
+        let mut buffers = [0u8; 4];
+        let mut ring = unsafe { Ring::new(&tail, &head, buffers.as_mut_ptr(), 4) };
+        assert!(ring.is_empty());
+        assert_eq!(ring.enqueue_batch(b"abcdef"), 4);
+        assert!(ring.is_full());
+        assert_eq!(ring.enqueue(b'g'), Err(b'g'));
+        assert_eq!(ring.dequeue(), Some(b'a'));
+        let mut dst = [0; 8];
+        assert_eq!(ring.dequeue_batch(&mut dst), 3);
+        assert_eq!(&dst[..3], b"bcd");
+        assert_eq!(ring.dequeue(), None);
+    }
+}
@@ -3,3 +3,218 @@
 //
 // SPDX-License-Identifier: BSD-2-Clause
 //
+
+use core::sync::atomic::AtomicU32;
+
+use crate::config::{MAGIC_LEN, RegionResource, magic};
+use crate::ring::{Ring, SignalFlag};
+
+pub const MAGIC: [u8; MAGIC_LEN] = magic(0x2);
+
+/// Layout of `serial_queue_t`. The bytes in the queue are in a separate data region.
+#[repr(C)]
+pub struct Queue {
+    tail: AtomicU32,
+    head: AtomicU32,
+    producer_signalled: AtomicU32,
+    consumer_signalled: AtomicU32,
+}
+
+/// A queue of bytes.
+///
+/// The producer writes bytes at the tail of the queue, and the consumer reads them from its head.
+/// Batch operations only publish the new tail or head once, after the whole batch.
+pub struct QueueHandle {
+    ring: Ring<u8>,
+    producer_signal: SignalFlag,
+    consumer_signal: SignalFlag,
+}
+
+impl QueueHandle {
+    /// # Safety
+    ///
+    /// `queue` must point to a serial queue, and `data_region` to its data region of `capacity`
+    /// bytes, both of which remain valid for the lifetime of this handle. Only one handle may exist
+    /// for each end of a queue.
+    pub unsafe fn new(queue: *mut Queue, capacity: u32, data_region: *mut u8) -> Self {
+        unsafe {
+            Self {
+                ring: Ring::new(
+                    &raw const (*queue).tail,
+                    &raw const (*queue).head,
+                    data_region,
+                    capacity,
+                ),
+                producer_signal: SignalFlag::new(&raw const (*queue).producer_signalled),
+                consumer_signal: SignalFlag::new(&raw const (*queue).consumer_signalled),
+            }
+        }
+    }
+
+    pub fn capacity(&self) -> u32 {
+        self.ring.capacity()
+    }
+
+    /// The number of bytes in the queue.
+    pub fn len(&self) -> u32 {
+        self.ring.len()
+    }
+
+    /// The number of bytes that can be enqueued before the queue is full.
+    pub fn num_free(&self) -> u32 {
+        self.capacity() - self.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ring.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.ring.is_full()
+    }
+
+    /// Returns the byte if the queue is full.
+    pub fn enqueue(&mut self, c: u8) -> Result<(), u8> {
+        self.ring.enqueue(c)
+    }
+
+    /// Enqueues as many bytes from `src` as fit, and returns how many that was.
+    pub fn enqueue_batch(&mut self, src: &[u8]) -> usize {
+        self.ring.enqueue_batch(src)
+    }
+
+    pub fn dequeue(&mut self) -> Option<u8> {
+        self.ring.dequeue()
+    }
+
+    /// Dequeues bytes into `dst` until either it is full or the queue is empty, and returns how
+    /// many were dequeued.
+    pub fn dequeue_batch(&mut self, dst: &mut [u8]) -> usize {
+        self.ring.dequeue_batch(dst)
+    }
+
+    /// As the consumer, asks the producer to signal after its next enqueue.
+    pub fn request_consumer_signal(&mut self) {
+        self.consumer_signal.request()
+    }
+
+    /// As the producer, records that the consumer is about to be signalled.
+    pub fn cancel_consumer_signal(&mut self) {
+        self.consumer_signal.cancel()
+    }
+
+    /// As the producer, whether the consumer has asked to be signalled.
+    pub fn require_consumer_signal(&self) -> bool {
+        self.consumer_signal.is_required()
+    }
+
+    /// As the producer, asks the consumer to signal after it next frees up space.
+    pub fn request_producer_signal(&mut self) {
+        self.producer_signal.request()
+    }
+
+    /// As the consumer, records that the producer is about to be signalled.
+    pub fn cancel_producer_signal(&mut self) {
+        self.producer_signal.cancel()
+    }
+
+    /// As the consumer, whether the producer has asked to be signalled.
+    pub fn require_producer_signal(&self) -> bool {
+        self.producer_signal.is_required()
+    }
+}
+
+/// Layout of `serial_connection_resource_t`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct ConnectionResource {
+    pub queue: RegionResource,
+    pub data: RegionResource,
+    pub id: u8,
+}
+
+impl ConnectionResource {
+    /// # Safety
+    ///
+    /// This resource must describe a valid queue, as it does when it is part of a configuration
+    /// written by the sDDF metaprogram. See [`QueueHandle::new`].
+    pub unsafe fn queue_handle(&self) -> QueueHandle {
+        unsafe {
+            QueueHandle::new(
+                self.queue.ptr(),
+                self.data.size.try_into().unwrap(),
+                self.data.ptr(),
+            )
+        }
+    }
+}
+
+/// Layout of `serial_client_config_t`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct ClientConfig {
+    pub magic: [u8; MAGIC_LEN],
+    pub rx: ConnectionResource,
+    pub tx: ConnectionResource,
+}
+
+impl ClientConfig {
+    pub fn check_magic(&self) -> bool {
+        self.magic == MAGIC
+    }
+}
+
+/// Layout of `serial_driver_config_t`. The driver produces into `rx` and consumes from `tx`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct DriverConfig {
+    pub magic: [u8; MAGIC_LEN],
+    pub rx: ConnectionResource,
+    pub tx: ConnectionResource,
+    pub default_baud: u64,
+    pub rx_enabled: bool,
+}
+
+impl DriverConfig {
+    pub fn check_magic(&self) -> bool {
+        self.magic == MAGIC
+    }
+}
+
+#[cfg(all(test, feature = "sddf-sys"))]
+mod test {
+    use super::*;
+
+    use crate::assert_layout_eq;
+
+    #[test]
+    fn layouts() {
+        assert_layout_eq!(
+            Queue,
+            sddf_sys::serial_queue_t {
+                tail,
+                head,
+                producer_signalled,
+                consumer_signalled,
+            }
+        );
+        assert_layout_eq!(
+            ConnectionResource,
+            sddf_sys::serial_connection_resource_t { queue, data, id }
+        );
+        assert_layout_eq!(
+            ClientConfig,
+            sddf_sys::serial_client_config_t { magic, rx, tx }
+        );
+        assert_layout_eq!(
+            DriverConfig,
+            sddf_sys::serial_driver_config_t {
+                magic,
+                rx,
+                tx,
+                default_baud,
+                rx_enabled,
+            }
+        );
+    }
+}
@@ -3,3 +3,51 @@
 //
 // SPDX-License-Identifier: BSD-2-Clause
 //
+
+// Timer clients make protected procedure calls to the timer driver, with the time in nanoseconds
+// in the first message register.
+
+use crate::config::{MAGIC_LEN, magic};
+
+pub const MAGIC: [u8; MAGIC_LEN] = magic(0x3);
+
+/// Label of a call which returns the current time.
+pub const GET_TIME: u64 = 0;
+
+/// Label of a call which requests a notification at the given time.
+pub const SET_TIMEOUT: u64 = 1;
+
+/// Layout of `timer_client_config_t`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct ClientConfig {
+    pub magic: [u8; MAGIC_LEN],
+    pub driver_id: u8,
+}
+
+impl ClientConfig {
+    pub fn check_magic(&self) -> bool {
+        self.magic == MAGIC
+    }
+}
+
+#[cfg(all(test, feature = "sddf-sys"))]
+mod test {
+    use super::*;
+
+    use crate::assert_layout_eq;
+
+    #[test]
+    fn layouts() {
+        assert_layout_eq!(
+            ClientConfig,
+            sddf_sys::timer_client_config_t { magic, driver_id }
+        );
+    }
+
+    #[test]
+    fn constants() {
+        assert_eq!(GET_TIME, sddf_sys::SDDF_TIMER_GET_TIME.into());
+        assert_eq!(SET_TIMEOUT, sddf_sys::SDDF_TIMER_SET_TIMEOUT.into());
+    }
+}
@@ -51,3 +51,42 @@ impl<R: RawMutex, T: Deref<Target = Mutex<R, U>>, U: GetBlockDeviceLayout> GetBl
         self.with_mut(|this| this.get_num_blocks())
     }
 }
+
+/// Synchronous access to a block device, in units of its block size.
+pub trait BlockIO: GetBlockDeviceLayout {
+    /// `buf.len()` must be a multiple of the block size.
+    fn read_blocks(&mut self, start_block: u64, buf: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// `buf.len()` must be a multiple of the block size.
+    fn write_blocks(&mut self, start_block: u64, buf: &[u8]) -> Result<(), Self::Error>;
+
+    fn flush(&mut self) -> Result<(), Self::Error>;
+}
+
+impl<T: Deref<Target = RefCell<U>>, U: BlockIO> BlockIO for &WrappedRefCell<T> {
+    fn read_blocks(&mut self, start_block: u64, buf: &mut [u8]) -> Result<(), Self::Error> {
+        self.with_mut(|this| this.read_blocks(start_block, buf))
+    }
+
+    fn write_blocks(&mut self, start_block: u64, buf: &[u8]) -> Result<(), Self::Error> {
+        self.with_mut(|this| this.write_blocks(start_block, buf))
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.with_mut(|this| this.flush())
+    }
+}
+
+impl<R: RawMutex, T: Deref<Target = Mutex<R, U>>, U: BlockIO> BlockIO for &WrappedMutex<T> {
+    fn read_blocks(&mut self, start_block: u64, buf: &mut [u8]) -> Result<(), Self::Error> {
+        self.with_mut(|this| this.read_blocks(start_block, buf))
+    }
+
+    fn write_blocks(&mut self, start_block: u64, buf: &[u8]) -> Result<(), Self::Error> {
+        self.with_mut(|this| this.write_blocks(start_block, buf))
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.with_mut(|this| this.flush())
+    }
+}
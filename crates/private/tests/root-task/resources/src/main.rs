@@ -0,0 +1,105 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use sel4_resources::{Error, ObjectAllocator, SlotAllocator, UntypedAllocator};
+use sel4_root_task::root_task;
+
+const POOL_SIZE_BITS: usize = 16;
+
+#[root_task(heap_size = 1024 * 64)]
+fn main(bootinfo: &sel4::BootInfoPtr) -> ! {
+    let mut allocator = pool_allocator(bootinfo);
+
+    test_reallocate(&mut allocator);
+    for _ in 0..4 {
+        test_coalesce(&mut allocator);
+    }
+
+    sel4_test_root_task::indicate_success()
+}
+
+/// Returns an allocator backed by a single untyped of `POOL_SIZE_BITS`, so that its state is known
+/// exactly.
+fn pool_allocator(bootinfo: &sel4::BootInfo) -> ObjectAllocator {
+    let cnode = sel4::init_thread::slot::CNODE.cap();
+    let empty = bootinfo.empty().range();
+    let mid = empty.start + empty.len() / 2;
+    let mut outer = ObjectAllocator::new(
+        SlotAllocator::new(cnode, empty.start..mid),
+        UntypedAllocator::from_bootinfo(bootinfo),
+    );
+    let pool = outer
+        .allocate_variable_sized::<sel4::cap_type::Untyped>(POOL_SIZE_BITS)
+        .unwrap();
+    let mut untyped = UntypedAllocator::new();
+    untyped.add(pool, POOL_SIZE_BITS);
+    ObjectAllocator::new(SlotAllocator::new(cnode, mid..empty.end), untyped)
+}
+
+fn test_reallocate(allocator: &mut ObjectAllocator) {
+    let nfn = allocator
+        .allocate_fixed_sized::<sel4::cap_type::Notification>()
+        .unwrap();
+    let ep = allocator
+        .allocate_fixed_sized::<sel4::cap_type::Endpoint>()
+        .unwrap();
+    let cnode = allocator
+        .allocate_variable_sized::<sel4::cap_type::CNode>(4)
+        .unwrap();
+    allocator.free(ep).unwrap();
+    assert_eq!(allocator.free(ep), Err(Error::NotAllocated));
+
+    let ep = allocator
+        .allocate_fixed_sized::<sel4::cap_type::Endpoint>()
+        .unwrap();
+    for obj in [
+        nfn.cast::<sel4::cap_type::Unspecified>(),
+        ep.cast(),
+        cnode.cast(),
+    ] {
+        allocator.free(obj).unwrap();
+    }
+}
+
+/// Fills the pool with granules, frees them, and then allocates the pool's entire size at once,
+/// which is only possible if the freed granules have been coalesced.
+fn test_coalesce(allocator: &mut ObjectAllocator) {
+    let num_free_slots = allocator.slots().num_free();
+    assert_eq!(allocator.untyped().num_free_bytes(), 1 << POOL_SIZE_BITS);
+
+    let mut frames = Vec::new();
+    loop {
+        match allocator.allocate_fixed_sized::<sel4::cap_type::Granule>() {
+            Ok(frame) => frames.push(frame),
+            Err(Error::OutOfMemory) => break,
+            Err(err) => panic!("{err:?}"),
+        }
+    }
+    assert_eq!(
+        frames.len(),
+        1 << (POOL_SIZE_BITS - sel4::FrameObjectType::GRANULE.bits())
+    );
+    assert_eq!(allocator.untyped().num_free_bytes(), 0);
+
+    for frame in frames {
+        allocator.free(frame).unwrap();
+    }
+    assert_eq!(allocator.untyped().num_free_bytes(), 1 << POOL_SIZE_BITS);
+    // The slots of split parents are returned along with them.
+    assert_eq!(allocator.slots().num_free(), num_free_slots);
+
+    let whole = allocator
+        .allocate_variable_sized::<sel4::cap_type::Untyped>(POOL_SIZE_BITS)
+        .unwrap();
+    allocator.free(whole).unwrap();
+}
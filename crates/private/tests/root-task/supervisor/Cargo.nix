@@ -0,0 +1,21 @@
+#
+# Copyright 2026, Colias Group, LLC
+#
+# SPDX-License-Identifier: BSD-2-Clause
+#
+
+{ mk, localCrates }:
+
+mk {
+  package.name = "tests-root-task-supervisor";
+  dependencies = {
+    inherit (localCrates)
+      sel4
+      sel4-resources
+      sel4-root-task
+      sel4-stack
+      sel4-supervisor
+      sel4-test-root-task
+    ;
+  };
+}
@@ -0,0 +1,288 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::vec::Vec;
+use core::ptr;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use sel4_resources::ObjectAllocator;
+use sel4_root_task::root_task;
+use sel4_stack::Stack;
+use sel4_supervisor::{Action, FaultReport, Policy, Restart, Supervisor};
+
+const NUM_CHILDREN: usize = 3;
+
+const RESTART_LIMIT: usize = 3;
+
+const CHILD_PRIORITY: sel4::Word = 254;
+
+static CHILD_STACKS: [Stack<4096>; NUM_CHILDREN] = [const { Stack::new() }; NUM_CHILDREN];
+
+static CHILD_RUNS: [AtomicUsize; NUM_CHILDREN] = [const { AtomicUsize::new(0) }; NUM_CHILDREN];
+
+#[root_task(heap_size = 1024 * 64)]
+fn main(bootinfo: &sel4::BootInfoPtr) -> ! {
+    let mut allocator = ObjectAllocator::from_bootinfo(bootinfo);
+    let fault_addr = init_fault_page(bootinfo);
+    let endpoint = allocator
+        .allocate_fixed_sized::<sel4::cap_type::Endpoint>()
+        .unwrap();
+    let reply_authority = reply_authority(&mut allocator);
+    let mut env = Env {
+        bootinfo,
+        allocator,
+        supervisor: Supervisor::new(endpoint, reply_authority),
+        fault_addr,
+    };
+
+    test_restart(&mut env);
+    test_suspend(&mut env);
+    test_escalate(&mut env);
+
+    sel4_test_root_task::indicate_success()
+}
+
+struct Env<'a> {
+    bootinfo: &'a sel4::BootInfo,
+    allocator: ObjectAllocator,
+    supervisor: Supervisor<ReplyAuthority>,
+    fault_addr: sel4::Word,
+}
+
+// The child is restarted at its entry point rather than at `_reset`, which is fine because it
+// needs only a few bytes of whatever stack it faulted with.
+fn test_restart(env: &mut Env) {
+    let child = 0;
+    let badge = badge(child);
+    let tcb = spawn_child(
+        env,
+        child,
+        Policy::Restart(Restart {
+            entry: child_entry as *const () as sel4::Word,
+            args: [child.try_into().unwrap(), 0, 0, 0],
+            limit: Some(RESTART_LIMIT),
+        }),
+    );
+
+    let mut actions = Vec::new();
+    let report = env
+        .supervisor
+        .run(|report, action| {
+            assert_eq!(report.badge(), badge);
+            actions.push(action);
+        })
+        .unwrap();
+    assert_eq!(actions[..RESTART_LIMIT], [Action::Restarted; RESTART_LIMIT]);
+    assert_eq!(actions[RESTART_LIMIT..], [Action::Escalated]);
+    assert_eq!(env.supervisor.restarts(badge), Some(RESTART_LIMIT));
+    assert_eq!(CHILD_RUNS[child].load(Ordering::SeqCst), RESTART_LIMIT + 1);
+
+    assert_eq!(report.addr(), Some(env.fault_addr));
+    let expected = format!(
+        "thread with badge {badge:#x}: VM fault on data access at {:#x}",
+        env.fault_addr
+    );
+    let displayed = format!("{report}");
+    assert!(displayed.starts_with(&expected), "{displayed}");
+
+    tcb.tcb_suspend().unwrap();
+}
+
+fn test_suspend(env: &mut Env) {
+    let child = 1;
+    let badge = badge(child);
+    let tcb = spawn_child(env, child, Policy::Suspend);
+
+    let report = env.supervisor.recv();
+    assert_eq!(report.badge(), badge);
+    assert_eq!(env.supervisor.handle(&report), Ok(Action::Suspended));
+
+    // Were the child still blocked on its fault, resuming it would have no effect. Instead, it
+    // retries the faulting access.
+    tcb.tcb_resume().unwrap();
+    let report = env.supervisor.recv();
+    assert_eq!(report.badge(), badge);
+    assert_eq!(env.supervisor.handle(&report), Ok(Action::Suspended));
+    assert_eq!(CHILD_RUNS[child].load(Ordering::SeqCst), 1);
+}
+
+fn test_escalate(env: &mut Env) {
+    let child = 2;
+    let badge = badge(child);
+    let tcb = spawn_child(env, child, Policy::Escalate);
+
+    let mut actions = Vec::new();
+    let report = env
+        .supervisor
+        .run(|_, action| actions.push(action))
+        .unwrap();
+    assert_eq!(report.badge(), badge);
+    assert_eq!(actions, [Action::Escalated]);
+
+    // Faults from threads which are not supervised are escalated too.
+    assert_eq!(env.supervisor.remove(badge), Some(tcb));
+    assert_eq!(env.supervisor.handle(&report), Ok(Action::Escalated));
+    let unknown = FaultReport::new(badge + 100, report.fault().clone());
+    assert_eq!(env.supervisor.handle(&unknown), Ok(Action::Escalated));
+
+    tcb.tcb_suspend().unwrap();
+}
+
+// // //
+
+unsafe extern "C" fn child_entry(child: sel4::Word) -> ! {
+    CHILD_RUNS[usize::try_from(child).unwrap()].fetch_add(1, Ordering::SeqCst);
+    unsafe {
+        ptr::read_volatile(ptr::addr_of!(FAULT_PAGE).cast::<u8>());
+    }
+    unreachable!()
+}
+
+fn badge(child: usize) -> sel4::Badge {
+    (child + 1).try_into().unwrap()
+}
+
+/// Starts a thread in this address space, without an IPC buffer or TLS, which faults as soon as
+/// it runs.
+fn spawn_child(env: &mut Env, child: usize, policy: Policy) -> sel4::cap::Tcb {
+    let allocator = &mut env.allocator;
+    let tcb = allocator
+        .allocate_fixed_sized::<sel4::cap_type::Tcb>()
+        .unwrap();
+
+    let fault_endpoint_slot = allocator.slots().allocate().unwrap();
+    allocator
+        .slots()
+        .absolute_cptr(fault_endpoint_slot)
+        .mint(
+            &allocator
+                .slots()
+                .cnode()
+                .absolute_cptr(env.supervisor.endpoint()),
+            sel4::CapRights::all(),
+            badge(child),
+        )
+        .unwrap();
+
+    let cnode = sel4::init_thread::slot::CNODE.cap();
+    let vspace = sel4::init_thread::slot::VSPACE.cap();
+    let authority = sel4::init_thread::slot::TCB.cap();
+    let no_ipc_buffer = sel4::init_thread::slot::NULL.cap().cast();
+    sel4::sel4_cfg_if! {
+        if #[sel4_cfg(KERNEL_MCS)] {
+            let sched_context = allocator
+                .allocate_variable_sized::<sel4::cap_type::SchedContext>(
+                    usize::try_from(sel4::sys::seL4_MinSchedContextBits).unwrap(),
+                )
+                .unwrap();
+            env.bootinfo
+                .sched_control()
+                .index(0)
+                .cap()
+                .sched_control_configure_flags(sched_context, 1000, 1000, 0, 0, 0)
+                .unwrap();
+            tcb.tcb_configure(cnode, sel4::CNodeCapData::new(0, 0), vspace, 0, no_ipc_buffer)
+                .unwrap();
+            tcb.tcb_set_sched_params(
+                authority,
+                0,
+                CHILD_PRIORITY,
+                sched_context,
+                allocator.slots().cap(fault_endpoint_slot).cast(),
+            )
+            .unwrap();
+        } else {
+            let _ = env.bootinfo;
+            tcb.tcb_configure(
+                sel4::CPtr::from_bits(fault_endpoint_slot.try_into().unwrap()),
+                cnode,
+                sel4::CNodeCapData::new(0, 0),
+                vspace,
+                0,
+                no_ipc_buffer,
+            )
+            .unwrap();
+            tcb.tcb_set_sched_params(authority, 0, CHILD_PRIORITY).unwrap();
+        }
+    }
+
+    env.supervisor.add(badge(child), tcb, policy);
+
+    let mut ctx = sel4::UserContext::default();
+    *ctx.sp_mut() = (CHILD_STACKS[child].bottom().ptr() as usize)
+        .try_into()
+        .unwrap();
+    *ctx.pc_mut() = (child_entry as *const () as usize).try_into().unwrap();
+    *ctx.c_param_mut(0) = child.try_into().unwrap();
+    #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+    {
+        ctx.inner_mut().gp = riscv_get_gp();
+    }
+    tcb.tcb_write_all_registers(true, &mut ctx).unwrap();
+
+    tcb
+}
+
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+fn riscv_get_gp() -> sel4::Word {
+    let val: sel4::Word;
+    unsafe {
+        core::arch::asm!("mv {}, gp", out(reg) val);
+    }
+    val
+}
+
+sel4::sel4_cfg_if! {
+    if #[sel4_cfg(KERNEL_MCS)] {
+        type ReplyAuthority = sel4::cap::Reply;
+
+        fn reply_authority(allocator: &mut ObjectAllocator) -> ReplyAuthority {
+            allocator.allocate_fixed_sized::<sel4::cap_type::Reply>().unwrap()
+        }
+    } else {
+        type ReplyAuthority = ();
+
+        fn reply_authority(_allocator: &mut ObjectAllocator) -> ReplyAuthority {}
+    }
+}
+
+// // //
+
+#[repr(C, align(4096))]
+struct FaultPage(#[allow(dead_code)] [u8; GRANULE_SIZE]);
+
+static FAULT_PAGE: FaultPage = FaultPage([0; GRANULE_SIZE]);
+
+/// Unmaps the page backing `FAULT_PAGE`, so that accessing it faults.
+fn init_fault_page(bootinfo: &sel4::BootInfo) -> sel4::Word {
+    let addr = ptr::addr_of!(FAULT_PAGE) as usize;
+    get_user_image_frame_slot(bootinfo, addr)
+        .cap()
+        .frame_unmap()
+        .unwrap();
+    addr.try_into().unwrap()
+}
+
+fn get_user_image_frame_slot(
+    bootinfo: &sel4::BootInfo,
+    addr: usize,
+) -> sel4::init_thread::Slot<sel4::cap_type::Granule> {
+    unsafe extern "C" {
+        static __executable_start: usize;
+    }
+    let user_image_addr = ptr::addr_of!(__executable_start) as usize;
+    bootinfo
+        .user_image_frames()
+        .index(addr / GRANULE_SIZE - user_image_addr / GRANULE_SIZE)
+}
+
+const GRANULE_SIZE: usize = sel4::FrameObjectType::GRANULE.bytes();
@@ -0,0 +1,22 @@
+#
+# Copyright 2026, Colias Group, LLC
+#
+# SPDX-License-Identifier: BSD-2-Clause
+#
+
+{ mk, localCrates }:
+
+mk {
+  package.name = "tests-root-task-thread";
+  dependencies = {
+    inherit (localCrates)
+      sel4
+      sel4-initialize-tls
+      sel4-phdrs
+      sel4-resources
+      sel4-root-task
+      sel4-test-root-task
+      sel4-thread
+    ;
+  };
+}
@@ -0,0 +1,174 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+use core::ptr;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use sel4_initialize_tls::{TlsImage, UncheckedTlsImage};
+use sel4_phdrs::{PT_TLS, locate_phdrs};
+use sel4_resources::ObjectAllocator;
+use sel4_root_task::{abort, root_task, set_global_allocator_mutex_notification};
+use sel4_thread::{Builder, Config, Error, Panicked, Runtime};
+
+const NUM_IPC_BUFFERS: usize = 2;
+
+#[root_task(heap_size = 1024 * 256)]
+fn main(bootinfo: &sel4::BootInfoPtr) -> ! {
+    let mut allocator = ObjectAllocator::from_bootinfo(bootinfo);
+
+    set_global_allocator_mutex_notification(
+        allocator
+            .allocate_fixed_sized::<sel4::cap_type::Notification>()
+            .unwrap(),
+    );
+
+    Runtime::init(allocator, config(bootinfo)).unwrap();
+
+    assert_eq!(sel4_thread::current().name(), Some("main"));
+
+    test_ipc_buffer_region_exhaustion();
+    test_resources_are_freed();
+    test_park();
+    test_panic();
+
+    sel4_test_root_task::indicate_success()
+}
+
+fn test_ipc_buffer_region_exhaustion() {
+    let handles = (0..NUM_IPC_BUFFERS)
+        .map(|i| sel4_thread::spawn(move || i))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        Builder::new().spawn(|| ()).err(),
+        Some(Error::OutOfIpcBufferRegion)
+    );
+    for (i, handle) in handles.into_iter().enumerate() {
+        assert_eq!(handle.join(), Ok(i));
+    }
+}
+
+// Spawning more threads than there are IPC buffers requires that joined threads' resources are
+// freed.
+fn test_resources_are_freed() {
+    for i in 0..16 {
+        let handles = (0..NUM_IPC_BUFFERS)
+            .map(|j| sel4_thread::spawn(move || i * j))
+            .collect::<Vec<_>>();
+        for (j, handle) in handles.into_iter().enumerate() {
+            assert_eq!(handle.join(), Ok(i * j));
+        }
+    }
+}
+
+fn test_park() {
+    static UNPARKED: AtomicBool = AtomicBool::new(false);
+
+    let handle = Builder::new()
+        .name("parked".into())
+        .spawn(|| {
+            while !UNPARKED.load(Ordering::Acquire) {
+                sel4_thread::park();
+            }
+            sel4_thread::current().name().map(String::from)
+        })
+        .unwrap();
+    UNPARKED.store(true, Ordering::Release);
+    handle.thread().unpark();
+    assert_eq!(handle.join().unwrap().as_deref(), Some("parked"));
+}
+
+fn test_panic() {
+    let handle = sel4_thread::spawn(|| panic!("test"));
+    assert_eq!(handle.join(), Err::<(), _>(Panicked));
+}
+
+// // //
+
+fn config(bootinfo: &sel4::BootInfo) -> Config {
+    let ipc_buffer_region = init_ipc_buffer_region(bootinfo);
+    sel4::sel4_cfg_if! {
+        if #[sel4_cfg(KERNEL_MCS)] {
+            let config = Config {
+                authority: sel4::init_thread::slot::TCB.cap(),
+                priority: 254,
+                sched_control: bootinfo.sched_control().index(0).cap(),
+                budget: 1000,
+                period: 1000,
+                vspace: sel4::init_thread::slot::VSPACE.cap(),
+                ipc_buffer_region,
+                tls_image: get_tls_image(),
+                default_stack_size: 4096 * 4,
+            };
+        } else {
+            let config = Config {
+                authority: sel4::init_thread::slot::TCB.cap(),
+                priority: 254,
+                vspace: sel4::init_thread::slot::VSPACE.cap(),
+                ipc_buffer_region,
+                tls_image: get_tls_image(),
+                default_stack_size: 4096 * 4,
+            };
+        }
+    }
+    config
+}
+
+fn get_tls_image() -> TlsImage {
+    let phdr = locate_phdrs()
+        .unwrap_or_else(|err| abort!("{err}"))
+        .find_by_type(PT_TLS)
+        .unwrap_or_else(|| abort!("missing PT_TLS program header"));
+    let unchecked = UncheckedTlsImage {
+        vaddr: phdr.p_vaddr,
+        filesz: phdr.p_filesz,
+        memsz: phdr.p_memsz,
+        align: phdr.p_align,
+    };
+    unchecked.check().unwrap()
+}
+
+// // //
+
+#[repr(C, align(4096))]
+struct IpcBufferRegionPlaceholder(#[allow(dead_code)] [u8; NUM_IPC_BUFFERS * GRANULE_SIZE]);
+
+static mut IPC_BUFFER_REGION_PLACEHOLDER: IpcBufferRegionPlaceholder =
+    IpcBufferRegionPlaceholder([0; NUM_IPC_BUFFERS * GRANULE_SIZE]);
+
+fn init_ipc_buffer_region(bootinfo: &sel4::BootInfo) -> Range<usize> {
+    let start = ptr::addr_of!(IPC_BUFFER_REGION_PLACEHOLDER) as usize;
+    let end = start + NUM_IPC_BUFFERS * GRANULE_SIZE;
+    for addr in (start..end).step_by(GRANULE_SIZE) {
+        get_user_image_frame_slot(bootinfo, addr)
+            .cap()
+            .frame_unmap()
+            .unwrap();
+    }
+    start..end
+}
+
+fn get_user_image_frame_slot(
+    bootinfo: &sel4::BootInfo,
+    addr: usize,
+) -> sel4::init_thread::Slot<sel4::cap_type::Granule> {
+    unsafe extern "C" {
+        static __executable_start: usize;
+    }
+    let user_image_addr = ptr::addr_of!(__executable_start) as usize;
+    bootinfo
+        .user_image_frames()
+        .index(addr / GRANULE_SIZE - user_image_addr / GRANULE_SIZE)
+}
+
+const GRANULE_SIZE: usize = sel4::FrameObjectType::GRANULE.bytes();
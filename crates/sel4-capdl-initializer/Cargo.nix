@@ -33,5 +33,16 @@ mk {
     deflate = [
       "sel4-capdl-initializer-types/deflate"
     ];
+    lz4 = [
+      "sel4-capdl-initializer-types/lz4"
+    ];
+    measurement = [
+      "sel4-capdl-initializer-types/measurement"
+    ];
+    # The zstd decoder needs a heap
+    zstd = [
+      "alloc"
+      "sel4-capdl-initializer-types/zstd"
+    ];
   };
 }
@@ -11,6 +11,7 @@ mk {
   dependencies = {
     inherit (versions)
       anyhow
+      hex
       serde_json
       num
       rkyv
@@ -26,6 +27,7 @@ mk {
         "serde"
         "deflate"
         "transform"
+        "measurement"
       ];
     };
   };
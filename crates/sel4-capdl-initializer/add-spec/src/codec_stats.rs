@@ -0,0 +1,82 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use sel4_capdl_initializer_types::*;
+
+use super::{GRANULE_SIZE_BITS, ObjectNamesLevel, reserialize_spec};
+
+/// How each codec would fare on the fill data that would not be embedded as whole frames.
+///
+/// Decode times are measured on the host, and so are only useful for comparing codecs with each
+/// other.
+#[derive(Debug, Clone)]
+pub struct CodecStats {
+    pub codec: Codec,
+    pub num_entries: usize,
+    pub raw_bytes: usize,
+    pub encoded_bytes: usize,
+    pub decode_time: Duration,
+}
+
+pub fn codec_stats(
+    spec: &InputSpec,
+    fill_dirs: &[impl AsRef<Path>],
+    embed_frames: bool,
+) -> Vec<CodecStats> {
+    let (output_spec, _) = reserialize_spec::reserialize_spec(
+        spec,
+        fill_dirs,
+        &ObjectNamesLevel::None,
+        embed_frames,
+        Codec::None,
+        0,
+        GRANULE_SIZE_BITS,
+    );
+
+    let raw_contents = output_spec
+        .objects
+        .iter()
+        .filter_map(|named_obj| match &named_obj.object {
+            Object::Frame(frame) => match &frame.init {
+                FrameInit::Fill(fill) => Some(fill.entries.iter()),
+                FrameInit::Embedded(_) => None,
+            },
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|entry| match &entry.content {
+            FillEntryContent::Data(Content::Bytes(bytes)) => Some(&bytes.bytes),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    Codec::ALL
+        .into_iter()
+        .map(|codec| {
+            let mut stats = CodecStats {
+                codec,
+                num_entries: raw_contents.len(),
+                raw_bytes: 0,
+                encoded_bytes: 0,
+                decode_time: Duration::ZERO,
+            };
+            for raw in raw_contents.iter() {
+                let packed = Content::pack(codec, raw);
+                let mut buf = vec![0; raw.len()];
+                let start = Instant::now();
+                packed.copy_out(&mut buf);
+                stats.decode_time += start.elapsed();
+                assert_eq!(&buf, *raw);
+                stats.raw_bytes += raw.len();
+                stats.encoded_bytes += packed.encoded_len();
+            }
+            stats
+        })
+        .collect()
+}
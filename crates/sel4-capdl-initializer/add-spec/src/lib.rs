@@ -4,40 +4,80 @@
 // SPDX-License-Identifier: BSD-2-Clause
 //
 
+use std::collections::BTreeSet;
 use std::path::Path;
 
+use anyhow::{Result, anyhow, bail};
+use object::{Object as _, ObjectSegment as _, ObjectSymbol as _};
 use rkyv::util::AlignedVec;
 
-use sel4_capdl_initializer_types::InputSpec;
+use sel4_capdl_initializer_types::measurement::ExpectedMeasurement;
+use sel4_capdl_initializer_types::{
+    Codec, Content, DedupStats, FrameInit, InputSpec, Object, SUPPORTED_CODECS_SYMBOL,
+    SpecForInitializer,
+};
 use sel4_patch_elf::dynamic::Patching;
 use sel4_phdrs_constants::{PT_SEL4_CAPDL_FRAME_DATA, PT_SEL4_CAPDL_SPEC};
 
+mod codec_stats;
 mod reserialize_spec;
 
+pub use codec_stats::{CodecStats, codec_stats};
+
 // HACK hardcoded
 const GRANULE_SIZE_BITS: u8 = 12;
 
 type ArchiveAlignedVec = AlignedVec;
 
+#[allow(clippy::too_many_arguments)]
 pub fn add_spec(
     initializer_without_spec: &[u8],
     spec: &InputSpec,
     fill_dirs: &[impl AsRef<Path>],
     object_names_level: &ObjectNamesLevel,
     embed_frames: bool,
-    deflate: bool,
+    dedup_embedded_frames: bool,
+    codec: Codec,
     initializer_verbosity: u8,
-) -> Vec<u8> {
-    let (output_spec, embedded_frame_data_list) = reserialize_spec::reserialize_spec(
+) -> Result<AddSpecOutput> {
+    let parsed = object::File::parse(initializer_without_spec)?;
+
+    let supported_codecs = supported_codecs(&parsed)?;
+
+    let (mut output_spec, mut embedded_frame_data_list) = reserialize_spec::reserialize_spec(
         spec,
         fill_dirs,
         object_names_level,
         embed_frames,
-        deflate,
+        codec,
         initializer_verbosity,
         GRANULE_SIZE_BITS,
     );
 
+    for used in used_codecs(&output_spec) {
+        if supported_codecs & used.bit() == 0 {
+            bail!(
+                "spec uses codec '{}', which the initializer does not support (supported: {})",
+                used.name(),
+                Codec::supported_in(supported_codecs)
+                    .map(|codec| codec.name())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            );
+        }
+    }
+
+    let dedup_stats = dedup_embedded_frames.then(|| {
+        let (deduped, stats) = output_spec.dedup_embedded_frames(&embedded_frame_data_list);
+        embedded_frame_data_list = deduped;
+        stats
+    });
+
+    let expected_measurements = output_spec
+        .measurement_log
+        .is_some()
+        .then(|| output_spec.expected_measurements(&embedded_frame_data_list));
+
     let spec_data: ArchiveAlignedVec = output_spec.to_bytes().unwrap();
 
     let embedded_frame_data = embedded_frame_data_list
@@ -45,8 +85,6 @@ pub fn add_spec(
         .flatten()
         .collect::<Vec<_>>();
 
-    let parsed = object::File::parse(initializer_without_spec).unwrap();
-
     let mut patching = Patching::new(&parsed);
 
     patching.add_data_segment_with_meta_phdr(
@@ -61,7 +99,49 @@ pub fn add_spec(
         &spec_data,
     );
 
-    patching.finalize()
+    Ok(AddSpecOutput {
+        elf: patching.finalize(),
+        dedup_stats,
+        expected_measurements,
+    })
+}
+
+fn supported_codecs(elf: &object::File) -> Result<u32> {
+    let symbol = elf
+        .symbol_by_name(SUPPORTED_CODECS_SYMBOL)
+        .ok_or_else(|| anyhow!("initializer does not record its supported codecs"))?;
+    let bytes = elf
+        .segments()
+        .find_map(|segment| segment.data_range(symbol.address(), 4).ok().flatten())
+        .ok_or_else(|| anyhow!("symbol {SUPPORTED_CODECS_SYMBOL} is not backed by file data"))?;
+    let bytes = bytes.try_into().unwrap();
+    Ok(if elf.is_little_endian() {
+        u32::from_le_bytes(bytes)
+    } else {
+        u32::from_be_bytes(bytes)
+    })
+}
+
+fn used_codecs(spec: &SpecForInitializer) -> BTreeSet<Codec> {
+    spec.objects
+        .iter()
+        .filter_map(|named_obj| match &named_obj.object {
+            Object::Frame(frame) => match &frame.init {
+                FrameInit::Fill(fill) => Some(fill.entries.iter()),
+                FrameInit::Embedded(_) => None,
+            },
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|entry| entry.content.as_data().map(Content::codec))
+        .collect()
+}
+
+pub struct AddSpecOutput {
+    pub elf: Vec<u8>,
+    pub dedup_stats: Option<DedupStats>,
+    /// Present if the spec has a measurement log
+    pub expected_measurements: Option<Vec<ExpectedMeasurement>>,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -5,16 +5,23 @@
 //
 
 use std::fs;
+use std::path::Path;
 
-use anyhow::Result;
-use clap::Parser;
+use anyhow::{Result, anyhow, bail};
+use clap::{Parser, ValueEnum};
+use serde_json::json;
 
-use sel4_capdl_initializer_add_spec::{ObjectNamesLevel, add_spec};
+use sel4_capdl_initializer_add_spec::{ObjectNamesLevel, add_spec, codec_stats};
+use sel4_capdl_initializer_types::measurement::{
+    ExpectedMeasurement, expected_aggregate, measurement_log_size,
+};
+use sel4_capdl_initializer_types::{Codec, InputSpec, Object, ObjectId};
 
 #[derive(Parser, Debug)]
 pub struct Cli {
     #[arg(long, short = 'e')]
     pub initializer_elf: String,
+    /// Spec in JSON or, if its name ends in `.cdl`, textual format
     #[arg(long, short = 'f')]
     pub spec_json: String,
     #[arg(long, short = 'd')]
@@ -25,14 +32,58 @@ pub struct Cli {
     pub object_names_level: u32,
     #[arg(long)]
     pub no_embed_frames: bool,
+    /// Do not share identical read-only embedded frames
     #[arg(long)]
+    pub no_dedup_frames: bool,
+    /// Codec for frame data that is not embedded as whole frames
+    #[arg(long, value_enum, default_value_t = CodecArg::Deflate)]
+    pub codec: CodecArg,
+    /// Equivalent to `--codec none`
+    #[arg(long, conflicts_with = "codec")]
     pub no_deflate: bool,
+    /// Print the size and decode time of frame data under each codec
+    #[arg(long)]
+    pub codec_stats: bool,
+    /// Name of a frame into which the initializer writes a measurement log (requires the
+    /// initializer's "measurement" feature)
+    #[arg(long)]
+    pub measurement_log: Option<String>,
+    /// Write the measurements that the initializer is expected to record to this JSON file
+    #[arg(long, requires = "measurement_log")]
+    pub expected_measurements: Option<String>,
     #[arg(long, value_parser = clap::value_parser!(u8).range(..=5), default_value_t = DEFAULT_INITIALIZER_VERBOSITY)]
     pub initializer_verbosity: u8,
+    #[arg(long)]
+    pub no_validate: bool,
+    #[arg(long)]
+    pub deny_warnings: bool,
     #[arg(long, short = 'v')]
     pub verbose: bool,
 }
 
+#[derive(ValueEnum, Debug, Copy, Clone)]
+pub enum CodecArg {
+    None,
+    Deflate,
+    Lz4,
+    /// Requires the initializer's "zstd" feature
+    Zstd,
+}
+
+impl From<CodecArg> for Codec {
+    fn from(arg: CodecArg) -> Self {
+        match arg {
+            CodecArg::None => Self::None,
+            CodecArg::Deflate => Self::Deflate,
+            CodecArg::Lz4 => Self::Lz4,
+            CodecArg::Zstd => Self::Zstd,
+        }
+    }
+}
+
+// HACK hardcoded, as in the library
+const GRANULE_SIZE_BITS: u8 = 12;
+
 const DEFAULT_INITIALIZER_VERBOSITY: u8 = 3; // log::LevelFilter::Info
 
 fn main() -> Result<()> {
@@ -44,8 +95,29 @@ fn main() -> Result<()> {
 
     let initializer_without_spec_buf = fs::read(&cli.initializer_elf)?;
 
-    let input_spec_json = fs::read_to_string(&cli.spec_json)?;
-    let input_spec = serde_json::from_str(&input_spec_json)?;
+    let input_spec_src = fs::read_to_string(&cli.spec_json)?;
+    let mut input_spec: InputSpec = if Path::new(&cli.spec_json)
+        .extension()
+        .is_some_and(|ext| ext == "cdl")
+    {
+        InputSpec::from_cdl(&input_spec_src).map_err(|err| anyhow!("{}:{err}", cli.spec_json))?
+    } else {
+        serde_json::from_str(&input_spec_src)?
+    };
+
+    if !cli.no_validate {
+        let diagnostics = input_spec.validate();
+        eprint!("{diagnostics}");
+        let num_errors = diagnostics.errors().count();
+        let num_warnings = diagnostics.warnings().count();
+        if num_errors > 0 || (cli.deny_warnings && num_warnings > 0) {
+            bail!("invalid spec ({num_errors} errors, {num_warnings} warnings)");
+        }
+    }
+
+    if let Some(name) = &cli.measurement_log {
+        input_spec.measurement_log = Some(find_measurement_log_frame(&input_spec, name)?);
+    }
 
     let object_names_level = match cli.object_names_level {
         0 => ObjectNamesLevel::None,
@@ -54,16 +126,110 @@ fn main() -> Result<()> {
         _ => unreachable!(),
     };
 
-    let rendered_initializer_elf_buf = add_spec(
+    let codec = if cli.no_deflate {
+        Codec::None
+    } else {
+        cli.codec.into()
+    };
+
+    if cli.codec_stats {
+        print_codec_stats(&input_spec, &cli, codec);
+    }
+
+    let output = add_spec(
         &initializer_without_spec_buf,
         &input_spec,
         &[&cli.fill_dir],
         &object_names_level,
         !cli.no_embed_frames,
-        !cli.no_deflate,
+        !cli.no_dedup_frames,
+        codec,
         cli.initializer_verbosity,
-    );
+    )?;
+
+    if cli.verbose
+        && let Some(stats) = &output.dedup_stats
+    {
+        eprintln!(
+            "Embedded frames: {} ({} read-only), {} shared with identical frames, saving {:#x} bytes",
+            stats.num_frames,
+            stats.num_read_only_frames,
+            stats.num_deduplicated,
+            stats.num_deduplicated << GRANULE_SIZE_BITS,
+        );
+    }
 
-    fs::write(&cli.out_file, rendered_initializer_elf_buf)?;
+    if let Some(measurements) = &output.expected_measurements {
+        let size = measurement_log_size(measurements.len());
+        if size > 1 << GRANULE_SIZE_BITS {
+            bail!(
+                "measurement log needs {size:#x} bytes for {} measurements, which does not fit in a frame",
+                measurements.len()
+            );
+        }
+        if let Some(path) = &cli.expected_measurements {
+            let out = expected_measurements_json(&input_spec, measurements);
+            fs::write(path, serde_json::to_string_pretty(&out)?)?;
+        }
+    }
+
+    fs::write(&cli.out_file, output.elf)?;
     Ok(())
 }
+
+fn find_measurement_log_frame(spec: &InputSpec, name: &str) -> Result<ObjectId> {
+    let Some((obj_id, named_obj)) = spec
+        .objects
+        .iter()
+        .enumerate()
+        .find(|(_, named_obj)| named_obj.name.as_deref() == Some(name))
+    else {
+        bail!("no object named '{name}'");
+    };
+    match &named_obj.object {
+        Object::Frame(frame) if frame.size_bits == GRANULE_SIZE_BITS => {
+            if !frame.init.entries.is_empty() {
+                bail!("measurement log frame '{name}' must not have fill data");
+            }
+        }
+        _ => bail!("measurement log '{name}' must be a {GRANULE_SIZE_BITS}-bit frame"),
+    }
+    Ok(obj_id.into())
+}
+
+fn expected_measurements_json(
+    spec: &InputSpec,
+    measurements: &[ExpectedMeasurement],
+) -> serde_json::Value {
+    json!({
+        "aggregate": expected_aggregate(measurements).map(hex::encode),
+        "measurements": measurements
+            .iter()
+            .map(|measurement| json!({
+                "kind": measurement.kind.name(),
+                "object": measurement.object.0,
+                "name": spec.objects[usize::from(measurement.object)].name,
+                "digest": measurement.digest.map(hex::encode),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn print_codec_stats(input_spec: &InputSpec, cli: &Cli, selected: Codec) {
+    eprintln!(
+        "  {:8} {:>8} {:>12} {:>12} {:>6} {:>14}",
+        "codec", "entries", "raw bytes", "encoded", "ratio", "host decode"
+    );
+    for stats in codec_stats(input_spec, &[&cli.fill_dir], !cli.no_embed_frames) {
+        eprintln!(
+            "{} {:8} {:>8} {:>12} {:>12} {:>6.3} {:>14?}",
+            if stats.codec == selected { '*' } else { ' ' },
+            stats.codec.name(),
+            stats.num_entries,
+            stats.raw_bytes,
+            stats.encoded_bytes,
+            stats.encoded_bytes as f64 / stats.raw_bytes.max(1) as f64,
+            stats.decode_time,
+        );
+    }
+}
@@ -20,7 +20,7 @@ pub fn reserialize_spec(
     fill_dirs: &[impl AsRef<Path>],
     object_names_level: &ObjectNamesLevel,
     embed_frames: bool,
-    deflate: bool,
+    codec: Codec,
     initializer_verbosity: u8,
     granule_size_bits: u8,
 ) -> (SpecForInitializer, Vec<Vec<u8>>) {
@@ -31,7 +31,7 @@ pub fn reserialize_spec(
         |_| embed_frames,
         |d, buf| {
             filler.read(d, buf);
-            deflate
+            codec
         },
     );
 
@@ -0,0 +1,45 @@
+#
+# Copyright 2026, Colias Group, LLC
+#
+# SPDX-License-Identifier: BSD-2-Clause
+#
+
+{ mk, localCrates, versions, serdeWith }:
+
+mk {
+  package.name = "sel4-capdl-initializer-inspect";
+  dependencies = {
+    inherit (versions)
+      anyhow
+      hex
+      object
+      serde_json
+      serde_yaml
+      sha2
+    ;
+    clap = { version = versions.clap; features = [ "derive" ]; };
+    serde = serdeWith [ "derive" ];
+    inherit (localCrates)
+      sel4-phdrs-constants
+    ;
+    sel4-capdl-initializer-types = localCrates.sel4-capdl-initializer-types // {
+      features = [
+        "serde"
+        "deflate"
+        "kernel-config"
+        "rkyv-deserialize"
+        "transform"
+      ];
+    };
+    sel4-config-types = localCrates.sel4-config-types // {
+      features = [
+        "serde"
+      ];
+    };
+    sel4-platform-info-types = localCrates.sel4-platform-info-types // {
+      features = [
+        "owned"
+      ];
+    };
+  };
+}
@@ -0,0 +1,519 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::PathBuf;
+use std::process;
+
+use anyhow::{Result, anyhow};
+use serde::Serialize;
+
+use sel4_capdl_initializer_types::{Object, Rights};
+
+use crate::Format;
+use crate::load::LoadedSpec;
+
+#[derive(clap::Args, Debug)]
+pub(crate) struct Args {
+    /// Spec JSON or rendered initializer ELF
+    old: PathBuf,
+    /// Spec JSON or rendered initializer ELF
+    new: PathBuf,
+    /// Fill directory for OLD, if it is a spec JSON (may be repeated)
+    #[arg(long)]
+    old_fill_dir: Vec<PathBuf>,
+    /// Fill directory for NEW, if it is a spec JSON (may be repeated)
+    #[arg(long)]
+    new_fill_dir: Vec<PathBuf>,
+    /// Match objects by ID rather than by name, e.g. for initializers rendered without object
+    /// names
+    #[arg(long)]
+    match_by_id: bool,
+    /// Exit with status 1 if the specs differ
+    #[arg(long)]
+    exit_code: bool,
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+pub(crate) fn run(args: &Args) -> Result<()> {
+    let old = Summary::new(
+        &LoadedSpec::load(&args.old, &args.old_fill_dir)?,
+        args.match_by_id,
+    )?;
+    let new = Summary::new(
+        &LoadedSpec::load(&args.new, &args.new_fill_dir)?,
+        args.match_by_id,
+    )?;
+
+    let changes = diff(&old, &new);
+
+    match args.format {
+        Format::Text => {
+            for change in changes.iter() {
+                println!("{change}");
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(&changes)?),
+    }
+
+    if args.exit_code && !changes.is_empty() {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+/// The parts of a spec that are compared, with objects keyed by name rather than by ID.
+///
+/// Unnamed objects (e.g. in an initializer rendered with a low object names level) are keyed by
+/// `#<id>`, and duplicate names are disambiguated with a `#<id>` suffix. With `match_by_id`, all
+/// objects are keyed by `#<id>`.
+struct Summary {
+    objects: BTreeMap<String, ObjectSummary>,
+}
+
+struct ObjectSummary {
+    object_type: &'static str,
+    caps: BTreeMap<u32, CapSummary>,
+    frame_digest: Option<[u8; 32]>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct CapSummary {
+    cap_type: &'static str,
+    object: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rights: Option<Rights>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    badge: Option<u64>,
+}
+
+impl Summary {
+    fn new(loaded: &LoadedSpec, match_by_id: bool) -> Result<Self> {
+        let spec = &loaded.spec;
+        let mut name_counts = BTreeMap::<&str, usize>::new();
+        for named_obj in spec.objects.iter() {
+            if let Some(name) = &named_obj.name {
+                *name_counts.entry(name).or_default() += 1;
+            }
+        }
+        let keys = spec
+            .objects
+            .iter()
+            .enumerate()
+            .map(|(obj_id, named_obj)| match &named_obj.name {
+                _ if match_by_id => format!("#{obj_id}"),
+                Some(name) if name_counts[name.as_str()] == 1 => name.clone(),
+                Some(name) => format!("{name}#{obj_id}"),
+                None => format!("#{obj_id}"),
+            })
+            .collect::<Vec<_>>();
+
+        let objects = spec
+            .objects
+            .iter()
+            .zip(keys.iter())
+            .map(|(named_obj, key)| {
+                let caps = named_obj
+                    .object
+                    .slots()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|entry| {
+                        let cap = &entry.cap;
+                        let object = keys.get(usize::from(cap.obj())).ok_or_else(|| {
+                            anyhow!(
+                                "cap in slot {} of object '{key}' refers to a nonexistent object",
+                                entry.slot.0
+                            )
+                        })?;
+                        Ok((
+                            entry.slot.0,
+                            CapSummary {
+                                cap_type: cap.type_name(),
+                                object: object.clone(),
+                                rights: cap.rights().copied(),
+                                badge: cap.badge().map(u64::from),
+                            },
+                        ))
+                    })
+                    .collect::<Result<_>>()?;
+                let frame_digest = match &named_obj.object {
+                    Object::Frame(frame) => loaded
+                        .frame_digest(frame)
+                        .map_err(|err| err.context(format!("frame '{key}'")))?,
+                    _ => None,
+                };
+                Ok((
+                    key.clone(),
+                    ObjectSummary {
+                        object_type: named_obj.object.type_name(),
+                        caps,
+                        frame_digest,
+                    },
+                ))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { objects })
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+enum Change {
+    ObjectAdded {
+        object: String,
+        object_type: &'static str,
+    },
+    ObjectRemoved {
+        object: String,
+        object_type: &'static str,
+    },
+    ObjectTypeChanged {
+        object: String,
+        old: &'static str,
+        new: &'static str,
+    },
+    CapAdded {
+        object: String,
+        slot: u32,
+        cap: CapSummary,
+    },
+    CapRemoved {
+        object: String,
+        slot: u32,
+        cap: CapSummary,
+    },
+    CapTargetChanged {
+        object: String,
+        slot: u32,
+        old: CapSummary,
+        new: CapSummary,
+    },
+    RightsChanged {
+        object: String,
+        slot: u32,
+        old: Option<Rights>,
+        new: Option<Rights>,
+    },
+    BadgeChanged {
+        object: String,
+        slot: u32,
+        old: Option<u64>,
+        new: Option<u64>,
+    },
+    FrameContentChanged {
+        object: String,
+        old: String,
+        new: String,
+    },
+}
+
+fn diff(old: &Summary, new: &Summary) -> Vec<Change> {
+    let mut changes = vec![];
+    let keys = old
+        .objects
+        .keys()
+        .chain(new.objects.keys())
+        .collect::<BTreeSet<_>>();
+    for key in keys {
+        let object = || key.clone();
+        let (old_obj, new_obj) = match (old.objects.get(key), new.objects.get(key)) {
+            (Some(old_obj), Some(new_obj)) => (old_obj, new_obj),
+            (Some(old_obj), None) => {
+                changes.push(Change::ObjectRemoved {
+                    object: object(),
+                    object_type: old_obj.object_type,
+                });
+                continue;
+            }
+            (None, Some(new_obj)) => {
+                changes.push(Change::ObjectAdded {
+                    object: object(),
+                    object_type: new_obj.object_type,
+                });
+                continue;
+            }
+            (None, None) => unreachable!(),
+        };
+
+        if old_obj.object_type != new_obj.object_type {
+            changes.push(Change::ObjectTypeChanged {
+                object: object(),
+                old: old_obj.object_type,
+                new: new_obj.object_type,
+            });
+            continue;
+        }
+
+        let slots = old_obj
+            .caps
+            .keys()
+            .chain(new_obj.caps.keys())
+            .collect::<BTreeSet<_>>();
+        for &slot in slots {
+            match (old_obj.caps.get(&slot), new_obj.caps.get(&slot)) {
+                (Some(old_cap), Some(new_cap)) => {
+                    if (old_cap.cap_type, &old_cap.object) != (new_cap.cap_type, &new_cap.object) {
+                        changes.push(Change::CapTargetChanged {
+                            object: object(),
+                            slot,
+                            old: old_cap.clone(),
+                            new: new_cap.clone(),
+                        });
+                    }
+                    if old_cap.rights != new_cap.rights {
+                        changes.push(Change::RightsChanged {
+                            object: object(),
+                            slot,
+                            old: old_cap.rights,
+                            new: new_cap.rights,
+                        });
+                    }
+                    if old_cap.badge != new_cap.badge {
+                        changes.push(Change::BadgeChanged {
+                            object: object(),
+                            slot,
+                            old: old_cap.badge,
+                            new: new_cap.badge,
+                        });
+                    }
+                }
+                (Some(old_cap), None) => changes.push(Change::CapRemoved {
+                    object: object(),
+                    slot,
+                    cap: old_cap.clone(),
+                }),
+                (None, Some(new_cap)) => changes.push(Change::CapAdded {
+                    object: object(),
+                    slot,
+                    cap: new_cap.clone(),
+                }),
+                (None, None) => unreachable!(),
+            }
+        }
+
+        if let (Some(old_digest), Some(new_digest)) = (old_obj.frame_digest, new_obj.frame_digest)
+            && old_digest != new_digest
+        {
+            changes.push(Change::FrameContentChanged {
+                object: object(),
+                old: hex::encode(old_digest),
+                new: hex::encode(new_digest),
+            });
+        }
+    }
+    changes
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ObjectAdded {
+                object,
+                object_type,
+            } => write!(f, "+ object '{object}' ({object_type})"),
+            Self::ObjectRemoved {
+                object,
+                object_type,
+            } => write!(f, "- object '{object}' ({object_type})"),
+            Self::ObjectTypeChanged { object, old, new } => {
+                write!(f, "~ object '{object}': type {old} -> {new}")
+            }
+            Self::CapAdded { object, slot, cap } => write!(f, "+ cap '{object}'[{slot}]: {cap}"),
+            Self::CapRemoved { object, slot, cap } => {
+                write!(f, "- cap '{object}'[{slot}]: {cap}")
+            }
+            Self::CapTargetChanged {
+                object,
+                slot,
+                old,
+                new,
+            } => write!(
+                f,
+                "~ cap '{object}'[{slot}]: target {} '{}' -> {} '{}'",
+                old.cap_type, old.object, new.cap_type, new.object
+            ),
+            Self::RightsChanged {
+                object,
+                slot,
+                old,
+                new,
+            } => write!(
+                f,
+                "~ cap '{object}'[{slot}]: rights {} -> {}",
+                DisplayRights(old),
+                DisplayRights(new)
+            ),
+            Self::BadgeChanged {
+                object,
+                slot,
+                old,
+                new,
+            } => write!(
+                f,
+                "~ cap '{object}'[{slot}]: badge {} -> {}",
+                DisplayBadge(old),
+                DisplayBadge(new)
+            ),
+            Self::FrameContentChanged { object, old, new } => {
+                write!(f, "~ frame '{object}': sha256 {old} -> {new}")
+            }
+        }
+    }
+}
+
+impl fmt::Display for CapSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} '{}'", self.cap_type, self.object)?;
+        if self.rights.is_some() {
+            write!(f, " rights={}", DisplayRights(&self.rights))?;
+        }
+        if self.badge.is_some() {
+            write!(f, " badge={}", DisplayBadge(&self.badge))?;
+        }
+        Ok(())
+    }
+}
+
+// Same letters as capDL: read, write, grant, grant-reply
+struct DisplayRights<'a>(&'a Option<Rights>);
+
+impl fmt::Display for DisplayRights<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Some(rights) = self.0 else {
+            return write!(f, "none");
+        };
+        let mut empty = true;
+        for (letter, present) in [
+            ('R', rights.read),
+            ('W', rights.write),
+            ('G', rights.grant),
+            ('P', rights.grant_reply),
+        ] {
+            if present {
+                write!(f, "{letter}")?;
+                empty = false;
+            }
+        }
+        if empty {
+            write!(f, "-")?;
+        }
+        Ok(())
+    }
+}
+
+struct DisplayBadge<'a>(&'a Option<u64>);
+
+impl fmt::Display for DisplayBadge<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(badge) => write!(f, "{badge:#x}"),
+            None => write!(f, "none"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sel4_capdl_initializer_types::{Codec, InputSpec, ObjectId};
+
+    use super::*;
+    use crate::GRANULE_SIZE_BITS;
+
+    fn loaded(src: &str) -> LoadedSpec {
+        let (spec, frame_data) = InputSpec::from_cdl(src).unwrap().embed_fill(
+            GRANULE_SIZE_BITS,
+            |_| true,
+            |key, buf| {
+                buf.fill(key.file_offset as u8);
+                Codec::None
+            },
+        );
+        LoadedSpec::from_parts(spec, frame_data.concat())
+    }
+
+    fn run(old: &str, new: &str, match_by_id: bool) -> Vec<String> {
+        let old = Summary::new(&loaded(old), match_by_id).unwrap();
+        let new = Summary::new(&loaded(new), match_by_id).unwrap();
+        diff(&old, &new).iter().map(ToString::to_string).collect()
+    }
+
+    const OLD: &str = "
+        objects {
+          cnode = cnode (2 bits)
+          ep = ep
+          ntfn = notification
+          data = frame (4k, fill: [{0 16 CDL_FrameFill_FileData \"a.bin\" 1}])
+        }
+        caps {
+          cnode {
+            0x0: ep (RW, badge: 0x1)
+            0x1: ntfn (R)
+            0x2: data (R)
+          }
+        }
+    ";
+
+    #[test]
+    fn identical() {
+        assert!(run(OLD, OLD, false).is_empty());
+    }
+
+    #[test]
+    fn changes() {
+        let new = "
+            objects {
+              cnode = cnode (2 bits)
+              reply_ep = ep
+              ntfn = notification
+              data = frame (4k, fill: [{0 16 CDL_FrameFill_FileData \"a.bin\" 2}])
+            }
+            caps {
+              cnode {
+                0x0: reply_ep (RW, badge: 0x1)
+                0x1: ntfn (RW)
+                0x2: data (R)
+                0x3: ntfn (R, badge: 0x2)
+              }
+            }
+        ";
+        let changes = run(OLD, new, false);
+        assert_eq!(changes.len(), 6, "{changes:#?}");
+        assert_eq!(
+            changes[0],
+            "~ cap 'cnode'[0]: target Endpoint 'ep' -> Endpoint 'reply_ep'"
+        );
+        assert_eq!(changes[1], "~ cap 'cnode'[1]: rights R -> RW");
+        assert_eq!(
+            changes[2],
+            "+ cap 'cnode'[3]: Notification 'ntfn' rights=R badge=0x2"
+        );
+        assert!(changes[3].starts_with("~ frame 'data': sha256 "));
+        assert_eq!(changes[4], "- object 'ep' (Endpoint)");
+        assert_eq!(changes[5], "+ object 'reply_ep' (Endpoint)");
+    }
+
+    #[test]
+    fn match_by_id() {
+        let new = OLD
+            .replace("ep = ep", "reply_ep = ep")
+            .replace("0x0: ep", "0x0: reply_ep");
+        assert!(run(OLD, &new, true).is_empty());
+        assert_eq!(run(OLD, &new, false).len(), 3);
+    }
+
+    #[test]
+    fn dangling_cap() {
+        let mut loaded = loaded(OLD);
+        loaded.spec.objects[0].object.slots_mut().unwrap()[0]
+            .cap
+            .set_obj(ObjectId(4));
+        assert!(Summary::new(&loaded, false).is_err());
+    }
+}
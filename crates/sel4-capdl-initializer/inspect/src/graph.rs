@@ -0,0 +1,355 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+use clap::ValueEnum;
+use serde::Serialize;
+
+use sel4_capdl_initializer_types::{
+    Access, AuthorityGraph, Edge, EdgeKind, ObjectId, Reply, SpecForInitializer,
+};
+
+use crate::Format;
+use crate::load::LoadedSpec;
+
+#[derive(clap::Args, Debug)]
+pub(crate) struct GraphArgs {
+    /// Spec JSON or rendered initializer ELF
+    spec: PathBuf,
+    /// Instead of the caps in each cap table, emit an edge from each thread to each object it can
+    /// access, directly or transitively, and from each thread to each thread it can reply to
+    #[arg(long)]
+    reachability: bool,
+    #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+    format: GraphFormat,
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone)]
+enum GraphFormat {
+    Dot,
+    Json,
+}
+
+#[derive(clap::Args, Debug)]
+pub(crate) struct WhoCanArgs {
+    /// Spec JSON or rendered initializer ELF
+    spec: PathBuf,
+    #[arg(value_enum)]
+    operation: Operation,
+    /// Object name, or `#<id>`
+    object: String,
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone)]
+enum Operation {
+    Read,
+    Write,
+    /// Send on an endpoint
+    Send,
+    /// Receive on an endpoint
+    Receive,
+    /// Signal a notification
+    Signal,
+    /// Wait on a notification
+    Wait,
+    Grant,
+    GrantReply,
+    /// Invoke a cap without rights, e.g. a TCB or CNode
+    Control,
+}
+
+impl Operation {
+    fn access(self) -> Access {
+        let mut access = Access::default();
+        match self {
+            Self::Read | Self::Receive | Self::Wait => access.read = true,
+            Self::Write | Self::Send | Self::Signal => access.write = true,
+            Self::Grant => access.grant = true,
+            Self::GrantReply => access.grant_reply = true,
+            Self::Control => access.control = true,
+        }
+        access
+    }
+}
+
+pub(crate) fn run_graph(args: &GraphArgs) -> Result<()> {
+    let loaded = LoadedSpec::load(&args.spec, &[])?;
+    let spec = &loaded.spec;
+    let graph = spec.authority_graph();
+    match args.format {
+        GraphFormat::Dot => print_dot(spec, &graph, args.reachability),
+        GraphFormat::Json => {
+            let out = JsonGraph::new(spec, &graph, args.reachability);
+            println!("{}", serde_json::to_string_pretty(&out)?);
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn run_who_can(args: &WhoCanArgs) -> Result<()> {
+    let loaded = LoadedSpec::load(&args.spec, &[])?;
+    let threads = who_can(&loaded.spec, args.operation, &args.object)?;
+    match args.format {
+        Format::Text => {
+            for thread in threads.iter() {
+                println!("{thread}");
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(&threads)?),
+    }
+    Ok(())
+}
+
+fn who_can(spec: &SpecForInitializer, operation: Operation, object: &str) -> Result<Vec<String>> {
+    let obj_id = resolve_object(spec, object)?;
+    Ok(spec
+        .authority_graph()
+        .threads_with_access(obj_id, operation.access())
+        .map(|thread| object_label(spec, thread))
+        .collect())
+}
+
+fn resolve_object(spec: &SpecForInitializer, s: &str) -> Result<ObjectId> {
+    if let Some(id) = s.strip_prefix('#')
+        && let Ok(id) = id.parse::<usize>()
+        && id < spec.objects.len()
+    {
+        return Ok(id.into());
+    }
+    let mut matches = spec
+        .objects
+        .iter()
+        .enumerate()
+        .filter(|(_, named_obj)| named_obj.name.as_deref() == Some(s))
+        .map(|(obj_id, _)| obj_id);
+    let obj_id = matches
+        .next()
+        .ok_or_else(|| anyhow!("no object named '{s}'"))?;
+    if matches.next().is_some() {
+        return Err(anyhow!("more than one object named '{s}'; use '#<id>'"));
+    }
+    Ok(obj_id.into())
+}
+
+fn object_label(spec: &SpecForInitializer, obj_id: ObjectId) -> String {
+    match &spec.objects[usize::from(obj_id)].name {
+        Some(name) => name.clone(),
+        None => format!("#{}", obj_id.0),
+    }
+}
+
+fn reachability_edges(
+    graph: &AuthorityGraph,
+) -> impl Iterator<Item = (ObjectId, ObjectId, Access)> {
+    graph.threads.iter().flat_map(|(thread, reachable)| {
+        reachable
+            .iter()
+            .filter(move |(obj_id, _)| *obj_id != thread)
+            .map(|(obj_id, access)| (*thread, *obj_id, *access))
+    })
+}
+
+fn print_dot(spec: &SpecForInitializer, graph: &AuthorityGraph, reachability: bool) {
+    println!("digraph authority {{");
+    println!("    node [shape=box];");
+    for (obj_id, named_obj) in spec.objects.iter().enumerate() {
+        let label = format!(
+            "{}\\n{}",
+            escape(&object_label(spec, obj_id.into())),
+            named_obj.object.type_name()
+        );
+        println!("    o{obj_id} [label=\"{label}\"];");
+    }
+    if reachability {
+        for (thread, obj_id, access) in reachability_edges(graph) {
+            println!("    o{} -> o{} [label=\"{access}\"];", thread.0, obj_id.0);
+        }
+        for reply in graph.replies.iter() {
+            println!(
+                "    o{} -> o{} [label=\"reply via o{}\", style=dotted];",
+                reply.receiver.0, reply.sender.0, reply.endpoint.0
+            );
+        }
+    } else {
+        for edge in graph.edges.iter() {
+            let style = match edge.kind {
+                EdgeKind::Cap => "solid",
+                EdgeKind::Mapping => "dashed",
+            };
+            println!(
+                "    o{} -> o{} [label=\"{}: {}\", style={style}];",
+                edge.source.0, edge.target.0, edge.slot.0, edge.access
+            );
+        }
+    }
+    println!("}}");
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[derive(Serialize)]
+struct JsonGraph {
+    objects: Vec<JsonObject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    edges: Option<Vec<Edge>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reachability: Option<Vec<JsonReachability>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    replies: Option<Vec<Reply>>,
+}
+
+#[derive(Serialize)]
+struct JsonObject {
+    id: ObjectId,
+    name: Option<String>,
+    object_type: &'static str,
+}
+
+#[derive(Serialize)]
+struct JsonReachability {
+    thread: ObjectId,
+    object: ObjectId,
+    access: Access,
+}
+
+impl JsonGraph {
+    fn new(spec: &SpecForInitializer, graph: &AuthorityGraph, reachability: bool) -> Self {
+        let objects = spec
+            .objects
+            .iter()
+            .enumerate()
+            .map(|(obj_id, named_obj)| JsonObject {
+                id: obj_id.into(),
+                name: named_obj.name.clone(),
+                object_type: named_obj.object.type_name(),
+            })
+            .collect();
+        if reachability {
+            Self {
+                objects,
+                edges: None,
+                reachability: Some(
+                    reachability_edges(graph)
+                        .map(|(thread, object, access)| JsonReachability {
+                            thread,
+                            object,
+                            access,
+                        })
+                        .collect(),
+                ),
+                replies: Some(graph.replies.iter().copied().collect()),
+            }
+        } else {
+            Self {
+                objects,
+                edges: Some(graph.edges.clone()),
+                reachability: None,
+                replies: None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sel4_capdl_initializer_types::{Codec, InputSpec};
+
+    use super::*;
+    use crate::GRANULE_SIZE_BITS;
+
+    fn spec(server_ep_rights: &str) -> SpecForInitializer {
+        let src = format!(
+            "
+            objects {{
+              client = tcb (addr: 0x1000, ip: 0x0, sp: 0x0, prio: 0, max_prio: 0)
+              client_cnode = cnode (2 bits)
+              server = tcb (addr: 0x1000, ip: 0x0, sp: 0x0, prio: 0, max_prio: 0)
+              server_cnode = cnode (2 bits)
+              ep = ep
+              secret = notification
+              #6 = notification
+            }}
+            caps {{
+              client {{
+                cspace: client_cnode
+              }}
+              client_cnode {{
+                0x0: ep (WP)
+              }}
+              server {{
+                cspace: server_cnode
+              }}
+              server_cnode {{
+                0x0: ep ({server_ep_rights})
+                0x1: secret (R)
+                0x2: #6 (W)
+              }}
+            }}
+            "
+        );
+        InputSpec::from_cdl(&src)
+            .unwrap()
+            .embed_fill(GRANULE_SIZE_BITS, |_| false, |_, _| Codec::None)
+            .0
+    }
+
+    #[test]
+    fn who_can_endpoint() {
+        let spec = spec("R");
+        assert_eq!(who_can(&spec, Operation::Send, "ep").unwrap(), ["client"]);
+        assert_eq!(
+            who_can(&spec, Operation::Receive, "ep").unwrap(),
+            ["server"]
+        );
+        assert_eq!(
+            who_can(&spec, Operation::GrantReply, "ep").unwrap(),
+            ["client"]
+        );
+        assert!(who_can(&spec, Operation::Grant, "ep").unwrap().is_empty());
+        assert_eq!(
+            who_can(&spec, Operation::Wait, "secret").unwrap(),
+            ["server"]
+        );
+    }
+
+    #[test]
+    fn who_can_through_reply() {
+        // The server's endpoint cap has grant rights, so it can grant caps to the client in its
+        // reply.
+        let spec = spec("RG");
+        assert_eq!(
+            who_can(&spec, Operation::Wait, "secret").unwrap(),
+            ["client", "server"]
+        );
+        assert_eq!(
+            who_can(&spec, Operation::Signal, "#6").unwrap(),
+            ["client", "server"]
+        );
+    }
+
+    #[test]
+    fn who_can_control() {
+        let spec = spec("R");
+        assert!(
+            who_can(&spec, Operation::Control, "client")
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn who_can_unknown_object() {
+        let spec = spec("R");
+        assert!(who_can(&spec, Operation::Read, "nonexistent").is_err());
+        assert!(who_can(&spec, Operation::Read, "#7").is_err());
+    }
+}
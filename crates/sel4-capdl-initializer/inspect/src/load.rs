@@ -0,0 +1,322 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::os::unix::fs::FileExt as _;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, anyhow, bail};
+use object::elf::{FileHeader32, FileHeader64};
+use object::read::elf::{FileHeader, ProgramHeader};
+use object::{Endianness, FileKind};
+use sha2::{Digest, Sha256};
+
+use sel4_capdl_initializer_types::{
+    Codec, FillEntryContent, FillEntryContentFileOffset, FrameInit, SpecForInitializer,
+    object::Frame,
+};
+use sel4_phdrs_constants::{PT_SEL4_CAPDL_FRAME_DATA, PT_SEL4_CAPDL_SPEC};
+
+use crate::{GRANULE_SIZE_BITS, read_input_spec};
+
+/// A spec read either from a spec JSON (with frame contents resolved against fill directories) or
+/// from the segments of a rendered initializer ELF.
+pub(crate) struct LoadedSpec {
+    pub(crate) spec: SpecForInitializer,
+    embedded_frame_data: Vec<u8>,
+    has_frame_contents: bool,
+}
+
+impl LoadedSpec {
+    pub(crate) fn load(path: &PathBuf, fill_dirs: &[PathBuf]) -> Result<Self> {
+        let buf = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+        if buf.starts_with(b"\x7fELF") {
+            Self::load_elf(&buf).with_context(|| format!("parsing {}", path.display()))
+        } else {
+            Self::load_json(path, fill_dirs)
+        }
+    }
+
+    fn load_json(path: &PathBuf, fill_dirs: &[PathBuf]) -> Result<Self> {
+        let input_spec = read_input_spec(path)?;
+        let has_frame_contents = !fill_dirs.is_empty();
+        let mut filler = Filler::new(fill_dirs);
+        let (spec, _) = input_spec.embed_fill_fallible(
+            GRANULE_SIZE_BITS,
+            |_| false,
+            |key, buf| {
+                if has_frame_contents {
+                    filler.read(key, buf)?;
+                }
+                Ok::<_, anyhow::Error>(Codec::None)
+            },
+        )?;
+        Ok(Self {
+            spec,
+            embedded_frame_data: vec![],
+            has_frame_contents,
+        })
+    }
+
+    fn load_elf(buf: &[u8]) -> Result<Self> {
+        let segments = match FileKind::parse(buf)? {
+            FileKind::Elf32 => segments::<FileHeader32<Endianness>>(buf)?,
+            FileKind::Elf64 => segments::<FileHeader64<Endianness>>(buf)?,
+            _ => bail!("unsupported ELF class"),
+        };
+        let spec_data = segments
+            .get(&PT_SEL4_CAPDL_SPEC)
+            .ok_or_else(|| anyhow!("no spec segment (is this a rendered initializer?)"))?;
+        let spec = SpecForInitializer::from_bytes(spec_data)?;
+        let diagnostics = spec.validate();
+        if diagnostics.has_errors() {
+            eprint!("{diagnostics}");
+            bail!("invalid spec");
+        }
+        Ok(Self {
+            spec,
+            embedded_frame_data: segments
+                .get(&PT_SEL4_CAPDL_FRAME_DATA)
+                .map(|data| data.to_vec())
+                .unwrap_or_default(),
+            has_frame_contents: true,
+        })
+    }
+
+    /// SHA-256 of the frame's initial contents, including any regions filled from bootinfo at
+    /// runtime, or `None` if frame contents are not available.
+    pub(crate) fn frame_digest(&self, frame: &Frame<FrameInit>) -> Result<Option<[u8; 32]>> {
+        if !self.has_frame_contents {
+            return Ok(None);
+        }
+        let mut hasher = Sha256::new();
+        match &frame.init {
+            FrameInit::Embedded(embedded) => {
+                let granule_size = 1 << GRANULE_SIZE_BITS;
+                let data = usize::try_from(embedded.index)
+                    .ok()
+                    .and_then(|index| index.checked_mul(granule_size))
+                    .and_then(|start| {
+                        self.embedded_frame_data
+                            .get(start..start.checked_add(granule_size)?)
+                    })
+                    .ok_or_else(|| {
+                        anyhow!("embedded frame index {} is out of bounds", embedded.index)
+                    })?;
+                hasher.update(data);
+            }
+            FrameInit::Fill(fill) => {
+                let size = 1usize
+                    .checked_shl(frame.size_bits.into())
+                    .ok_or_else(|| anyhow!("frame size bits {} too large", frame.size_bits))?;
+                let mut buf = vec![0; size];
+                let mut bootinfo = vec![];
+                for entry in fill.entries.iter() {
+                    let range =
+                        usize::try_from(entry.range.start)?..usize::try_from(entry.range.end)?;
+                    let dst = buf
+                        .get_mut(range.clone())
+                        .ok_or_else(|| anyhow!("fill entry {:?} is out of bounds", entry.range))?;
+                    match &entry.content {
+                        FillEntryContent::Data(content) => {
+                            if content.codec() == Codec::None && content.encoded_len() != dst.len()
+                            {
+                                bail!("fill entry {:?} has the wrong length", entry.range);
+                            }
+                            content.copy_out(dst)
+                        }
+                        FillEntryContent::BootInfo(content) => bootinfo
+                            .push(format!("{:?}:{}:{:?}", content.id, content.offset, range)),
+                    }
+                }
+                hasher.update(&buf);
+                for entry in bootinfo {
+                    hasher.update(entry.as_bytes());
+                }
+            }
+        }
+        Ok(Some(hasher.finalize().into()))
+    }
+}
+
+fn segments<T: FileHeader<Endian = Endianness>>(buf: &[u8]) -> Result<BTreeMap<u32, &[u8]>> {
+    let header = T::parse(buf)?;
+    let endian = header.endian()?;
+    let mut segments = BTreeMap::new();
+    for phdr in header.program_headers(endian, buf)? {
+        let data = phdr
+            .data(endian, buf)
+            .map_err(|_| anyhow!("invalid program header"))?;
+        segments.insert(phdr.p_type(endian), data);
+    }
+    Ok(segments)
+}
+
+struct Filler {
+    fill_dirs: Vec<PathBuf>,
+    file_handles: BTreeMap<String, File>,
+}
+
+impl Filler {
+    fn new(fill_dirs: &[PathBuf]) -> Self {
+        Self {
+            fill_dirs: fill_dirs.to_vec(),
+            file_handles: BTreeMap::new(),
+        }
+    }
+
+    fn read(&mut self, key: &FillEntryContentFileOffset, buf: &mut [u8]) -> Result<()> {
+        if !self.file_handles.contains_key(&key.file) {
+            let path = self
+                .fill_dirs
+                .iter()
+                .map(|dir| dir.join(&key.file))
+                .find(|path| path.exists())
+                .ok_or_else(|| anyhow!("file {:?} not found", key.file))?;
+            self.file_handles
+                .insert(key.file.clone(), File::open(path)?);
+        }
+        self.file_handles[&key.file].read_exact_at(buf, key.file_offset)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl LoadedSpec {
+    pub(crate) fn from_parts(spec: SpecForInitializer, embedded_frame_data: Vec<u8>) -> Self {
+        Self {
+            spec,
+            embedded_frame_data,
+            has_frame_contents: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sel4_capdl_initializer_types::{EmbeddedFrameIndex, InputSpec, Object};
+
+    use super::*;
+
+    const SPEC: &str = "
+        objects {
+          frame = frame (4k, fill: [{0 16 CDL_FrameFill_FileData \"a.bin\" 0}])
+        }
+    ";
+
+    fn spec() -> (SpecForInitializer, Vec<u8>) {
+        let (spec, frame_data) = InputSpec::from_cdl(SPEC).unwrap().embed_fill(
+            GRANULE_SIZE_BITS,
+            |_| true,
+            |_, buf| {
+                buf.fill(0xaa);
+                Codec::None
+            },
+        );
+        (spec, frame_data.concat())
+    }
+
+    // A minimal little-endian ELF64 file with one segment per entry of `segments`.
+    fn elf(segments: &[(u32, &[u8])]) -> Vec<u8> {
+        const EHDR_SIZE: u16 = 64;
+        const PHDR_SIZE: u16 = 56;
+        let mut buf = vec![];
+        buf.extend_from_slice(b"\x7fELF\x02\x01\x01\0\0\0\0\0\0\0\0\0");
+        buf.extend_from_slice(&2u16.to_le_bytes()); // e_type
+        buf.extend_from_slice(&183u16.to_le_bytes()); // e_machine
+        buf.extend_from_slice(&1u32.to_le_bytes()); // e_version
+        buf.extend_from_slice(&0u64.to_le_bytes()); // e_entry
+        buf.extend_from_slice(&u64::from(EHDR_SIZE).to_le_bytes()); // e_phoff
+        buf.extend_from_slice(&0u64.to_le_bytes()); // e_shoff
+        buf.extend_from_slice(&0u32.to_le_bytes()); // e_flags
+        buf.extend_from_slice(&EHDR_SIZE.to_le_bytes());
+        buf.extend_from_slice(&PHDR_SIZE.to_le_bytes());
+        buf.extend_from_slice(&u16::try_from(segments.len()).unwrap().to_le_bytes());
+        buf.extend_from_slice(&[0; 6]); // e_shentsize, e_shnum, e_shstrndx
+        let mut offset = u64::from(EHDR_SIZE) + u64::from(PHDR_SIZE) * segments.len() as u64;
+        for (p_type, data) in segments {
+            let size = data.len() as u64;
+            buf.extend_from_slice(&p_type.to_le_bytes());
+            buf.extend_from_slice(&0u32.to_le_bytes()); // p_flags
+            buf.extend_from_slice(&offset.to_le_bytes());
+            buf.extend_from_slice(&[0; 16]); // p_vaddr, p_paddr
+            buf.extend_from_slice(&size.to_le_bytes()); // p_filesz
+            buf.extend_from_slice(&size.to_le_bytes()); // p_memsz
+            buf.extend_from_slice(&1u64.to_le_bytes()); // p_align
+            offset += size;
+        }
+        for (_, data) in segments {
+            buf.extend_from_slice(data);
+        }
+        buf
+    }
+
+    fn frame(spec: &SpecForInitializer) -> &Frame<FrameInit> {
+        match &spec.objects[0].object {
+            Object::Frame(frame) => frame,
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn load_elf() {
+        let (spec, frame_data) = spec();
+        let buf = elf(&[
+            (PT_SEL4_CAPDL_SPEC, spec.to_bytes().unwrap().as_slice()),
+            (PT_SEL4_CAPDL_FRAME_DATA, frame_data.as_slice()),
+        ]);
+        let loaded = LoadedSpec::load_elf(&buf).unwrap();
+        assert_eq!(loaded.spec, spec);
+        let expected = LoadedSpec::from_parts(spec, frame_data);
+        assert_eq!(
+            loaded.frame_digest(frame(&loaded.spec)).unwrap(),
+            expected.frame_digest(frame(&expected.spec)).unwrap(),
+        );
+    }
+
+    #[test]
+    fn load_elf_without_spec() {
+        assert!(LoadedSpec::load_elf(&elf(&[])).is_err());
+    }
+
+    #[test]
+    fn load_elf_invalid_spec() {
+        let (mut spec, _) = spec();
+        spec.root_objects.end.0 += 1;
+        let buf = elf(&[(PT_SEL4_CAPDL_SPEC, spec.to_bytes().unwrap().as_slice())]);
+        assert!(LoadedSpec::load_elf(&buf).is_err());
+    }
+
+    #[test]
+    fn embedded_frame_out_of_bounds() {
+        let (mut spec, frame_data) = spec();
+        let Object::Frame(obj) = &mut spec.objects[0].object else {
+            panic!()
+        };
+        obj.init = FrameInit::Embedded(EmbeddedFrameIndex { index: 1 });
+        let loaded = LoadedSpec::from_parts(spec, frame_data);
+        assert!(loaded.frame_digest(frame(&loaded.spec)).is_err());
+    }
+
+    #[test]
+    fn fill_out_of_bounds() {
+        let (mut spec, _) = InputSpec::from_cdl(SPEC).unwrap().embed_fill(
+            GRANULE_SIZE_BITS,
+            |_| false,
+            |_, _| Codec::None,
+        );
+        let Object::Frame(obj) = &mut spec.objects[0].object else {
+            panic!()
+        };
+        let FrameInit::Fill(fill) = &mut obj.init else {
+            panic!()
+        };
+        fill.entries[0].range = 4090..4106;
+        let loaded = LoadedSpec::from_parts(spec, vec![]);
+        assert!(loaded.frame_digest(frame(&loaded.spec)).is_err());
+    }
+}
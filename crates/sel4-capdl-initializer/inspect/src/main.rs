@@ -0,0 +1,82 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow, bail};
+use clap::{Parser, Subcommand, ValueEnum};
+
+use sel4_capdl_initializer_types::InputSpec;
+
+mod diff;
+mod graph;
+mod load;
+mod simulate;
+
+// HACK hardcoded, as in sel4-capdl-initializer-add-spec
+const GRANULE_SIZE_BITS: u8 = 12;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Replay the initializer's object allocation against a list of untypeds
+    Simulate(simulate::Args),
+    /// Compare two specs, given as spec JSONs or rendered initializer ELFs
+    Diff(diff::Args),
+    /// Emit the authority graph of a spec in Graphviz or JSON format
+    Graph(graph::GraphArgs),
+    /// List the threads that can perform an operation on an object, directly or transitively
+    WhoCan(graph::WhoCanArgs),
+    /// Print a spec JSON in the textual `.cdl` format
+    Cdl(CdlArgs),
+}
+
+#[derive(clap::Args, Debug)]
+struct CdlArgs {
+    spec_json: PathBuf,
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone)]
+enum Format {
+    Text,
+    Json,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Simulate(args) => simulate::run(&args),
+        Command::Diff(args) => diff::run(&args),
+        Command::Graph(args) => graph::run_graph(&args),
+        Command::WhoCan(args) => graph::run_who_can(&args),
+        Command::Cdl(args) => {
+            print!("{}", read_input_spec(&args.spec_json)?.to_cdl());
+            Ok(())
+        }
+    }
+}
+
+fn read_input_spec(path: &PathBuf) -> Result<InputSpec> {
+    let src = fs::read_to_string(path)?;
+    let spec: InputSpec = if path.extension().is_some_and(|ext| ext == "cdl") {
+        InputSpec::from_cdl(&src).map_err(|err| anyhow!("{}:{err}", path.display()))?
+    } else {
+        serde_json::from_str(&src)?
+    };
+    let diagnostics = spec.validate();
+    if diagnostics.has_errors() {
+        eprint!("{diagnostics}");
+        bail!("invalid spec: {}", path.display());
+    }
+    Ok(spec)
+}
@@ -0,0 +1,199 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use std::fs;
+use std::ops::Range;
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow, bail};
+
+use sel4_capdl_initializer_types::{
+    AllocationReport, Codec, ObjectSizes, SimulatedUntyped, SpecForInitializer,
+};
+use sel4_config_types::Configuration;
+use sel4_platform_info_types::OwnedPlatformInfo;
+
+use crate::{Format, GRANULE_SIZE_BITS, read_input_spec};
+
+#[derive(clap::Args, Debug)]
+pub(crate) struct Args {
+    /// CapDL spec in JSON format
+    #[arg(long, short = 'f')]
+    spec_json: PathBuf,
+    /// Untypeds in JSON format, as a list of `{ "paddr", "size_bits", "is_device" }`
+    #[arg(long, conflicts_with = "platform_info")]
+    untypeds: Option<PathBuf>,
+    /// Derive untypeds from a `platform_gen.yaml`
+    #[arg(long, required_unless_present = "untypeds")]
+    platform_info: Option<PathBuf>,
+    /// Physical memory to exclude from the platform's memory regions (e.g. the kernel and the
+    /// initializer image), as `start..end`
+    #[arg(long, value_parser = parse_range)]
+    reserve: Vec<Range<u64>>,
+    #[arg(long, default_value_t = 47)]
+    max_untyped_size_bits: u8,
+    /// Derive object sizes from the kernel's `gen_config.json`
+    #[arg(long, required_unless_present = "object_sizes")]
+    kernel_config: Option<PathBuf>,
+    /// Object sizes in JSON format, for kernel configurations not covered by `--kernel-config`
+    #[arg(long, conflicts_with = "kernel_config")]
+    object_sizes: Option<PathBuf>,
+    #[arg(long)]
+    no_embed_frames: bool,
+    /// Number of empty slots in the initializer's CSpace, from `BootInfo::empty()`
+    #[arg(long)]
+    empty_slots: Option<usize>,
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+pub(crate) fn run(args: &Args) -> Result<()> {
+    let input_spec = read_input_spec(&args.spec_json)?;
+
+    // Frame contents do not affect allocation, so skip reading them.
+    let (mut spec, _) = input_spec.embed_fill(
+        GRANULE_SIZE_BITS,
+        |_| !args.no_embed_frames,
+        |_, _| Codec::None,
+    );
+    spec.cache_orig_cap_slots();
+
+    let sizes = match &args.object_sizes {
+        Some(path) => serde_json::from_str(&fs::read_to_string(path)?)?,
+        None => {
+            let config: Configuration =
+                serde_json::from_str(&fs::read_to_string(args.kernel_config.as_ref().unwrap())?)?;
+            ObjectSizes::from_kernel_config(&config)
+                .ok_or_else(|| anyhow!("unsupported kernel configuration"))?
+        }
+    };
+
+    let untypeds = get_untypeds(args)?;
+
+    let report = spec.simulate_allocation(&untypeds, &sizes)?;
+
+    match args.format {
+        Format::Text => print_report(&spec, &report, args.empty_slots),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    if !report.is_ok() {
+        bail!("simulated allocation failed");
+    }
+    if let Some(empty_slots) = args.empty_slots
+        && report.cslots.peak() > empty_slots
+    {
+        bail!(
+            "simulated allocation needs {} cslots, but only {} are available",
+            report.cslots.peak(),
+            empty_slots
+        );
+    }
+    Ok(())
+}
+
+fn get_untypeds(args: &Args) -> Result<Vec<SimulatedUntyped>> {
+    if let Some(path) = &args.untypeds {
+        return Ok(serde_json::from_str(&fs::read_to_string(path)?)?);
+    }
+    let platform_info: OwnedPlatformInfo =
+        serde_yaml::from_reader(fs::File::open(args.platform_info.as_ref().unwrap())?)?;
+    let mut untypeds = vec![];
+    for region in platform_info.memory.iter() {
+        for region in subtract(region, &args.reserve) {
+            untypeds.extend(SimulatedUntyped::from_region(
+                &region,
+                args.max_untyped_size_bits,
+                false,
+            ));
+        }
+    }
+    for region in platform_info.devices.iter() {
+        untypeds.extend(SimulatedUntyped::from_region(
+            region,
+            args.max_untyped_size_bits,
+            true,
+        ));
+    }
+    Ok(untypeds)
+}
+
+fn subtract(region: &Range<u64>, holes: &[Range<u64>]) -> Vec<Range<u64>> {
+    let mut acc = vec![region.clone()];
+    for hole in holes {
+        acc = acc
+            .into_iter()
+            .flat_map(|r| {
+                [r.start..r.end.min(hole.start), r.start.max(hole.end)..r.end]
+                    .into_iter()
+                    .filter(|r| !r.is_empty())
+            })
+            .collect();
+    }
+    acc
+}
+
+fn parse_range(s: &str) -> Result<Range<u64>> {
+    let Some((start, end)) = s.split_once("..") else {
+        bail!("expected 'start..end'");
+    };
+    Ok(parse_u64(start)?..parse_u64(end)?)
+}
+
+fn parse_u64(s: &str) -> Result<u64> {
+    Ok(match s.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16)?,
+        None => s.parse()?,
+    })
+}
+
+fn print_report(spec: &SpecForInitializer, report: &AllocationReport, empty_slots: Option<usize>) {
+    println!("Untypeds:");
+    for usage in report.untypeds.iter() {
+        let ut = &usage.untyped;
+        println!(
+            "    [{:#014x}..{:#014x}) size_bits = {:2}{} objects = {:5} used = {:#x} dummies = {:#x} free = {:#x}",
+            ut.paddr,
+            ut.paddr + (1 << ut.size_bits),
+            ut.size_bits,
+            if ut.is_device { " (device)" } else { "" },
+            usage.num_objects,
+            usage.object_bytes,
+            usage.dummy_bytes,
+            usage.free_bytes(),
+        );
+    }
+    println!();
+    println!("Bytes lost to watermark manipulation: {:#x}", report.dummy_bytes());
+    println!("Free RAM after allocation: {:#x}", report.free_ram_bytes());
+    for obj_id in report.unsatisfiable_paddrs.iter() {
+        let named_obj = &spec.objects[usize::from(*obj_id)];
+        println!(
+            "Cannot satisfy paddr {:#x} of object '{}'",
+            named_obj.object.paddr().unwrap().0,
+            named_obj.name.as_deref().unwrap_or("<unnamed>"),
+        );
+    }
+    for (size_bits, shortfall) in report.shortfall_by_size_bits.iter().enumerate() {
+        if *shortfall > 0 {
+            println!("Short {shortfall} objects of size bit {size_bits}");
+        }
+    }
+    let cslots = &report.cslots;
+    println!(
+        "Peak CSlot usage: {} (orig = {}, hold = {}, mapping copies = {}, badged copies = {}, asid pool copies = {}){}",
+        cslots.peak(),
+        cslots.orig,
+        cslots.hold,
+        cslots.mapping_copies,
+        cslots.badged_copies,
+        cslots.asid_pool_copies,
+        match empty_slots {
+            Some(n) => format!(" of {n} available"),
+            None => String::new(),
+        },
+    );
+}
@@ -9,34 +9,238 @@ use core::fmt;
 
 use crate::cslot_allocator::CSlotAllocatorError;
 
+/// An error encountered while initializing the system described by a spec.
+///
+/// The [`fmt::Display`] implementation emits a single line of `key=value` pairs, so that boot logs
+/// can be parsed mechanically.
 #[derive(Debug)]
-pub enum CapDLInitializerError {
-    CSlotAllocatorError(CSlotAllocatorError),
-    #[allow(dead_code)] // false positive
+pub struct CapDLInitializerError<'a> {
+    pub phase: Option<Phase>,
+    pub object: Option<ObjectRef<'a>>,
+    pub slot: Option<usize>,
+    pub kind: ErrorKind,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Phase {
+    Setup,
+    CreateObjects,
+    InitIrqs,
+    InitAsids,
+    InitMeasurementLog,
+    InitFrames,
+    InitVSpaces,
+    InitIOSpaces,
+    InitSchedContexts,
+    InitTcbs,
+    InitCSpaces,
+    InitDomainSchedule,
+    StartThreads,
+    StartDomainSchedule,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ObjectRef<'a> {
+    pub id: usize,
+    pub name: Option<&'a str>,
+}
+
+#[derive(Debug)]
+pub enum ErrorKind {
     SeL4Error(sel4::Error),
+    CSlotAllocatorError(CSlotAllocatorError),
+    UnsatisfiablePaddr { paddr: sel4::Word, size_bits: usize },
+    OutOfUntypeds { size_bits: usize, shortfall: usize },
+    UnexpectedObjectType,
+    UnexpectedCapType,
+    MissingCap,
+    MissingFaultEp,
+    BadgedTimeoutFaultEp { badge: sel4::Word },
+    UnsupportedFrameSize { size_bits: usize },
+    UnsupportedIOPTLevels { num_levels: usize },
+    UnexpectedIOPTLevel { level: usize },
+    UnsupportedEptRoot,
+    DurationOverflow { duration_us: u64 },
+    DurationNotMultipleOfTick { duration_us: u64 },
+    MissingTscFreq,
+    MeasurementLogFull,
+    MeasurementUnsupported,
+}
+
+impl<'a> CapDLInitializerError<'a> {
+    pub fn new(kind: ErrorKind) -> Self {
+        Self {
+            phase: None,
+            object: None,
+            slot: None,
+            kind,
+        }
+    }
+
+    pub(crate) fn with_object(mut self, object: ObjectRef<'a>) -> Self {
+        self.object.get_or_insert(object);
+        self
+    }
+
+    pub(crate) fn with_slot(mut self, slot: usize) -> Self {
+        self.slot.get_or_insert(slot);
+        self
+    }
+
+    pub fn sel4_error(&self) -> Option<sel4::Error> {
+        match &self.kind {
+            ErrorKind::SeL4Error(err) => Some(*err),
+            _ => None,
+        }
+    }
 }
 
-impl From<CSlotAllocatorError> for CapDLInitializerError {
+impl ObjectRef<'_> {
+    pub(crate) fn unnamed(id: impl Into<usize>) -> Self {
+        Self {
+            id: id.into(),
+            name: None,
+        }
+    }
+}
+
+impl From<ErrorKind> for CapDLInitializerError<'_> {
+    fn from(kind: ErrorKind) -> Self {
+        Self::new(kind)
+    }
+}
+
+impl From<CSlotAllocatorError> for CapDLInitializerError<'_> {
     fn from(err: CSlotAllocatorError) -> Self {
-        Self::CSlotAllocatorError(err)
+        Self::new(ErrorKind::CSlotAllocatorError(err))
     }
 }
 
-impl From<sel4::Error> for CapDLInitializerError {
+impl From<sel4::Error> for CapDLInitializerError<'_> {
     fn from(err: sel4::Error) -> Self {
-        Self::SeL4Error(err)
+        Self::new(ErrorKind::SeL4Error(err))
     }
 }
 
-impl From<Infallible> for CapDLInitializerError {
+impl From<Infallible> for CapDLInitializerError<'_> {
     fn from(err: Infallible) -> Self {
         match err {}
     }
 }
 
-impl fmt::Display for CapDLInitializerError {
+/// Attaches context to errors as they propagate. The innermost context wins.
+pub(crate) trait ResultExt<'a, T> {
+    fn in_phase(self, phase: Phase) -> Result<T, CapDLInitializerError<'a>>;
+
+    fn for_object(self, object: ObjectRef<'a>) -> Result<T, CapDLInitializerError<'a>>;
+
+    fn at_slot(self, slot: usize) -> Result<T, CapDLInitializerError<'a>>;
+}
+
+impl<'a, T, E: Into<CapDLInitializerError<'a>>> ResultExt<'a, T> for Result<T, E> {
+    fn in_phase(self, phase: Phase) -> Result<T, CapDLInitializerError<'a>> {
+        self.map_err(|err| {
+            let mut err = err.into();
+            err.phase.get_or_insert(phase);
+            err
+        })
+    }
+
+    fn for_object(self, object: ObjectRef<'a>) -> Result<T, CapDLInitializerError<'a>> {
+        self.map_err(|err| err.into().with_object(object))
+    }
+
+    fn at_slot(self, slot: usize) -> Result<T, CapDLInitializerError<'a>> {
+        self.map_err(|err| err.into().with_slot(slot))
+    }
+}
+
+impl fmt::Display for CapDLInitializerError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "capdl-initializer-error:")?;
+        if let Some(phase) = &self.phase {
+            write!(f, " phase={phase}")?;
+        }
+        if let Some(object) = &self.object {
+            write!(f, " object={}", object.id)?;
+            if let Some(name) = object.name {
+                write!(f, " name={name:?}")?;
+            }
+        }
+        if let Some(slot) = &self.slot {
+            write!(f, " slot={slot}")?;
+        }
+        write!(f, " {}", self.kind)
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Self::Setup => "setup",
+            Self::CreateObjects => "create_objects",
+            Self::InitIrqs => "init_irqs",
+            Self::InitAsids => "init_asids",
+            Self::InitMeasurementLog => "init_measurement_log",
+            Self::InitFrames => "init_frames",
+            Self::InitVSpaces => "init_vspaces",
+            Self::InitIOSpaces => "init_iospaces",
+            Self::InitSchedContexts => "init_sched_contexts",
+            Self::InitTcbs => "init_tcbs",
+            Self::InitCSpaces => "init_cspaces",
+            Self::InitDomainSchedule => "init_domain_schedule",
+            Self::StartThreads => "start_threads",
+            Self::StartDomainSchedule => "start_domain_schedule",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl fmt::Display for ErrorKind {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // TODO
-        write!(f, "{self:?}")
+        match self {
+            Self::SeL4Error(err) => write!(f, "kind=sel4_error sel4_error={err:?}"),
+            Self::CSlotAllocatorError(CSlotAllocatorError::OutOfSlots) => {
+                write!(f, "kind=out_of_cslots")
+            }
+            Self::UnsatisfiablePaddr { paddr, size_bits } => write!(
+                f,
+                "kind=unsatisfiable_paddr paddr={paddr:#x} size_bits={size_bits}"
+            ),
+            Self::OutOfUntypeds {
+                size_bits,
+                shortfall,
+            } => write!(
+                f,
+                "kind=out_of_untypeds size_bits={size_bits} shortfall={shortfall}"
+            ),
+            Self::UnexpectedObjectType => write!(f, "kind=unexpected_object_type"),
+            Self::UnexpectedCapType => write!(f, "kind=unexpected_cap_type"),
+            Self::MissingCap => write!(f, "kind=missing_cap"),
+            Self::MissingFaultEp => write!(f, "kind=missing_fault_ep"),
+            Self::BadgedTimeoutFaultEp { badge } => {
+                write!(f, "kind=badged_timeout_fault_ep badge={badge:#x}")
+            }
+            Self::UnsupportedFrameSize { size_bits } => {
+                write!(f, "kind=unsupported_frame_size size_bits={size_bits}")
+            }
+            Self::UnsupportedIOPTLevels { num_levels } => {
+                write!(f, "kind=unsupported_iopt_levels num_levels={num_levels}")
+            }
+            Self::UnexpectedIOPTLevel { level } => {
+                write!(f, "kind=unexpected_iopt_level level={level}")
+            }
+            Self::UnsupportedEptRoot => write!(f, "kind=unsupported_ept_root"),
+            Self::DurationOverflow { duration_us } => {
+                write!(f, "kind=duration_overflow duration_us={duration_us}")
+            }
+            Self::DurationNotMultipleOfTick { duration_us } => write!(
+                f,
+                "kind=duration_not_multiple_of_tick duration_us={duration_us}"
+            ),
+            Self::MissingTscFreq => write!(f, "kind=missing_tsc_freq"),
+            Self::MeasurementLogFull => write!(f, "kind=measurement_log_full"),
+            Self::MeasurementUnsupported => write!(f, "kind=measurement_unsupported"),
+        }
     }
 }
@@ -22,7 +22,7 @@ impl<T> HoldSlots<T> {
     pub(crate) fn new(
         cslot_allocator: &mut CSlotAllocator,
         absolute_cptr_of: T,
-    ) -> Result<Self, CapDLInitializerError> {
+    ) -> Result<Self, CapDLInitializerError<'static>> {
         Ok(Self {
             slots: {
                 let mut f = || cslot_allocator.alloc();
@@ -36,7 +36,7 @@ impl<T> HoldSlots<T> {
 }
 
 impl<T: FnMut(Slot) -> AbsoluteCPtr> HoldSlots<T> {
-    pub(crate) fn get_slot(&mut self) -> Result<Slot, CapDLInitializerError> {
+    pub(crate) fn get_slot(&mut self) -> Result<Slot, CapDLInitializerError<'static>> {
         if self.slots_occupied[self.which_slot] {
             (self.absolute_cptr_of)(self.slots[self.which_slot]).delete()?;
             self.slots_occupied[self.which_slot] = false;
@@ -23,11 +23,15 @@ use sel4::{
 use sel4_capdl_initializer_types::*;
 
 use crate::cslot_allocator::CSlotAllocator;
-use crate::error::CapDLInitializerError;
+use crate::error::{CapDLInitializerError, ErrorKind, ObjectRef, Phase, ResultExt};
 use crate::hold_slots::HoldSlots;
+use crate::measurement::MeasurementLog;
 use crate::memory::{CopyAddrs, get_user_image_frame_slot};
 
-type Result<T> = CoreResult<T, CapDLInitializerError>;
+#[cfg(feature = "measurement")]
+use crate::memory::init_measurement_log_addr;
+
+type Result<T> = CoreResult<T, CapDLInitializerError<'static>>;
 
 pub struct Initializer<'a> {
     bootinfo: &'a sel4::BootInfoPtr,
@@ -37,6 +41,7 @@ pub struct Initializer<'a> {
     embedded_frames_base_addr: usize,
     orig_cslots: Range<Slot>,
     cslot_allocator: &'a mut CSlotAllocator,
+    measurement_log: MeasurementLog,
 }
 
 impl<'a> Initializer<'a> {
@@ -45,10 +50,10 @@ impl<'a> Initializer<'a> {
         user_image_bounds: Range<usize>,
         spec: &'a <SpecForInitializer as Archive>::Archived,
         embedded_frames_base_addr: usize,
-    ) -> ! {
+    ) -> CoreResult<(), CapDLInitializerError<'a>> {
         info!("Starting CapDL initializer");
 
-        let copy_addrs = CopyAddrs::init(bootinfo, &user_image_bounds).unwrap();
+        let copy_addrs = CopyAddrs::init(bootinfo, &user_image_bounds).in_phase(Phase::Setup)?;
 
         let mut cslot_allocator = CSlotAllocator::new(bootinfo.empty().range());
 
@@ -61,7 +66,7 @@ impl<'a> Initializer<'a> {
                     .try_into()
                     .unwrap(),
             )
-            .unwrap();
+            .in_phase(Phase::Setup)?;
 
         Initializer {
             bootinfo,
@@ -71,42 +76,45 @@ impl<'a> Initializer<'a> {
             embedded_frames_base_addr,
             orig_cslots,
             cslot_allocator: &mut cslot_allocator,
+            measurement_log: MeasurementLog::inactive(),
         }
         .run()
-        .unwrap_or_else(|err| panic!("Error: {}", err));
-
-        debug!("CapDL initializer done, suspending");
-
-        init_thread::suspend_self()
+        .map_err(|err| with_object_name(spec, err))
     }
 
     // // //
 
     fn run(&mut self) -> Result<()> {
-        self.create_objects()?;
-
-        self.init_irqs()?;
-        self.init_asids()?;
-        self.init_frames()?;
-        self.init_vspaces()?;
+        self.create_objects().in_phase(Phase::CreateObjects)?;
+
+        self.init_irqs().in_phase(Phase::InitIrqs)?;
+        self.init_asids().in_phase(Phase::InitAsids)?;
+        self.init_measurement_log()
+            .in_phase(Phase::InitMeasurementLog)?;
+        self.init_frames().in_phase(Phase::InitFrames)?;
+        self.init_vspaces().in_phase(Phase::InitVSpaces)?;
         sel4::sel4_cfg_if! {
             if #[sel4_cfg(all(ARCH_X86_64,IOMMU))] {
-                self.init_iospaces()?;
+                self.init_iospaces().in_phase(Phase::InitIOSpaces)?;
             }
         }
 
         sel4::sel4_cfg_if! {
             if #[sel4_cfg(KERNEL_MCS)] {
-                self.init_sched_contexts()?;
+                self.init_sched_contexts().in_phase(Phase::InitSchedContexts)?;
             }
         }
 
-        self.init_tcbs()?;
-        self.init_cspaces()?;
-        self.init_domain_schedule()?;
+        self.init_tcbs().in_phase(Phase::InitTcbs)?;
+        self.finish_measurement_log()
+            .in_phase(Phase::InitMeasurementLog)?;
+        self.init_cspaces().in_phase(Phase::InitCSpaces)?;
+        self.init_domain_schedule()
+            .in_phase(Phase::InitDomainSchedule)?;
 
-        self.start_threads()?;
-        self.start_domain_schedule()?;
+        self.start_threads().in_phase(Phase::StartThreads)?;
+        self.start_domain_schedule()
+            .in_phase(Phase::StartDomainSchedule)?;
 
         Ok(())
     }
@@ -137,7 +145,7 @@ impl<'a> Initializer<'a> {
 
         // Sanity check that all objects with a paddr attached can be allocated.
         // Currently this is only applicable to Frame objects
-        let mut phys_addrs_ok = true;
+        let mut first_unsatisfiable = None;
         for obj_with_paddr_id in 0..first_obj_without_paddr {
             let named_obj = self.named_object(obj_with_paddr_id.into());
             let paddr_base = named_obj.object.paddr().unwrap().to_sel4();
@@ -145,6 +153,11 @@ impl<'a> Initializer<'a> {
             let blueprint = named_obj.object.blueprint().unwrap();
             let obj_size_bytes = 1 << blueprint.physical_size_bits();
             let paddr_range = paddr_base..paddr_base + obj_size_bytes;
+            let unsatisfiable = CapDLInitializerError::new(ErrorKind::UnsatisfiablePaddr {
+                paddr: paddr_base,
+                size_bits: blueprint.physical_size_bits(),
+            })
+            .with_object(ObjectRef::unnamed(obj_with_paddr_id));
 
             // Binary search for the UT that is next to the UT that might fit.
             // i.e. we are looking for the first UT that is uts[i_ut].paddr() > paddr_range.start
@@ -155,7 +168,7 @@ impl<'a> Initializer<'a> {
             if ut_after_candidate_idx == 0 {
                 // Predicate returned false for the first UT, cannot allocate this object as all UTs are
                 // after the object.
-                phys_addrs_ok = false;
+                first_unsatisfiable.get_or_insert(unsatisfiable);
             } else {
                 let candidate_ut = &uts[uts_by_paddr[ut_after_candidate_idx - 1]];
                 let candidate_ut_range =
@@ -170,12 +183,12 @@ impl<'a> Initializer<'a> {
                         paddr_range.end,
                         blueprint.physical_size_bits()
                     );
-                    phys_addrs_ok = false;
+                    first_unsatisfiable.get_or_insert(unsatisfiable);
                 }
             }
         }
 
-        if !phys_addrs_ok {
+        if let Some(err) = first_unsatisfiable {
             error!("Below are the valid ranges of memory to be allocated from:");
             error!("Valid ranges outside of main memory:");
             for i_ut in uts_by_paddr.iter().filter(|i_ut| uts[**i_ut].is_device()) {
@@ -193,9 +206,7 @@ impl<'a> Initializer<'a> {
                 let end = base + (1 << size_bit);
                 error!("     [0x{base:0>12x}..0x{end:0>12x})");
             }
-            panic!(
-                "Encountered a spec object with physical address constraint that cannot be satisfied."
-            );
+            return Err(err);
         }
 
         let mut by_size_start: [usize; sel4::WORD_SIZE] = array::from_fn(|_| 0);
@@ -284,12 +295,14 @@ impl<'a> Initializer<'a> {
                                     blueprint.physical_size_bits(),
                                     object_name_or_default(named_obj)
                                 );
-                                self.ut_cap(*i_ut).untyped_retype(
-                                    &blueprint,
-                                    &init_thread_cnode_absolute_cptr(),
-                                    self.orig_cslot((*obj_id).into()).index(),
-                                    1,
-                                )?;
+                                self.ut_cap(*i_ut)
+                                    .untyped_retype(
+                                        &blueprint,
+                                        &init_thread_cnode_absolute_cptr(),
+                                        self.orig_cslot((*obj_id).into()).index(),
+                                        1,
+                                    )
+                                    .for_object(ObjectRef::unnamed(*obj_id))?;
                                 cur_paddr += 1 << size_bits;
                                 *obj_id += 1;
                                 created = true;
@@ -328,12 +341,14 @@ impl<'a> Initializer<'a> {
                         blueprint.physical_size_bits(),
                         object_name_or_default(named_obj)
                     );
-                    self.ut_cap(*i_ut).untyped_retype(
-                        &blueprint,
-                        &init_thread_cnode_absolute_cptr(),
-                        self.orig_cslot(obj_id.into()).index(),
-                        1,
-                    )?;
+                    self.ut_cap(*i_ut)
+                        .untyped_retype(
+                            &blueprint,
+                            &init_thread_cnode_absolute_cptr(),
+                            self.orig_cslot(obj_id.into()).index(),
+                            1,
+                        )
+                        .for_object(ObjectRef::unnamed(obj_id))?;
                     cur_paddr += 1 << blueprint.physical_size_bits();
                     next_obj_with_paddr += 1;
                 } else {
@@ -343,18 +358,21 @@ impl<'a> Initializer<'a> {
         }
 
         // Ensure that we've created every root object
-        let mut oom = false;
+        let mut first_oom = None;
         for bits in 0..sel4::WORD_SIZE {
             if by_size_start[bits] != by_size_end[bits] {
-                oom = true;
                 let shortfall = by_size_end[bits] - by_size_start[bits];
                 error!(
                     "Error: ran out of untypeds for allocating objects of size bit {bits}, still need to create {shortfall} more objects."
                 );
+                first_oom.get_or_insert(ErrorKind::OutOfUntypeds {
+                    size_bits: bits,
+                    shortfall,
+                });
             }
         }
-        if oom {
-            panic!("Out of untypeds.");
+        if let Some(kind) = first_oom {
+            return Err(kind.into());
         }
 
         // Create child objects
@@ -372,12 +390,14 @@ impl<'a> Initializer<'a> {
                     object_name_or_default(child),
                     object_name_or_default(parent),
                 );
-                parent_cptr.untyped_retype(
-                    &child.object.blueprint().unwrap(),
-                    &init_thread_cnode_absolute_cptr(),
-                    self.orig_cslot(child_obj_id.into()).index(),
-                    1,
-                )?;
+                parent_cptr
+                    .untyped_retype(
+                        &child.object.blueprint().unwrap(),
+                        &init_thread_cnode_absolute_cptr(),
+                        self.orig_cslot(child_obj_id.into()).index(),
+                        1,
+                    )
+                    .for_object(ObjectRef::unnamed(child_obj_id))?;
             }
         }
 
@@ -386,13 +406,8 @@ impl<'a> Initializer<'a> {
         // upstream C CapDL loader).
         {
             for obj_id in self.spec.asid_slots.iter() {
-                let orig = self.orig_cslot(*obj_id);
-                let orig_absolute = cslot_to_absolute_cptr(orig);
-                let copy = self.copy(orig.cap().downcast())?;
-                orig_absolute.delete()?;
-                init_thread::slot::ASID_CONTROL
-                    .cap()
-                    .asid_control_make_pool(copy, &orig_absolute)?;
+                self.make_asid_pool(*obj_id)
+                    .for_object(ObjectRef::unnamed(*obj_id))?;
             }
         }
 
@@ -400,11 +415,13 @@ impl<'a> Initializer<'a> {
         {
             for ArchivedIrqEntry { irq, handler } in self.spec.irqs.iter() {
                 let slot = self.orig_cslot(*handler);
+                let handler_ref = ObjectRef::unnamed(*handler);
                 sel4::sel4_cfg_wrap_match! {
                     match self.object(*handler) {
                         ArchivedObject::Irq(_) => {
                             init_thread::slot::IRQ_CONTROL.cap()
-                                .irq_control_get(irq.to_sel4(), &cslot_to_absolute_cptr(slot))?;
+                                .irq_control_get(irq.to_sel4(), &cslot_to_absolute_cptr(slot))
+                                .for_object(handler_ref)?;
                         }
                         #[sel4_cfg(ARCH_ARM)]
                         ArchivedObject::ArmIrq(obj) => {
@@ -414,14 +431,14 @@ impl<'a> Initializer<'a> {
                                         irq.to_sel4(),
                                         obj.extra.trigger != 0,
                                         &cslot_to_absolute_cptr(slot),
-                                    )?;
+                                    ).for_object(handler_ref)?;
                                 } else {
                                     init_thread::slot::IRQ_CONTROL.cap().irq_control_get_trigger_core(
                                         irq.to_sel4(),
                                         obj.extra.trigger != 0,
                                         obj.extra.target.to_sel4(),
                                         &cslot_to_absolute_cptr(slot),
-                                    )?;
+                                    ).for_object(handler_ref)?;
                                 }
                             }
                         }
@@ -434,7 +451,7 @@ impl<'a> Initializer<'a> {
                                 obj.extra.handle.to_sel4(),
                                 irq.to_sel4(),
                                 &cslot_to_absolute_cptr(slot),
-                            )?;
+                            ).for_object(handler_ref)?;
                         }
                         #[sel4_cfg(ARCH_X86_64)]
                         ArchivedObject::IrqIOApic(obj) => {
@@ -445,7 +462,7 @@ impl<'a> Initializer<'a> {
                                 obj.extra.polarity.to_sel4(),
                                 irq.to_sel4(),
                                 &cslot_to_absolute_cptr(slot),
-                            )?;
+                            ).for_object(handler_ref)?;
                         }
                         #[sel4_cfg(any(ARCH_RISCV64, ARCH_RISCV32))]
                         ArchivedObject::RiscvIrq(obj) => {
@@ -453,10 +470,11 @@ impl<'a> Initializer<'a> {
                                 irq.to_sel4(),
                                 obj.extra.trigger != 0,
                                 &cslot_to_absolute_cptr(slot),
-                            )?;
+                            ).for_object(handler_ref)?;
                         }
                         _ => {
-                            panic!();
+                            return Err(CapDLInitializerError::new(ErrorKind::UnexpectedObjectType)
+                                .with_object(handler_ref));
                         }
                     }
                 }
@@ -475,7 +493,8 @@ impl<'a> Initializer<'a> {
                         let slot = self.orig_cslot(obj_id);
                         init_thread::slot::IO_PORT_CONTROL
                             .cap()
-                            .ioport_control_issue(start_port.to_sel4(), end_port.to_sel4(), &cslot_to_absolute_cptr(slot))?;
+                            .ioport_control_issue(start_port.to_sel4(), end_port.to_sel4(), &cslot_to_absolute_cptr(slot))
+                            .for_object(ObjectRef::unnamed(obj_id))?;
                     }
                 }
             }
@@ -484,6 +503,17 @@ impl<'a> Initializer<'a> {
         Ok(())
     }
 
+    fn make_asid_pool(&mut self, obj_id: ArchivedObjectId) -> Result<()> {
+        let orig = self.orig_cslot(obj_id);
+        let orig_absolute = cslot_to_absolute_cptr(orig);
+        let copy = self.copy(orig.cap().downcast())?;
+        orig_absolute.delete()?;
+        init_thread::slot::ASID_CONTROL
+            .cap()
+            .asid_control_make_pool(copy, &orig_absolute)?;
+        Ok(())
+    }
+
     fn init_irqs(&mut self) -> Result<()> {
         debug!("Initializing IRQs");
 
@@ -509,31 +539,45 @@ impl<'a> Initializer<'a> {
             .chain(ioapic_irq_notifications)
             .chain(riscv_irq_notifications);
         for (obj_id, notification) in all_irq_notifications {
-            let irq_handler = self.orig_cap::<cap_type::IrqHandler>(obj_id);
             if let Some(logical_nfn_cap) = notification {
-                let nfn = match logical_nfn_cap.badge.to_sel4() {
-                    0 => self.orig_cap(logical_nfn_cap.object),
-                    badge => {
-                        let orig_cptr = self.orig_absolute_cptr(logical_nfn_cap.object);
-                        let slot = self.cslot_alloc_or_panic();
-                        let cptr = cslot_to_absolute_cptr(slot);
-                        cptr.mint(&orig_cptr, CapRights::all(), badge)?;
-                        slot.cap().downcast()
-                    }
-                };
-                irq_handler.irq_handler_set_notification(nfn)?;
+                self.init_irq(obj_id, logical_nfn_cap)
+                    .for_object(ObjectRef::unnamed(obj_id))
+                    .at_slot(object::Irq::SLOT_NOTIFICATION.into())?;
             }
         }
         Ok(())
     }
 
+    fn init_irq(
+        &mut self,
+        obj_id: ArchivedObjectId,
+        logical_nfn_cap: &<cap::Notification as Archive>::Archived,
+    ) -> Result<()> {
+        let irq_handler = self.orig_cap::<cap_type::IrqHandler>(obj_id);
+        let nfn = match logical_nfn_cap.badge.to_sel4() {
+            0 => self.orig_cap(logical_nfn_cap.object),
+            badge => {
+                let orig_cptr = self.orig_absolute_cptr(logical_nfn_cap.object);
+                let slot = self.cslot_alloc()?;
+                let cptr = cslot_to_absolute_cptr(slot);
+                cptr.mint(&orig_cptr, CapRights::all(), badge)?;
+                slot.cap().downcast()
+            }
+        };
+        irq_handler.irq_handler_set_notification(nfn)?;
+        Ok(())
+    }
+
     fn init_asids(&self) -> Result<()> {
         debug!("Initializing ASIDs");
         for (obj_id, _obj) in
             self.filter_objects_with::<object::ArchivedPageTable>(|obj| obj.is_root)
         {
             let pgd = self.orig_cap::<cap_type::VSpace>(obj_id);
-            init_thread::slot::ASID_POOL.cap().asid_pool_assign(pgd)?;
+            init_thread::slot::ASID_POOL
+                .cap()
+                .asid_pool_assign(pgd)
+                .for_object(ObjectRef::unnamed(obj_id))?;
         }
         Ok(())
     }
@@ -542,24 +586,40 @@ impl<'a> Initializer<'a> {
         debug!("Initializing Frames");
         for (obj_id, obj) in self.filter_objects::<object::ArchivedFrame<_>>() {
             // TODO make more platform-agnostic
-            if let ArchivedFrameInit::Fill(fill) = &obj.init
-                && !fill.entries.is_empty()
-            {
-                let frame_object_type =
-                    sel4::FrameObjectType::from_bits(obj.size_bits.into()).unwrap();
-                let frame = self.orig_cap::<cap_type::UnspecifiedPage>(obj_id);
-                self.fill_frame(frame, frame_object_type, &fill.entries)?;
+            match &obj.init {
+                ArchivedFrameInit::Fill(fill) if !fill.entries.is_empty() => {
+                    let size_bits = obj.size_bits.into();
+                    let frame_object_type = sel4::FrameObjectType::from_bits(size_bits)
+                        .ok_or(ErrorKind::UnsupportedFrameSize { size_bits })
+                        .for_object(ObjectRef::unnamed(obj_id))?;
+                    self.fill_frame(obj_id, frame_object_type, &fill.entries)
+                        .for_object(ObjectRef::unnamed(obj_id))?;
+                }
+                ArchivedFrameInit::Embedded(embedded) => {
+                    let frame_addr = self.embedded_frame_addr(embedded);
+                    let contents = unsafe {
+                        slice::from_raw_parts(
+                            frame_addr as *const u8,
+                            cap_type::Granule::FRAME_OBJECT_TYPE.bytes(),
+                        )
+                    };
+                    self.measurement_log
+                        .frame(obj_id, contents)
+                        .for_object(ObjectRef::unnamed(obj_id))?;
+                }
+                _ => {}
             }
         }
         Ok(())
     }
 
     fn fill_frame(
-        &self,
-        frame: sel4::Cap<cap_type::UnspecifiedPage>,
+        &mut self,
+        obj_id: ArchivedObjectId,
         frame_object_type: sel4::FrameObjectType,
         fill: &[ArchivedFillEntry<Content>],
     ) -> Result<()> {
+        let frame = self.orig_cap::<cap_type::UnspecifiedPage>(obj_id);
         frame.frame_map(
             init_thread::slot::VSPACE.cap(),
             self.copy_addrs.select(frame_object_type),
@@ -597,10 +657,74 @@ impl<'a> Initializer<'a> {
                 }
             }
         }
+        let contents = unsafe {
+            slice::from_raw_parts(
+                self.copy_addrs.select(frame_object_type) as *const u8,
+                frame_object_type.bytes(),
+            )
+        };
+        self.measurement_log.frame(obj_id, contents)?;
         frame.frame_unmap()?;
         Ok(())
     }
 
+    #[cfg(feature = "measurement")]
+    fn init_measurement_log(&mut self) -> Result<()> {
+        let ArchivedOption::Some(obj_id) = self.spec.measurement_log else {
+            return Ok(());
+        };
+        debug!("Initializing measurement log");
+        let obj_ref = ObjectRef::unnamed(obj_id);
+        // The log must start out zeroed, so the frame must not have any fill.
+        match self.object(obj_id) {
+            ArchivedObject::Frame(frame)
+                if usize::from(frame.size_bits) == cap_type::Granule::FRAME_OBJECT_TYPE.bits()
+                    && matches!(&frame.init, ArchivedFrameInit::Fill(fill) if fill.entries.is_empty()) =>
+                {}
+            _ => {
+                return Err(CapDLInitializerError::new(ErrorKind::UnexpectedObjectType))
+                    .for_object(obj_ref);
+            }
+        }
+        let addr = init_measurement_log_addr(self.bootinfo, &self.user_image_bounds)?;
+        self.orig_cap::<cap_type::Granule>(obj_id)
+            .frame_map(
+                init_thread::slot::VSPACE.cap(),
+                addr,
+                CapRights::read_write(),
+                vm_attributes_from_whether_cached_and_exec(true, false, false),
+            )
+            .for_object(obj_ref)?;
+        self.measurement_log =
+            unsafe { MeasurementLog::start(addr, cap_type::Granule::FRAME_OBJECT_TYPE.bytes()) }
+                .for_object(obj_ref)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "measurement"))]
+    fn init_measurement_log(&mut self) -> Result<()> {
+        if self.spec.measurement_log.is_some() {
+            return Err(ErrorKind::MeasurementUnsupported.into());
+        }
+        Ok(())
+    }
+
+    fn finish_measurement_log(&mut self) -> Result<()> {
+        if let ArchivedOption::Some(obj_id) = self.spec.measurement_log {
+            self.measurement_log = MeasurementLog::inactive();
+            self.orig_cap::<cap_type::Granule>(obj_id)
+                .frame_unmap()
+                .for_object(ObjectRef::unnamed(obj_id))?;
+        }
+        Ok(())
+    }
+
+    fn embedded_frame_addr(&self, embedded: &ArchivedEmbeddedFrameIndex) -> usize {
+        self.embedded_frames_base_addr
+            + usize::try_from(embedded.index).unwrap()
+                * cap_type::Granule::FRAME_OBJECT_TYPE.bytes()
+    }
+
     fn init_vspaces(&mut self) -> Result<()> {
         debug!("Initializing VSpaces");
         for (obj_id, obj) in
@@ -611,14 +735,17 @@ impl<'a> Initializer<'a> {
                 sel4::sel4_cfg_if! {
                     if #[sel4_cfg(all(ARCH_X86_64, VTX))] {
                         let vspace = self.orig_cap::<cap_type::EPTPML4>(obj_id);
-                        self.init_vspace_x86_ept(vspace, root_level, 0, obj)?;
+                        self.init_vspace_x86_ept(vspace, root_level, 0, obj)
+                            .for_object(ObjectRef::unnamed(obj_id))?;
                     } else {
-                        panic!("encountered an EPT root without VTX configuration!");
+                        return Err(CapDLInitializerError::new(ErrorKind::UnsupportedEptRoot)
+                            .with_object(ObjectRef::unnamed(obj_id)));
                     }
                 }
             } else {
                 let vspace = self.orig_cap::<cap_type::VSpace>(obj_id);
-                self.init_vspace(vspace, root_level, 0, obj)?;
+                self.init_vspace(vspace, root_level, 0, obj)
+                    .for_object(ObjectRef::unnamed(obj_id))?;
             }
         }
         Ok(())
@@ -638,12 +765,9 @@ impl<'a> Initializer<'a> {
                 PageTableEntry::Frame(cap) => {
                     let frame = self.orig_cap::<cap_type::UnspecifiedPage>(cap.object);
                     let rights = cap.rights.to_sel4();
-                    self.copy(frame)?.ept_frame_map(
-                        eptpml4,
-                        vaddr,
-                        rights,
-                        cap.vm_attributes(true),
-                    )?;
+                    self.copy(frame)?
+                        .ept_frame_map(eptpml4, vaddr, rights, cap.vm_attributes(true))
+                        .at_slot(i.into())?;
                 }
                 PageTableEntry::PageTable(cap) => {
                     self.orig_cap::<cap_type::UnspecifiedIntermediateTranslationTable>(cap.object)
@@ -652,9 +776,11 @@ impl<'a> Initializer<'a> {
                             eptpml4,
                             vaddr,
                             cap.vm_attributes(true),
-                        )?;
+                        )
+                        .at_slot(i.into())?;
                     let obj = self.object_as::<object::ArchivedPageTable>(cap.object);
-                    self.init_vspace_x86_ept(eptpml4, level + 1, vaddr, obj)?;
+                    self.init_vspace_x86_ept(eptpml4, level + 1, vaddr, obj)
+                        .for_object(ObjectRef::unnamed(cap.object))?;
                 }
             }
         }
@@ -675,7 +801,8 @@ impl<'a> Initializer<'a> {
                     let frame = self.orig_cap::<cap_type::UnspecifiedPage>(cap.object);
                     let rights = cap.rights.to_sel4();
                     self.copy(frame)?
-                        .frame_map(vspace, vaddr, rights, cap.vm_attributes(false))?;
+                        .frame_map(vspace, vaddr, rights, cap.vm_attributes(false))
+                        .at_slot(i.into())?;
                 }
                 PageTableEntry::PageTable(cap) => {
                     self.orig_cap::<cap_type::UnspecifiedIntermediateTranslationTable>(cap.object)
@@ -684,9 +811,11 @@ impl<'a> Initializer<'a> {
                             vspace,
                             vaddr,
                             cap.vm_attributes(false),
-                        )?;
+                        )
+                        .at_slot(i.into())?;
                     let obj = self.object_as::<object::ArchivedPageTable>(cap.object);
-                    self.init_vspace(vspace, level + 1, vaddr, obj)?;
+                    self.init_vspace(vspace, level + 1, vaddr, obj)
+                        .for_object(ObjectRef::unnamed(cap.object))?;
                 }
             }
         }
@@ -705,13 +834,15 @@ impl<'a> Initializer<'a> {
         }
 
         let num_iopt_levels = self.bootinfo.inner().numIOPTLevels as usize;
-        if num_iopt_levels < x86_io_address_space::CAPDL_NUM_IOPT_LEVELS {
-            panic!(
-                "Error: we have assumed at least a 39 bit (3-level) address space would be supported."
-            )
-        }
-        if num_iopt_levels > x86_io_address_space::MAX_RUNTIME_NUM_LEVELS {
-            panic!("Error: seL4 reported an unsupported x86 IOPT depth.")
+        // We have assumed at least a 39 bit (3-level) address space would be supported.
+        if !(x86_io_address_space::CAPDL_NUM_IOPT_LEVELS
+            ..=x86_io_address_space::MAX_RUNTIME_NUM_LEVELS)
+            .contains(&num_iopt_levels)
+        {
+            return Err(ErrorKind::UnsupportedIOPTLevels {
+                num_levels: num_iopt_levels,
+            }
+            .into());
         }
 
         // We assume that the IO Address Space was created to support 39 bit addresses (seL4 calls this 3 level)
@@ -719,12 +850,17 @@ impl<'a> Initializer<'a> {
         let prefix_depth = num_iopt_levels - x86_io_address_space::CAPDL_NUM_IOPT_LEVELS;
 
         for (obj_id, obj) in self.filter_objects::<object::ArchivedIOSpace>() {
-            let iospace = self.mint_iospace_cap(obj_id, obj)?;
+            let obj_ref = ObjectRef::unnamed(obj_id);
+
+            let iospace = self.mint_iospace_cap(obj_id, obj).for_object(obj_ref)?;
 
-            let root_iopt_obj_id = self.init_iospace_prefix(iospace, obj, prefix_depth)?;
+            let root_iopt_obj_id = self
+                .init_iospace_prefix(iospace, obj, prefix_depth)
+                .for_object(obj_ref)?;
 
             let root_iopt = self.object_as::<object::ArchivedIOPageTable>(root_iopt_obj_id);
-            self.init_iospace(iospace, 0, root_iopt)?;
+            self.init_iospace(iospace, 0, root_iopt)
+                .for_object(ObjectRef::unnamed(root_iopt_obj_id))?;
         }
 
         Ok(())
@@ -750,9 +886,8 @@ impl<'a> Initializer<'a> {
         let cap_data =
             sel4::io_space::IOSpaceCapData::new(obj.domain_id.to_sel4(), pci_bus, pci_dev, pci_fn);
 
-        dst.mint(&src, CapRights::all(), cap_data.into())
-            .map(|_| Ok(self.orig_cap::<cap_type::IOSpace>(obj_id)))
-            .unwrap_or_else(|err| panic!("Error: {err} when minting an x86 IOSpace capability."))
+        dst.mint(&src, CapRights::all(), cap_data.into())?;
+        Ok(self.orig_cap::<cap_type::IOSpace>(obj_id))
     }
 
     #[sel4::sel4_cfg(all(ARCH_X86_64, IOMMU))]
@@ -763,13 +898,14 @@ impl<'a> Initializer<'a> {
         prefix_depth: usize,
     ) -> Result<ArchivedObjectId> {
         for slot in (0..=prefix_depth).rev() {
-            let iopt = Self::get_iopt_obj_id(obj, slot);
+            let iopt = Self::get_iopt_obj_id(obj, slot)?;
             self.orig_cap::<cap_type::IOPageTable>(iopt)
-                .io_page_table_map(iospace, 0)?;
+                .io_page_table_map(iospace, 0)
+                .at_slot(slot)?;
         }
 
         // Return obj id of the root page table
-        Ok(Self::get_iopt_obj_id(obj, 0))
+        Self::get_iopt_obj_id(obj, 0)
     }
 
     #[sel4::sel4_cfg(all(ARCH_X86_64, IOMMU))]
@@ -781,10 +917,8 @@ impl<'a> Initializer<'a> {
     ) -> Result<()> {
         let iopt_level = obj.level.to_sel4() as usize;
         if iopt_level >= x86_io_address_space::CAPDL_NUM_IOPT_LEVELS {
-            panic!(
-                "Error: the capdl spec contains an extra level of page tables, violating our assumed {} level format!",
-                x86_io_address_space::CAPDL_NUM_IOPT_LEVELS
-            );
+            // The spec contains an extra level of page tables, violating our assumed format.
+            return Err(ErrorKind::UnexpectedIOPTLevel { level: iopt_level }.into());
         }
         for (i, entry) in obj.entries() {
             let ioaddr = ioaddr
@@ -797,17 +931,20 @@ impl<'a> Initializer<'a> {
             match entry {
                 IOPageTableEntry::IOPageTable(cap) => {
                     self.orig_cap::<cap_type::IOPageTable>(cap.object)
-                        .io_page_table_map(iospace, ioaddr.try_into().unwrap())?;
+                        .io_page_table_map(iospace, ioaddr.try_into().unwrap())
+                        .at_slot(i.into())?;
 
                     let iopt_object = self.object_as::<object::ArchivedIOPageTable>(cap.object);
-                    self.init_iospace(iospace, ioaddr, iopt_object)?;
+                    self.init_iospace(iospace, ioaddr, iopt_object)
+                        .for_object(ObjectRef::unnamed(cap.object))?;
                 }
                 IOPageTableEntry::Frame(cap) => {
                     let frame = self.orig_cap::<cap_type::UnspecifiedPage>(cap.object);
                     let rights = cap.rights.to_sel4();
 
                     self.copy(frame)?
-                        .io_frame_map(iospace, rights, ioaddr.try_into().unwrap())?;
+                        .io_frame_map(iospace, rights, ioaddr.try_into().unwrap())
+                        .at_slot(i.into())?;
                 }
             }
         }
@@ -815,22 +952,25 @@ impl<'a> Initializer<'a> {
     }
 
     #[sel4::sel4_cfg(all(ARCH_X86_64, IOMMU))]
-    fn get_iopt_obj_id(obj: &object::ArchivedIOSpace, slot: usize) -> ArchivedObjectId {
-        obj.slots
+    fn get_iopt_obj_id(obj: &object::ArchivedIOSpace, slot: usize) -> Result<ArchivedObjectId> {
+        let entry = obj
+            .slots
             .iter()
             .find(|entry| usize::from(entry.slot) == slot)
-            .map(|entry| match &entry.cap {
-                ArchivedCap::IOPageTable(cap) => cap.object,
-                _ => panic!("unexpected non-IOPageTable cap in IOSpace slot"),
-            })
-            .unwrap_or_else(|| panic!("missing IOPageTable in IOSpace slot {slot}"))
+            .ok_or(ErrorKind::MissingCap)
+            .at_slot(slot)?;
+        match &entry.cap {
+            ArchivedCap::IOPageTable(cap) => Ok(cap.object),
+            _ => Err(CapDLInitializerError::new(ErrorKind::UnexpectedCapType).with_slot(slot)),
+        }
     }
 
     #[sel4::sel4_cfg(KERNEL_MCS)]
     fn init_sched_contexts(&self) -> Result<()> {
         debug!("Initializing scheduling contexts");
         for (obj_id, _obj) in self.filter_objects::<object::ArchivedSchedContext>() {
-            self.init_sched_context(obj_id, 0)?;
+            self.init_sched_context(obj_id, 0)
+                .for_object(ObjectRef::unnamed(obj_id))?;
         }
         Ok(())
     }
@@ -858,27 +998,33 @@ impl<'a> Initializer<'a> {
         debug!("Initializing TCBs");
 
         for (obj_id, obj) in self.filter_objects::<object::ArchivedTcb>() {
+            let obj_ref = ObjectRef::unnamed(obj_id);
             let tcb = self.orig_cap::<cap_type::Tcb>(obj_id);
 
             if let Some(bound_notification) = obj.bound_notification() {
                 let bound_notification =
                     self.orig_cap::<cap_type::Notification>(bound_notification.object);
-                tcb.tcb_bind_notification(bound_notification)?;
+                tcb.tcb_bind_notification(bound_notification)
+                    .for_object(obj_ref)?;
             }
 
             sel4::sel4_cfg_if! {
                 if #[sel4_cfg(any(all(ARCH_ARM, ARM_HYPERVISOR_SUPPORT), all(ARCH_X86_64, VTX)))] {
                     if let Some(vcpu) = obj.vcpu() {
                         let vcpu = self.orig_cap::<cap_type::VCpu>(vcpu.object);
-                        vcpu.vcpu_set_tcb(tcb)?;
+                        vcpu.vcpu_set_tcb(tcb).for_object(obj_ref)?;
                     }
                 }
             }
             sel4::sel4_cfg_if! {
                 if #[sel4_cfg(all(ARCH_X86_64, VTX))] {
                     if let Some(_vcpu) = obj.vcpu() {
-                        let eptpml4 = self.orig_cap::<cap_type::EPTPML4>(obj.x86_eptpml4().unwrap().object);
-                        tcb.tcb_set_ept_root(eptpml4)?;
+                        let eptpml4 = obj.x86_eptpml4()
+                            .ok_or(ErrorKind::MissingCap)
+                            .for_object(obj_ref)
+                            .at_slot(object::Tcb::SLOT_X86_EPTPML4.into())?;
+                        let eptpml4 = self.orig_cap::<cap_type::EPTPML4>(eptpml4.object);
+                        tcb.tcb_set_ept_root(eptpml4).for_object(obj_ref)?;
                     }
                 }
             }
@@ -901,7 +1047,7 @@ impl<'a> Initializer<'a> {
                     if #[sel4_cfg(not(NUM_DOMAINS = "1"))] {
                         if let ArchivedOption::Some(domain_id) = obj.extra.domain {
                             init_thread::slot::DOMAIN_SET.cap().domain_set_set(domain_id, tcb)
-                                .unwrap_or_else(|err| panic!("Error setting domain of thread to {}: {:?}", domain_id, err));
+                                .for_object(obj_ref)?;
                         }
                     }
                 }
@@ -914,7 +1060,8 @@ impl<'a> Initializer<'a> {
                 sel4::sel4_cfg_if! {
                     if #[sel4_cfg(KERNEL_MCS)] {
                         if let Some(sched_context_cap) = obj.sc() {
-                            self.init_sched_context(sched_context_cap.object, affinity.try_into().unwrap())?;
+                            self.init_sched_context(sched_context_cap.object, affinity.try_into().unwrap())
+                                .for_object(ObjectRef::unnamed(sched_context_cap.object))?;
                         }
 
                         tcb.tcb_configure(
@@ -923,7 +1070,7 @@ impl<'a> Initializer<'a> {
                             vspace,
                             ipc_buffer_addr,
                             ipc_buffer_frame,
-                        )?;
+                        ).for_object(obj_ref)?;
 
                         let sc = match obj.sc() {
                             None => init_thread::slot::NULL.cap().cast::<cap_type::SchedContext>(),
@@ -940,9 +1087,11 @@ impl<'a> Initializer<'a> {
                                     orig
                                 } else {
                                     let src = init_thread::slot::CNODE.cap().absolute_cptr(orig);
-                                    let new = self.cslot_alloc_or_panic().cap();
+                                    let new = self.cslot_alloc().for_object(obj_ref)?.cap();
                                     let dst = init_thread::slot::CNODE.cap().absolute_cptr(new);
-                                    dst.mint(&src, rights, badge)?;
+                                    dst.mint(&src, rights, badge)
+                                        .for_object(obj_ref)
+                                        .at_slot(object::Tcb::SLOT_FAULT_EP.into())?;
                                     new.cast()
                                 }
                             },
@@ -951,7 +1100,13 @@ impl<'a> Initializer<'a> {
                         let temp_fault_ep = match obj.temp_fault_ep() {
                             None => init_thread::slot::NULL.cap().cast::<cap_type::Endpoint>(),
                             Some(cap) => {
-                                assert_eq!(cap.badge.to_sel4(), 0); // HACK
+                                // The kernel does not support badged timeout fault endpoints.
+                                let badge = cap.badge.to_sel4();
+                                if badge != 0 {
+                                    return Err(ErrorKind::BadgedTimeoutFaultEp { badge })
+                                        .for_object(obj_ref)
+                                        .at_slot(object::Tcb::SLOT_TEMP_FAULT_EP.into());
+                                }
                                 self.orig_cap::<cap_type::Endpoint>(cap.object)
                             },
                         };
@@ -962,7 +1117,7 @@ impl<'a> Initializer<'a> {
                             prio,
                             sc,
                             fault_ep,
-                        )?;
+                        ).for_object(obj_ref)?;
 
                         let tcb_flags = sel4::TcbFlagsBuilder::new()
                             .fpu_disabled(fpu_disabled)
@@ -970,11 +1125,14 @@ impl<'a> Initializer<'a> {
                         tcb.tcb_set_flags(
                             0,
                             tcb_flags,
-                        )?;
+                        ).for_object(obj_ref)?;
 
-                        tcb.tcb_set_timeout_endpoint(temp_fault_ep)?;
+                        tcb.tcb_set_timeout_endpoint(temp_fault_ep).for_object(obj_ref)?;
                     } else {
-                        let fault_ep = sel4::CPtr::from_bits(obj.extra.master_fault_ep.as_ref().unwrap().to_sel4());
+                        let fault_ep = obj.extra.master_fault_ep.as_ref()
+                            .ok_or(ErrorKind::MissingFaultEp)
+                            .for_object(obj_ref)?;
+                        let fault_ep = sel4::CPtr::from_bits(fault_ep.to_sel4());
 
                         tcb.tcb_configure(
                             fault_ep,
@@ -983,13 +1141,13 @@ impl<'a> Initializer<'a> {
                             vspace,
                             ipc_buffer_addr,
                             ipc_buffer_frame,
-                        )?;
+                        ).for_object(obj_ref)?;
 
                         tcb.tcb_set_sched_params(
                             authority,
                             max_prio,
                             prio,
-                        )?;
+                        ).for_object(obj_ref)?;
 
                         let tcb_flags = sel4::TcbFlagsBuilder::new()
                             .fpu_disabled(fpu_disabled)
@@ -997,17 +1155,19 @@ impl<'a> Initializer<'a> {
                         tcb.tcb_set_flags(
                             0,
                             tcb_flags,
-                        )?;
+                        ).for_object(obj_ref)?;
 
                         sel4::sel4_cfg_if! {
                             if #[sel4_cfg(not(MAX_NUM_NODES = "1"))] {
-                                tcb.tcb_set_affinity(affinity)?;
+                                tcb.tcb_set_affinity(affinity).for_object(obj_ref)?;
                             }
                         }
                     }
                 }
             }
 
+            self.measurement_log.tcb(obj_id, obj).for_object(obj_ref)?;
+
             {
                 let mut regs = sel4::UserContext::default();
                 *regs.pc_mut() = obj.extra.ip.to_sel4();
@@ -1015,7 +1175,8 @@ impl<'a> Initializer<'a> {
                 for (i, value) in obj.extra.gprs.iter().enumerate() {
                     *regs.c_param_mut(i) = value.to_sel4();
                 }
-                tcb.tcb_write_all_registers(false, &mut regs)?;
+                tcb.tcb_write_all_registers(false, &mut regs)
+                    .for_object(obj_ref)?;
             }
 
             sel4::sel4_cfg_if! {
@@ -1035,19 +1196,22 @@ impl<'a> Initializer<'a> {
         for (obj_id, obj) in self.filter_objects::<object::ArchivedCNode>() {
             let cnode = self.orig_cap::<cap_type::CNode>(obj_id);
             for entry in obj.slots() {
+                let slot = usize::from(entry.slot);
                 let badge = entry.cap.badge();
                 let rights = entry.cap.rights().unwrap_or(CapRights::all());
                 let src = init_thread::slot::CNODE
                     .cap()
                     .absolute_cptr(self.orig_cap::<cap_type::Unspecified>(entry.cap.obj()));
                 let dst = cnode.absolute_cptr_from_bits_with_depth(
-                    usize::from(entry.slot).try_into().unwrap(),
+                    slot.try_into().unwrap(),
                     obj.size_bits.into(),
                 );
                 match badge {
                     None => dst.copy(&src, rights),
                     Some(badge) => dst.mint(&src, rights, badge),
-                }?;
+                }
+                .for_object(ObjectRef::unnamed(obj_id))
+                .at_slot(slot)?;
             }
         }
         Ok(())
@@ -1068,11 +1232,9 @@ impl<'a> Initializer<'a> {
                 //       https://github.com/seL4/capdl/issues/98
                 // See also the corresponding implementation in capDL.
 
+                // Input us would overflow 64-bits or exceed 56-bit output tick maximum
                 if (duration_us / US_IN_SECOND) >= (1u64 << 56) / f {
-                    panic!(
-                        "Input us {duration_us} would overflow 64-bits of \
-                         exceed 56-bit output tick maximum @ freq {f} Hz"
-                    );
+                    return Err(ErrorKind::DurationOverflow { duration_us }.into());
                 }
 
                 let s: u64 = duration_us / US_IN_SECOND;
@@ -1087,17 +1249,14 @@ impl<'a> Initializer<'a> {
                     .bootinfo
                     .extra()
                     .find(|bi| bi.id == sel4::BootInfoExtraId::X86TscFreq)
-                    .expect("Unable to determine timer frequency as \
-                             no TSC frequency provided in bootinfo");
+                    .ok_or(ErrorKind::MissingTscFreq)?;
 
                 // For x86 platforms, the timer frequency is provided in MHz by the bootinfo
                 let tsc_freq_mhz = u32::from_le_bytes(tsc_freq_bi.content().try_into().unwrap());
 
                 let ticks = duration_us
                     .checked_mul(tsc_freq_mhz.into())
-                    .unwrap_or_else(|| panic!(
-                            "Output would overflow when computing ticks for duration \
-                             {duration_us} us @ freq {tsc_freq_mhz} MHz"));
+                    .ok_or(ErrorKind::DurationOverflow { duration_us })?;
 
                 Ok(ticks)
             } else {
@@ -1120,10 +1279,7 @@ impl<'a> Initializer<'a> {
         let remainder: u64 = duration_us % period_us;
 
         if remainder != 0 {
-            panic!(
-                "domain schedule duration {duration_us} is not an \
-                 integer multiple of TIMER_TICK_MS ({TIMER_TICK_MS} ms)"
-            );
+            return Err(ErrorKind::DurationNotMultipleOfTick { duration_us }.into());
         }
 
         Ok(ticks)
@@ -1192,7 +1348,7 @@ impl<'a> Initializer<'a> {
         for (obj_id, obj) in self.filter_objects::<object::ArchivedTcb>() {
             let tcb = self.orig_cap::<cap_type::Tcb>(obj_id);
             if obj.extra.resume {
-                tcb.tcb_resume()?;
+                tcb.tcb_resume().for_object(ObjectRef::unnamed(obj_id))?;
             }
         }
         Ok(())
@@ -1244,7 +1400,7 @@ impl<'a> Initializer<'a> {
     //
 
     fn copy<U: sel4::CapType>(&mut self, cap: sel4::Cap<U>) -> Result<sel4::Cap<U>> {
-        let slot = self.cslot_alloc_or_panic();
+        let slot = self.cslot_alloc()?;
         let src = init_thread::slot::CNODE.cap().absolute_cptr(cap);
         cslot_to_absolute_cptr(slot).copy(&src, CapRights::all())?;
         Ok(slot.cap().downcast())
@@ -1252,8 +1408,8 @@ impl<'a> Initializer<'a> {
 
     //
 
-    fn cslot_alloc_or_panic(&mut self) -> Slot {
-        self.cslot_allocator.alloc().unwrap()
+    fn cslot_alloc(&mut self) -> Result<Slot> {
+        Ok(self.cslot_allocator.alloc()?)
     }
 
     fn orig_cslot(&self, obj_id: ArchivedObjectId) -> Slot {
@@ -1272,9 +1428,7 @@ impl<'a> Initializer<'a> {
                 init: ArchivedFrameInit::Embedded(embedded),
                 ..
             }) => {
-                let frame_addr = self.embedded_frames_base_addr
-                    + usize::try_from(embedded.index).unwrap()
-                        * cap_type::Granule::FRAME_OBJECT_TYPE.bytes();
+                let frame_addr = self.embedded_frame_addr(embedded);
                 get_user_image_frame_slot(self.bootinfo, &self.user_image_bounds, frame_addr)
                     .upcast()
             }
@@ -1316,6 +1470,16 @@ fn object_name(named_obj: &ArchivedNamedObject<FrameInit>) -> Option<&str> {
     named_obj.name.as_ref().map(|x| x.as_str())
 }
 
+fn with_object_name<'a>(
+    spec: &'a <SpecForInitializer as Archive>::Archived,
+    mut err: CapDLInitializerError<'a>,
+) -> CapDLInitializerError<'a> {
+    if let Some(object) = &mut err.object {
+        object.name = spec.objects.get(object.id).and_then(object_name);
+    }
+    err
+}
+
 fn object_name_or_default(named_obj: &ArchivedNamedObject<FrameInit>) -> &str {
     object_name(named_obj).unwrap_or("<unnamed>")
 }
@@ -17,7 +17,17 @@ mod error;
 mod hold_slots;
 mod initialize;
 mod lib_main;
+mod measurement;
 mod memory;
 
+pub use cslot_allocator::CSlotAllocatorError;
+pub use error::{CapDLInitializerError, ErrorKind, ObjectRef, Phase};
+
+// Read by add-spec, which refuses to produce a spec that uses a codec this build cannot decode.
+#[used]
+#[unsafe(no_mangle)]
+static sel4_capdl_initializer_supported_codecs: u32 =
+    sel4_capdl_initializer_types::Codec::SUPPORTED;
+
 #[sel4::sel4_cfg(all(ARCH_RISCV64, not(PT_LEVELS = "3")))]
 compile_error!("unsupported configuration");
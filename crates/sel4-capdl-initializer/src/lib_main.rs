@@ -6,8 +6,10 @@
 
 use core::ops::Range;
 
+use log::{debug, error};
 use rkyv::Archive;
 
+use sel4::init_thread;
 use sel4_capdl_initializer_types::SpecForInitializer;
 use sel4_immediate_sync_once_cell::ImmediateSyncOnceCell;
 use sel4_logging::{LevelFilter, Logger, LoggerBuilder};
@@ -28,7 +30,7 @@ static LOGGER: ImmediateSyncOnceCell<Logger> = ImmediateSyncOnceCell::new();
 fn main(bootinfo: &sel4::BootInfoPtr) -> ! {
     let spec = access_spec(get_spec_bytes());
     init_logging(spec.log_level.unwrap());
-    Initializer::initialize(
+    let result = Initializer::initialize(
         bootinfo,
         user_image_bounds(),
         spec,
@@ -37,7 +39,13 @@ fn main(bootinfo: &sel4::BootInfoPtr) -> ! {
             .find_by_type(PT_SEL4_CAPDL_FRAME_DATA)
             .unwrap()
             .p_vaddr,
-    )
+    );
+    match result {
+        Ok(()) => debug!("CapDL initializer done, suspending"),
+        // Logged as is, so that the line can be parsed mechanically.
+        Err(err) => error!("{err}"),
+    }
+    init_thread::suspend_self()
 }
 
 fn init_logging(spec_log_level: u8) {
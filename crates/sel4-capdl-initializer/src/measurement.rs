@@ -0,0 +1,90 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+#[cfg(feature = "measurement")]
+use core::slice;
+
+use sel4_capdl_initializer_types::{ArchivedObjectId, object};
+
+#[cfg(feature = "measurement")]
+use sel4_capdl_initializer_types::{
+    ObjectId,
+    measurement::{Digest, MeasurementEntry, MeasurementKind, MeasurementLogWriter, measure_frame},
+};
+
+use crate::error::ErrorKind;
+
+/// Records measurements of frames and TCBs as they are initialized, if the spec asks for a
+/// measurement log.
+pub(crate) struct MeasurementLog {
+    #[cfg(feature = "measurement")]
+    writer: Option<MeasurementLogWriter<'static>>,
+}
+
+impl MeasurementLog {
+    pub(crate) fn inactive() -> Self {
+        Self {
+            #[cfg(feature = "measurement")]
+            writer: None,
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `addr..addr + size` must be a zeroed mapping that remains valid until this log is dropped.
+    #[cfg(feature = "measurement")]
+    pub(crate) unsafe fn start(addr: usize, size: usize) -> Result<Self, ErrorKind> {
+        let buf = unsafe { slice::from_raw_parts_mut(addr as *mut u8, size) };
+        Ok(Self {
+            writer: Some(
+                MeasurementLogWriter::new(buf).map_err(|_| ErrorKind::MeasurementLogFull)?,
+            ),
+        })
+    }
+
+    #[cfg_attr(not(feature = "measurement"), allow(unused_variables))]
+    pub(crate) fn frame(
+        &mut self,
+        obj_id: ArchivedObjectId,
+        contents: &[u8],
+    ) -> Result<(), ErrorKind> {
+        #[cfg(feature = "measurement")]
+        self.push(MeasurementKind::Frame, obj_id, || measure_frame(contents))?;
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "measurement"), allow(unused_variables))]
+    pub(crate) fn tcb(
+        &mut self,
+        obj_id: ArchivedObjectId,
+        tcb: &object::ArchivedTcb,
+    ) -> Result<(), ErrorKind> {
+        #[cfg(feature = "measurement")]
+        self.push(MeasurementKind::Tcb, obj_id, || {
+            tcb.measure(usize::from(obj_id).into())
+        })?;
+        Ok(())
+    }
+
+    #[cfg(feature = "measurement")]
+    fn push(
+        &mut self,
+        kind: MeasurementKind,
+        obj_id: ArchivedObjectId,
+        measure: impl FnOnce() -> Digest,
+    ) -> Result<(), ErrorKind> {
+        if let Some(writer) = &mut self.writer {
+            writer
+                .push(&MeasurementEntry {
+                    kind,
+                    object: ObjectId::from(usize::from(obj_id)),
+                    digest: measure(),
+                })
+                .map_err(|_| ErrorKind::MeasurementLogFull)?;
+        }
+        Ok(())
+    }
+}
@@ -23,6 +23,10 @@ struct SmallPagePlaceholder(#[allow(dead_code)] [u8; SMALL_PAGE_PLACEHOLDER_SIZE
 static SMALL_PAGE_PLACEHOLDER: SmallPagePlaceholder =
     SmallPagePlaceholder([0; SMALL_PAGE_PLACEHOLDER_SIZE]);
 
+#[cfg(feature = "measurement")]
+static MEASUREMENT_LOG_PLACEHOLDER: SmallPagePlaceholder =
+    SmallPagePlaceholder([0; SMALL_PAGE_PLACEHOLDER_SIZE]);
+
 pub(crate) struct CopyAddrs {
     smaller_frame_copy_addr: usize,
     larger_frame_copy_addr: usize,
@@ -33,18 +37,11 @@ impl CopyAddrs {
         bootinfo: &sel4::BootInfoPtr,
         user_image_bounds: &Range<usize>,
     ) -> Result<Self, sel4::Error> {
-        let smaller_frame_copy_addr = {
-            let addr = ptr::addr_of!(SMALL_PAGE_PLACEHOLDER) as usize;
-            let start_slot_index =
-                get_user_image_frame_slot(bootinfo, user_image_bounds, addr).index();
-            for i in 0..(SMALL_PAGE_PLACEHOLDER_SIZE / cap_type::Granule::FRAME_OBJECT_TYPE.bytes())
-            {
-                let slot = init_thread::Slot::<cap_type::Granule>::from_index(start_slot_index + i);
-                let cap = slot.cap();
-                cap.frame_unmap()?;
-            }
-            addr
-        };
+        let smaller_frame_copy_addr = unmap_small_page_placeholder(
+            bootinfo,
+            user_image_bounds,
+            ptr::addr_of!(SMALL_PAGE_PLACEHOLDER) as usize,
+        )?;
         let larger_frame_copy_addr = {
             let level = sel4::vspace_levels::NUM_LEVELS - 2;
             let outer_span = 1u64 << sel4::vspace_levels::span_bits(level);
@@ -81,6 +78,33 @@ impl CopyAddrs {
     }
 }
 
+/// Address at which the measurement log frame can be mapped for the duration of initialization.
+#[cfg(feature = "measurement")]
+pub(crate) fn init_measurement_log_addr(
+    bootinfo: &sel4::BootInfoPtr,
+    user_image_bounds: &Range<usize>,
+) -> Result<usize, sel4::Error> {
+    unmap_small_page_placeholder(
+        bootinfo,
+        user_image_bounds,
+        ptr::addr_of!(MEASUREMENT_LOG_PLACEHOLDER) as usize,
+    )
+}
+
+fn unmap_small_page_placeholder(
+    bootinfo: &sel4::BootInfoPtr,
+    user_image_bounds: &Range<usize>,
+    addr: usize,
+) -> Result<usize, sel4::Error> {
+    let start_slot_index = get_user_image_frame_slot(bootinfo, user_image_bounds, addr).index();
+    for i in 0..(SMALL_PAGE_PLACEHOLDER_SIZE / cap_type::Granule::FRAME_OBJECT_TYPE.bytes()) {
+        let slot = init_thread::Slot::<cap_type::Granule>::from_index(start_slot_index + i);
+        let cap = slot.cap();
+        cap.frame_unmap()?;
+    }
+    Ok(addr)
+}
+
 pub(crate) fn get_user_image_frame_slot(
     bootinfo: &sel4::BootInfoPtr,
     user_image_bounds: &Range<usize>,
@@ -12,15 +12,27 @@ mk {
   package.name = "sel4-capdl-initializer-types";
   dependencies = {
     miniz_oxide = { version = versions.miniz_oxide; default-features = false; features = [ "with-alloc" ]; optional = true; };
+    lz4_flex = { version = versions.lz4_flex; default-features = false; features = [ "safe-decode" "safe-encode" ]; optional = true; };
+    ruzstd = { version = versions.ruzstd; default-features = false; optional = true; };
+    sha2 = { version = versions.sha2; default-features = false; optional = true; };
     serde = serdeWith [ "derive" "alloc" ] // { optional = true; };
     rkyv = { version = versions.rkyv; default-features = false; features = [ "alloc" "bytecheck" "pointer_width_32" ]; };
     inherit (localCrates)
       sel4-capdl-initializer-types-derive
     ;
     sel4 = localCrates.sel4 // { optional = true; default-features = false; };
+    sel4-config-types = localCrates.sel4-config-types // { optional = true; };
+  };
+  dev-dependencies = {
+    inherit (versions) serde_json;
   };
   features = {
     deflate = [ "dep:miniz_oxide" ];
-    transform = [ "deflate" ];
+    kernel-config = [ "dep:sel4-config-types" ];
+    lz4 = [ "dep:lz4_flex" ];
+    measurement = [ "dep:sha2" ];
+    rkyv-deserialize = [];
+    zstd = [ "dep:ruzstd" ];
+    transform = [ "deflate" "lz4" "zstd" ];
   };
 }
@@ -0,0 +1,565 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::BitOr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Cap, CapSlot, HasCapTable, Object, ObjectId, Rights, Spec, object};
+
+impl<D> Spec<D> {
+    /// Computes which objects each thread can access, directly or transitively.
+    ///
+    /// A thread starts with the caps in its CSpace (with their rights), access to the frames mapped
+    /// into its VSpace (with the rights of the mapping), and the ability to wait on its bound
+    /// notification. From there:
+    ///
+    /// - A CNode cap grants the caps in that CNode.
+    /// - A TCB cap grants everything that thread can access.
+    /// - Sending with grant rights on an endpoint grants the sender's caps to every thread that can
+    ///   receive on that endpoint.
+    /// - Sending with grant or grant-reply rights on an endpoint allows each receiver to reply to the
+    ///   sender. If the receiver's cap to the endpoint has grant rights, then so does the reply, and
+    ///   the receiver's caps are granted to the sender.
+    ///
+    /// The result is an over-approximation: it assumes that every cap that could be transferred
+    /// eventually is.
+    pub fn authority_graph(&self) -> AuthorityGraph {
+        let mut edges = vec![];
+        for (obj_id, named_obj) in self.objects.iter().enumerate() {
+            let Some(slots) = named_obj.object.slots() else {
+                continue;
+            };
+            let kind = match &named_obj.object {
+                Object::PageTable(_) | Object::IOPageTable(_) => EdgeKind::Mapping,
+                _ => EdgeKind::Cap,
+            };
+            for entry in slots {
+                edges.push(Edge {
+                    source: obj_id.into(),
+                    slot: entry.slot,
+                    target: entry.cap.obj(),
+                    kind,
+                    access: Access::of_cap(&entry.cap),
+                });
+            }
+        }
+
+        let mut threads = BTreeMap::new();
+        let mut endpoints = BTreeSet::new();
+        for (obj_id, named_obj) in self.objects.iter().enumerate() {
+            match &named_obj.object {
+                Object::Tcb(tcb) => {
+                    threads.insert(ObjectId::from(obj_id), self.direct_thread_access(tcb));
+                }
+                Object::Endpoint => {
+                    endpoints.insert(ObjectId::from(obj_id));
+                }
+                _ => {}
+            }
+        }
+
+        let replies = close(&mut threads, &endpoints);
+
+        AuthorityGraph {
+            edges,
+            threads: threads
+                .into_iter()
+                .map(|(thread, access)| (thread, access.all))
+                .collect(),
+            replies,
+        }
+    }
+
+    fn direct_thread_access(&self, tcb: &object::Tcb) -> ThreadAccess {
+        let mut access = ThreadAccess::default();
+        for entry in tcb.slots() {
+            match entry.slot {
+                object::Tcb::SLOT_CSPACE => {
+                    self.add_cspace_access(&mut access.held, entry.cap.obj());
+                }
+                object::Tcb::SLOT_VSPACE => {
+                    self.add_vspace_access(&mut access.all, entry.cap.obj());
+                }
+                object::Tcb::SLOT_BOUND_NOTIFICATION => {
+                    add(&mut access.all, entry.cap.obj(), Access::READ);
+                }
+                _ => {}
+            }
+        }
+        add_all(&mut access.all, &access.held.clone());
+        access
+    }
+
+    fn add_cspace_access(&self, access: &mut AccessMap, root: ObjectId) {
+        let mut visited = BTreeSet::new();
+        let mut stack = vec![root];
+        while let Some(cnode) = stack.pop() {
+            if !visited.insert(cnode) {
+                continue;
+            }
+            let Some(slots) = self.object(cnode).slots() else {
+                continue;
+            };
+            for entry in slots {
+                add(access, entry.cap.obj(), Access::of_cap(&entry.cap));
+                if let Cap::CNode(cap) = &entry.cap {
+                    stack.push(cap.object);
+                }
+            }
+        }
+    }
+
+    fn add_vspace_access(&self, access: &mut AccessMap, root: ObjectId) {
+        let mut stack = vec![root];
+        while let Some(table) = stack.pop() {
+            let Object::PageTable(table) = self.object(table) else {
+                continue;
+            };
+            for entry in table.slots.iter() {
+                match &entry.cap {
+                    Cap::Frame(cap) => {
+                        add(access, cap.object, Access::from(&cap.rights));
+                    }
+                    Cap::PageTable(cap) => stack.push(cap.object),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn object(&self, obj_id: ObjectId) -> &Object<D> {
+        &self.objects[usize::from(obj_id)].object
+    }
+}
+
+type AccessMap = BTreeMap<ObjectId, Access>;
+
+#[derive(Clone, Default)]
+struct ThreadAccess {
+    /// Access through caps in the thread's CSpace, which can be transferred
+    held: AccessMap,
+    /// Also includes access through the thread's VSpace and bound notification
+    all: AccessMap,
+}
+
+/// How the caps of one thread are made available to another.
+#[derive(Copy, Clone)]
+enum Transfer {
+    /// Everything the source can access, as with a TCB cap.
+    All,
+    /// The caps in the source's CSpace, as with a message with grant rights.
+    Held,
+}
+
+// Each transfer between two threads depends only on what those two threads can access, so
+// whenever a thread gains access, only the transfers involving that thread need to be revisited.
+fn close(
+    threads: &mut BTreeMap<ObjectId, ThreadAccess>,
+    endpoints: &BTreeSet<ObjectId>,
+) -> BTreeSet<Reply> {
+    let mut replies = BTreeSet::new();
+    let mut worklist = threads.keys().copied().collect::<Vec<_>>();
+    let mut queued = worklist.iter().copied().collect::<BTreeSet<_>>();
+    while let Some(thread) = worklist.pop() {
+        queued.remove(&thread);
+        let mut transfers = vec![];
+        transfers_involving(threads, endpoints, thread, &mut transfers, &mut replies);
+        for (src, dst, transfer) in transfers {
+            let held = threads[&src].held.clone();
+            let all = match transfer {
+                Transfer::All => Some(threads[&src].all.clone()),
+                Transfer::Held => None,
+            };
+            let dst_access = threads.get_mut(&dst).unwrap();
+            let mut changed = add_all(&mut dst_access.held, &held);
+            changed |= add_all(&mut dst_access.all, all.as_ref().unwrap_or(&held));
+            if changed && queued.insert(dst) {
+                worklist.push(dst);
+            }
+        }
+    }
+    replies
+}
+
+fn transfers_involving(
+    threads: &BTreeMap<ObjectId, ThreadAccess>,
+    endpoints: &BTreeSet<ObjectId>,
+    thread: ObjectId,
+    transfers: &mut Vec<(ObjectId, ObjectId, Transfer)>,
+    replies: &mut BTreeSet<Reply>,
+) {
+    let access = &threads[&thread].all;
+    for (obj_id, have) in access.iter() {
+        if have.control && *obj_id != thread && threads.contains_key(obj_id) {
+            transfers.push((*obj_id, thread, Transfer::All));
+        }
+    }
+    for (other, other_access) in threads.iter() {
+        if *other == thread {
+            continue;
+        }
+        if other_access
+            .all
+            .get(&thread)
+            .is_some_and(|have| have.control)
+        {
+            transfers.push((thread, *other, Transfer::All));
+        }
+        for endpoint in access.keys().filter(|obj_id| endpoints.contains(obj_id)) {
+            let Some(other_have) = other_access.all.get(endpoint) else {
+                continue;
+            };
+            let have = &access[endpoint];
+            for ((sender, sender_have), (receiver, receiver_have)) in [
+                ((thread, have), (*other, other_have)),
+                ((*other, other_have), (thread, have)),
+            ] {
+                if !sender_have.write || !receiver_have.read {
+                    continue;
+                }
+                if sender_have.grant {
+                    transfers.push((sender, receiver, Transfer::Held));
+                }
+                if sender_have.grant || sender_have.grant_reply {
+                    replies.insert(Reply {
+                        receiver,
+                        sender,
+                        endpoint: *endpoint,
+                    });
+                    if receiver_have.grant {
+                        transfers.push((receiver, sender, Transfer::Held));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn add(access: &mut AccessMap, obj_id: ObjectId, new: Access) -> bool {
+    let entry = access.entry(obj_id).or_default();
+    let old = *entry;
+    *entry = old | new;
+    *entry != old
+}
+
+fn add_all(access: &mut AccessMap, other: &AccessMap) -> bool {
+    let mut changed = false;
+    for (obj_id, new) in other.iter() {
+        changed |= add(access, *obj_id, *new);
+    }
+    changed
+}
+
+/// The authority relationships of a spec. See [`Spec::authority_graph`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AuthorityGraph {
+    /// Every cap in every cap table, including CNodes, TCBs, IRQs, and page tables.
+    pub edges: Vec<Edge>,
+    /// What each thread (identified by its TCB) can access, after closing over transfers.
+    pub threads: BTreeMap<ObjectId, BTreeMap<ObjectId, Access>>,
+    /// Pairs of threads where the receiver can reply to a message from the sender.
+    pub replies: BTreeSet<Reply>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Reply {
+    pub receiver: ObjectId,
+    pub sender: ObjectId,
+    /// The endpoint on which the message is sent.
+    pub endpoint: ObjectId,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Edge {
+    pub source: ObjectId,
+    pub slot: CapSlot,
+    pub target: ObjectId,
+    pub kind: EdgeKind,
+    pub access: Access,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum EdgeKind {
+    Cap,
+    Mapping,
+}
+
+/// The ways in which a thread can use an object.
+///
+/// For endpoints, `read` and `write` mean receive and send. For notifications, they mean wait and
+/// signal. `control` means every operation the object's cap supports.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Access {
+    pub read: bool,
+    pub write: bool,
+    pub grant: bool,
+    pub grant_reply: bool,
+    pub control: bool,
+}
+
+impl Access {
+    pub const READ: Self = Self {
+        read: true,
+        write: false,
+        grant: false,
+        grant_reply: false,
+        control: false,
+    };
+
+    pub const WRITE: Self = Self {
+        read: false,
+        write: true,
+        grant: false,
+        grant_reply: false,
+        control: false,
+    };
+
+    pub const CONTROL: Self = Self {
+        read: false,
+        write: false,
+        grant: false,
+        grant_reply: false,
+        control: true,
+    };
+
+    pub fn of_cap(cap: &Cap) -> Self {
+        match cap.rights() {
+            Some(rights) => rights.into(),
+            None => Self::CONTROL,
+        }
+    }
+
+    pub fn contains(&self, other: &Self) -> bool {
+        (*self | *other) == *self
+    }
+}
+
+impl From<&Rights> for Access {
+    fn from(rights: &Rights) -> Self {
+        Self {
+            read: rights.read,
+            write: rights.write,
+            grant: rights.grant,
+            grant_reply: rights.grant_reply,
+            control: false,
+        }
+    }
+}
+
+impl BitOr for Access {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self {
+            read: self.read || rhs.read,
+            write: self.write || rhs.write,
+            grant: self.grant || rhs.grant,
+            grant_reply: self.grant_reply || rhs.grant_reply,
+            control: self.control || rhs.control,
+        }
+    }
+}
+
+// Same letters as capDL, plus 'C' for control
+impl fmt::Display for Access {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut empty = true;
+        for (letter, present) in [
+            ('R', self.read),
+            ('W', self.write),
+            ('G', self.grant),
+            ('P', self.grant_reply),
+            ('C', self.control),
+        ] {
+            if present {
+                write!(f, "{letter}")?;
+                empty = false;
+            }
+        }
+        if empty {
+            write!(f, "-")?;
+        }
+        Ok(())
+    }
+}
+
+impl AuthorityGraph {
+    /// Threads that can access `obj_id` in at least the ways described by `access`.
+    pub fn threads_with_access(
+        &self,
+        obj_id: ObjectId,
+        access: Access,
+    ) -> impl Iterator<Item = ObjectId> + '_ {
+        self.threads.iter().filter_map(move |(thread, reachable)| {
+            reachable
+                .get(&obj_id)
+                .filter(|have| have.contains(&access))
+                .map(|_| *thread)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::format;
+    use alloc::string::String;
+
+    use super::*;
+    use crate::InputSpec;
+
+    // Threads t1, t2, and t3, each with its own CNode, whose contents are given by `cnodes`, and a
+    // notification n1, n2, and n3 respectively.
+    fn spec(cnodes: [&str; 3]) -> InputSpec {
+        let mut objects = String::new();
+        let mut caps = String::new();
+        for (i, cnode) in cnodes.iter().enumerate() {
+            let i = i + 1;
+            objects += &format!(
+                "t{i} = tcb (addr: 0x1000, ip: 0x0, sp: 0x0, prio: 0, max_prio: 0)
+                 c{i} = cnode (3 bits)
+                 n{i} = notification
+                "
+            );
+            caps += &format!(
+                "t{i} {{ cspace: c{i} }}
+                 c{i} {{ 0x0: n{i} (RW) {cnode} }}
+                "
+            );
+        }
+        InputSpec::from_cdl(&format!(
+            "objects {{ {objects} ep_a = ep ep_b = ep }} caps {{ {caps} }}"
+        ))
+        .unwrap()
+    }
+
+    fn id(spec: &InputSpec, name: &str) -> ObjectId {
+        spec.objects
+            .iter()
+            .position(|named_obj| named_obj.name.as_deref() == Some(name))
+            .unwrap()
+            .into()
+    }
+
+    struct Graph {
+        spec: InputSpec,
+        graph: AuthorityGraph,
+    }
+
+    impl Graph {
+        fn new(cnodes: [&str; 3]) -> Self {
+            let spec = spec(cnodes);
+            let graph = spec.authority_graph();
+            Self { spec, graph }
+        }
+
+        fn can(&self, thread: &str, obj: &str) -> bool {
+            self.graph.threads[&id(&self.spec, thread)].contains_key(&id(&self.spec, obj))
+        }
+
+        fn replies(&self) -> Vec<(ObjectId, ObjectId)> {
+            self.graph
+                .replies
+                .iter()
+                .map(|reply| (reply.receiver, reply.sender))
+                .collect()
+        }
+
+        fn pair(&self, receiver: &str, sender: &str) -> (ObjectId, ObjectId) {
+            (id(&self.spec, receiver), id(&self.spec, sender))
+        }
+    }
+
+    #[test]
+    fn no_transfer() {
+        let g = Graph::new(["0x1: ep_a (W)", "0x1: ep_a (RG)", ""]);
+        assert!(g.can("t1", "n1"));
+        assert!(!g.can("t1", "n2"));
+        assert!(!g.can("t2", "n1"));
+        assert!(g.replies().is_empty());
+    }
+
+    #[test]
+    fn grant() {
+        let g = Graph::new(["0x1: ep_a (WG)", "0x1: ep_a (R)", "0x1: ep_b (RW)"]);
+        assert!(g.can("t2", "n1"));
+        assert!(!g.can("t3", "n1"));
+        assert!(!g.can("t1", "n3"));
+        assert!(g.replies().contains(&g.pair("t2", "t1")));
+    }
+
+    #[test]
+    fn grant_reply() {
+        let g = Graph::new(["0x1: ep_a (WP)", "0x1: ep_a (R)", ""]);
+        assert!(!g.can("t2", "n1"));
+        assert!(!g.can("t1", "n2"));
+        assert_eq!(g.replies(), [g.pair("t2", "t1")]);
+
+        // The reply has grant rights if the receiver's cap does.
+        let g = Graph::new(["0x1: ep_a (WP)", "0x1: ep_a (RG)", ""]);
+        assert!(g.can("t1", "n2"));
+        assert!(g.replies().contains(&g.pair("t2", "t1")));
+    }
+
+    #[test]
+    fn transitive() {
+        // t1 grants its caps to t2, which grants them (and its own) to t3.
+        let g = Graph::new([
+            "0x1: ep_a (WG)",
+            "0x1: ep_a (R) 0x2: ep_b (WG)",
+            "0x1: ep_b (R)",
+        ]);
+        assert!(g.can("t3", "n1"));
+        assert!(g.can("t3", "n2"));
+
+        // t2 is granted a cap to ep_b, which it then uses to grant to t3.
+        let g = Graph::new([
+            "0x1: ep_a (WG) 0x2: ep_b (WG)",
+            "0x1: ep_a (R)",
+            "0x1: ep_b (R)",
+        ]);
+        assert!(g.can("t2", "ep_b"));
+        assert!(g.can("t3", "n2"));
+        assert!(g.replies().contains(&g.pair("t3", "t2")));
+    }
+
+    #[test]
+    fn tcb_cap() {
+        let g = Graph::new(["0x1: t2", "0x1: ep_a (WG)", "0x1: ep_a (R)"]);
+        assert!(g.can("t1", "n2"));
+        assert!(g.can("t1", "ep_a"));
+        // Through t2, t1 can also send on ep_a with grant rights.
+        assert!(g.can("t3", "n1"));
+        assert!(g.replies().contains(&g.pair("t3", "t1")));
+    }
+
+    #[test]
+    fn threads_with_access() {
+        let g = Graph::new(["0x1: ep_a (W)", "0x1: ep_a (R)", "0x1: ep_a (W)"]);
+        let ep_a = id(&g.spec, "ep_a");
+        let senders = g
+            .graph
+            .threads_with_access(ep_a, Access::WRITE)
+            .collect::<Vec<_>>();
+        assert_eq!(senders, [id(&g.spec, "t1"), id(&g.spec, "t3")]);
+        let n1 = id(&g.spec, "n1");
+        let waiters = g
+            .graph
+            .threads_with_access(n1, Access::READ)
+            .collect::<Vec<_>>();
+        assert_eq!(waiters, [id(&g.spec, "t1")]);
+    }
+}
@@ -0,0 +1,86 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+// A textual form of `InputSpec`, modeled after the upstream `.cdl` syntax. For example:
+//
+//   objects {
+//     tcb_a = tcb (addr: 0x201000, ip: 0x200000, sp: 0x203000, prio: 254, max_prio: 254)
+//     cnode_a = cnode (10 bits)
+//     vspace_a = pt (level: 0, root)
+//     ipc_a = frame (4k, fill: [{0 4096 CDL_FrameFill_FileData "a.bin" 0}])
+//     ep = ep
+//     ut = ut (21 bits, paddr: 0x40000000) { covered_a covered_b }
+//   }
+//
+//   caps {
+//     tcb_a {
+//       cspace: cnode_a (guard: 0, guard_size: 54)
+//       vspace: vspace_a
+//       ipc_buffer: ipc_a (RW)
+//     }
+//     cnode_a {
+//       0x1: ep (RWG, badge: 0x5)
+//     }
+//   }
+//
+//   irq maps {
+//     0x21: irq_a
+//   }
+//
+//   asid slots { pool_a }
+//
+//   domain schedule (start: 0x0, shift: 0x0) {
+//     0: 10 ticks
+//     1: 100 us
+//   }
+//
+// An object without a name is declared and referred to as `#<n>`, where `n` is its position among
+// the declarations, and names that are not identifiers are quoted. The type of each cap is
+// determined by the type of the object that it refers to. Root objects are those that are not
+// covered by an untyped. Objects are numbered in the order in which they are declared, except
+// that root objects with a paddr come first, then the other root objects, and then covered
+// objects, as the initializer requires.
+//
+// Comments start with `--` and run to the end of the line.
+
+mod parse;
+mod print;
+
+pub use parse::CdlParseError;
+
+use crate::object;
+
+const TCB_SLOT_NAMES: [(&str, u32); 9] = [
+    ("cspace", object::Tcb::SLOT_CSPACE.0),
+    ("vspace", object::Tcb::SLOT_VSPACE.0),
+    ("ipc_buffer", object::Tcb::SLOT_IPC_BUFFER.0),
+    ("fault_ep", object::Tcb::SLOT_FAULT_EP.0),
+    ("sc", object::Tcb::SLOT_SC.0),
+    ("temp_fault_ep", object::Tcb::SLOT_TEMP_FAULT_EP.0),
+    ("bound_notification", object::Tcb::SLOT_BOUND_NOTIFICATION.0),
+    ("vcpu", object::Tcb::SLOT_VCPU.0),
+    ("x86_eptpml4", object::Tcb::SLOT_X86_EPTPML4.0),
+];
+
+const BOOTINFO_IDS: [(&str, crate::FillEntryContentBootInfoId); 7] = {
+    use crate::FillEntryContentBootInfoId::*;
+    [
+        ("CDL_FrameFill_BootInfo_Padding", Padding),
+        ("CDL_FrameFill_BootInfo_X86_VBE", X86Vbe),
+        ("CDL_FrameFill_BootInfo_X86_Mbmmap", X86Mbmmap),
+        ("CDL_FrameFill_BootInfo_X86_AcpiRsdp", X86AcpiRsdp),
+        ("CDL_FrameFill_BootInfo_X86_FrameBuffer", X86FrameBuffer),
+        ("CDL_FrameFill_BootInfo_X86_TSC_Freq", X86TscFreq),
+        ("CDL_FrameFill_BootInfo_FDT", Fdt),
+    ]
+};
+
+const FILL_FILE_DATA: &str = "CDL_FrameFill_FileData";
+
+const FILL_BOOTINFO: &str = "CDL_FrameFill_BootInfo";
+
+#[cfg(all(test, feature = "serde"))]
+mod tests;
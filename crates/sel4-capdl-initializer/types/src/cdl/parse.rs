@@ -0,0 +1,1151 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::num::NonZero;
+
+use super::{BOOTINFO_IDS, FILL_BOOTINFO, FILL_FILE_DATA, TCB_SLOT_NAMES};
+use crate::{
+    Cap, CapSlot, CapTableEntry, DomainSchedDuration, DomainSchedEntry, Fill, FillEntry,
+    FillEntryContent, FillEntryContentBootInfo, FillEntryContentFileOffset, InputSpec, IrqEntry,
+    NamedObject, Object, ObjectId, Rights, Spec, UntypedCover, Word, cap, object,
+};
+
+type InputObject = Object<Fill<FillEntryContentFileOffset>>;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CdlParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl CdlParseError {
+    fn new(pos: Pos, message: impl Into<String>) -> Self {
+        Self {
+            line: pos.line,
+            column: pos.column,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for CdlParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl core::error::Error for CdlParseError {}
+
+type Result<T> = core::result::Result<T, CdlParseError>;
+
+impl InputSpec {
+    /// Parses the textual `.cdl` form of a spec.
+    pub fn from_cdl(src: &str) -> Result<Self> {
+        let toks = Lexer::new(src).lex()?;
+        let mut parser = Parser::new(toks);
+        parser.file()?;
+        parser.build()
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Pos {
+    line: usize,
+    column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Ident(String),
+    Num(u64),
+    Size(u8),
+    Str(String),
+    Hash(u64),
+    Punct(char),
+    Eof,
+}
+
+impl fmt::Display for Tok {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Ident(s) => write!(f, "`{s}`"),
+            Self::Num(n) => write!(f, "`{n}`"),
+            Self::Size(_) => write!(f, "size"),
+            Self::Str(s) => write!(f, "{s:?}"),
+            Self::Hash(n) => write!(f, "`#{n}`"),
+            Self::Punct(c) => write!(f, "`{c}`"),
+            Self::Eof => write!(f, "end of input"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    tok: Tok,
+    pos: Pos,
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_ident_continue(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '.'
+}
+
+pub(super) fn is_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(is_ident_start) && chars.all(is_ident_continue)
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    i: usize,
+    pos: Pos,
+}
+
+impl Lexer {
+    fn new(src: &str) -> Self {
+        Self {
+            chars: src.chars().collect(),
+            i: 0,
+            pos: Pos { line: 1, column: 1 },
+        }
+    }
+
+    fn peek(&self, k: usize) -> Option<char> {
+        self.chars.get(self.i + k).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek(0)?;
+        self.i += 1;
+        if c == '\n' {
+            self.pos.line += 1;
+            self.pos.column = 1;
+        } else {
+            self.pos.column += 1;
+        }
+        Some(c)
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> String {
+        let mut s = String::new();
+        while let Some(c) = self.peek(0).filter(|c| f(*c)) {
+            s.push(c);
+            self.bump();
+        }
+        s
+    }
+
+    fn lex(mut self) -> Result<Vec<Token>> {
+        let mut toks = vec![];
+        loop {
+            let pos = self.pos;
+            let Some(c) = self.peek(0) else {
+                toks.push(Token { tok: Tok::Eof, pos });
+                return Ok(toks);
+            };
+            let tok = if c.is_whitespace() {
+                self.bump();
+                continue;
+            } else if c == '-' && self.peek(1) == Some('-') {
+                self.take_while(|c| c != '\n');
+                continue;
+            } else if is_ident_start(c) {
+                Tok::Ident(self.take_while(is_ident_continue))
+            } else if c.is_ascii_digit() {
+                self.number(pos)?
+            } else if c == '"' {
+                self.string(pos)?
+            } else if c == '#' {
+                self.bump();
+                match self.peek(0).filter(char::is_ascii_digit) {
+                    Some(_) => match self.number(pos)? {
+                        Tok::Num(n) => Tok::Hash(n),
+                        _ => return Err(CdlParseError::new(pos, "invalid object id")),
+                    },
+                    None => return Err(CdlParseError::new(pos, "expected object id after `#`")),
+                }
+            } else if "{}()[]=:,".contains(c) {
+                self.bump();
+                Tok::Punct(c)
+            } else {
+                return Err(CdlParseError::new(
+                    pos,
+                    format!("unexpected character {c:?}"),
+                ));
+            };
+            toks.push(Token { tok, pos });
+        }
+    }
+
+    fn number(&mut self, pos: Pos) -> Result<Tok> {
+        let (digits, radix) =
+            if self.peek(0) == Some('0') && matches!(self.peek(1), Some('x' | 'X')) {
+                self.bump();
+                self.bump();
+                (self.take_while(|c| c.is_ascii_hexdigit()), 16)
+            } else {
+                (self.take_while(|c| c.is_ascii_digit()), 10)
+            };
+        let invalid = || CdlParseError::new(pos, "invalid number");
+        let n = u64::from_str_radix(&digits, radix).map_err(|_| invalid())?;
+        let shift = match self.take_while(is_ident_continue).as_str() {
+            "" => return Ok(Tok::Num(n)),
+            "k" => 10,
+            "M" => 20,
+            "G" => 30,
+            _ => return Err(invalid()),
+        };
+        if !n.is_power_of_two() {
+            return Err(CdlParseError::new(pos, "size must be a power of two"));
+        }
+        Ok(Tok::Size((n.trailing_zeros() + shift) as u8))
+    }
+
+    fn string(&mut self, pos: Pos) -> Result<Tok> {
+        self.bump();
+        let mut s = String::new();
+        loop {
+            match self.bump() {
+                None | Some('\n') => return Err(CdlParseError::new(pos, "unterminated string")),
+                Some('"') => return Ok(Tok::Str(s)),
+                Some('\\') => match self.bump() {
+                    Some(c @ ('"' | '\\')) => s.push(c),
+                    _ => return Err(CdlParseError::new(pos, "invalid escape in string")),
+                },
+                Some(c) => s.push(c),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum RefName {
+    Name(String),
+    Id(u64),
+}
+
+#[derive(Debug, Clone)]
+struct Ref {
+    name: RefName,
+    pos: Pos,
+}
+
+#[derive(Debug, Clone)]
+enum ValueKind {
+    Num(u64),
+    Ident(String),
+    Str(String),
+    List(Vec<Value>),
+    Group(Vec<Value>),
+}
+
+#[derive(Debug, Clone)]
+struct Value {
+    kind: ValueKind,
+    pos: Pos,
+}
+
+impl Value {
+    fn int<T: TryFrom<u64>>(&self) -> Result<T> {
+        match &self.kind {
+            ValueKind::Num(n) => {
+                T::try_from(*n).map_err(|_| CdlParseError::new(self.pos, "number out of range"))
+            }
+            _ => Err(CdlParseError::new(self.pos, "expected number")),
+        }
+    }
+
+    fn word(&self) -> Result<Word> {
+        self.int().map(Word)
+    }
+
+    fn ident(&self) -> Result<&str> {
+        match &self.kind {
+            ValueKind::Ident(s) => Ok(s),
+            _ => Err(CdlParseError::new(self.pos, "expected identifier")),
+        }
+    }
+
+    fn str(&self) -> Result<&str> {
+        match &self.kind {
+            ValueKind::Str(s) => Ok(s),
+            _ => Err(CdlParseError::new(self.pos, "expected string")),
+        }
+    }
+
+    fn list(&self) -> Result<&[Value]> {
+        match &self.kind {
+            ValueKind::List(values) => Ok(values),
+            _ => Err(CdlParseError::new(self.pos, "expected list")),
+        }
+    }
+
+    fn group(&self) -> Result<&[Value]> {
+        match &self.kind {
+            ValueKind::Group(values) => Ok(values),
+            _ => Err(CdlParseError::new(self.pos, "expected `{`")),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum ParamKind {
+    Bits(u8),
+    Flag(String),
+    Key(String, Value),
+}
+
+#[derive(Debug, Clone)]
+struct Param {
+    kind: ParamKind,
+    pos: Pos,
+}
+
+// Parameters are consumed by the object or cap that they belong to, and any that are left over are
+// reported as unexpected.
+#[derive(Debug, Clone)]
+struct Params {
+    items: Vec<Option<Param>>,
+    pos: Pos,
+}
+
+impl Params {
+    fn empty(pos: Pos) -> Self {
+        Self { items: vec![], pos }
+    }
+
+    fn take(&mut self, f: impl Fn(&ParamKind) -> bool) -> Option<Param> {
+        self.items
+            .iter_mut()
+            .find(|item| item.as_ref().is_some_and(|param| f(&param.kind)))
+            .and_then(Option::take)
+    }
+
+    fn bits(&mut self) -> Result<u8> {
+        match self.take(|kind| matches!(kind, ParamKind::Bits(_))) {
+            Some(Param {
+                kind: ParamKind::Bits(bits),
+                ..
+            }) => Ok(bits),
+            _ => Err(CdlParseError::new(self.pos, "missing size")),
+        }
+    }
+
+    fn flag(&mut self, name: &str) -> bool {
+        self.take(|kind| matches!(kind, ParamKind::Flag(flag) if flag == name))
+            .is_some()
+    }
+
+    fn value(&mut self, key: &str) -> Option<Value> {
+        match self.take(|kind| matches!(kind, ParamKind::Key(k, _) if k == key)) {
+            Some(Param {
+                kind: ParamKind::Key(_, value),
+                ..
+            }) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn int<T: TryFrom<u64>>(&mut self, key: &str) -> Result<Option<T>> {
+        self.value(key).map(|value| value.int()).transpose()
+    }
+
+    fn int_or_zero<T: TryFrom<u64> + Default>(&mut self, key: &str) -> Result<T> {
+        self.int(key).map(Option::unwrap_or_default)
+    }
+
+    fn word(&mut self, key: &str) -> Result<Option<Word>> {
+        self.int(key).map(|n| n.map(Word))
+    }
+
+    fn word_or_zero(&mut self, key: &str) -> Result<Word> {
+        self.int_or_zero(key).map(Word)
+    }
+
+    fn rights(&mut self, allow_executable: bool) -> Result<(Rights, bool)> {
+        let mut rights = Rights {
+            read: false,
+            write: false,
+            grant: false,
+            grant_reply: false,
+        };
+        let mut executable = false;
+        let param = self.take(
+            |kind| matches!(kind, ParamKind::Flag(flag) if flag.chars().all(|c| "RWGPX".contains(c))),
+        );
+        if let Some(Param {
+            kind: ParamKind::Flag(flag),
+            pos,
+        }) = param
+        {
+            for c in flag.chars() {
+                match c {
+                    'R' => rights.read = true,
+                    'W' => rights.write = true,
+                    'G' => rights.grant = true,
+                    'P' => rights.grant_reply = true,
+                    'X' if allow_executable => executable = true,
+                    _ => {
+                        return Err(CdlParseError::new(
+                            pos,
+                            format!("right `{c}` does not apply to this cap"),
+                        ));
+                    }
+                }
+            }
+        }
+        Ok((rights, executable))
+    }
+
+    fn fill(&mut self) -> Result<Vec<FillEntry<FillEntryContentFileOffset>>> {
+        let Some(value) = self.value("fill") else {
+            return Ok(vec![]);
+        };
+        value.list()?.iter().map(fill_entry).collect()
+    }
+
+    fn finish(self) -> Result<()> {
+        match self.items.into_iter().flatten().next() {
+            Some(param) => Err(CdlParseError::new(param.pos, "unexpected parameter")),
+            None => Ok(()),
+        }
+    }
+}
+
+fn fill_entry(value: &Value) -> Result<FillEntry<FillEntryContentFileOffset>> {
+    let atoms = value.group()?;
+    let [start, len, kind, source, offset] = atoms else {
+        return Err(CdlParseError::new(
+            value.pos,
+            "expected `{start length kind source offset}`",
+        ));
+    };
+    let start: u64 = start.int()?;
+    let end = start
+        .checked_add(len.int()?)
+        .ok_or_else(|| CdlParseError::new(len.pos, "number out of range"))?;
+    let content = match kind.ident()? {
+        FILL_FILE_DATA => FillEntryContent::Data(FillEntryContentFileOffset {
+            file: source.str()?.into(),
+            file_offset: offset.int()?,
+        }),
+        FILL_BOOTINFO => FillEntryContent::BootInfo(FillEntryContentBootInfo {
+            id: BOOTINFO_IDS
+                .iter()
+                .find(|(name, _)| *name == source.ident().unwrap_or_default())
+                .map(|(_, id)| *id)
+                .ok_or_else(|| CdlParseError::new(source.pos, "unknown bootinfo id"))?,
+            offset: offset.int()?,
+        }),
+        _ => return Err(CdlParseError::new(kind.pos, "unknown fill kind")),
+    };
+    Ok(FillEntry {
+        range: start..end,
+        content,
+    })
+}
+
+fn object_from_params(ty: &str, pos: Pos, mut p: Params) -> Result<InputObject> {
+    let object = match ty {
+        "ut" => Object::Untyped(object::Untyped {
+            size_bits: p.bits()?,
+            paddr: p.word("paddr")?,
+        }),
+        "ep" => Object::Endpoint,
+        "notification" => Object::Notification,
+        "cnode" => Object::CNode(object::CNode {
+            size_bits: p.bits()?,
+            slots: vec![],
+        }),
+        "tcb" => Object::Tcb(object::Tcb {
+            slots: vec![],
+            extra: Box::new(object::TcbExtraInfo {
+                ipc_buffer_addr: p.word_or_zero("addr")?,
+                affinity: p.word_or_zero("affinity")?,
+                prio: p.int_or_zero("prio")?,
+                max_prio: p.int_or_zero("max_prio")?,
+                fpu_disabled: p.flag("fpu_disabled"),
+                resume: !p.flag("suspended"),
+                domain: p.int("dom")?,
+                ip: p.word_or_zero("ip")?,
+                sp: p.word_or_zero("sp")?,
+                gprs: match p.value("init") {
+                    Some(value) => value
+                        .list()?
+                        .iter()
+                        .map(Value::word)
+                        .collect::<Result<_>>()?,
+                    None => vec![],
+                },
+                master_fault_ep: p.word("fault_ep")?,
+            }),
+        }),
+        "irq" => Object::Irq(object::Irq { slots: vec![] }),
+        "vcpu" => Object::VCpu,
+        "frame" => Object::Frame(object::Frame {
+            size_bits: p.bits()?,
+            paddr: p.word("paddr")?,
+            init: Fill { entries: p.fill()? },
+        }),
+        "pt" => Object::PageTable(object::PageTable {
+            x86_ept: p.flag("ept"),
+            is_root: p.flag("root"),
+            level: p.int("level")?,
+            slots: vec![],
+        }),
+        "asid_pool" => Object::AsidPool(object::AsidPool {
+            high: p.word_or_zero("high")?,
+        }),
+        "io_space" => Object::IOSpace(object::IOSpace {
+            slots: vec![],
+            domain_id: p.word_or_zero("domain_id")?,
+            pci_device: {
+                let value = p
+                    .value("pci")
+                    .ok_or_else(|| CdlParseError::new(pos, "missing `pci`"))?;
+                let [bus, device, function] = value.list()? else {
+                    return Err(CdlParseError::new(
+                        value.pos,
+                        "expected `[bus, device, function]`",
+                    ));
+                };
+                object::PCIDevice {
+                    bus: bus.int()?,
+                    device: device.int()?,
+                    function: function.int()?,
+                }
+            },
+        }),
+        "io_pt" => Object::IOPageTable(object::IOPageTable {
+            slots: vec![],
+            level: p.word_or_zero("level")?,
+        }),
+        "arm_irq" => Object::ArmIrq(object::ArmIrq {
+            slots: vec![],
+            extra: Box::new(object::ArmIrqExtraInfo {
+                trigger: p.int_or_zero("trigger")?,
+                target: p.word_or_zero("target")?,
+            }),
+        }),
+        "msi_irq" => Object::IrqMsi(object::IrqMsi {
+            slots: vec![],
+            extra: Box::new(object::IrqMsiExtraInfo {
+                handle: p.word_or_zero("handle")?,
+                pci_bus: p.word_or_zero("pci_bus")?,
+                pci_dev: p.word_or_zero("pci_dev")?,
+                pci_func: p.word_or_zero("pci_func")?,
+            }),
+        }),
+        "ioapic_irq" => Object::IrqIOApic(object::IrqIOApic {
+            slots: vec![],
+            extra: Box::new(object::IrqIOApicExtraInfo {
+                ioapic: p.word_or_zero("ioapic")?,
+                pin: p.word_or_zero("pin")?,
+                level: p.word_or_zero("level")?,
+                polarity: p.word_or_zero("polarity")?,
+            }),
+        }),
+        "riscv_irq" => Object::RiscvIrq(object::RiscvIrq {
+            slots: vec![],
+            extra: object::RiscvIrqExtraInfo {
+                trigger: p.int_or_zero("trigger")?,
+            },
+        }),
+        "io_ports" => Object::IOPorts(object::IOPorts {
+            start_port: p.word_or_zero("start_port")?,
+            end_port: p.word_or_zero("end_port")?,
+        }),
+        "sc" => Object::SchedContext(object::SchedContext {
+            size_bits: p.bits()?,
+            extra: object::SchedContextExtraInfo {
+                period: p.int_or_zero("period")?,
+                budget: p.int_or_zero("budget")?,
+                badge: p.word_or_zero("badge")?,
+            },
+        }),
+        "rtreply" => Object::Reply,
+        "arm_smc" => Object::ArmSmc,
+        "domain_set" => Object::DomainSet,
+        _ => {
+            return Err(CdlParseError::new(
+                pos,
+                format!("unknown object type `{ty}`"),
+            ));
+        }
+    };
+    p.finish()?;
+    Ok(object)
+}
+
+fn cap_from_params(target: &InputObject, object: ObjectId, mut p: Params) -> Result<Cap> {
+    let cap = match target {
+        Object::Untyped(_) => Cap::Untyped(cap::Untyped { object }),
+        Object::Endpoint => Cap::Endpoint(cap::Endpoint {
+            object,
+            rights: p.rights(false)?.0,
+            badge: p.word_or_zero("badge")?,
+        }),
+        Object::Notification => Cap::Notification(cap::Notification {
+            object,
+            rights: p.rights(false)?.0,
+            badge: p.word_or_zero("badge")?,
+        }),
+        Object::CNode(_) => Cap::CNode(cap::CNode {
+            object,
+            guard: p.word_or_zero("guard")?,
+            guard_size: p.int_or_zero("guard_size")?,
+        }),
+        Object::Tcb(_) => Cap::Tcb(cap::Tcb { object }),
+        Object::Irq(_) => Cap::IrqHandler(cap::IrqHandler { object }),
+        Object::VCpu => Cap::VCpu(cap::VCpu { object }),
+        Object::Frame(_) => {
+            let (rights, executable) = p.rights(true)?;
+            Cap::Frame(cap::Frame {
+                object,
+                rights,
+                cached: !p.flag("uncached"),
+                executable,
+            })
+        }
+        Object::PageTable(_) => Cap::PageTable(cap::PageTable { object }),
+        Object::AsidPool(_) => Cap::AsidPool(cap::AsidPool { object }),
+        Object::IOSpace(_) => Cap::IOSpace(cap::IOSpace { object }),
+        Object::IOPageTable(_) => Cap::IOPageTable(cap::IOPageTable { object }),
+        Object::ArmIrq(_) => Cap::ArmIrqHandler(cap::ArmIrqHandler { object }),
+        Object::IrqMsi(_) => Cap::IrqMsiHandler(cap::IrqMsiHandler { object }),
+        Object::IrqIOApic(_) => Cap::IrqIOApicHandler(cap::IrqIOApicHandler { object }),
+        Object::RiscvIrq(_) => Cap::RiscvIrqHandler(cap::RiscvIrqHandler { object }),
+        Object::IOPorts(_) => Cap::IOPorts(cap::IOPorts { object }),
+        Object::SchedContext(_) => Cap::SchedContext(cap::SchedContext { object }),
+        Object::Reply => Cap::Reply(cap::Reply { object }),
+        Object::ArmSmc => Cap::ArmSmc(cap::ArmSmc { object }),
+        Object::DomainSet => Cap::DomainSet(cap::DomainSet { object }),
+    };
+    p.finish()?;
+    Ok(cap)
+}
+
+#[derive(Debug, Clone)]
+enum SlotName {
+    Num(u64),
+    Name(String),
+}
+
+#[derive(Debug, Clone)]
+struct CapDecl {
+    slot: SlotName,
+    slot_pos: Pos,
+    target: Ref,
+    params: Params,
+}
+
+// References to objects may appear before the objects are declared, so they are only resolved once
+// the whole file has been parsed.
+struct Parser {
+    toks: Vec<Token>,
+    i: usize,
+    objects: Vec<NamedObject<Fill<FillEntryContentFileOffset>>>,
+    // `None` marks a name that is shared by more than one object.
+    names: BTreeMap<String, Option<usize>>,
+    children: Vec<(usize, Vec<Ref>)>,
+    caps: Vec<(Ref, Vec<CapDecl>)>,
+    irqs: Vec<(Word, Ref)>,
+    asid_slots: Vec<Ref>,
+    domain_schedule: Option<Vec<DomainSchedEntry>>,
+    domain_set_start: Option<Word>,
+    domain_idx_shift: Option<Word>,
+}
+
+impl Parser {
+    fn new(toks: Vec<Token>) -> Self {
+        Self {
+            toks,
+            i: 0,
+            objects: vec![],
+            names: BTreeMap::new(),
+            children: vec![],
+            caps: vec![],
+            irqs: vec![],
+            asid_slots: vec![],
+            domain_schedule: None,
+            domain_set_start: None,
+            domain_idx_shift: None,
+        }
+    }
+
+    fn peek(&self) -> &Token {
+        &self.toks[self.i]
+    }
+
+    fn next(&mut self) -> Token {
+        let tok = self.toks[self.i].clone();
+        if tok.tok != Tok::Eof {
+            self.i += 1;
+        }
+        tok
+    }
+
+    fn unexpected<T>(tok: &Token, expected: &str) -> Result<T> {
+        Err(CdlParseError::new(
+            tok.pos,
+            format!("expected {expected}, found {}", tok.tok),
+        ))
+    }
+
+    fn eat_punct(&mut self, c: char) -> bool {
+        let found = self.peek().tok == Tok::Punct(c);
+        if found {
+            self.next();
+        }
+        found
+    }
+
+    fn expect_punct(&mut self, c: char) -> Result<()> {
+        let tok = self.next();
+        if tok.tok != Tok::Punct(c) {
+            return Self::unexpected(&tok, &format!("`{c}`"));
+        }
+        Ok(())
+    }
+
+    fn expect_ident(&mut self) -> Result<(String, Pos)> {
+        let tok = self.next();
+        match tok.tok {
+            Tok::Ident(s) => Ok((s, tok.pos)),
+            _ => Self::unexpected(&tok, "identifier"),
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        let tok = self.next();
+        if !matches!(&tok.tok, Tok::Ident(s) if s == keyword) {
+            return Self::unexpected(&tok, &format!("`{keyword}`"));
+        }
+        Ok(())
+    }
+
+    fn expect_num(&mut self) -> Result<(u64, Pos)> {
+        let tok = self.next();
+        match tok.tok {
+            Tok::Num(n) => Ok((n, tok.pos)),
+            _ => Self::unexpected(&tok, "number"),
+        }
+    }
+
+    fn file(&mut self) -> Result<()> {
+        loop {
+            let tok = self.next();
+            match &tok.tok {
+                Tok::Eof => return Ok(()),
+                Tok::Ident(section) => match section.as_str() {
+                    "arch" => {
+                        self.expect_ident()?;
+                    }
+                    "objects" => self.objects()?,
+                    "caps" => self.caps()?,
+                    "irq" => {
+                        self.expect_keyword("maps")?;
+                        self.irq_maps()?;
+                    }
+                    "asid" => {
+                        self.expect_keyword("slots")?;
+                        self.asid_slots = self.ref_block()?;
+                    }
+                    "domain" => {
+                        self.expect_keyword("schedule")?;
+                        self.domain_schedule()?;
+                    }
+                    _ => return Self::unexpected(&tok, "section"),
+                },
+                _ => return Self::unexpected(&tok, "section"),
+            }
+        }
+    }
+
+    fn objects(&mut self) -> Result<()> {
+        self.expect_punct('{')?;
+        while !self.eat_punct('}') {
+            self.object_decl()?;
+        }
+        Ok(())
+    }
+
+    fn object_decl(&mut self) -> Result<()> {
+        let id = self.objects.len();
+        let tok = self.next();
+        let name = match tok.tok {
+            Tok::Ident(s) | Tok::Str(s) => Some(s),
+            Tok::Hash(n) if n == id as u64 => None,
+            Tok::Hash(_) => {
+                return Err(CdlParseError::new(
+                    tok.pos,
+                    format!("unnamed object must be declared as `#{id}`"),
+                ));
+            }
+            _ => return Self::unexpected(&tok, "object name"),
+        };
+        self.expect_punct('=')?;
+        let (ty, ty_pos) = self.expect_ident()?;
+        let params = self.params_opt(ty_pos)?;
+        let object = object_from_params(&ty, ty_pos, params)?;
+        if self.peek().tok == Tok::Punct('{') {
+            if !matches!(object, Object::Untyped(_)) {
+                return Self::unexpected(self.peek(), "object declaration");
+            }
+            let children = self.ref_block()?;
+            if !children.is_empty() {
+                self.children.push((id, children));
+            }
+        }
+        if let Some(name) = &name {
+            self.names
+                .entry(name.clone())
+                .and_modify(|entry| *entry = None)
+                .or_insert(Some(id));
+        }
+        self.objects.push(NamedObject { name, object });
+        Ok(())
+    }
+
+    fn caps(&mut self) -> Result<()> {
+        self.expect_punct('{')?;
+        while !self.eat_punct('}') {
+            let holder = self.reference()?;
+            let mut decls = vec![];
+            self.expect_punct('{')?;
+            while !self.eat_punct('}') {
+                let tok = self.next();
+                let slot = match tok.tok {
+                    Tok::Num(n) => SlotName::Num(n),
+                    Tok::Ident(s) => SlotName::Name(s),
+                    _ => return Self::unexpected(&tok, "slot"),
+                };
+                self.expect_punct(':')?;
+                let target = self.reference()?;
+                let params = self.params_opt(target.pos)?;
+                decls.push(CapDecl {
+                    slot,
+                    slot_pos: tok.pos,
+                    target,
+                    params,
+                });
+            }
+            self.caps.push((holder, decls));
+        }
+        Ok(())
+    }
+
+    fn irq_maps(&mut self) -> Result<()> {
+        self.expect_punct('{')?;
+        while !self.eat_punct('}') {
+            let (irq, _) = self.expect_num()?;
+            self.expect_punct(':')?;
+            let handler = self.reference()?;
+            self.irqs.push((Word(irq), handler));
+        }
+        Ok(())
+    }
+
+    fn domain_schedule(&mut self) -> Result<()> {
+        let pos = self.peek().pos;
+        let mut params = self.params_opt(pos)?;
+        self.domain_set_start = params.word("start")?;
+        self.domain_idx_shift = params.word("shift")?;
+        params.finish()?;
+        if self.eat_punct('{') {
+            let mut entries = vec![];
+            while !self.eat_punct('}') {
+                let (domain, pos) = self.expect_num()?;
+                let domain = u8::try_from(domain)
+                    .map_err(|_| CdlParseError::new(pos, "number out of range"))?;
+                self.expect_punct(':')?;
+                let tok = self.next();
+                let duration = match tok.tok {
+                    Tok::Ident(s) if s == "end" => DomainSchedDuration::EndMarker,
+                    Tok::Num(n) => {
+                        let n = NonZero::new(n).ok_or_else(|| {
+                            CdlParseError::new(tok.pos, "duration must be non-zero")
+                        })?;
+                        let (unit, _) = self.expect_ident()?;
+                        match unit.as_str() {
+                            "ticks" => DomainSchedDuration::Ticks(n),
+                            "us" => DomainSchedDuration::Us(n),
+                            _ => {
+                                return Err(CdlParseError::new(
+                                    tok.pos,
+                                    "expected `ticks` or `us`",
+                                ));
+                            }
+                        }
+                    }
+                    _ => return Self::unexpected(&tok, "duration"),
+                };
+                entries.push(DomainSchedEntry { domain, duration });
+            }
+            self.domain_schedule = Some(entries);
+        }
+        Ok(())
+    }
+
+    fn reference(&mut self) -> Result<Ref> {
+        let tok = self.next();
+        let name = match tok.tok {
+            Tok::Ident(s) | Tok::Str(s) => RefName::Name(s),
+            Tok::Hash(n) => RefName::Id(n),
+            _ => return Self::unexpected(&tok, "object"),
+        };
+        Ok(Ref { name, pos: tok.pos })
+    }
+
+    fn ref_block(&mut self) -> Result<Vec<Ref>> {
+        self.expect_punct('{')?;
+        let mut refs = vec![];
+        while !self.eat_punct('}') {
+            refs.push(self.reference()?);
+        }
+        Ok(refs)
+    }
+
+    fn params_opt(&mut self, pos: Pos) -> Result<Params> {
+        let mut params = Params::empty(pos);
+        if self.eat_punct('(') && !self.eat_punct(')') {
+            loop {
+                params.items.push(Some(self.param()?));
+                if self.eat_punct(')') {
+                    break;
+                }
+                self.expect_punct(',')?;
+            }
+        }
+        Ok(params)
+    }
+
+    fn param(&mut self) -> Result<Param> {
+        let tok = self.next();
+        let kind = match tok.tok {
+            Tok::Size(bits) => ParamKind::Bits(bits),
+            Tok::Num(n) => {
+                self.expect_keyword("bits")?;
+                ParamKind::Bits(
+                    u8::try_from(n)
+                        .map_err(|_| CdlParseError::new(tok.pos, "number out of range"))?,
+                )
+            }
+            Tok::Ident(key) if self.eat_punct(':') => ParamKind::Key(key, self.value()?),
+            Tok::Ident(flag) => ParamKind::Flag(flag),
+            _ => return Self::unexpected(&tok, "parameter"),
+        };
+        Ok(Param { kind, pos: tok.pos })
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        let tok = self.next();
+        let kind = match tok.tok {
+            Tok::Num(n) => ValueKind::Num(n),
+            Tok::Ident(s) => ValueKind::Ident(s),
+            Tok::Str(s) => ValueKind::Str(s),
+            Tok::Punct('[') => {
+                let mut values = vec![];
+                if !self.eat_punct(']') {
+                    loop {
+                        values.push(self.value()?);
+                        if self.eat_punct(']') {
+                            break;
+                        }
+                        self.expect_punct(',')?;
+                    }
+                }
+                ValueKind::List(values)
+            }
+            Tok::Punct('{') => {
+                let mut values = vec![];
+                while !self.eat_punct('}') {
+                    values.push(self.value()?);
+                }
+                ValueKind::Group(values)
+            }
+            _ => return Self::unexpected(&tok, "value"),
+        };
+        Ok(Value { kind, pos: tok.pos })
+    }
+
+    fn resolve(&self, r: &Ref) -> Result<ObjectId> {
+        match &r.name {
+            RefName::Id(id) => match usize::try_from(*id) {
+                Ok(id) if id < self.objects.len() => Ok(id.into()),
+                _ => Err(CdlParseError::new(r.pos, format!("no object `#{id}`"))),
+            },
+            RefName::Name(name) => match self.names.get(name) {
+                Some(Some(id)) => Ok((*id).into()),
+                Some(None) => Err(CdlParseError::new(
+                    r.pos,
+                    format!("more than one object is named `{name}`"),
+                )),
+                None => Err(CdlParseError::new(
+                    r.pos,
+                    format!("no object named `{name}`"),
+                )),
+            },
+        }
+    }
+
+    fn build(mut self) -> Result<InputSpec> {
+        for (holder, decls) in core::mem::take(&mut self.caps) {
+            let holder_id = usize::from(self.resolve(&holder)?);
+            for decl in decls {
+                let slot = match &decl.slot {
+                    SlotName::Num(n) => {
+                        CapSlot(u32::try_from(*n).map_err(|_| {
+                            CdlParseError::new(decl.slot_pos, "number out of range")
+                        })?)
+                    }
+                    SlotName::Name(name) => {
+                        let slot = match &self.objects[holder_id].object {
+                            Object::Tcb(_) => TCB_SLOT_NAMES
+                                .iter()
+                                .find(|(slot_name, _)| slot_name == name),
+                            _ => None,
+                        };
+                        CapSlot(
+                            slot.ok_or_else(|| {
+                                CdlParseError::new(decl.slot_pos, format!("unknown slot `{name}`"))
+                            })?
+                            .1,
+                        )
+                    }
+                };
+                let target = self.resolve(&decl.target)?;
+                let cap = cap_from_params(
+                    &self.objects[usize::from(target)].object,
+                    target,
+                    decl.params,
+                )?;
+                let slots = self.objects[holder_id]
+                    .object
+                    .slots_mut()
+                    .ok_or_else(|| CdlParseError::new(holder.pos, "object cannot hold caps"))?;
+                if slots.iter().any(|entry| entry.slot == slot) {
+                    return Err(CdlParseError::new(
+                        decl.slot_pos,
+                        "slot is already occupied",
+                    ));
+                }
+                slots.push(CapTableEntry { slot, cap });
+            }
+        }
+
+        let mut untyped_covers = vec![];
+        for (parent, children) in &self.children {
+            let ids = children
+                .iter()
+                .map(|child| self.resolve(child))
+                .collect::<Result<Vec<_>>>()?;
+            for (i, (child, id)) in children.iter().zip(&ids).enumerate() {
+                if id.0 != ids[0].0 + i as u32 {
+                    return Err(CdlParseError::new(
+                        child.pos,
+                        "children of an untyped must be consecutive objects",
+                    ));
+                }
+            }
+            untyped_covers.push(UntypedCover {
+                parent: (*parent).into(),
+                children: ids[0]..ObjectId(ids[ids.len() - 1].0 + 1),
+            });
+        }
+
+        let mut spec = Spec {
+            irqs: self
+                .irqs
+                .iter()
+                .map(|(irq, handler)| {
+                    Ok(IrqEntry {
+                        irq: *irq,
+                        handler: self.resolve(handler)?,
+                    })
+                })
+                .collect::<Result<_>>()?,
+            asid_slots: self
+                .asid_slots
+                .iter()
+                .map(|pool| self.resolve(pool))
+                .collect::<Result<_>>()?,
+            domain_schedule: self.domain_schedule,
+            domain_set_start: self.domain_set_start,
+            domain_idx_shift: self.domain_idx_shift,
+            root_objects: ObjectId(0)..ObjectId(0),
+            untyped_covers,
+            cached_orig_cap_slots: None,
+            log_level: None,
+            measurement_log: None,
+            objects: self.objects,
+        };
+        renumber_objects(&mut spec);
+        Ok(spec)
+    }
+}
+
+// Renumbers objects so that root objects with a paddr come first, followed by the other root
+// objects, followed by the objects covered by untypeds, as the initializer requires. Each group
+// keeps the order in which its objects were declared, so the children of each untyped remain
+// consecutive.
+fn renumber_objects(spec: &mut InputSpec) {
+    let num_objects = spec.objects.len();
+    let mut is_covered = vec![false; num_objects];
+    for cover in spec.untyped_covers.iter() {
+        for child in ObjectId::into_usize_range(&cover.children) {
+            is_covered[child] = true;
+        }
+    }
+    let mut order = (0..num_objects).collect::<Vec<_>>();
+    order.sort_by_key(|&i| (is_covered[i], spec.objects[i].object.paddr().is_none()));
+    let num_root_objects = is_covered.iter().filter(|covered| !**covered).count();
+    spec.root_objects = ObjectId(0)..num_root_objects.into();
+
+    let mut new_ids = vec![ObjectId(0); num_objects];
+    for (new_id, &old_id) in order.iter().enumerate() {
+        new_ids[old_id] = new_id.into();
+    }
+    let remap = |id: ObjectId| new_ids[usize::from(id)];
+
+    let mut objects = core::mem::take(&mut spec.objects)
+        .into_iter()
+        .map(Some)
+        .collect::<Vec<_>>();
+    spec.objects = order
+        .iter()
+        .map(|&old_id| objects[old_id].take().unwrap())
+        .collect();
+    for named_obj in spec.objects.iter_mut() {
+        for entry in named_obj.object.slots_mut().into_iter().flatten() {
+            entry.cap.set_obj(remap(entry.cap.obj()));
+        }
+    }
+    for irq in spec.irqs.iter_mut() {
+        irq.handler = remap(irq.handler);
+    }
+    for pool in spec.asid_slots.iter_mut() {
+        *pool = remap(*pool);
+    }
+    for cover in spec.untyped_covers.iter_mut() {
+        let len = cover.children.end.0 - cover.children.start.0;
+        let start = remap(cover.children.start);
+        cover.parent = remap(cover.parent);
+        cover.children = start..ObjectId(start.0 + len);
+    }
+    spec.measurement_log = spec.measurement_log.map(remap);
+}
@@ -0,0 +1,424 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+use super::parse::is_ident;
+use super::{BOOTINFO_IDS, FILL_BOOTINFO, FILL_FILE_DATA, TCB_SLOT_NAMES};
+use crate::{
+    Cap, DomainSchedDuration, Fill, FillEntryContent, FillEntryContentFileOffset, InputSpec,
+    Object, ObjectId, Rights, Word,
+};
+
+impl InputSpec {
+    /// Prints this spec in the textual `.cdl` form accepted by [`InputSpec::from_cdl`].
+    ///
+    /// `cached_orig_cap_slots`, `log_level`, and `measurement_log` are not part of the textual
+    /// form.
+    pub fn to_cdl(&self) -> String {
+        let mut s = String::new();
+        Printer::new(self).print(&mut s).unwrap();
+        s
+    }
+}
+
+struct Printer<'a> {
+    spec: &'a InputSpec,
+    refs: Vec<String>,
+}
+
+impl<'a> Printer<'a> {
+    fn new(spec: &'a InputSpec) -> Self {
+        let mut name_counts = BTreeMap::<&str, usize>::new();
+        for obj in &spec.objects {
+            if let Some(name) = &obj.name {
+                *name_counts.entry(name).or_default() += 1;
+            }
+        }
+        let refs = spec
+            .objects
+            .iter()
+            .enumerate()
+            .map(|(i, obj)| match &obj.name {
+                Some(name) if name_counts[name.as_str()] == 1 => quote_name(name),
+                _ => format!("#{i}"),
+            })
+            .collect();
+        Self { spec, refs }
+    }
+
+    fn reference(&self, id: ObjectId) -> &str {
+        &self.refs[usize::from(id)]
+    }
+
+    fn print(&self, w: &mut impl Write) -> fmt::Result {
+        self.print_objects(w)?;
+        self.print_caps(w)?;
+        self.print_irqs(w)?;
+        self.print_asid_slots(w)?;
+        self.print_domain_schedule(w)?;
+        Ok(())
+    }
+
+    fn print_objects(&self, w: &mut impl Write) -> fmt::Result {
+        writeln!(w, "objects {{")?;
+        for (i, obj) in self.spec.objects.iter().enumerate() {
+            let name = match &obj.name {
+                Some(name) => quote_name(name),
+                None => format!("#{i}"),
+            };
+            let (ty, params) = object_params(&obj.object);
+            write!(w, "  {name} = {ty}")?;
+            write_params(w, &params)?;
+            if let Some(cover) = self
+                .spec
+                .untyped_covers
+                .iter()
+                .find(|cover| usize::from(cover.parent) == i && !cover.children.is_empty())
+            {
+                writeln!(w, " {{")?;
+                for child in ObjectId::into_usize_range(&cover.children) {
+                    writeln!(w, "    {}", self.reference(child.into()))?;
+                }
+                write!(w, "  }}")?;
+            }
+            writeln!(w)?;
+        }
+        writeln!(w, "}}")
+    }
+
+    fn print_caps(&self, w: &mut impl Write) -> fmt::Result {
+        let holders = self
+            .spec
+            .objects
+            .iter()
+            .enumerate()
+            .filter_map(|(i, obj)| Some((i, obj.object.slots().filter(|s| !s.is_empty())?)))
+            .collect::<Vec<_>>();
+        if holders.is_empty() {
+            return Ok(());
+        }
+        writeln!(w)?;
+        writeln!(w, "caps {{")?;
+        for (i, slots) in holders {
+            let is_tcb = matches!(self.spec.objects[i].object, Object::Tcb(_));
+            writeln!(w, "  {} {{", self.reference(i.into()))?;
+            for entry in slots {
+                let slot = TCB_SLOT_NAMES
+                    .iter()
+                    .find(|(_, n)| is_tcb && *n == entry.slot.0)
+                    .map(|(name, _)| name.to_string())
+                    .unwrap_or_else(|| format!("{:#x}", entry.slot.0));
+                write!(w, "    {slot}: {}", self.reference(entry.cap.obj()))?;
+                write_params(w, &cap_params(&entry.cap))?;
+                writeln!(w)?;
+            }
+            writeln!(w, "  }}")?;
+        }
+        writeln!(w, "}}")
+    }
+
+    fn print_irqs(&self, w: &mut impl Write) -> fmt::Result {
+        if self.spec.irqs.is_empty() {
+            return Ok(());
+        }
+        writeln!(w)?;
+        writeln!(w, "irq maps {{")?;
+        for entry in &self.spec.irqs {
+            writeln!(w, "  {:#x}: {}", entry.irq.0, self.reference(entry.handler))?;
+        }
+        writeln!(w, "}}")
+    }
+
+    fn print_asid_slots(&self, w: &mut impl Write) -> fmt::Result {
+        if self.spec.asid_slots.is_empty() {
+            return Ok(());
+        }
+        writeln!(w)?;
+        writeln!(w, "asid slots {{")?;
+        for pool in &self.spec.asid_slots {
+            writeln!(w, "  {}", self.reference(*pool))?;
+        }
+        writeln!(w, "}}")
+    }
+
+    fn print_domain_schedule(&self, w: &mut impl Write) -> fmt::Result {
+        let spec = self.spec;
+        if spec.domain_schedule.is_none()
+            && spec.domain_set_start.is_none()
+            && spec.domain_idx_shift.is_none()
+        {
+            return Ok(());
+        }
+        writeln!(w)?;
+        write!(w, "domain schedule")?;
+        let mut params = vec![];
+        if let Some(start) = spec.domain_set_start {
+            params.push(format!("start: {}", hex(start)));
+        }
+        if let Some(shift) = spec.domain_idx_shift {
+            params.push(format!("shift: {}", hex(shift)));
+        }
+        write_params(w, &params)?;
+        if let Some(entries) = &spec.domain_schedule {
+            writeln!(w, " {{")?;
+            for entry in entries {
+                match entry.duration {
+                    DomainSchedDuration::Ticks(n) => writeln!(w, "  {}: {n} ticks", entry.domain)?,
+                    DomainSchedDuration::Us(n) => writeln!(w, "  {}: {n} us", entry.domain)?,
+                    DomainSchedDuration::EndMarker => writeln!(w, "  {}: end", entry.domain)?,
+                }
+            }
+            write!(w, "}}")?;
+        }
+        writeln!(w)
+    }
+}
+
+fn write_params(w: &mut impl Write, params: &[String]) -> fmt::Result {
+    if !params.is_empty() {
+        write!(w, " ({})", params.join(", "))?;
+    }
+    Ok(())
+}
+
+fn quote_name(name: &str) -> String {
+    if is_ident(name) {
+        name.to_string()
+    } else {
+        quote(name)
+    }
+}
+
+fn quote(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn hex(word: Word) -> String {
+    format!("{:#x}", word.0)
+}
+
+fn size(size_bits: u8) -> String {
+    match size_bits {
+        12 => "4k".to_string(),
+        21 => "2M".to_string(),
+        30 => "1G".to_string(),
+        _ => format!("{size_bits} bits"),
+    }
+}
+
+fn rights(rights: &Rights, executable: bool) -> Option<String> {
+    let s = [
+        (rights.read, 'R'),
+        (rights.write, 'W'),
+        (rights.grant, 'G'),
+        (rights.grant_reply, 'P'),
+        (executable, 'X'),
+    ]
+    .iter()
+    .filter(|(present, _)| *present)
+    .map(|(_, c)| c)
+    .collect::<String>();
+    (!s.is_empty()).then_some(s)
+}
+
+fn object_params(object: &Object<Fill<FillEntryContentFileOffset>>) -> (&'static str, Vec<String>) {
+    let mut params = vec![];
+    let ty = match object {
+        Object::Untyped(obj) => {
+            params.push(format!("{} bits", obj.size_bits));
+            if let Some(paddr) = obj.paddr {
+                params.push(format!("paddr: {}", hex(paddr)));
+            }
+            "ut"
+        }
+        Object::Endpoint => "ep",
+        Object::Notification => "notification",
+        Object::CNode(obj) => {
+            params.push(format!("{} bits", obj.size_bits));
+            "cnode"
+        }
+        Object::Tcb(obj) => {
+            let extra = &obj.extra;
+            params.push(format!("addr: {}", hex(extra.ipc_buffer_addr)));
+            params.push(format!("ip: {}", hex(extra.ip)));
+            params.push(format!("sp: {}", hex(extra.sp)));
+            params.push(format!("prio: {}", extra.prio));
+            params.push(format!("max_prio: {}", extra.max_prio));
+            params.push(format!("affinity: {}", extra.affinity.0));
+            if let Some(domain) = extra.domain {
+                params.push(format!("dom: {domain}"));
+            }
+            if !extra.gprs.is_empty() {
+                let gprs = extra.gprs.iter().map(|gpr| hex(*gpr)).collect::<Vec<_>>();
+                params.push(format!("init: [{}]", gprs.join(", ")));
+            }
+            if let Some(fault_ep) = extra.master_fault_ep {
+                params.push(format!("fault_ep: {}", hex(fault_ep)));
+            }
+            if extra.fpu_disabled {
+                params.push("fpu_disabled".to_string());
+            }
+            if !extra.resume {
+                params.push("suspended".to_string());
+            }
+            "tcb"
+        }
+        Object::Irq(_) => "irq",
+        Object::VCpu => "vcpu",
+        Object::Frame(obj) => {
+            params.push(size(obj.size_bits));
+            if let Some(paddr) = obj.paddr {
+                params.push(format!("paddr: {}", hex(paddr)));
+            }
+            if !obj.init.entries.is_empty() {
+                let entries = obj
+                    .init
+                    .entries
+                    .iter()
+                    .map(|entry| {
+                        let (kind, source, offset) = match &entry.content {
+                            FillEntryContent::Data(data) => {
+                                (FILL_FILE_DATA, quote(&data.file), data.file_offset)
+                            }
+                            FillEntryContent::BootInfo(bootinfo) => (
+                                FILL_BOOTINFO,
+                                BOOTINFO_IDS
+                                    .iter()
+                                    .find(|(_, id)| *id == bootinfo.id)
+                                    .unwrap()
+                                    .0
+                                    .to_string(),
+                                bootinfo.offset,
+                            ),
+                        };
+                        format!(
+                            "{{{} {} {kind} {source} {offset}}}",
+                            entry.range.start,
+                            entry.range.end - entry.range.start,
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                params.push(format!("fill: [{}]", entries.join(", ")));
+            }
+            "frame"
+        }
+        Object::PageTable(obj) => {
+            if let Some(level) = obj.level {
+                params.push(format!("level: {level}"));
+            }
+            if obj.is_root {
+                params.push("root".to_string());
+            }
+            if obj.x86_ept {
+                params.push("ept".to_string());
+            }
+            "pt"
+        }
+        Object::AsidPool(obj) => {
+            params.push(format!("high: {}", hex(obj.high)));
+            "asid_pool"
+        }
+        Object::IOSpace(obj) => {
+            params.push(format!("domain_id: {}", hex(obj.domain_id)));
+            params.push(format!(
+                "pci: [{}, {}, {}]",
+                obj.pci_device.bus, obj.pci_device.device, obj.pci_device.function
+            ));
+            "io_space"
+        }
+        Object::IOPageTable(obj) => {
+            params.push(format!("level: {}", obj.level.0));
+            "io_pt"
+        }
+        Object::ArmIrq(obj) => {
+            params.push(format!("trigger: {}", obj.extra.trigger));
+            params.push(format!("target: {}", obj.extra.target.0));
+            "arm_irq"
+        }
+        Object::IrqMsi(obj) => {
+            params.push(format!("handle: {}", hex(obj.extra.handle)));
+            params.push(format!("pci_bus: {}", obj.extra.pci_bus.0));
+            params.push(format!("pci_dev: {}", obj.extra.pci_dev.0));
+            params.push(format!("pci_func: {}", obj.extra.pci_func.0));
+            "msi_irq"
+        }
+        Object::IrqIOApic(obj) => {
+            params.push(format!("ioapic: {}", obj.extra.ioapic.0));
+            params.push(format!("pin: {}", obj.extra.pin.0));
+            params.push(format!("level: {}", obj.extra.level.0));
+            params.push(format!("polarity: {}", obj.extra.polarity.0));
+            "ioapic_irq"
+        }
+        Object::RiscvIrq(obj) => {
+            params.push(format!("trigger: {}", obj.extra.trigger));
+            "riscv_irq"
+        }
+        Object::IOPorts(obj) => {
+            params.push(format!("start_port: {}", hex(obj.start_port)));
+            params.push(format!("end_port: {}", hex(obj.end_port)));
+            "io_ports"
+        }
+        Object::SchedContext(obj) => {
+            params.push(format!("{} bits", obj.size_bits));
+            params.push(format!("period: {}", obj.extra.period));
+            params.push(format!("budget: {}", obj.extra.budget));
+            params.push(format!("badge: {}", hex(obj.extra.badge)));
+            "sc"
+        }
+        Object::Reply => "rtreply",
+        Object::ArmSmc => "arm_smc",
+        Object::DomainSet => "domain_set",
+    };
+    (ty, params)
+}
+
+fn cap_params(cap: &Cap) -> Vec<String> {
+    let mut params = vec![];
+    match cap {
+        Cap::Endpoint(cap) => {
+            params.extend(rights(&cap.rights, false));
+            if cap.badge.0 != 0 {
+                params.push(format!("badge: {}", hex(cap.badge)));
+            }
+        }
+        Cap::Notification(cap) => {
+            params.extend(rights(&cap.rights, false));
+            if cap.badge.0 != 0 {
+                params.push(format!("badge: {}", hex(cap.badge)));
+            }
+        }
+        Cap::CNode(cap) => {
+            if cap.guard.0 != 0 {
+                params.push(format!("guard: {}", hex(cap.guard)));
+            }
+            if cap.guard_size != 0 {
+                params.push(format!("guard_size: {}", cap.guard_size));
+            }
+        }
+        Cap::Frame(cap) => {
+            params.extend(rights(&cap.rights, cap.executable));
+            if !cap.cached {
+                params.push("uncached".to_string());
+            }
+        }
+        _ => {}
+    }
+    params
+}
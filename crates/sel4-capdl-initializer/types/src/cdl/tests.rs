@@ -0,0 +1,121 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{CdlParseError, InputSpec, ObjectId};
+
+const EXAMPLE_CDL: &str = include_str!("../../test-data/example.cdl");
+const EXAMPLE_JSON: &str = include_str!("../../test-data/example.json");
+
+fn example() -> InputSpec {
+    serde_json::from_str(EXAMPLE_JSON).unwrap()
+}
+
+fn parse_error(src: &str) -> (usize, usize, String) {
+    let CdlParseError {
+        line,
+        column,
+        message,
+    } = InputSpec::from_cdl(src).unwrap_err();
+    (line, column, message)
+}
+
+#[test]
+fn parse_matches_json() {
+    assert_eq!(InputSpec::from_cdl(EXAMPLE_CDL).unwrap(), example());
+}
+
+#[test]
+fn example_is_valid() {
+    let diagnostics = InputSpec::from_cdl(EXAMPLE_CDL).unwrap().validate();
+    assert!(!diagnostics.has_errors(), "{diagnostics}");
+}
+
+#[test]
+fn objects_are_renumbered() {
+    let src = "objects {
+                 u = ut (21 bits) { a #2 }
+                 a = frame (4k)
+                 #2 = frame (4k)
+                 b = ep
+                 m = frame (4k, paddr: 0x9000000)
+                 c = cnode (1 bits)
+               }
+               caps { c { 0x0: b 0x1: u } }";
+    let spec = InputSpec::from_cdl(src).unwrap();
+    let names = spec
+        .objects
+        .iter()
+        .map(|named_obj| named_obj.name.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [Some("m"), Some("u"), Some("b"), Some("c"), Some("a"), None]
+    );
+    assert_eq!(spec.root_objects, ObjectId(0)..ObjectId(4));
+    assert_eq!(spec.untyped_covers[0].parent, ObjectId(1));
+    assert_eq!(spec.untyped_covers[0].children, ObjectId(4)..ObjectId(6));
+    let caps = spec.objects[3].object.slots().unwrap();
+    assert_eq!(caps[0].cap.obj(), ObjectId(2));
+    assert_eq!(caps[1].cap.obj(), ObjectId(1));
+    assert!(!spec.validate().has_errors());
+    assert_eq!(InputSpec::from_cdl(&spec.to_cdl()).unwrap(), spec);
+}
+
+#[test]
+fn print_round_trips() {
+    let spec = example();
+    assert_eq!(InputSpec::from_cdl(&spec.to_cdl()).unwrap(), spec);
+}
+
+#[test]
+fn print_is_stable() {
+    let printed = example().to_cdl();
+    assert_eq!(InputSpec::from_cdl(&printed).unwrap().to_cdl(), printed);
+}
+
+#[test]
+fn unnamed_and_duplicate_names() {
+    let src = "objects { #0 = ep x = ep x = notification c = cnode (1 bits) }
+               caps { c { 0x0: #1 0x1: #2 (R) } }";
+    let spec = InputSpec::from_cdl(src).unwrap();
+    assert_eq!(spec.objects[0].name, None);
+    let printed = spec.to_cdl();
+    assert!(printed.contains("0x0: #1\n"));
+    assert!(printed.contains("0x1: #2 (R)\n"));
+    assert_eq!(InputSpec::from_cdl(&printed).unwrap(), spec);
+
+    assert_eq!(
+        parse_error("objects { x = ep x = ep c = cnode (1 bits) } caps { c { 0x0: x } }"),
+        (1, 62, "more than one object is named `x`".into()),
+    );
+}
+
+#[test]
+fn errors_have_positions() {
+    assert_eq!(
+        parse_error("objects {\n  a = frobnicate\n}"),
+        (2, 7, "unknown object type `frobnicate`".into()),
+    );
+    assert_eq!(
+        parse_error("objects { a = cnode (10 bits, paddr: 0x0) }"),
+        (1, 31, "unexpected parameter".into()),
+    );
+    assert_eq!(
+        parse_error("objects { a = cnode (10 bits) }\ncaps { a { 0x0: b } }"),
+        (2, 17, "no object named `b`".into()),
+    );
+    assert_eq!(
+        parse_error("objects { a = cnode (10 bits) b = ep }\ncaps { a { 0x0: b (X) } }"),
+        (2, 20, "right `X` does not apply to this cap".into()),
+    );
+    assert_eq!(
+        parse_error("objects { a = ep"),
+        (1, 17, "expected object name, found end of input".into()),
+    );
+}
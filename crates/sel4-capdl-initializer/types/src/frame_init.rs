@@ -13,6 +13,7 @@ use core::ops::Range;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Eq, PartialEq, rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub enum FrameInit {
     Fill(Fill<Content>),
     Embedded(EmbeddedFrameIndex),
@@ -31,6 +32,7 @@ impl ArchivedFrameInit {
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub struct EmbeddedFrameIndex {
     pub index: u64,
 }
@@ -38,6 +40,7 @@ pub struct EmbeddedFrameIndex {
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub struct Fill<D> {
     pub entries: Vec<FillEntry<D>>,
 }
@@ -45,6 +48,7 @@ pub struct Fill<D> {
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub struct FillEntry<D> {
     pub range: Range<u64>,
     pub content: FillEntryContent<D>,
@@ -53,6 +57,7 @@ pub struct FillEntry<D> {
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub enum FillEntryContent<D> {
     Data(D),
     BootInfo(FillEntryContentBootInfo),
@@ -85,6 +90,7 @@ impl<D> FillEntryContent<D> {
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub struct FillEntryContentBootInfo {
     pub id: FillEntryContentBootInfoId,
     pub offset: u64,
@@ -93,6 +99,7 @@ pub struct FillEntryContentBootInfo {
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub enum FillEntryContentBootInfoId {
     Padding,
     X86Vbe,
@@ -106,36 +113,141 @@ pub enum FillEntryContentBootInfoId {
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub struct FillEntryContentFileOffset {
     pub file: String,
     pub file_offset: u64,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub enum Content {
     Bytes(BytesContent),
     DeflatedBytes(DeflatedBytesContent),
+    Lz4Bytes(Lz4BytesContent),
+    ZstdBytes(ZstdBytesContent),
+}
+
+/// How the data of a fill entry is encoded in the spec.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Codec {
+    None,
+    Deflate,
+    Lz4,
+    Zstd,
+}
+
+/// Name of the `u32` symbol at which the initializer records [`Codec::SUPPORTED`] for the build of
+/// this crate that it uses.
+pub const SUPPORTED_CODECS_SYMBOL: &str = "sel4_capdl_initializer_supported_codecs";
+
+impl Codec {
+    pub const ALL: [Self; 4] = [Self::None, Self::Deflate, Self::Lz4, Self::Zstd];
+
+    /// The set of codecs that this build of this crate can decode, with one [`Codec::bit`] per
+    /// codec.
+    pub const SUPPORTED: u32 = Self::None.bit()
+        | if cfg!(feature = "deflate") {
+            Self::Deflate.bit()
+        } else {
+            0
+        }
+        | if cfg!(feature = "lz4") {
+            Self::Lz4.bit()
+        } else {
+            0
+        }
+        | if cfg!(feature = "zstd") {
+            Self::Zstd.bit()
+        } else {
+            0
+        };
+
+    pub const fn bit(self) -> u32 {
+        1 << self as u32
+    }
+
+    pub fn supported_in(codecs: u32) -> impl Iterator<Item = Self> {
+        Self::ALL
+            .into_iter()
+            .filter(move |codec| codecs & codec.bit() != 0)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Deflate => "deflate",
+            Self::Lz4 => "lz4",
+            Self::Zstd => "zstd",
+        }
+    }
+}
+
+#[cfg(feature = "transform")]
+impl Content {
+    pub fn pack(codec: Codec, raw_content: &[u8]) -> Self {
+        match codec {
+            Codec::None => Self::Bytes(BytesContent::pack(raw_content)),
+            Codec::Deflate => Self::DeflatedBytes(DeflatedBytesContent::pack(raw_content)),
+            Codec::Lz4 => Self::Lz4Bytes(Lz4BytesContent::pack(raw_content)),
+            Codec::Zstd => Self::ZstdBytes(ZstdBytesContent::pack(raw_content)),
+        }
+    }
 }
 
 impl Content {
+    pub fn codec(&self) -> Codec {
+        match self {
+            Self::Bytes(_) => Codec::None,
+            Self::DeflatedBytes(_) => Codec::Deflate,
+            Self::Lz4Bytes(_) => Codec::Lz4,
+            Self::ZstdBytes(_) => Codec::Zstd,
+        }
+    }
+
+    pub fn encoded_len(&self) -> usize {
+        match self {
+            Self::Bytes(bytes) => bytes.bytes.len(),
+            Self::DeflatedBytes(deflated_bytes) => deflated_bytes.deflated_bytes.len(),
+            Self::Lz4Bytes(lz4_bytes) => lz4_bytes.lz4_bytes.len(),
+            Self::ZstdBytes(zstd_bytes) => zstd_bytes.encoded_len(),
+        }
+    }
+
     pub fn copy_out(&self, dst: &mut [u8]) {
         match self {
             Self::Bytes(bytes) => bytes.copy_out(dst),
             Self::DeflatedBytes(deflated_bytes) => deflated_bytes.copy_out(dst),
+            Self::Lz4Bytes(lz4_bytes) => lz4_bytes.copy_out(dst),
+            Self::ZstdBytes(zstd_bytes) => zstd_bytes.copy_out(dst),
         }
     }
 }
 
 impl ArchivedContent {
+    pub fn codec(&self) -> Codec {
+        match self {
+            Self::Bytes(_) => Codec::None,
+            Self::DeflatedBytes(_) => Codec::Deflate,
+            Self::Lz4Bytes(_) => Codec::Lz4,
+            Self::ZstdBytes(_) => Codec::Zstd,
+        }
+    }
+
     pub fn copy_out(&self, dst: &mut [u8]) {
         match self {
             Self::Bytes(bytes) => bytes.copy_out(dst),
             Self::DeflatedBytes(deflated_bytes) => deflated_bytes.copy_out(dst),
+            Self::Lz4Bytes(lz4_bytes) => lz4_bytes.copy_out(dst),
+            Self::ZstdBytes(zstd_bytes) => zstd_bytes.copy_out(dst),
         }
     }
 }
 
 #[derive(Clone, Eq, PartialEq, rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub struct BytesContent {
     pub bytes: Vec<u8>,
 }
@@ -169,6 +281,7 @@ impl ArchivedBytesContent {
 }
 
 #[derive(Clone, Eq, PartialEq, rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub struct DeflatedBytesContent {
     pub deflated_bytes: Vec<u8>,
 }
@@ -219,6 +332,134 @@ fn copy_out_deflated(_deflated_src: &[u8], _dst: &mut [u8]) {
     panic!("found deflated data but \"deflate\" feature is not enabled")
 }
 
+#[derive(Clone, Eq, PartialEq, rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
+pub struct Lz4BytesContent {
+    pub lz4_bytes: Vec<u8>,
+}
+
+impl fmt::Debug for Lz4BytesContent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lz4BytesContent")
+            .field("lz4_bytes", &Omitted)
+            .finish()
+    }
+}
+
+#[cfg(feature = "lz4")]
+impl Lz4BytesContent {
+    pub fn pack(raw_content: &[u8]) -> Self {
+        Self {
+            lz4_bytes: lz4_flex::block::compress(raw_content),
+        }
+    }
+}
+
+impl Lz4BytesContent {
+    pub fn copy_out(&self, dst: &mut [u8]) {
+        copy_out_lz4(&self.lz4_bytes, dst)
+    }
+}
+
+impl ArchivedLz4BytesContent {
+    pub fn copy_out(&self, dst: &mut [u8]) {
+        copy_out_lz4(&self.lz4_bytes, dst)
+    }
+}
+
+#[cfg(feature = "lz4")]
+fn copy_out_lz4(lz4_src: &[u8], dst: &mut [u8]) {
+    let n = lz4_flex::block::decompress_into(lz4_src, dst).unwrap();
+    assert_eq!(n, dst.len())
+}
+
+#[cfg(not(feature = "lz4"))]
+fn copy_out_lz4(_lz4_src: &[u8], _dst: &mut [u8]) {
+    panic!("found lz4 data but \"lz4\" feature is not enabled")
+}
+
+/// Zstandard-compressed data, as a sequence of independent frames, each of which (except for the
+/// last) decompresses to [`ZstdBytesContent::FRAME_SIZE`] bytes.
+///
+/// Splitting the data this way bounds the memory the decoder needs.
+#[derive(Clone, Eq, PartialEq, rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
+pub struct ZstdBytesContent {
+    pub zstd_frames: Vec<Vec<u8>>,
+}
+
+impl fmt::Debug for ZstdBytesContent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZstdBytesContent")
+            .field("zstd_frames", &Omitted)
+            .finish()
+    }
+}
+
+impl ZstdBytesContent {
+    pub const FRAME_SIZE: usize = 1 << 13;
+}
+
+#[cfg(feature = "zstd")]
+impl ZstdBytesContent {
+    pub fn pack(raw_content: &[u8]) -> Self {
+        Self {
+            zstd_frames: raw_content
+                .chunks(Self::FRAME_SIZE)
+                .map(|chunk| {
+                    ruzstd::encoding::compress_to_vec(
+                        chunk,
+                        ruzstd::encoding::CompressionLevel::Fastest,
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ZstdBytesContent {
+    pub fn encoded_len(&self) -> usize {
+        self.zstd_frames.iter().map(Vec::len).sum()
+    }
+
+    pub fn copy_out(&self, dst: &mut [u8]) {
+        for (zstd_frame, dst_chunk) in self
+            .zstd_frames
+            .iter()
+            .zip(dst.chunks_mut(Self::FRAME_SIZE))
+        {
+            copy_out_zstd(zstd_frame, dst_chunk)
+        }
+    }
+}
+
+impl ArchivedZstdBytesContent {
+    pub fn copy_out(&self, dst: &mut [u8]) {
+        for (zstd_frame, dst_chunk) in self
+            .zstd_frames
+            .iter()
+            .zip(dst.chunks_mut(ZstdBytesContent::FRAME_SIZE))
+        {
+            copy_out_zstd(zstd_frame, dst_chunk)
+        }
+    }
+}
+
+// NOTE
+// Unlike the other decoders, this one allocates.
+#[cfg(feature = "zstd")]
+fn copy_out_zstd(zstd_src: &[u8], dst: &mut [u8]) {
+    let n = ruzstd::decoding::FrameDecoder::new()
+        .decode_all(zstd_src, dst)
+        .unwrap();
+    assert_eq!(n, dst.len())
+}
+
+#[cfg(not(feature = "zstd"))]
+fn copy_out_zstd(_zstd_src: &[u8], _dst: &mut [u8]) {
+    panic!("found zstd data but \"zstd\" feature is not enabled")
+}
+
 // impl Debug helper
 struct Omitted;
 
@@ -227,3 +468,77 @@ impl fmt::Debug for Omitted {
         write!(f, "<omitted>")
     }
 }
+
+#[cfg(all(test, feature = "transform"))]
+mod test {
+    use alloc::vec;
+
+    use rkyv::rancor;
+
+    use super::*;
+
+    fn inputs() -> Vec<Vec<u8>> {
+        let mut pseudo_random = 1u32;
+        vec![
+            vec![],
+            vec![0x5a],
+            vec![0; 4096],
+            (0..4096u32).map(|i| i as u8).collect(),
+            // Spans several zstd frames, the last of which is partial.
+            (0..ZstdBytesContent::FRAME_SIZE * 3 + 123)
+                .map(|_| {
+                    pseudo_random = pseudo_random.wrapping_mul(1103515245).wrapping_add(12345);
+                    (pseudo_random >> 16) as u8
+                })
+                .collect(),
+        ]
+    }
+
+    #[test]
+    fn supported() {
+        assert_eq!(
+            Codec::supported_in(Codec::SUPPORTED).collect::<Vec<_>>(),
+            Codec::ALL
+        );
+        assert_eq!(
+            Codec::supported_in(Codec::None.bit() | Codec::Lz4.bit()).collect::<Vec<_>>(),
+            [Codec::None, Codec::Lz4]
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for codec in Codec::ALL {
+            for raw in inputs() {
+                let content = Content::pack(codec, &raw);
+                assert_eq!(content.codec(), codec);
+                let mut buf = vec![0xff; raw.len()];
+                content.copy_out(&mut buf);
+                assert_eq!(buf, raw, "{}", codec.name());
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_archived() {
+        for codec in Codec::ALL {
+            for raw in inputs() {
+                let bytes = rkyv::to_bytes::<rancor::Error>(&Content::pack(codec, &raw)).unwrap();
+                let archived = rkyv::access::<ArchivedContent, rancor::Error>(&bytes).unwrap();
+                assert_eq!(archived.codec(), codec);
+                let mut buf = vec![0xff; raw.len()];
+                archived.copy_out(&mut buf);
+                assert_eq!(buf, raw, "{}", codec.name());
+            }
+        }
+    }
+
+    #[test]
+    fn zstd_frames() {
+        let raw = vec![7; ZstdBytesContent::FRAME_SIZE * 2 + 1];
+        let Content::ZstdBytes(content) = Content::pack(Codec::Zstd, &raw) else {
+            panic!()
+        };
+        assert_eq!(content.zstd_frames.len(), 3);
+    }
+}
@@ -12,9 +12,15 @@ use rkyv::Archive;
 use rkyv::rancor;
 use rkyv::util::AlignedVec;
 
+mod authority;
 mod cap_table;
+mod cdl;
 mod frame_init;
+#[cfg(feature = "measurement")]
+pub mod measurement;
+mod simulate;
 mod spec;
+mod validate;
 pub mod x86_io_address_space;
 
 #[cfg(feature = "transform")]
@@ -23,9 +29,20 @@ mod transform;
 #[cfg(feature = "sel4")]
 mod when_sel4;
 
+pub use authority::{Access, AuthorityGraph, Edge, EdgeKind, Reply};
 pub use cap_table::{HasArchivedCapTable, HasCapTable};
+pub use cdl::CdlParseError;
 pub use frame_init::*;
+pub use simulate::{
+    AllocationReport, CSlotUsage, ObjectSizes, SimulatedUntyped, SimulationError, UntypedUsage,
+};
 pub use spec::*;
+pub use validate::{
+    Diagnostic, DiagnosticKind, Diagnostics, FrameContents, Location, ObjectRef, Severity,
+};
+
+#[cfg(feature = "transform")]
+pub use transform::DedupStats;
 
 #[cfg(feature = "sel4")]
 pub use when_sel4::*;
@@ -39,6 +56,13 @@ impl SpecForInitializer {
         rkyv::to_bytes(self)
     }
 
+    #[cfg(feature = "rkyv-deserialize")]
+    pub fn from_bytes(buf: &[u8]) -> Result<Self, rancor::Error> {
+        let mut aligned = AlignedVec::<16>::with_capacity(buf.len());
+        aligned.extend_from_slice(buf);
+        rkyv::from_bytes::<_, rancor::Error>(&aligned)
+    }
+
     pub fn access(buf: &[u8]) -> Result<&<Self as Archive>::Archived, rancor::Error> {
         rkyv::access(buf)
     }
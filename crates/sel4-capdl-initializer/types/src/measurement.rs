@@ -0,0 +1,548 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+// The measurement log is written by the initializer into the frame named by
+// `Spec::measurement_log`, and read by whichever component that frame is mapped into.
+//
+// Layout (all integers little-endian):
+//
+//   header (48 bytes):
+//     magic: [u8; 4] = "CDLM"
+//     version: u32
+//     num_entries: u32
+//     reserved: u32
+//     aggregate: [u8; 32]
+//
+//   entry (40 bytes), repeated num_entries times:
+//     kind: u32
+//     object: u32
+//     digest: [u8; 32]
+//
+// `aggregate` starts as all zeros and is extended with each entry in turn, as
+// `aggregate = SHA-256(aggregate || entry)`, so that a verifier can compare a single digest.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use sha2::{Digest as _, Sha256};
+
+use crate::{
+    ArchivedCap, Cap, FillEntryContent, FrameInit, Object, ObjectId, SpecForInitializer, object,
+};
+
+pub type Digest = [u8; 32];
+
+pub const MEASUREMENT_LOG_MAGIC: [u8; 4] = *b"CDLM";
+
+pub const MEASUREMENT_LOG_VERSION: u32 = 1;
+
+pub const MEASUREMENT_LOG_HEADER_SIZE: usize = 48;
+
+pub const MEASUREMENT_LOG_ENTRY_SIZE: usize = 40;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MeasurementKind {
+    /// SHA-256 of the entire contents of a frame, once filled
+    Frame,
+    /// SHA-256 of the configuration of a TCB (see [`object::Tcb::measure`])
+    Tcb,
+}
+
+impl MeasurementKind {
+    fn to_u32(self) -> u32 {
+        match self {
+            Self::Frame => 1,
+            Self::Tcb => 2,
+        }
+    }
+
+    fn from_u32(x: u32) -> Option<Self> {
+        Some(match x {
+            1 => Self::Frame,
+            2 => Self::Tcb,
+            _ => return None,
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Frame => "frame",
+            Self::Tcb => "tcb",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct MeasurementEntry {
+    pub kind: MeasurementKind,
+    pub object: ObjectId,
+    pub digest: Digest,
+}
+
+impl MeasurementEntry {
+    pub fn to_bytes(&self) -> [u8; MEASUREMENT_LOG_ENTRY_SIZE] {
+        let mut buf = [0; MEASUREMENT_LOG_ENTRY_SIZE];
+        buf[0..4].copy_from_slice(&self.kind.to_u32().to_le_bytes());
+        buf[4..8].copy_from_slice(&self.object.0.to_le_bytes());
+        buf[8..].copy_from_slice(&self.digest);
+        buf
+    }
+
+    pub fn from_bytes(buf: &[u8; MEASUREMENT_LOG_ENTRY_SIZE]) -> Option<Self> {
+        Some(Self {
+            kind: MeasurementKind::from_u32(u32::from_le_bytes(buf[0..4].try_into().unwrap()))?,
+            object: ObjectId(u32::from_le_bytes(buf[4..8].try_into().unwrap())),
+            digest: buf[8..].try_into().unwrap(),
+        })
+    }
+}
+
+/// Extends `aggregate` with `entry`.
+pub fn extend_aggregate(aggregate: &Digest, entry: &MeasurementEntry) -> Digest {
+    Sha256::new()
+        .chain_update(aggregate)
+        .chain_update(entry.to_bytes())
+        .finalize()
+        .into()
+}
+
+pub fn measure_frame(contents: &[u8]) -> Digest {
+    Sha256::digest(contents).into()
+}
+
+pub fn measurement_log_size(num_entries: usize) -> usize {
+    MEASUREMENT_LOG_HEADER_SIZE + num_entries * MEASUREMENT_LOG_ENTRY_SIZE
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct MeasurementLogFull;
+
+pub struct MeasurementLogWriter<'a> {
+    buf: &'a mut [u8],
+    num_entries: u32,
+    aggregate: Digest,
+}
+
+impl<'a> MeasurementLogWriter<'a> {
+    /// Writes an empty log to `buf`, which is assumed to be zeroed.
+    pub fn new(buf: &'a mut [u8]) -> Result<Self, MeasurementLogFull> {
+        if buf.len() < MEASUREMENT_LOG_HEADER_SIZE {
+            return Err(MeasurementLogFull);
+        }
+        let mut this = Self {
+            buf,
+            num_entries: 0,
+            aggregate: [0; 32],
+        };
+        this.buf[0..4].copy_from_slice(&MEASUREMENT_LOG_MAGIC);
+        this.buf[4..8].copy_from_slice(&MEASUREMENT_LOG_VERSION.to_le_bytes());
+        this.write_header();
+        Ok(this)
+    }
+
+    pub fn push(&mut self, entry: &MeasurementEntry) -> Result<(), MeasurementLogFull> {
+        let offset = measurement_log_size(self.num_entries.try_into().unwrap());
+        self.buf
+            .get_mut(offset..offset + MEASUREMENT_LOG_ENTRY_SIZE)
+            .ok_or(MeasurementLogFull)?
+            .copy_from_slice(&entry.to_bytes());
+        self.num_entries += 1;
+        self.aggregate = extend_aggregate(&self.aggregate, entry);
+        self.write_header();
+        Ok(())
+    }
+
+    fn write_header(&mut self) {
+        self.buf[8..12].copy_from_slice(&self.num_entries.to_le_bytes());
+        self.buf[16..48].copy_from_slice(&self.aggregate);
+    }
+}
+
+/// A measurement log, as read by the component that the log frame is mapped into.
+pub struct MeasurementLog<'a> {
+    aggregate: Digest,
+    entries: &'a [u8],
+}
+
+impl<'a> MeasurementLog<'a> {
+    pub fn parse(buf: &'a [u8]) -> Option<Self> {
+        let header = buf.get(..MEASUREMENT_LOG_HEADER_SIZE)?;
+        if header[0..4] != MEASUREMENT_LOG_MAGIC
+            || u32::from_le_bytes(header[4..8].try_into().unwrap()) != MEASUREMENT_LOG_VERSION
+        {
+            return None;
+        }
+        let num_entries = u32::from_le_bytes(header[8..12].try_into().unwrap());
+        Some(Self {
+            aggregate: header[16..48].try_into().unwrap(),
+            entries: buf.get(
+                MEASUREMENT_LOG_HEADER_SIZE..measurement_log_size(num_entries.try_into().ok()?),
+            )?,
+        })
+    }
+
+    pub fn aggregate(&self) -> &Digest {
+        &self.aggregate
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = Option<MeasurementEntry>> + 'a {
+        self.entries
+            .chunks_exact(MEASUREMENT_LOG_ENTRY_SIZE)
+            .map(|chunk| MeasurementEntry::from_bytes(chunk.try_into().unwrap()))
+    }
+}
+
+/// A measurement that the initializer will record, as predicted on the host.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ExpectedMeasurement {
+    pub kind: MeasurementKind,
+    pub object: ObjectId,
+    /// `None` for frames whose contents depend on the bootinfo
+    pub digest: Option<Digest>,
+}
+
+impl SpecForInitializer {
+    /// Predicts the measurements that the initializer will record, in order.
+    ///
+    /// Frames are measured in the order of their object IDs, followed by TCBs. Only frames with
+    /// contents are measured.
+    pub fn expected_measurements(
+        &self,
+        embedded_frame_data: &[Vec<u8>],
+    ) -> Vec<ExpectedMeasurement> {
+        let mut measurements = vec![];
+        for (obj_id, named_obj) in self.objects.iter().enumerate() {
+            if let Object::Frame(frame) = &named_obj.object {
+                let digest = match &frame.init {
+                    FrameInit::Fill(fill) if fill.entries.is_empty() => continue,
+                    FrameInit::Fill(fill) => {
+                        let mut buf = vec![0; 1 << frame.size_bits];
+                        let mut depends_on_bootinfo = false;
+                        for entry in fill.entries.iter() {
+                            let range = usize::try_from(entry.range.start).unwrap()
+                                ..usize::try_from(entry.range.end).unwrap();
+                            match &entry.content {
+                                FillEntryContent::Data(content) => {
+                                    content.copy_out(&mut buf[range])
+                                }
+                                FillEntryContent::BootInfo(_) => depends_on_bootinfo = true,
+                            }
+                        }
+                        (!depends_on_bootinfo).then(|| measure_frame(&buf))
+                    }
+                    FrameInit::Embedded(embedded) => Some(measure_frame(
+                        &embedded_frame_data[usize::try_from(embedded.index).unwrap()],
+                    )),
+                };
+                measurements.push(ExpectedMeasurement {
+                    kind: MeasurementKind::Frame,
+                    object: obj_id.into(),
+                    digest,
+                });
+            }
+        }
+        for (obj_id, named_obj) in self.objects.iter().enumerate() {
+            if let Object::Tcb(tcb) = &named_obj.object {
+                measurements.push(ExpectedMeasurement {
+                    kind: MeasurementKind::Tcb,
+                    object: obj_id.into(),
+                    digest: Some(tcb.measure(obj_id.into())),
+                });
+            }
+        }
+        measurements
+    }
+}
+
+/// The final value of the aggregate, if every measurement can be predicted.
+pub fn expected_aggregate(measurements: &[ExpectedMeasurement]) -> Option<Digest> {
+    let mut aggregate = [0; 32];
+    for measurement in measurements {
+        aggregate = extend_aggregate(
+            &aggregate,
+            &MeasurementEntry {
+                kind: measurement.kind,
+                object: measurement.object,
+                digest: measurement.digest?,
+            },
+        );
+    }
+    Some(aggregate)
+}
+
+// // //
+
+// The TCB encoding is shared between the host (`object::Tcb`) and the initializer
+// (`object::ArchivedTcb`), whose implementations are expanded from the same macro so that the two
+// cannot drift apart.
+
+#[derive(Default)]
+struct CapFields {
+    object: u32,
+    badge: u64,
+    rights: u8,
+    guard: u64,
+    guard_size: u8,
+}
+
+struct TcbHasher(Sha256);
+
+impl TcbHasher {
+    fn new(obj_id: u32, num_slots: usize) -> Self {
+        let mut this = Self(Sha256::new());
+        this.u32(obj_id);
+        this.u32(num_slots.try_into().unwrap());
+        this
+    }
+
+    fn slot(&mut self, slot: u32, cap: CapFields) {
+        self.u32(slot);
+        self.u32(cap.object);
+        self.u64(cap.badge);
+        self.u8(cap.rights);
+        self.u64(cap.guard);
+        self.u8(cap.guard_size);
+    }
+
+    fn u8(&mut self, x: u8) {
+        self.0.update([x]);
+    }
+
+    fn u32(&mut self, x: u32) {
+        self.0.update(x.to_le_bytes());
+    }
+
+    fn u64(&mut self, x: u64) {
+        self.0.update(x.to_le_bytes());
+    }
+
+    fn option_u64(&mut self, x: Option<u64>) {
+        self.u8(x.is_some().into());
+        self.u64(x.unwrap_or(0));
+    }
+
+    fn finish(self) -> Digest {
+        self.0.finalize().into()
+    }
+}
+
+fn rights_bits(read: bool, write: bool, grant: bool, grant_reply: bool) -> u8 {
+    u8::from(read) | (u8::from(write) << 1) | (u8::from(grant) << 2) | (u8::from(grant_reply) << 3)
+}
+
+macro_rules! impl_tcb_measure {
+    ($tcb_ty:ty, $cap_ty:ident) => {
+        impl $tcb_ty {
+            /// SHA-256 over the TCB's caps (with badges, rights, and guards) and the rest of its
+            /// configuration (IPC buffer address, affinity, priorities, domain, flags, and
+            /// registers).
+            pub fn measure(&self, obj_id: ObjectId) -> Digest {
+                let mut hasher = TcbHasher::new(obj_id.0, self.slots.len());
+                for entry in self.slots.iter() {
+                    let mut fields = CapFields {
+                        object: u32::from(entry.cap.obj().0),
+                        ..Default::default()
+                    };
+                    match &entry.cap {
+                        $cap_ty::Endpoint(cap) => {
+                            fields.badge = u64::from(cap.badge.0);
+                            let rights = &cap.rights;
+                            fields.rights = rights_bits(
+                                rights.read,
+                                rights.write,
+                                rights.grant,
+                                rights.grant_reply,
+                            );
+                        }
+                        $cap_ty::Notification(cap) => {
+                            fields.badge = u64::from(cap.badge.0);
+                            let rights = &cap.rights;
+                            fields.rights = rights_bits(
+                                rights.read,
+                                rights.write,
+                                rights.grant,
+                                rights.grant_reply,
+                            );
+                        }
+                        $cap_ty::Frame(cap) => {
+                            let rights = &cap.rights;
+                            fields.rights = rights_bits(
+                                rights.read,
+                                rights.write,
+                                rights.grant,
+                                rights.grant_reply,
+                            );
+                        }
+                        $cap_ty::CNode(cap) => {
+                            fields.guard = u64::from(cap.guard.0);
+                            fields.guard_size = cap.guard_size;
+                        }
+                        _ => {}
+                    }
+                    hasher.slot(u32::from(entry.slot.0), fields);
+                }
+                let extra = &self.extra;
+                hasher.u64(u64::from(extra.ipc_buffer_addr.0));
+                hasher.u64(u64::from(extra.affinity.0));
+                hasher.u8(extra.prio);
+                hasher.u8(extra.max_prio);
+                hasher.u8(extra.fpu_disabled.into());
+                hasher.u8(extra.resume.into());
+                hasher.option_u64(extra.domain.as_ref().map(|domain| u64::from(*domain)));
+                hasher.u64(u64::from(extra.ip.0));
+                hasher.u64(u64::from(extra.sp.0));
+                hasher.u32(extra.gprs.len().try_into().unwrap());
+                for gpr in extra.gprs.iter() {
+                    hasher.u64(u64::from(gpr.0));
+                }
+                hasher.option_u64(extra.master_fault_ep.as_ref().map(|word| u64::from(word.0)));
+                hasher.finish()
+            }
+        }
+    };
+}
+
+impl_tcb_measure!(object::Tcb, Cap);
+impl_tcb_measure!(object::ArchivedTcb, ArchivedCap);
+
+#[cfg(test)]
+mod test {
+    use rkyv::rancor;
+
+    use super::*;
+    use crate::InputSpec;
+
+    fn entry(kind: MeasurementKind, object: u32, fill: u8) -> MeasurementEntry {
+        MeasurementEntry {
+            kind,
+            object: ObjectId(object),
+            digest: [fill; 32],
+        }
+    }
+
+    #[test]
+    fn log_round_trip() {
+        let entries = [
+            entry(MeasurementKind::Frame, 3, 0x11),
+            entry(MeasurementKind::Frame, 7, 0x22),
+            entry(MeasurementKind::Tcb, 1, 0x33),
+        ];
+        let mut buf = vec![0; 4096];
+        let mut writer = MeasurementLogWriter::new(&mut buf).unwrap();
+        for entry in entries.iter() {
+            writer.push(entry).unwrap();
+        }
+        let log = MeasurementLog::parse(&buf).unwrap();
+        assert_eq!(log.entries().collect::<Option<Vec<_>>>().unwrap(), entries);
+        let expected = entries
+            .iter()
+            .map(|entry| ExpectedMeasurement {
+                kind: entry.kind,
+                object: entry.object,
+                digest: Some(entry.digest),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(Some(*log.aggregate()), expected_aggregate(&expected));
+    }
+
+    #[test]
+    fn log_empty() {
+        let mut buf = vec![0; MEASUREMENT_LOG_HEADER_SIZE];
+        MeasurementLogWriter::new(&mut buf).unwrap();
+        let log = MeasurementLog::parse(&buf).unwrap();
+        assert_eq!(log.entries().count(), 0);
+        assert_eq!(log.aggregate(), &[0; 32]);
+    }
+
+    #[test]
+    fn log_full() {
+        let mut buf = vec![0; measurement_log_size(1)];
+        let mut writer = MeasurementLogWriter::new(&mut buf).unwrap();
+        writer
+            .push(&entry(MeasurementKind::Frame, 0, 0x11))
+            .unwrap();
+        assert_eq!(
+            writer.push(&entry(MeasurementKind::Frame, 1, 0x22)),
+            Err(MeasurementLogFull)
+        );
+        assert_eq!(MeasurementLog::parse(&buf).unwrap().entries().count(), 1);
+        assert!(MeasurementLogWriter::new(&mut [0; MEASUREMENT_LOG_HEADER_SIZE - 1]).is_err());
+    }
+
+    #[test]
+    fn log_invalid() {
+        let mut buf = vec![0; 4096];
+        assert!(MeasurementLog::parse(&buf).is_none());
+        MeasurementLogWriter::new(&mut buf).unwrap();
+        buf[4] = 0xff;
+        assert!(MeasurementLog::parse(&buf).is_none());
+        let mut buf = vec![0; MEASUREMENT_LOG_HEADER_SIZE];
+        MeasurementLogWriter::new(&mut buf).unwrap();
+        buf[8] = 1;
+        assert!(MeasurementLog::parse(&buf).is_none());
+    }
+
+    const SPEC: &str = "
+        objects {
+          tcb = tcb (addr: 0x203000, ip: 0x200000, sp: 0x202ff0, prio: 254, max_prio: 254, affinity: 1, dom: 2, init: [0x1, 0x2], fault_ep: 0x3)
+          cnode = cnode (4 bits)
+          vspace = pt (level: 0, root)
+          ipc = frame (4k)
+          ep = ep
+          ntfn = notification
+        }
+        caps {
+          tcb {
+            cspace: cnode (guard: 0x5, guard_size: 60)
+            vspace: vspace
+            ipc_buffer: ipc (RW)
+            fault_ep: ep (RWG, badge: 0x7)
+            bound_notification: ntfn (R, badge: 0x9)
+          }
+        }
+    ";
+
+    fn tcb(src: &str) -> object::Tcb {
+        let spec = InputSpec::from_cdl(src).unwrap();
+        match &spec.objects[0].object {
+            Object::Tcb(tcb) => tcb.clone(),
+            _ => panic!(),
+        }
+    }
+
+    fn archived_digest(tcb: &object::Tcb, obj_id: ObjectId) -> Digest {
+        let bytes = rkyv::to_bytes::<rancor::Error>(tcb).unwrap();
+        rkyv::access::<object::ArchivedTcb, rancor::Error>(&bytes)
+            .unwrap()
+            .measure(obj_id)
+    }
+
+    #[test]
+    fn tcb_host_matches_initializer() {
+        let tcb = tcb(SPEC);
+        assert_eq!(tcb.measure(ObjectId(0)), archived_digest(&tcb, ObjectId(0)));
+        assert_ne!(tcb.measure(ObjectId(0)), tcb.measure(ObjectId(1)));
+    }
+
+    #[test]
+    fn tcb_sensitivity() {
+        let orig = tcb(SPEC).measure(ObjectId(0));
+        for (from, to) in [
+            ("prio: 254,", "prio: 253,"),
+            ("init: [0x1, 0x2]", "init: [0x1, 0x3]"),
+            ("guard: 0x5", "guard: 0x6"),
+            ("badge: 0x7", "badge: 0x8"),
+            ("ipc (RW)", "ipc (R)"),
+            ("dom: 2", "dom: 3"),
+        ] {
+            assert!(SPEC.contains(from));
+            let tcb = tcb(&SPEC.replace(from, to));
+            let digest = tcb.measure(ObjectId(0));
+            assert_ne!(digest, orig, "{from} -> {to}");
+            assert_eq!(digest, archived_digest(&tcb, ObjectId(0)), "{from} -> {to}");
+        }
+    }
+}
@@ -0,0 +1,720 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "kernel-config")]
+use sel4_config_types::{Configuration, Value};
+
+use crate::{Cap, FrameInit, Object, ObjectId, SpecForInitializer, object};
+
+const MAX_SIZE_BITS: usize = 64;
+
+/// Sizes of kernel objects whose size is not specified in the spec itself.
+///
+/// These correspond to the `seL4_*Bits` constants of a particular kernel configuration. Objects
+/// whose size is `None` are not supported by that configuration, and are skipped, just as the
+/// initializer skips objects without a blueprint.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ObjectSizes {
+    pub slot_bits: u8,
+    pub tcb_bits: u8,
+    pub endpoint_bits: u8,
+    pub notification_bits: u8,
+    pub page_table_bits: u8,
+    pub vspace_bits: u8,
+    pub asid_pool_bits: u8,
+    pub vcpu_bits: Option<u8>,
+    pub io_page_table_bits: Option<u8>,
+    pub reply_bits: Option<u8>,
+    pub min_sched_context_bits: Option<u8>,
+}
+
+impl ObjectSizes {
+    /// Derives sizes from a kernel configuration, as found in `kernel/gen_config.json` and
+    /// exposed by `sel4-config`. Returns `None` for unsupported configurations.
+    #[cfg(feature = "kernel-config")]
+    pub fn from_kernel_config(config: &Configuration) -> Option<Self> {
+        let is_set = |key| config.get(key).and_then(Value::as_bool).unwrap_or(false);
+        let get_str = |key| config.get(key).and_then(Value::as_str);
+        let mcs = is_set("KERNEL_MCS");
+        let mut sizes = match get_str("WORD_SIZE")? {
+            "64" => Self {
+                slot_bits: 5,
+                tcb_bits: 11,
+                endpoint_bits: 4,
+                notification_bits: if mcs { 6 } else { 5 },
+                page_table_bits: 12,
+                vspace_bits: 12,
+                asid_pool_bits: 12,
+                vcpu_bits: None,
+                io_page_table_bits: None,
+                reply_bits: mcs.then_some(5),
+                min_sched_context_bits: mcs.then_some(8),
+            },
+            "32" => Self {
+                slot_bits: 4,
+                tcb_bits: 9,
+                endpoint_bits: 4,
+                notification_bits: if mcs { 5 } else { 4 },
+                page_table_bits: 12,
+                vspace_bits: 12,
+                asid_pool_bits: 12,
+                vcpu_bits: None,
+                io_page_table_bits: None,
+                reply_bits: mcs.then_some(4),
+                min_sched_context_bits: mcs.then_some(7),
+            },
+            _ => return None,
+        };
+        match get_str("SEL4_ARCH")? {
+            "aarch64" => {
+                if is_set("ARM_HYPERVISOR_SUPPORT") {
+                    sizes.vcpu_bits = Some(12);
+                    if is_set("ARM_PA_SIZE_BITS_40") {
+                        sizes.vspace_bits = 13;
+                    }
+                }
+            }
+            "aarch32" => {
+                if is_set("HAVE_FPU") {
+                    sizes.tcb_bits = 10;
+                }
+                sizes.vspace_bits = 14;
+                if is_set("ARM_HYPERVISOR_SUPPORT") {
+                    sizes.vcpu_bits = Some(12);
+                } else {
+                    sizes.page_table_bits = 10;
+                }
+            }
+            "riscv64" => {
+                sizes.tcb_bits = 10;
+            }
+            "riscv32" => {}
+            "x86_64" => {
+                let xsave_size = get_str("XSAVE_SIZE").and_then(|v| v.parse::<usize>().ok());
+                if xsave_size.is_some_and(|v| v >= 832) {
+                    sizes.tcb_bits = 12;
+                }
+                if is_set("VTX") {
+                    sizes.vcpu_bits = Some(14);
+                }
+                if is_set("IOMMU") {
+                    sizes.io_page_table_bits = Some(12);
+                }
+            }
+            _ => return None,
+        }
+        Some(sizes)
+    }
+
+    fn physical_size_bits(&self, obj: &Object<FrameInit>) -> Option<u8> {
+        Some(match obj {
+            Object::Untyped(obj) => obj.size_bits,
+            Object::Endpoint => self.endpoint_bits,
+            Object::Notification => self.notification_bits,
+            Object::CNode(obj) => self.slot_bits.saturating_add(obj.size_bits),
+            Object::Tcb(_) => self.tcb_bits,
+            Object::VCpu => self.vcpu_bits?,
+            Object::Frame(obj) => obj.size_bits,
+            Object::PageTable(obj) => {
+                if obj.is_root {
+                    self.vspace_bits
+                } else {
+                    self.page_table_bits
+                }
+            }
+            Object::IOPageTable(_) => self.io_page_table_bits?,
+            Object::AsidPool(_) => self.asid_pool_bits,
+            Object::SchedContext(obj) => obj.size_bits.max(self.min_sched_context_bits?),
+            Object::Reply => self.reply_bits?,
+            _ => return None,
+        })
+    }
+}
+
+/// An untyped as it would appear in `sel4::BootInfo::untyped_list()`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SimulatedUntyped {
+    pub paddr: u64,
+    pub size_bits: u8,
+    pub is_device: bool,
+}
+
+impl SimulatedUntyped {
+    /// Returns `None` if this untyped extends beyond the end of the physical address space.
+    pub fn range(&self) -> Option<Range<u64>> {
+        checked_range(self.paddr, self.size_bits)
+    }
+
+    /// Splits a region of physical memory into maximal naturally-aligned untypeds, in the same way
+    /// that the kernel does at boot time.
+    pub fn from_region(region: &Range<u64>, max_size_bits: u8, is_device: bool) -> Vec<Self> {
+        let mut untypeds = vec![];
+        let mut cur = region.start;
+        while cur < region.end {
+            let alignment_bits = cur.trailing_zeros();
+            let distance_bits = (region.end - cur).ilog2();
+            let size_bits = alignment_bits
+                .min(distance_bits)
+                .min(max_size_bits.into())
+                .try_into()
+                .unwrap();
+            untypeds.push(Self {
+                paddr: cur,
+                size_bits,
+                is_device,
+            });
+            cur += 1 << size_bits;
+        }
+        untypeds
+    }
+}
+
+fn checked_range(start: u64, size_bits: u8) -> Option<Range<u64>> {
+    let size = 1u64.checked_shl(size_bits.into())?;
+    Some(start..start.checked_add(size)?)
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SimulationError {
+    /// A root object with a `paddr` has no size in the given configuration.
+    NoSizeForObject(ObjectId),
+    /// A root object is too large for the physical address space.
+    ObjectTooLarge(ObjectId),
+    /// An untyped extends beyond the end of the physical address space.
+    UntypedTooLarge(SimulatedUntyped),
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoSizeForObject(obj_id) => {
+                write!(f, "no size for object #{}", usize::from(*obj_id))
+            }
+            Self::ObjectTooLarge(obj_id) => {
+                write!(f, "object #{} is too large", usize::from(*obj_id))
+            }
+            Self::UntypedTooLarge(ut) => write!(
+                f,
+                "untyped at {:#x} with size bits {} is too large",
+                ut.paddr, ut.size_bits
+            ),
+        }
+    }
+}
+
+impl core::error::Error for SimulationError {}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AllocationReport {
+    pub untypeds: Vec<UntypedUsage>,
+    /// Root objects whose `paddr` is not covered by any untyped.
+    pub unsatisfiable_paddrs: Vec<ObjectId>,
+    /// Number of root objects of each size that could not be allocated, indexed by size bits.
+    pub shortfall_by_size_bits: Vec<usize>,
+    pub cslots: CSlotUsage,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UntypedUsage {
+    pub untyped: SimulatedUntyped,
+    pub num_objects: usize,
+    /// Bytes occupied by objects from the spec.
+    pub object_bytes: u64,
+    /// Bytes occupied by dummy objects used to advance the watermark towards a `paddr`.
+    pub dummy_bytes: u64,
+}
+
+impl UntypedUsage {
+    /// Bytes left above the watermark.
+    ///
+    /// The untypeds in a report produced by [`SpecForInitializer::simulate_allocation`] are known
+    /// to fit in the physical address space.
+    pub fn free_bytes(&self) -> u64 {
+        (1 << self.untyped.size_bits) - self.object_bytes - self.dummy_bytes
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CSlotUsage {
+    pub orig: usize,
+    pub hold: usize,
+    pub mapping_copies: usize,
+    pub badged_copies: usize,
+    /// Copies of the untypeds from which ASID pools are made.
+    pub asid_pool_copies: usize,
+}
+
+impl CSlotUsage {
+    /// Slots are never freed, so the peak is just the total.
+    pub fn peak(&self) -> usize {
+        self.orig + self.hold + self.mapping_copies + self.badged_copies + self.asid_pool_copies
+    }
+}
+
+impl AllocationReport {
+    pub fn is_ok(&self) -> bool {
+        self.unsatisfiable_paddrs.is_empty() && self.shortfall_by_size_bits.iter().all(|n| *n == 0)
+    }
+
+    /// Bytes lost to watermark manipulation.
+    pub fn dummy_bytes(&self) -> u64 {
+        self.untypeds.iter().map(|usage| usage.dummy_bytes).sum()
+    }
+
+    pub fn num_objects_short(&self) -> usize {
+        self.shortfall_by_size_bits.iter().sum()
+    }
+
+    pub fn free_ram_bytes(&self) -> u64 {
+        self.untypeds
+            .iter()
+            .filter(|usage| !usage.untyped.is_device)
+            .map(UntypedUsage::free_bytes)
+            .sum()
+    }
+}
+
+// NOTE
+// This must be kept in sync with `Initializer::create_objects()` in `sel4-capdl-initializer`.
+
+impl SpecForInitializer {
+    /// Replays the initializer's object allocation algorithm against `untypeds`.
+    pub fn simulate_allocation(
+        &self,
+        untypeds: &[SimulatedUntyped],
+        sizes: &ObjectSizes,
+    ) -> Result<AllocationReport, SimulationError> {
+        let mut uts_by_paddr = untypeds
+            .iter()
+            .map(|ut| {
+                ut.range()
+                    .map(|range| (*ut, range))
+                    .ok_or(SimulationError::UntypedTooLarge(*ut))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        uts_by_paddr.sort_unstable_by_key(|(ut, _)| ut.paddr);
+
+        let root_objects = &self.objects[ObjectId::into_usize_range(&self.root_objects)];
+        let root_objects_start = usize::from(self.root_objects.start);
+
+        let num_objs_with_paddr =
+            root_objects.partition_point(|named_obj| named_obj.object.paddr().is_some());
+
+        let obj_id = |i: usize| ObjectId::from(root_objects_start + i);
+
+        let size_of = |i: usize| {
+            let size_bits = sizes
+                .physical_size_bits(&root_objects[i].object)
+                .ok_or(SimulationError::NoSizeForObject(obj_id(i)))?;
+            if usize::from(size_bits) >= MAX_SIZE_BITS {
+                return Err(SimulationError::ObjectTooLarge(obj_id(i)));
+            }
+            Ok(size_bits)
+        };
+
+        let mut paddr_sizes = vec![];
+        let mut unsatisfiable_paddrs = vec![];
+        for (i, named_obj) in root_objects.iter().enumerate().take(num_objs_with_paddr) {
+            let size_bits = size_of(i)?;
+            paddr_sizes.push(size_bits);
+            let paddr = named_obj.object.paddr().unwrap().0;
+            let range = checked_range(paddr, size_bits)
+                .ok_or(SimulationError::ObjectTooLarge(obj_id(i)))?;
+            let covered = uts_by_paddr
+                .iter()
+                .any(|(_, ut_range)| ut_range.start <= range.start && ut_range.end >= range.end);
+            if !covered {
+                unsatisfiable_paddrs.push(obj_id(i));
+            }
+        }
+
+        let mut by_size: Vec<Vec<usize>> = vec![vec![]; MAX_SIZE_BITS];
+        for (i, named_obj) in root_objects.iter().enumerate().skip(num_objs_with_paddr) {
+            let obj = &named_obj.object;
+            let is_embedded = matches!(obj, Object::Frame(frame) if frame.init.is_embedded());
+            if !is_embedded && let Some(size_bits) = sizes.physical_size_bits(obj) {
+                by_size
+                    .get_mut(usize::from(size_bits))
+                    .ok_or(SimulationError::ObjectTooLarge(obj_id(i)))?
+                    .push(i);
+            }
+        }
+        let mut by_size_next = vec![0; MAX_SIZE_BITS];
+
+        let mut usages = vec![];
+        let mut next_obj_with_paddr = 0;
+        for (ut, ut_range) in uts_by_paddr.iter() {
+            let mut usage = UntypedUsage {
+                untyped: *ut,
+                num_objects: 0,
+                object_bytes: 0,
+                dummy_bytes: 0,
+            };
+            let mut cur_paddr = ut_range.start;
+            loop {
+                let target = if next_obj_with_paddr < num_objs_with_paddr {
+                    ut_range
+                        .end
+                        .min(root_objects[next_obj_with_paddr].object.paddr().unwrap().0)
+                } else {
+                    ut_range.end
+                };
+                let target_is_obj_with_paddr = target < ut_range.end;
+                while cur_paddr < target {
+                    let max_size_bits = {
+                        let alignment_bits = (cur_paddr - ut_range.start).trailing_zeros();
+                        let distance_bits = (target - cur_paddr).ilog2();
+                        usize::try_from(alignment_bits.min(distance_bits)).unwrap()
+                    };
+                    let mut created = false;
+                    if !ut.is_device {
+                        for size_bits in (0..=max_size_bits.min(MAX_SIZE_BITS - 1)).rev() {
+                            if by_size_next[size_bits] < by_size[size_bits].len() {
+                                cur_paddr += 1 << size_bits;
+                                by_size_next[size_bits] += 1;
+                                usage.num_objects += 1;
+                                usage.object_bytes += 1 << size_bits;
+                                created = true;
+                                break;
+                            }
+                        }
+                    }
+                    if !created {
+                        if target_is_obj_with_paddr {
+                            cur_paddr += 1 << max_size_bits;
+                            usage.dummy_bytes += 1 << max_size_bits;
+                        } else {
+                            cur_paddr = target;
+                        }
+                    }
+                }
+                if target_is_obj_with_paddr {
+                    let size_bits = paddr_sizes[next_obj_with_paddr];
+                    cur_paddr += 1 << size_bits;
+                    next_obj_with_paddr += 1;
+                    usage.num_objects += 1;
+                    usage.object_bytes += 1 << size_bits;
+                } else {
+                    break;
+                }
+            }
+            usages.push(usage);
+        }
+
+        let shortfall_by_size_bits = by_size
+            .iter()
+            .zip(by_size_next.iter())
+            .map(|(objs, next)| objs.len() - next)
+            .collect();
+
+        Ok(AllocationReport {
+            untypeds: usages,
+            unsatisfiable_paddrs,
+            shortfall_by_size_bits,
+            cslots: self.simulate_cslot_usage(),
+        })
+    }
+
+    fn simulate_cslot_usage(&self) -> CSlotUsage {
+        let mut usage = CSlotUsage {
+            orig: self
+                .cached_orig_cap_slots
+                .as_ref()
+                .map(|orig| orig.num_occupied.try_into().unwrap())
+                .unwrap_or(0),
+            hold: 2,
+            asid_pool_copies: self.asid_slots.len(),
+            ..Default::default()
+        };
+        for named_obj in self.objects.iter() {
+            match &named_obj.object {
+                Object::PageTable(obj) => {
+                    usage.mapping_copies += obj
+                        .slots
+                        .iter()
+                        .filter(|entry| matches!(entry.cap, Cap::Frame(_)))
+                        .count();
+                }
+                Object::IOPageTable(obj) => {
+                    usage.mapping_copies += obj
+                        .slots
+                        .iter()
+                        .filter(|entry| matches!(entry.cap, Cap::Frame(_)))
+                        .count();
+                }
+                Object::Irq(_)
+                | Object::ArmIrq(_)
+                | Object::IrqMsi(_)
+                | Object::IrqIOApic(_)
+                | Object::RiscvIrq(_) => {
+                    if let Some(Cap::Notification(cap)) = named_obj
+                        .object
+                        .slots()
+                        .unwrap()
+                        .iter()
+                        .find(|entry| entry.slot == object::Irq::SLOT_NOTIFICATION)
+                        .map(|entry| &entry.cap)
+                        && cap.badge.0 != 0
+                    {
+                        usage.badged_copies += 1;
+                    }
+                }
+                Object::Tcb(obj) => {
+                    if let Some(cap) = obj.mcs_fault_ep() {
+                        let all = cap.rights.read
+                            && cap.rights.write
+                            && cap.rights.grant
+                            && cap.rights.grant_reply;
+                        if cap.badge.0 != 0 || !all {
+                            usage.badged_copies += 1;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        usage
+    }
+}
+
+#[cfg(all(test, feature = "transform"))]
+mod test {
+    use alloc::vec;
+
+    use super::*;
+    use crate::{Codec, InputSpec};
+
+    const SPEC: &str = "
+        objects {
+          mmio = frame (4k, paddr: 0x9001000)
+          tcb = tcb (addr: 0x1000, ip: 0x0, sp: 0x0, prio: 0, max_prio: 0)
+          cnode = cnode (2 bits)
+          vspace = pt (level: 0, root)
+          pt = pt (level: 1)
+          ipc = frame (4k)
+          ep = ep
+          ntfn = notification
+          pool = asid_pool (high: 0x0)
+        }
+        caps {
+          tcb {
+            cspace: cnode
+            vspace: vspace
+            ipc_buffer: ipc (RW)
+          }
+          cnode {
+            0x1: ep (RW)
+            0x2: ntfn (RW, badge: 0x1)
+          }
+          vspace {
+            0x0: pt
+          }
+          pt {
+            0x0: ipc (RW)
+            0x1: mmio (RW, uncached)
+          }
+        }
+        asid slots {
+          pool
+        }
+    ";
+
+    const SIZES: ObjectSizes = ObjectSizes {
+        slot_bits: 5,
+        tcb_bits: 11,
+        endpoint_bits: 4,
+        notification_bits: 5,
+        page_table_bits: 12,
+        vspace_bits: 12,
+        asid_pool_bits: 12,
+        vcpu_bits: None,
+        io_page_table_bits: None,
+        reply_bits: None,
+        min_sched_context_bits: None,
+    };
+
+    const DEVICE: SimulatedUntyped = SimulatedUntyped {
+        paddr: 0x900_0000,
+        size_bits: 16,
+        is_device: true,
+    };
+
+    fn ram(size_bits: u8) -> SimulatedUntyped {
+        SimulatedUntyped {
+            paddr: 0x4000_0000,
+            size_bits,
+            is_device: false,
+        }
+    }
+
+    fn spec(src: &str) -> SpecForInitializer {
+        let (mut spec, _) =
+            InputSpec::from_cdl(src)
+                .unwrap()
+                .embed_fill(12, |_| false, |_, _| Codec::None);
+        spec.cache_orig_cap_slots();
+        spec
+    }
+
+    #[test]
+    fn footprint() {
+        let report = spec(SPEC)
+            .simulate_allocation(&[ram(16), DEVICE], &SIZES)
+            .unwrap();
+        assert!(report.is_ok());
+        assert_eq!(
+            report.untypeds,
+            vec![
+                UntypedUsage {
+                    untyped: DEVICE,
+                    num_objects: 1,
+                    object_bytes: 0x1000,
+                    dummy_bytes: 0x1000,
+                },
+                UntypedUsage {
+                    untyped: ram(16),
+                    num_objects: 8,
+                    object_bytes: 4 * 0x1000 + 0x800 + 0x80 + 0x20 + 0x10,
+                    dummy_bytes: 0,
+                },
+            ]
+        );
+        assert_eq!(report.dummy_bytes(), 0x1000);
+        assert_eq!(report.free_ram_bytes(), 0x1_0000 - 0x48b0);
+    }
+
+    #[test]
+    fn shortfall() {
+        let report = spec(SPEC)
+            .simulate_allocation(&[ram(13), DEVICE], &SIZES)
+            .unwrap();
+        assert!(!report.is_ok());
+        assert!(report.unsatisfiable_paddrs.is_empty());
+        assert_eq!(report.shortfall_by_size_bits[12], 2);
+        assert_eq!(report.num_objects_short(), 6);
+    }
+
+    #[test]
+    fn unsatisfiable_paddr() {
+        let report = spec(SPEC).simulate_allocation(&[ram(16)], &SIZES).unwrap();
+        assert!(!report.is_ok());
+        assert_eq!(report.unsatisfiable_paddrs, vec![ObjectId(0)]);
+    }
+
+    #[test]
+    fn cslots() {
+        let report = spec(SPEC)
+            .simulate_allocation(&[ram(16), DEVICE], &SIZES)
+            .unwrap();
+        assert_eq!(
+            report.cslots,
+            CSlotUsage {
+                orig: 9,
+                hold: 2,
+                mapping_copies: 2,
+                badged_copies: 0,
+                asid_pool_copies: 1,
+            }
+        );
+        assert_eq!(report.cslots.peak(), 14);
+    }
+
+    #[test]
+    fn too_large() {
+        let huge = SimulatedUntyped {
+            paddr: 0x1000,
+            size_bits: 64,
+            is_device: false,
+        };
+        assert_eq!(
+            spec(SPEC).simulate_allocation(&[huge], &SIZES),
+            Err(SimulationError::UntypedTooLarge(huge))
+        );
+        let spec = spec("objects { cnode = cnode (60 bits) }");
+        assert_eq!(
+            spec.simulate_allocation(&[ram(16)], &SIZES),
+            Err(SimulationError::ObjectTooLarge(ObjectId(0)))
+        );
+    }
+
+    #[cfg(feature = "kernel-config")]
+    #[test]
+    fn from_kernel_config() {
+        use alloc::string::ToString;
+
+        let config = |entries: &[(&str, Value)]| {
+            Configuration::new(
+                entries
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.clone()))
+                    .collect(),
+            )
+        };
+        let word_size = ("WORD_SIZE", Value::String("64".to_string()));
+        let arch = |arch: &str| ("SEL4_ARCH", Value::String(arch.to_string()));
+        assert_eq!(
+            ObjectSizes::from_kernel_config(&config(&[word_size.clone(), arch("aarch64")])),
+            Some(SIZES)
+        );
+        let mcs = ObjectSizes::from_kernel_config(&config(&[
+            word_size.clone(),
+            arch("riscv64"),
+            ("KERNEL_MCS", Value::Bool(true)),
+        ]))
+        .unwrap();
+        assert_eq!(mcs.tcb_bits, 10);
+        assert_eq!(mcs.notification_bits, 6);
+        assert_eq!(mcs.reply_bits, Some(5));
+        let word_size_32 = ("WORD_SIZE", Value::String("32".to_string()));
+        assert_eq!(
+            ObjectSizes::from_kernel_config(&config(&[
+                word_size_32.clone(),
+                arch("aarch32"),
+                ("HAVE_FPU", Value::Bool(true)),
+            ])),
+            Some(ObjectSizes {
+                slot_bits: 4,
+                tcb_bits: 10,
+                endpoint_bits: 4,
+                notification_bits: 4,
+                page_table_bits: 10,
+                vspace_bits: 14,
+                asid_pool_bits: 12,
+                vcpu_bits: None,
+                io_page_table_bits: None,
+                reply_bits: None,
+                min_sched_context_bits: None,
+            })
+        );
+        let riscv32_mcs = ObjectSizes::from_kernel_config(&config(&[
+            word_size_32.clone(),
+            arch("riscv32"),
+            ("KERNEL_MCS", Value::Bool(true)),
+        ]))
+        .unwrap();
+        assert_eq!(riscv32_mcs.tcb_bits, 9);
+        assert_eq!(riscv32_mcs.notification_bits, 5);
+        assert_eq!(riscv32_mcs.reply_bits, Some(4));
+        assert_eq!(riscv32_mcs.min_sched_context_bits, Some(7));
+        assert_eq!(
+            ObjectSizes::from_kernel_config(&config(&[word_size_32, arch("ia32")])),
+            None
+        );
+    }
+}
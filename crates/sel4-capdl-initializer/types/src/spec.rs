@@ -20,9 +20,10 @@ use sel4_capdl_initializer_types_derive::{HasCapTable, IsCap, IsObject};
 
 use crate::{HasArchivedCapTable, HasCapTable};
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 #[rkyv(derive(Debug, Copy, Clone, Eq, PartialEq))]
 pub struct ObjectId(pub u32);
 
@@ -65,6 +66,7 @@ impl ArchivedObjectId {
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 #[rkyv(derive(Debug, Copy, Clone, Eq, PartialEq))]
 pub struct CapSlot(pub u32);
 
@@ -95,6 +97,7 @@ impl From<ArchivedCapSlot> for usize {
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub struct CapTableEntry {
     pub slot: CapSlot,
     pub cap: Cap,
@@ -103,6 +106,7 @@ pub struct CapTableEntry {
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub struct Spec<D> {
     pub objects: Vec<NamedObject<D>>,
     pub irqs: Vec<IrqEntry>,
@@ -116,11 +120,15 @@ pub struct Spec<D> {
     pub cached_orig_cap_slots: Option<OrigCapSlots>,
     #[cfg_attr(feature = "serde", serde(default))]
     pub log_level: Option<u8>,
+    /// Frame into which the initializer writes its measurement log
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub measurement_log: Option<ObjectId>,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 #[rkyv(derive(Debug, Copy, Clone, Eq, PartialEq))]
 pub struct Word(pub u64);
 
@@ -139,6 +147,7 @@ impl From<Word> for u64 {
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub struct IrqEntry {
     pub irq: Word,
     pub handler: ObjectId,
@@ -147,6 +156,7 @@ pub struct IrqEntry {
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub enum DomainSchedDuration {
     Ticks(NonZero<u64>),
     Us(NonZero<u64>),
@@ -156,6 +166,7 @@ pub enum DomainSchedDuration {
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub struct DomainSchedEntry {
     pub domain: u8,
     pub duration: DomainSchedDuration,
@@ -166,6 +177,7 @@ pub type AsidSlotEntry = ObjectId;
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub struct UntypedCover {
     pub parent: ObjectId,
     pub children: Range<ObjectId>,
@@ -174,6 +186,7 @@ pub struct UntypedCover {
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub struct OrigCapSlots {
     pub num_occupied: u32,
     pub offsets_by_object: Vec<Option<u32>>,
@@ -182,6 +195,7 @@ pub struct OrigCapSlots {
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub struct NamedObject<D> {
     pub name: Option<String>,
     pub object: Object<D>,
@@ -190,6 +204,7 @@ pub struct NamedObject<D> {
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub enum Object<D> {
     Untyped(object::Untyped),
     Endpoint,
@@ -232,6 +247,32 @@ impl<D> Object<D> {
         }
     }
 
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Untyped(_) => "Untyped",
+            Self::Endpoint => "Endpoint",
+            Self::Notification => "Notification",
+            Self::CNode(_) => "CNode",
+            Self::Tcb(_) => "Tcb",
+            Self::Irq(_) => "Irq",
+            Self::VCpu => "VCpu",
+            Self::Frame(_) => "Frame",
+            Self::PageTable(_) => "PageTable",
+            Self::AsidPool(_) => "AsidPool",
+            Self::IOSpace(_) => "IOSpace",
+            Self::IOPageTable(_) => "IOPageTable",
+            Self::ArmIrq(_) => "ArmIrq",
+            Self::IrqMsi(_) => "IrqMsi",
+            Self::IrqIOApic(_) => "IrqIOApic",
+            Self::RiscvIrq(_) => "RiscvIrq",
+            Self::IOPorts(_) => "IOPorts",
+            Self::SchedContext(_) => "SchedContext",
+            Self::Reply => "Reply",
+            Self::ArmSmc => "ArmSmc",
+            Self::DomainSet => "DomainSet",
+        }
+    }
+
     pub fn slots(&self) -> Option<&[CapTableEntry]> {
         Some(match self {
             Self::CNode(obj) => obj.slots(),
@@ -239,6 +280,7 @@ impl<D> Object<D> {
             Self::PageTable(obj) => obj.slots(),
             Self::IOSpace(obj) => obj.slots(),
             Self::IOPageTable(obj) => obj.slots(),
+            Self::Irq(obj) => obj.slots(),
             Self::ArmIrq(obj) => obj.slots(),
             Self::IrqMsi(obj) => obj.slots(),
             Self::IrqIOApic(obj) => obj.slots(),
@@ -254,6 +296,7 @@ impl<D> Object<D> {
             Self::PageTable(obj) => &mut obj.slots,
             Self::IOSpace(obj) => &mut obj.slots,
             Self::IOPageTable(obj) => &mut obj.slots,
+            Self::Irq(obj) => &mut obj.slots,
             Self::ArmIrq(obj) => &mut obj.slots,
             Self::IrqMsi(obj) => &mut obj.slots,
             Self::IrqIOApic(obj) => &mut obj.slots,
@@ -284,6 +327,7 @@ impl<D: Archive> ArchivedObject<D> {
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub enum Cap {
     Untyped(cap::Untyped),
     Endpoint(cap::Endpoint),
@@ -318,6 +362,49 @@ impl Cap {
         T::try_from_cap(self)
     }
 
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Untyped(_) => "Untyped",
+            Self::Endpoint(_) => "Endpoint",
+            Self::Notification(_) => "Notification",
+            Self::CNode(_) => "CNode",
+            Self::Tcb(_) => "Tcb",
+            Self::IrqHandler(_) => "IrqHandler",
+            Self::VCpu(_) => "VCpu",
+            Self::Frame(_) => "Frame",
+            Self::PageTable(_) => "PageTable",
+            Self::AsidPool(_) => "AsidPool",
+            Self::IOSpace(_) => "IOSpace",
+            Self::IOPageTable(_) => "IOPageTable",
+            Self::ArmIrqHandler(_) => "ArmIrqHandler",
+            Self::IrqMsiHandler(_) => "IrqMsiHandler",
+            Self::IrqIOApicHandler(_) => "IrqIOApicHandler",
+            Self::RiscvIrqHandler(_) => "RiscvIrqHandler",
+            Self::IOPorts(_) => "IOPorts",
+            Self::SchedContext(_) => "SchedContext",
+            Self::Reply(_) => "Reply",
+            Self::ArmSmc(_) => "ArmSmc",
+            Self::DomainSet(_) => "DomainSet",
+        }
+    }
+
+    pub fn rights(&self) -> Option<&Rights> {
+        match self {
+            Self::Endpoint(cap) => Some(&cap.rights),
+            Self::Notification(cap) => Some(&cap.rights),
+            Self::Frame(cap) => Some(&cap.rights),
+            _ => None,
+        }
+    }
+
+    pub fn badge(&self) -> Option<Word> {
+        match self {
+            Self::Endpoint(cap) => Some(cap.badge),
+            Self::Notification(cap) => Some(cap.badge),
+            _ => None,
+        }
+    }
+
     pub fn obj(&self) -> ObjectId {
         match self {
             Self::Untyped(cap) => cap.object,
@@ -413,6 +500,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq, IsObject)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct Untyped {
         pub size_bits: u8,
         pub paddr: Option<Word>,
@@ -421,6 +509,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq, IsObject, HasCapTable)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct CNode {
         pub size_bits: u8,
         pub slots: Vec<CapTableEntry>,
@@ -429,6 +518,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq, IsObject, HasCapTable)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct Tcb {
         pub slots: Vec<CapTableEntry>,
         pub extra: Box<TcbExtraInfo>,
@@ -437,6 +527,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct TcbExtraInfo {
         pub ipc_buffer_addr: Word,
 
@@ -458,6 +549,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq, IsObject, HasCapTable)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct Irq {
         pub slots: Vec<CapTableEntry>,
     }
@@ -465,6 +557,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq, IsObject)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct Frame<D> {
         pub size_bits: u8,
         pub paddr: Option<Word>,
@@ -474,6 +567,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq, IsObject, HasCapTable)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct PageTable {
         #[cfg_attr(feature = "serde", serde(default))]
         pub x86_ept: bool,
@@ -485,6 +579,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq, IsObject)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct AsidPool {
         pub high: Word,
     }
@@ -492,6 +587,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq, IsObject, HasCapTable)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct IOSpace {
         pub slots: Vec<CapTableEntry>,
         pub domain_id: Word,
@@ -501,6 +597,7 @@ pub mod object {
     #[derive(Debug, Clone, Copy, Eq, PartialEq)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct PCIDevice {
         pub bus: u8,
         pub device: u8,
@@ -517,6 +614,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq, IsObject, HasCapTable)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct IOPageTable {
         pub slots: Vec<CapTableEntry>,
         pub level: Word,
@@ -525,6 +623,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq, IsObject, HasCapTable)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct ArmIrq {
         pub slots: Vec<CapTableEntry>,
         pub extra: Box<ArmIrqExtraInfo>,
@@ -533,6 +632,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct ArmIrqExtraInfo {
         pub trigger: u8,
         pub target: Word,
@@ -541,6 +641,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq, IsObject, HasCapTable)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct IrqMsi {
         pub slots: Vec<CapTableEntry>,
         pub extra: Box<IrqMsiExtraInfo>,
@@ -549,6 +650,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct IrqMsiExtraInfo {
         pub handle: Word,
         pub pci_bus: Word,
@@ -559,6 +661,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq, IsObject, HasCapTable)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct IrqIOApic {
         pub slots: Vec<CapTableEntry>,
         pub extra: Box<IrqIOApicExtraInfo>,
@@ -567,6 +670,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct IrqIOApicExtraInfo {
         pub ioapic: Word,
         pub pin: Word,
@@ -577,6 +681,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq, IsObject, HasCapTable)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct RiscvIrq {
         pub slots: Vec<CapTableEntry>,
         pub extra: RiscvIrqExtraInfo,
@@ -585,6 +690,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct RiscvIrqExtraInfo {
         pub trigger: u8,
     }
@@ -592,6 +698,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq, IsObject)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct IOPorts {
         pub start_port: Word,
         pub end_port: Word,
@@ -600,6 +707,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq, IsObject)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct SchedContext {
         pub size_bits: u8,
         pub extra: SchedContextExtraInfo,
@@ -608,6 +716,7 @@ pub mod object {
     #[derive(Debug, Clone, Eq, PartialEq)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct SchedContextExtraInfo {
         pub period: u64,
         pub budget: u64,
@@ -619,6 +728,7 @@ pub mod object {
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(rkyv::Archive, rkyv::Serialize)]
+#[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
 pub struct Rights {
     pub read: bool,
     pub write: bool,
@@ -632,6 +742,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct Untyped {
         pub object: ObjectId,
     }
@@ -639,6 +750,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct Endpoint {
         pub object: ObjectId,
         // TODO
@@ -653,6 +765,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct Notification {
         pub object: ObjectId,
         pub badge: Word,
@@ -662,6 +775,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct CNode {
         pub object: ObjectId,
         pub guard: Word,
@@ -671,6 +785,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct Tcb {
         pub object: ObjectId,
     }
@@ -678,6 +793,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct IrqHandler {
         pub object: ObjectId,
     }
@@ -685,6 +801,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct VCpu {
         pub object: ObjectId,
     }
@@ -692,6 +809,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct Frame {
         pub object: ObjectId,
         pub rights: Rights,
@@ -702,6 +820,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct PageTable {
         pub object: ObjectId,
     }
@@ -709,6 +828,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct AsidPool {
         pub object: ObjectId,
     }
@@ -716,6 +836,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct IOSpace {
         pub object: ObjectId,
     }
@@ -723,6 +844,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct IOPageTable {
         pub object: ObjectId,
     }
@@ -730,6 +852,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct ArmIrqHandler {
         pub object: ObjectId,
     }
@@ -737,6 +860,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct IrqMsiHandler {
         pub object: ObjectId,
     }
@@ -744,6 +868,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct IrqIOApicHandler {
         pub object: ObjectId,
     }
@@ -751,6 +876,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct RiscvIrqHandler {
         pub object: ObjectId,
     }
@@ -758,6 +884,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct IOPorts {
         pub object: ObjectId,
     }
@@ -765,6 +892,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct SchedContext {
         pub object: ObjectId,
     }
@@ -772,6 +900,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct Reply {
         pub object: ObjectId,
     }
@@ -779,6 +908,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct ArmSmc {
         pub object: ObjectId,
     }
@@ -786,6 +916,7 @@ pub mod cap {
     #[derive(Debug, Clone, Eq, PartialEq, IsCap)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(rkyv::Archive, rkyv::Serialize)]
+    #[cfg_attr(feature = "rkyv-deserialize", derive(rkyv::Deserialize))]
     pub struct DomainSet {
         pub object: ObjectId,
     }
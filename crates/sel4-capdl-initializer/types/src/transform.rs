@@ -4,15 +4,15 @@
 // SPDX-License-Identifier: BSD-2-Clause
 //
 
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::vec;
 use alloc::vec::Vec;
 use core::convert::Infallible;
 use core::ops::Range;
 
 use crate::{
-    BytesContent, Content, DeflatedBytesContent, EmbeddedFrameIndex, Fill, FillEntry,
-    FillEntryContent, FrameInit, NamedObject, Object, ObjectId, OrigCapSlots, Spec,
-    SpecForInitializer, object,
+    Cap, Codec, Content, EmbeddedFrameIndex, Fill, FillEntry, FillEntryContent, FrameInit,
+    NamedObject, Object, ObjectId, OrigCapSlots, Spec, SpecForInitializer, object,
 };
 
 impl<D> Spec<Fill<D>> {
@@ -20,7 +20,7 @@ impl<D> Spec<Fill<D>> {
         &self,
         granule_size_bits: u8,
         should_embed: impl FnMut(&Fill<D>) -> bool,
-        mut f: impl FnMut(&D, &mut [u8]) -> bool,
+        mut f: impl FnMut(&D, &mut [u8]) -> Codec,
     ) -> (SpecForInitializer, Vec<Vec<u8>>) {
         self.embed_fill_fallible(granule_size_bits, should_embed, |x1, x2| Ok(f(x1, x2)))
             .unwrap_or_else(|absurdity: Infallible| match absurdity {})
@@ -30,7 +30,7 @@ impl<D> Spec<Fill<D>> {
         &self,
         granule_size_bits: u8,
         mut should_embed: impl FnMut(&Fill<D>) -> bool,
-        mut f: impl FnMut(&D, &mut [u8]) -> Result<bool, E>,
+        mut f: impl FnMut(&D, &mut [u8]) -> Result<Codec, E>,
     ) -> Result<(SpecForInitializer, Vec<Vec<u8>>), E> {
         let granule_size = 1 << granule_size_bits;
         let mut frame_data = vec![];
@@ -56,12 +56,8 @@ impl<D> Spec<Fill<D>> {
                         frame.init.traverse_fallible(|range, data| {
                             let length = (range.end - range.start).try_into().unwrap();
                             let mut buf = vec![0; length];
-                            let deflate = f(data, &mut buf)?;
-                            Ok(if deflate {
-                                Content::DeflatedBytes(DeflatedBytesContent::pack(&buf))
-                            } else {
-                                Content::Bytes(BytesContent::pack(&buf))
-                            })
+                            let codec = f(data, &mut buf)?;
+                            Ok(Content::pack(codec, &buf))
                         })?
                     })
                 },
@@ -127,6 +123,7 @@ impl<D> Spec<D> {
             untyped_covers: self.untyped_covers.clone(),
             cached_orig_cap_slots: self.cached_orig_cap_slots.clone(),
             log_level: self.log_level,
+            measurement_log: self.measurement_log,
         })
     }
 }
@@ -205,6 +202,80 @@ impl SpecForInitializer {
     pub fn set_log_level(&mut self, log_level: u8) {
         self.log_level = Some(log_level);
     }
+
+    /// Makes embedded frames with identical contents share a single copy in `frame_data`, and
+    /// returns the remaining frame data.
+    ///
+    /// An embedded frame is used directly as the frame object, so sharing is only sound for frames
+    /// that are never written to (see [`Self::writable_frames`]).
+    pub fn dedup_embedded_frames(&mut self, frame_data: &[Vec<u8>]) -> (Vec<Vec<u8>>, DedupStats) {
+        let writable = self.writable_frames();
+        let mut stats = DedupStats::default();
+        let mut deduped_frame_data = vec![];
+        let mut by_content = BTreeMap::<&[u8], u64>::new();
+        for (obj_id, named_obj) in self.objects.iter_mut().enumerate() {
+            let Object::Frame(object::Frame {
+                init: FrameInit::Embedded(embedded),
+                ..
+            }) = &mut named_obj.object
+            else {
+                continue;
+            };
+            stats.num_frames += 1;
+            let content = frame_data[usize::try_from(embedded.index).unwrap()].as_slice();
+            let shareable = !writable.contains(&ObjectId::from(obj_id));
+            if shareable {
+                stats.num_read_only_frames += 1;
+                if let Some(index) = by_content.get(content) {
+                    embedded.index = *index;
+                    stats.num_deduplicated += 1;
+                    continue;
+                }
+            }
+            let index = deduped_frame_data.len().try_into().unwrap();
+            deduped_frame_data.push(content.to_vec());
+            if shareable {
+                by_content.insert(content, index);
+            }
+            embedded.index = index;
+        }
+        (deduped_frame_data, stats)
+    }
+
+    /// Frames that may be written to after initialization, either through a cap in the spec or by
+    /// the kernel, which writes to a thread's IPC buffer regardless of the rights of the cap with
+    /// which it was configured.
+    fn writable_frames(&self) -> BTreeSet<ObjectId> {
+        self.objects
+            .iter()
+            .filter_map(|named_obj| {
+                let is_tcb = matches!(named_obj.object, Object::Tcb(_));
+                Some(
+                    named_obj
+                        .object
+                        .slots()?
+                        .iter()
+                        .filter_map(move |entry| match &entry.cap {
+                            Cap::Frame(cap)
+                                if cap.rights.write
+                                    || (is_tcb && entry.slot == object::Tcb::SLOT_IPC_BUFFER) =>
+                            {
+                                Some(cap.object)
+                            }
+                            _ => None,
+                        }),
+                )
+            })
+            .flatten()
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct DedupStats {
+    pub num_frames: usize,
+    pub num_read_only_frames: usize,
+    pub num_deduplicated: usize,
 }
 
 impl Object<FrameInit> {
@@ -216,3 +287,100 @@ impl Object<FrameInit> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::InputSpec;
+
+    const SPEC: &str = "
+        objects {
+          tcb = tcb (addr: 0x1000, ip: 0x0, sp: 0x0, prio: 0, max_prio: 0)
+          cnode = cnode (2 bits)
+          vspace = pt (level: 0, root)
+          ipc = frame (4k, fill: [{0 4096 CDL_FrameFill_FileData \"a\" 0}])
+          ro_a = frame (4k, fill: [{0 4096 CDL_FrameFill_FileData \"a\" 0}])
+          ro_b = frame (4k, fill: [{0 4096 CDL_FrameFill_FileData \"a\" 0}])
+          rw = frame (4k, fill: [{0 4096 CDL_FrameFill_FileData \"a\" 0}])
+        }
+        caps {
+          tcb {
+            cspace: cnode
+            vspace: vspace
+            ipc_buffer: ipc (R)
+          }
+          cnode {
+            0x0: ro_a (R)
+            0x1: ro_b (R)
+            0x2: rw (RW)
+          }
+        }
+    ";
+
+    fn embedded_index(spec: &SpecForInitializer, name: &str) -> u64 {
+        let obj = spec
+            .objects
+            .iter()
+            .find(|named_obj| named_obj.name.as_deref() == Some(name))
+            .unwrap();
+        match &obj.object {
+            Object::Frame(object::Frame {
+                init: FrameInit::Embedded(embedded),
+                ..
+            }) => embedded.index,
+            _ => panic!("{name} is not embedded"),
+        }
+    }
+
+    #[test]
+    fn dedup() {
+        let (mut spec, frame_data) = InputSpec::from_cdl(SPEC).unwrap().embed_fill(
+            12,
+            |_| true,
+            |_, buf| {
+                buf.fill(0x5a);
+                Codec::None
+            },
+        );
+        assert_eq!(frame_data.len(), 4);
+        let (deduped, stats) = spec.dedup_embedded_frames(&frame_data);
+        assert_eq!(
+            stats,
+            DedupStats {
+                num_frames: 4,
+                num_read_only_frames: 2,
+                num_deduplicated: 1,
+            }
+        );
+        assert_eq!(deduped.len(), 3);
+        assert_eq!(embedded_index(&spec, "ro_a"), embedded_index(&spec, "ro_b"));
+        let distinct = ["ipc", "ro_a", "rw"]
+            .map(|name| embedded_index(&spec, name))
+            .into_iter()
+            .collect::<BTreeSet<_>>();
+        assert_eq!(distinct.len(), 3);
+        for data in &deduped {
+            assert!(data.iter().all(|b| *b == 0x5a));
+        }
+    }
+
+    #[test]
+    fn writable_frames() {
+        let (spec, _) =
+            InputSpec::from_cdl(SPEC)
+                .unwrap()
+                .embed_fill(12, |_| true, |_, _| Codec::None);
+        let id = |name| {
+            ObjectId::from(
+                spec.objects
+                    .iter()
+                    .position(|named_obj| named_obj.name.as_deref() == Some(name))
+                    .unwrap(),
+            )
+        };
+        assert_eq!(
+            spec.writable_frames(),
+            BTreeSet::from([id("ipc"), id("rw")])
+        );
+    }
+}
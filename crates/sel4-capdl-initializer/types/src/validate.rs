@@ -0,0 +1,1111 @@
+//
+// Copyright 2023, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{
+    Cap, CapSlot, CapTableEntry, Fill, FrameInit, IrqEntry, NamedObject, Object, ObjectId, Spec,
+    object,
+};
+
+// NOTE
+// These checks mirror the assumptions that the initializer makes (often via `unwrap()` or
+// `panic!()`) while walking an archived spec on the target. Keep them in sync.
+
+impl<D: FrameContents> Spec<D> {
+    /// Checks the structural invariants that the initializer relies on.
+    ///
+    /// Every problem found is reported, rather than just the first.
+    pub fn validate(&self) -> Diagnostics {
+        let mut validator = Validator {
+            spec: self,
+            diagnostics: vec![],
+        };
+        validator.run();
+        Diagnostics {
+            inner: validator.diagnostics,
+        }
+    }
+}
+
+/// The initial contents of frames, as far as validation is concerned.
+pub trait FrameContents {
+    /// Whether the frame starts out zeroed.
+    fn is_empty(&self) -> bool;
+}
+
+impl<D> FrameContents for Fill<D> {
+    fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl FrameContents for FrameInit {
+    fn is_empty(&self) -> bool {
+        matches!(self, Self::Fill(fill) if fill.is_empty())
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Diagnostics {
+    inner: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn as_slice(&self) -> &[Diagnostic] {
+        &self.inner
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.inner.iter().filter(|d| d.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.inner.iter().filter(|d| d.severity == Severity::Warning)
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub location: Location,
+    pub kind: DiagnosticKind,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Location {
+    Spec,
+    Object(ObjectRef),
+    Slot(ObjectRef, CapSlot),
+    Irq(u64),
+    UntypedCover(usize),
+    AsidSlot(usize),
+    MeasurementLog,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ObjectRef {
+    pub id: ObjectId,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DiagnosticKind {
+    DanglingObjectId(ObjectId),
+    CapObjectTypeMismatch {
+        cap: &'static str,
+        object: ObjectRef,
+        object_type: &'static str,
+    },
+    UnexpectedObjectType {
+        expected: &'static str,
+        object: ObjectRef,
+        object_type: &'static str,
+    },
+    UnexpectedCapType {
+        expected: &'static str,
+        found: &'static str,
+    },
+    DuplicateSlot,
+    SlotOutOfBounds {
+        size_bits: u8,
+    },
+    MissingRequiredSlot {
+        name: &'static str,
+    },
+    FrameMappedTwice,
+    PageTableMappedMoreThanOnce {
+        first_parent: ObjectRef,
+    },
+    RootPageTableMappedAsChild,
+    DuplicateIrq,
+    IrqHandlerWithoutIrq,
+    RootObjectsOutOfBounds,
+    RootObjectsWithPaddrNotFirst,
+    UntypedCoverOverlapsRootObjects,
+    ObjectNotCreated,
+    UnreferencedObject,
+    DuplicateName {
+        first: ObjectId,
+    },
+    MeasurementLogNotGranule {
+        size_bits: u8,
+    },
+    MeasurementLogNotEmpty,
+}
+
+const TCB_SLOTS: [(CapSlot, &str, &str, bool); 9] = [
+    (object::Tcb::SLOT_CSPACE, "cspace", "CNode", true),
+    (object::Tcb::SLOT_VSPACE, "vspace", "PageTable", true),
+    (object::Tcb::SLOT_IPC_BUFFER, "ipc_buffer", "Frame", true),
+    (object::Tcb::SLOT_FAULT_EP, "fault_ep", "Endpoint", false),
+    (object::Tcb::SLOT_SC, "sc", "SchedContext", false),
+    (object::Tcb::SLOT_TEMP_FAULT_EP, "temp_fault_ep", "Endpoint", false),
+    (object::Tcb::SLOT_BOUND_NOTIFICATION, "bound_notification", "Notification", false),
+    (object::Tcb::SLOT_VCPU, "vcpu", "VCpu", false),
+    (object::Tcb::SLOT_X86_EPTPML4, "x86_eptpml4", "PageTable", false),
+];
+
+const GRANULE_SIZE_BITS: u8 = 12;
+
+struct Validator<'a, D> {
+    spec: &'a Spec<D>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<D: FrameContents> Validator<'_, D> {
+    fn run(&mut self) {
+        self.check_root_objects();
+        self.check_cap_tables();
+        self.check_tcbs();
+        self.check_page_tables();
+        self.check_irqs();
+        self.check_untyped_covers();
+        self.check_created();
+        self.check_asid_slots();
+        self.check_measurement_log();
+        self.check_names();
+        self.check_unreferenced();
+    }
+
+    fn check_root_objects(&mut self) {
+        let root_objects = ObjectId::into_usize_range(&self.spec.root_objects);
+        if root_objects.start > root_objects.end || root_objects.end > self.spec.objects.len() {
+            self.error(Location::Spec, DiagnosticKind::RootObjectsOutOfBounds);
+            return;
+        }
+        // The initializer finds the root objects with paddrs using a partition point.
+        let objs = &self.spec.objects[root_objects.clone()];
+        let first_without_paddr = objs
+            .iter()
+            .position(|named_obj| named_obj.object.paddr().is_none())
+            .unwrap_or(objs.len());
+        for (i, named_obj) in objs.iter().enumerate().skip(first_without_paddr) {
+            if named_obj.object.paddr().is_some() {
+                let location = Location::Object(self.object_ref((root_objects.start + i).into()));
+                self.error(location, DiagnosticKind::RootObjectsWithPaddrNotFirst);
+            }
+        }
+    }
+
+    fn check_cap_tables(&mut self) {
+        for (obj_id, named_obj) in self.spec.objects.iter().enumerate() {
+            let Some(slots) = named_obj.object.slots() else {
+                continue;
+            };
+            let obj_id = ObjectId::from(obj_id);
+            let mut seen = BTreeSet::new();
+            for entry in slots {
+                let location = Location::Slot(self.object_ref(obj_id), entry.slot);
+                if !seen.insert(entry.slot.0) {
+                    let kind = if matches!(named_obj.object, Object::PageTable(_)) {
+                        DiagnosticKind::FrameMappedTwice
+                    } else {
+                        DiagnosticKind::DuplicateSlot
+                    };
+                    self.error(location.clone(), kind);
+                }
+                if let Object::CNode(cnode) = &named_obj.object
+                    && u64::from(entry.slot.0)
+                        .checked_shr(cnode.size_bits.into())
+                        .unwrap_or(0)
+                        != 0
+                {
+                    self.error(
+                        location.clone(),
+                        DiagnosticKind::SlotOutOfBounds {
+                            size_bits: cnode.size_bits,
+                        },
+                    );
+                }
+                self.check_cap(location, &entry.cap);
+            }
+        }
+    }
+
+    fn check_cap(&mut self, location: Location, cap: &Cap) {
+        let Some(target) = self.lookup(cap.obj()) else {
+            self.error(location, DiagnosticKind::DanglingObjectId(cap.obj()));
+            return;
+        };
+        if !cap_matches_object(cap, &target.object) {
+            let object = self.object_ref(cap.obj());
+            self.error(
+                location,
+                DiagnosticKind::CapObjectTypeMismatch {
+                    cap: cap.type_name(),
+                    object,
+                    object_type: target.object.type_name(),
+                },
+            );
+        }
+    }
+
+    fn check_tcbs(&mut self) {
+        for (obj_id, named_obj) in self.spec.objects.iter().enumerate() {
+            let Object::Tcb(tcb) = &named_obj.object else {
+                continue;
+            };
+            let obj_id = ObjectId::from(obj_id);
+            for (slot, name, expected, required) in TCB_SLOTS {
+                match find_slot(&tcb.slots, slot) {
+                    None if required => {
+                        self.error(
+                            Location::Object(self.object_ref(obj_id)),
+                            DiagnosticKind::MissingRequiredSlot { name },
+                        );
+                    }
+                    None => {}
+                    Some(cap) if cap.type_name() != expected => {
+                        self.error(
+                            Location::Slot(self.object_ref(obj_id), slot),
+                            DiagnosticKind::UnexpectedCapType {
+                                expected,
+                                found: cap.type_name(),
+                            },
+                        );
+                    }
+                    Some(cap) => {
+                        if slot == object::Tcb::SLOT_VSPACE
+                            && let Some(Object::PageTable(pt)) =
+                                self.lookup(cap.obj()).map(|o| &o.object)
+                            && !pt.is_root
+                        {
+                            let object = self.object_ref(cap.obj());
+                            self.error(
+                                Location::Slot(self.object_ref(obj_id), slot),
+                                DiagnosticKind::UnexpectedObjectType {
+                                    expected: "root PageTable",
+                                    object,
+                                    object_type: "non-root PageTable",
+                                },
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_page_tables(&mut self) {
+        let mut parents: BTreeMap<u32, ObjectId> = BTreeMap::new();
+        for (obj_id, named_obj) in self.spec.objects.iter().enumerate() {
+            let obj_id = ObjectId::from(obj_id);
+            let (slots, expected) = match &named_obj.object {
+                Object::PageTable(obj) => (&obj.slots, "PageTable or Frame"),
+                Object::IOPageTable(obj) => (&obj.slots, "IOPageTable or Frame"),
+                _ => continue,
+            };
+            for entry in slots {
+                let location = Location::Slot(self.object_ref(obj_id), entry.slot);
+                let is_child_table = match (&named_obj.object, &entry.cap) {
+                    (_, Cap::Frame(_)) => false,
+                    (Object::PageTable(_), Cap::PageTable(_))
+                    | (Object::IOPageTable(_), Cap::IOPageTable(_)) => true,
+                    _ => {
+                        self.error(
+                            location,
+                            DiagnosticKind::UnexpectedCapType {
+                                expected,
+                                found: entry.cap.type_name(),
+                            },
+                        );
+                        continue;
+                    }
+                };
+                if !is_child_table {
+                    continue;
+                }
+                if let Some(Object::PageTable(child)) =
+                    self.lookup(entry.cap.obj()).map(|o| &o.object)
+                    && child.is_root
+                {
+                    self.error(location.clone(), DiagnosticKind::RootPageTableMappedAsChild);
+                }
+                if let Some(first_parent) = parents.insert(entry.cap.obj().0, obj_id) {
+                    let first_parent = self.object_ref(first_parent);
+                    self.error(
+                        location,
+                        DiagnosticKind::PageTableMappedMoreThanOnce { first_parent },
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_irqs(&mut self) {
+        let mut seen = BTreeSet::new();
+        let mut handlers = BTreeSet::new();
+        for IrqEntry { irq, handler } in self.spec.irqs.iter() {
+            let location = Location::Irq(irq.0);
+            if !seen.insert(irq.0) {
+                self.error(location.clone(), DiagnosticKind::DuplicateIrq);
+            }
+            handlers.insert(handler.0);
+            let Some(named_obj) = self.lookup(*handler) else {
+                self.error(location, DiagnosticKind::DanglingObjectId(*handler));
+                continue;
+            };
+            if !is_irq_object(&named_obj.object) {
+                let object = self.object_ref(*handler);
+                self.error(
+                    location,
+                    DiagnosticKind::UnexpectedObjectType {
+                        expected: "IRQ handler",
+                        object,
+                        object_type: named_obj.object.type_name(),
+                    },
+                );
+            }
+        }
+        for (obj_id, named_obj) in self.spec.objects.iter().enumerate() {
+            if !is_irq_object(&named_obj.object) {
+                continue;
+            }
+            let obj_id = ObjectId::from(obj_id);
+            if !handlers.contains(&obj_id.0) {
+                self.error(
+                    Location::Object(self.object_ref(obj_id)),
+                    DiagnosticKind::IrqHandlerWithoutIrq,
+                );
+            }
+            // All IRQ handler objects share the same slot layout.
+            let slot = object::Irq::SLOT_NOTIFICATION;
+            if let Some(cap) = find_slot(named_obj.object.slots().unwrap(), slot)
+                && !matches!(cap, Cap::Notification(_))
+            {
+                self.error(
+                    Location::Slot(self.object_ref(obj_id), slot),
+                    DiagnosticKind::UnexpectedCapType {
+                        expected: "Notification",
+                        found: cap.type_name(),
+                    },
+                );
+            }
+        }
+    }
+
+    fn check_untyped_covers(&mut self) {
+        let root_objects = ObjectId::into_usize_range(&self.spec.root_objects);
+        for (i, cover) in self.spec.untyped_covers.iter().enumerate() {
+            let location = Location::UntypedCover(i);
+            match self.lookup(cover.parent) {
+                None => {
+                    self.error(
+                        location.clone(),
+                        DiagnosticKind::DanglingObjectId(cover.parent),
+                    );
+                }
+                Some(named_obj) if !matches!(named_obj.object, Object::Untyped(_)) => {
+                    let object = self.object_ref(cover.parent);
+                    self.error(
+                        location.clone(),
+                        DiagnosticKind::UnexpectedObjectType {
+                            expected: "Untyped",
+                            object,
+                            object_type: named_obj.object.type_name(),
+                        },
+                    );
+                }
+                _ => {}
+            }
+            let children = ObjectId::into_usize_range(&cover.children);
+            if children.end > self.spec.objects.len() {
+                self.error(
+                    location.clone(),
+                    DiagnosticKind::DanglingObjectId(cover.children.end),
+                );
+            }
+            if children.start < root_objects.end && root_objects.start < children.end {
+                self.error(location, DiagnosticKind::UntypedCoverOverlapsRootObjects);
+            }
+        }
+    }
+
+    // The initializer only creates root objects and the children of untyped covers.
+    fn check_created(&mut self) {
+        let root_objects = ObjectId::into_usize_range(&self.spec.root_objects);
+        let mut created = vec![false; self.spec.objects.len()];
+        let covers = self.spec.untyped_covers.iter();
+        for range in [root_objects]
+            .into_iter()
+            .chain(covers.map(|cover| ObjectId::into_usize_range(&cover.children)))
+        {
+            let len = created.len();
+            if let Some(c) = created.get_mut(range.start.min(len)..range.end.min(len)) {
+                c.fill(true);
+            }
+        }
+        for (obj_id, c) in created.into_iter().enumerate() {
+            if !c {
+                self.error(
+                    Location::Object(self.object_ref(obj_id.into())),
+                    DiagnosticKind::ObjectNotCreated,
+                );
+            }
+        }
+    }
+
+    fn check_asid_slots(&mut self) {
+        for (i, obj_id) in self.spec.asid_slots.iter().enumerate() {
+            let location = Location::AsidSlot(i);
+            match self.lookup(*obj_id) {
+                None => self.error(location, DiagnosticKind::DanglingObjectId(*obj_id)),
+                Some(named_obj) if !matches!(named_obj.object, Object::AsidPool(_)) => {
+                    let object = self.object_ref(*obj_id);
+                    self.error(
+                        location,
+                        DiagnosticKind::UnexpectedObjectType {
+                            expected: "AsidPool",
+                            object,
+                            object_type: named_obj.object.type_name(),
+                        },
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // The initializer maps the measurement log frame in place of a single page, and relies on it
+    // starting out zeroed.
+    fn check_measurement_log(&mut self) {
+        let Some(obj_id) = self.spec.measurement_log else {
+            return;
+        };
+        let frame = match self.spec.objects.get(usize::from(obj_id)) {
+            None => {
+                self.error(
+                    Location::MeasurementLog,
+                    DiagnosticKind::DanglingObjectId(obj_id),
+                );
+                return;
+            }
+            Some(named_obj) => match &named_obj.object {
+                Object::Frame(frame) => frame,
+                _ => {
+                    let object = self.object_ref(obj_id);
+                    self.error(
+                        Location::MeasurementLog,
+                        DiagnosticKind::UnexpectedObjectType {
+                            expected: "Frame",
+                            object,
+                            object_type: named_obj.object.type_name(),
+                        },
+                    );
+                    return;
+                }
+            },
+        };
+        let location = Location::Object(self.object_ref(obj_id));
+        if frame.size_bits != GRANULE_SIZE_BITS {
+            self.error(
+                location.clone(),
+                DiagnosticKind::MeasurementLogNotGranule {
+                    size_bits: frame.size_bits,
+                },
+            );
+        }
+        if !frame.init.is_empty() {
+            self.error(location, DiagnosticKind::MeasurementLogNotEmpty);
+        }
+    }
+
+    fn check_names(&mut self) {
+        let mut first_by_name: BTreeMap<&str, ObjectId> = BTreeMap::new();
+        for (obj_id, named_obj) in self.spec.objects.iter().enumerate() {
+            let Some(name) = &named_obj.name else {
+                continue;
+            };
+            let obj_id = ObjectId::from(obj_id);
+            if let Some(first) = first_by_name.get(name.as_str()) {
+                let first = *first;
+                self.warning(
+                    Location::Object(self.object_ref(obj_id)),
+                    DiagnosticKind::DuplicateName { first },
+                );
+            } else {
+                first_by_name.insert(name, obj_id);
+            }
+        }
+    }
+
+    fn check_unreferenced(&mut self) {
+        let mut referenced = vec![false; self.spec.objects.len()];
+        let mut mark = |obj_id: ObjectId| {
+            if let Some(r) = referenced.get_mut(usize::from(obj_id)) {
+                *r = true;
+            }
+        };
+        for named_obj in self.spec.objects.iter() {
+            for entry in named_obj.object.slots().unwrap_or(&[]) {
+                mark(entry.cap.obj());
+            }
+        }
+        for entry in self.spec.irqs.iter() {
+            mark(entry.handler);
+        }
+        for obj_id in self.spec.asid_slots.iter() {
+            mark(*obj_id);
+        }
+        for cover in self.spec.untyped_covers.iter() {
+            mark(cover.parent);
+        }
+        for (obj_id, named_obj) in self.spec.objects.iter().enumerate() {
+            // TCBs and root page tables are the roots of the authority graph.
+            let is_root = match &named_obj.object {
+                Object::Tcb(_) => true,
+                Object::PageTable(obj) => obj.is_root,
+                Object::IOSpace(_) => true,
+                _ => false,
+            };
+            if !referenced[obj_id] && !is_root {
+                self.warning(
+                    Location::Object(self.object_ref(obj_id.into())),
+                    DiagnosticKind::UnreferencedObject,
+                );
+            }
+        }
+    }
+
+    fn lookup(&self, obj_id: ObjectId) -> Option<&NamedObject<D>> {
+        self.spec.objects.get(usize::from(obj_id))
+    }
+
+    fn object_ref(&self, obj_id: ObjectId) -> ObjectRef {
+        ObjectRef {
+            id: obj_id,
+            name: self
+                .lookup(obj_id)
+                .and_then(|named_obj| named_obj.name.clone()),
+        }
+    }
+
+    fn error(&mut self, location: Location, kind: DiagnosticKind) {
+        self.push(Severity::Error, location, kind)
+    }
+
+    fn warning(&mut self, location: Location, kind: DiagnosticKind) {
+        self.push(Severity::Warning, location, kind)
+    }
+
+    fn push(&mut self, severity: Severity, location: Location, kind: DiagnosticKind) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            location,
+            kind,
+        })
+    }
+}
+
+fn find_slot(slots: &[CapTableEntry], slot: CapSlot) -> Option<&Cap> {
+    slots
+        .iter()
+        .find(|entry| entry.slot == slot)
+        .map(|entry| &entry.cap)
+}
+
+fn is_irq_object<D>(obj: &Object<D>) -> bool {
+    matches!(
+        obj,
+        Object::Irq(_)
+            | Object::ArmIrq(_)
+            | Object::IrqMsi(_)
+            | Object::IrqIOApic(_)
+            | Object::RiscvIrq(_)
+    )
+}
+
+fn cap_matches_object<D>(cap: &Cap, obj: &Object<D>) -> bool {
+    matches!(
+        (cap, obj),
+        (Cap::Untyped(_), Object::Untyped(_))
+            | (Cap::Endpoint(_), Object::Endpoint)
+            | (Cap::Notification(_), Object::Notification)
+            | (Cap::CNode(_), Object::CNode(_))
+            | (Cap::Tcb(_), Object::Tcb(_))
+            | (Cap::IrqHandler(_), Object::Irq(_))
+            | (Cap::VCpu(_), Object::VCpu)
+            | (Cap::Frame(_), Object::Frame(_))
+            | (Cap::PageTable(_), Object::PageTable(_))
+            | (Cap::AsidPool(_), Object::AsidPool(_))
+            | (Cap::IOSpace(_), Object::IOSpace(_))
+            | (Cap::IOPageTable(_), Object::IOPageTable(_))
+            | (Cap::ArmIrqHandler(_), Object::ArmIrq(_))
+            | (Cap::IrqMsiHandler(_), Object::IrqMsi(_))
+            | (Cap::IrqIOApicHandler(_), Object::IrqIOApic(_))
+            | (Cap::RiscvIrqHandler(_), Object::RiscvIrq(_))
+            | (Cap::IOPorts(_), Object::IOPorts(_))
+            | (Cap::SchedContext(_), Object::SchedContext(_))
+            | (Cap::Reply(_), Object::Reply)
+            | (Cap::ArmSmc(_), Object::ArmSmc)
+            | (Cap::DomainSet(_), Object::DomainSet)
+    )
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for diagnostic in self.inner.iter() {
+            writeln!(f, "{diagnostic}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.severity, self.location, self.kind)
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+impl fmt::Display for ObjectRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "'{}' (#{})", name, self.id.0),
+            None => write!(f, "<unnamed> (#{})", self.id.0),
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Spec => write!(f, "spec"),
+            Self::Object(obj) => write!(f, "object {obj}"),
+            Self::Slot(obj, slot) => write!(f, "object {obj}, slot {}", slot.0),
+            Self::Irq(irq) => write!(f, "irq {irq}"),
+            Self::UntypedCover(i) => write!(f, "untyped cover #{i}"),
+            Self::AsidSlot(i) => write!(f, "asid slot #{i}"),
+            Self::MeasurementLog => write!(f, "measurement log"),
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::DanglingObjectId(obj_id) => {
+                write!(f, "reference to nonexistent object #{}", obj_id.0)
+            }
+            Self::CapObjectTypeMismatch {
+                cap,
+                object,
+                object_type,
+            } => write!(
+                f,
+                "{cap} cap refers to {object}, which has type {object_type}"
+            ),
+            Self::UnexpectedObjectType {
+                expected,
+                object,
+                object_type,
+            } => write!(
+                f,
+                "expected {expected}, but {object} has type {object_type}"
+            ),
+            Self::UnexpectedCapType { expected, found } => {
+                write!(f, "expected {expected} cap, found {found} cap")
+            }
+            Self::DuplicateSlot => write!(f, "slot occupied more than once"),
+            Self::SlotOutOfBounds { size_bits } => {
+                write!(
+                    f,
+                    "slot out of bounds for CNode with size_bits = {size_bits}"
+                )
+            }
+            Self::MissingRequiredSlot { name } => write!(f, "missing required '{name}' slot"),
+            Self::FrameMappedTwice => {
+                write!(f, "more than one mapping at the same virtual address")
+            }
+            Self::PageTableMappedMoreThanOnce { first_parent } => {
+                write!(f, "page table already mapped by {first_parent}")
+            }
+            Self::RootPageTableMappedAsChild => {
+                write!(f, "root page table mapped into another page table")
+            }
+            Self::DuplicateIrq => write!(f, "IRQ handled more than once"),
+            Self::IrqHandlerWithoutIrq => write!(f, "IRQ handler object not in spec IRQ list"),
+            Self::RootObjectsOutOfBounds => write!(f, "root objects range out of bounds"),
+            Self::RootObjectsWithPaddrNotFirst => write!(
+                f,
+                "root object with paddr follows a root object without a paddr"
+            ),
+            Self::UntypedCoverOverlapsRootObjects => {
+                write!(f, "untyped cover children overlap root objects")
+            }
+            Self::ObjectNotCreated => write!(
+                f,
+                "object is neither a root object nor covered by an untyped, so it is never created"
+            ),
+            Self::UnreferencedObject => write!(f, "object is never referenced"),
+            Self::DuplicateName { first } => {
+                write!(f, "name already used by object #{}", first.0)
+            }
+            Self::MeasurementLogNotGranule { size_bits } => write!(
+                f,
+                "measurement log frame must be 4K, but has size_bits = {size_bits}"
+            ),
+            Self::MeasurementLogNotEmpty => {
+                write!(f, "measurement log frame must not have any fill")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::format;
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::{InputSpec, Rights, UntypedCover, Word, cap};
+
+    const BASE: &str = "
+        objects {
+          tcb = tcb (addr: 0x1000, ip: 0x0, sp: 0x0, prio: 0, max_prio: 0)
+          cnode = cnode (2 bits)
+          vspace = pt (level: 0, root)
+          pt = pt (level: 1)
+          ipc = frame (4k)
+          ep = ep
+        }
+        caps {
+          tcb {
+            cspace: cnode
+            vspace: vspace
+            ipc_buffer: ipc (RW)
+          }
+          cnode {
+            0x1: ep (RW)
+          }
+          vspace {
+            0x0: pt
+          }
+          pt {
+            0x0: ipc (RW)
+          }
+        }
+    ";
+
+    fn parse(src: &str) -> InputSpec {
+        InputSpec::from_cdl(src).unwrap()
+    }
+
+    fn base() -> InputSpec {
+        parse(BASE)
+    }
+
+    fn id(spec: &InputSpec, name: &str) -> ObjectId {
+        spec.objects
+            .iter()
+            .position(|named_obj| named_obj.name.as_deref() == Some(name))
+            .unwrap()
+            .into()
+    }
+
+    fn slots<'a>(spec: &'a mut InputSpec, name: &str) -> &'a mut Vec<CapTableEntry> {
+        let obj_id = id(spec, name);
+        spec.objects[usize::from(obj_id)]
+            .object
+            .slots_mut()
+            .unwrap()
+    }
+
+    /// Adds a copy of the cap in the first slot of `cnode`, referring to `obj_id`, in slot 2.
+    fn add_cap(spec: &mut InputSpec, obj_id: ObjectId) -> &mut Cap {
+        let slots = slots(spec, "cnode");
+        let mut entry = slots[0].clone();
+        entry.slot = CapSlot(2);
+        entry.cap.set_obj(obj_id);
+        slots.push(entry);
+        &mut slots.last_mut().unwrap().cap
+    }
+
+    fn kinds(spec: &InputSpec) -> Vec<(Severity, DiagnosticKind)> {
+        spec.validate()
+            .as_slice()
+            .iter()
+            .map(|d| (d.severity, d.kind.clone()))
+            .collect()
+    }
+
+    fn assert_only_error(spec: &InputSpec, check: impl Fn(&DiagnosticKind) -> bool) {
+        let kinds = kinds(spec);
+        assert!(
+            matches!(kinds.as_slice(), [(Severity::Error, kind)] if check(kind)),
+            "{kinds:?}"
+        );
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(kinds(&base()), []);
+    }
+
+    #[test]
+    fn dangling_object_id() {
+        let mut spec = base();
+        add_cap(&mut spec, ObjectId(100));
+        assert_only_error(&spec, |kind| {
+            *kind == DiagnosticKind::DanglingObjectId(ObjectId(100))
+        });
+    }
+
+    #[test]
+    fn cap_object_type_mismatch() {
+        let mut spec = base();
+        let cnode = id(&spec, "cnode");
+        add_cap(&mut spec, cnode);
+        assert_only_error(&spec, |kind| {
+            matches!(
+                kind,
+                DiagnosticKind::CapObjectTypeMismatch {
+                    cap: "Endpoint",
+                    object_type: "CNode",
+                    ..
+                }
+            )
+        });
+    }
+
+    #[test]
+    fn unexpected_object_type() {
+        let spec = parse(&BASE.replace("vspace: vspace", "vspace: pt"));
+        let kinds = kinds(&spec);
+        assert!(
+            kinds.iter().any(|(_, kind)| matches!(
+                kind,
+                DiagnosticKind::UnexpectedObjectType {
+                    expected: "root PageTable",
+                    ..
+                }
+            )),
+            "{kinds:?}"
+        );
+    }
+
+    #[test]
+    fn unexpected_cap_type() {
+        let spec = parse(&BASE.replace("ipc_buffer: ipc (RW)", "ipc_buffer: ep"));
+        assert!(kinds(&spec).contains(&(
+            Severity::Error,
+            DiagnosticKind::UnexpectedCapType {
+                expected: "Frame",
+                found: "Endpoint",
+            }
+        )));
+    }
+
+    #[test]
+    fn duplicate_slot() {
+        let mut spec = base();
+        let slots = slots(&mut spec, "cnode");
+        slots.push(slots[0].clone());
+        assert_only_error(&spec, |kind| *kind == DiagnosticKind::DuplicateSlot);
+    }
+
+    #[test]
+    fn slot_out_of_bounds() {
+        let mut spec = base();
+        slots(&mut spec, "cnode")[0].slot = CapSlot(4);
+        assert_only_error(&spec, |kind| {
+            *kind == DiagnosticKind::SlotOutOfBounds { size_bits: 2 }
+        });
+    }
+
+    #[test]
+    fn missing_required_slot() {
+        let mut spec = base();
+        slots(&mut spec, "tcb").retain(|entry| entry.slot != object::Tcb::SLOT_IPC_BUFFER);
+        assert_only_error(&spec, |kind| {
+            *kind == DiagnosticKind::MissingRequiredSlot { name: "ipc_buffer" }
+        });
+    }
+
+    #[test]
+    fn frame_mapped_twice() {
+        let mut spec = base();
+        let slots = slots(&mut spec, "pt");
+        slots.push(slots[0].clone());
+        assert_only_error(&spec, |kind| *kind == DiagnosticKind::FrameMappedTwice);
+    }
+
+    #[test]
+    fn page_table_mapped_more_than_once() {
+        let src = BASE
+            .replace("ep = ep", "ep = ep\n vspace2 = pt (level: 0, root)")
+            .replace("pt {", "vspace2 { 0x0: pt } pt {");
+        assert_only_error(&parse(&src), |kind| {
+            matches!(kind, DiagnosticKind::PageTableMappedMoreThanOnce { .. })
+        });
+    }
+
+    #[test]
+    fn root_page_table_mapped_as_child() {
+        let spec = parse(&BASE.replace("0x0: pt", "0x0: pt 0x1: vspace"));
+        assert_only_error(&spec, |kind| {
+            *kind == DiagnosticKind::RootPageTableMappedAsChild
+        });
+    }
+
+    #[test]
+    fn irqs() {
+        let src = BASE
+            .replace("ep = ep", "ep = ep\n irq = irq\n irq2 = irq")
+            .replace("0x1: ep (RW)", "0x1: ep (RW) 0x2: irq 0x3: irq2");
+        let mut spec = parse(&format!("{src} irq maps {{ 0x1: irq }}"));
+        spec.irqs.push(spec.irqs[0]);
+        let kinds = kinds(&spec);
+        assert_eq!(
+            kinds,
+            [
+                (Severity::Error, DiagnosticKind::DuplicateIrq),
+                (Severity::Error, DiagnosticKind::IrqHandlerWithoutIrq),
+            ]
+        );
+    }
+
+    #[test]
+    fn root_objects_out_of_bounds() {
+        let mut spec = base();
+        spec.root_objects.end = ObjectId(100);
+        assert!(kinds(&spec).contains(&(Severity::Error, DiagnosticKind::RootObjectsOutOfBounds)));
+    }
+
+    #[test]
+    fn root_objects_with_paddr_not_first() {
+        let mut spec = base();
+        let ipc = id(&spec, "ipc");
+        let Object::Frame(frame) = &mut spec.objects[usize::from(ipc)].object else {
+            panic!()
+        };
+        frame.paddr = Some(Word(0x1000));
+        assert_only_error(&spec, |kind| {
+            *kind == DiagnosticKind::RootObjectsWithPaddrNotFirst
+        });
+    }
+
+    #[test]
+    fn untyped_cover_overlaps_root_objects() {
+        let src = BASE
+            .replace("ep = ep", "ep = ep\n ut = ut (21 bits)")
+            .replace("0x1: ep (RW)", "0x1: ep (RW) 0x2: ut");
+        let mut spec = parse(&src);
+        spec.untyped_covers.push(UntypedCover {
+            parent: id(&spec, "ut"),
+            children: ObjectId(0)..ObjectId(1),
+        });
+        assert_only_error(&spec, |kind| {
+            *kind == DiagnosticKind::UntypedCoverOverlapsRootObjects
+        });
+    }
+
+    #[test]
+    fn object_not_created() {
+        let mut spec = base();
+        let lost = ObjectId::from(spec.objects.len());
+        spec.objects.push(NamedObject {
+            name: Some("lost".into()),
+            object: Object::Notification,
+        });
+        *add_cap(&mut spec, lost) = Cap::Notification(cap::Notification {
+            object: lost,
+            badge: Word(0),
+            rights: Rights {
+                read: true,
+                write: true,
+                grant: false,
+                grant_reply: false,
+            },
+        });
+        assert_only_error(&spec, |kind| *kind == DiagnosticKind::ObjectNotCreated);
+    }
+
+    #[test]
+    fn measurement_log() {
+        let mut spec = base();
+        spec.measurement_log = Some(id(&spec, "ipc"));
+        assert_eq!(kinds(&spec), []);
+
+        spec.measurement_log = Some(ObjectId(100));
+        assert_only_error(&spec, |kind| {
+            *kind == DiagnosticKind::DanglingObjectId(ObjectId(100))
+        });
+
+        spec.measurement_log = Some(id(&spec, "ep"));
+        assert_only_error(&spec, |kind| {
+            matches!(
+                kind,
+                DiagnosticKind::UnexpectedObjectType {
+                    expected: "Frame",
+                    object_type: "Endpoint",
+                    ..
+                }
+            )
+        });
+
+        let ipc = id(&spec, "ipc");
+        spec.measurement_log = Some(ipc);
+        let Object::Frame(frame) = &mut spec.objects[usize::from(ipc)].object else {
+            unreachable!()
+        };
+        frame.size_bits = 21;
+        assert_only_error(&spec, |kind| {
+            *kind == DiagnosticKind::MeasurementLogNotGranule { size_bits: 21 }
+        });
+
+        let mut spec = parse(&BASE.replace(
+            "ipc = frame (4k)",
+            "ipc = frame (4k, fill: [{0 16 CDL_FrameFill_BootInfo CDL_FrameFill_BootInfo_FDT 0}])",
+        ));
+        spec.measurement_log = Some(id(&spec, "ipc"));
+        assert_only_error(&spec, |kind| *kind == DiagnosticKind::MeasurementLogNotEmpty);
+    }
+
+    #[test]
+    fn warnings() {
+        let mut spec = parse(&BASE.replace("ep = ep", "ep = ep\n stray = notification"));
+        let ep = id(&spec, "ep");
+        spec.objects[usize::from(ep)].name = Some("cnode".into());
+        assert_eq!(
+            kinds(&spec),
+            [
+                (
+                    Severity::Warning,
+                    DiagnosticKind::DuplicateName { first: ObjectId(1) }
+                ),
+                (Severity::Warning, DiagnosticKind::UnreferencedObject),
+            ]
+        );
+    }
+}
@@ -10,20 +10,24 @@ mk {
   package.name = "sel4-kernel-loader";
   package.license = "BSD-2-Clause AND GPL-2.0-only";
   dependencies = {
-    inherit (versions) cfg-if log embedded-hal-nb;
+    inherit (versions) cfg-if log embedded-hal-nb fdt;
     rkyv = { version = versions.rkyv; default-features = false; };
     spin = { version = versions.spin; features = [ "lock_api" ]; };
     inherit (localCrates)
-      sel4-platform-info
+      sel4-platform-info-types
       sel4-logging
       sel4-config
-      sel4-kernel-loader-payload-types
       sel4-stack
       sel4-phdrs
       sel4-phdrs-patched
       sel4-no-allocator
       sel4-immutable-cell
+      sel4-kernel-loader-log-buffer
     ;
+    sel4-kernel-loader-payload-types = localCrates.sel4-kernel-loader-payload-types // { features = [ "deflate" "lz4" "signature" ]; };
+  };
+  target."cfg(not(target_arch = \"x86_64\"))".dependencies = {
+    inherit (localCrates) sel4-platform-info;
   };
   target."cfg(any(target_arch = \"riscv32\", target_arch = \"riscv64\"))".dependencies = {
     inherit (versions) sbi riscv;
@@ -43,11 +47,11 @@ mk {
       prettyplease
       cc
       glob
+      hex
     ;
     postcard = postcardWith [ "alloc" ];
     syn = { version = versions.syn; features = [ "parsing" ]; };
     inherit (localCrates)
-      sel4-platform-info
       sel4-config
       sel4-build-env
       sel4-kernel-loader-payload-types
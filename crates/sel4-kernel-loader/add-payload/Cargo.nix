@@ -17,13 +17,15 @@ mk {
       object
       rkyv
       bitfield
+      hex
     ;
     clap = { version = versions.clap; features = [ "derive" ]; };
     inherit (localCrates)
       sel4-patch-elf
       sel4-phdrs-constants
-      sel4-kernel-loader-payload-types
+      sel4-kernel-loader-log-buffer
     ;
+    sel4-kernel-loader-payload-types = localCrates.sel4-kernel-loader-payload-types // { features = [ "deflate" "lz4" "signature" ]; };
     sel4-config-types = localCrates.sel4-config-types // { features = [ "serde" ]; };
     sel4-platform-info-types = localCrates.sel4-platform-info-types // { features = [ "owned" ]; };
   };
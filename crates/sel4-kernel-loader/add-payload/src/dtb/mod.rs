@@ -0,0 +1,308 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use std::ops::Range;
+use std::str::FromStr;
+
+use anyhow::{Context, Error, Result, bail, ensure};
+
+use sel4_kernel_loader_payload_types::DtbFixup;
+
+mod tree;
+
+use tree::{Node, Tree, decode_reg, encode_cells};
+
+const HARDWARE_RNG_SEED_SIZE: usize = 64;
+const KASLR_SEED_SIZE: usize = 8;
+
+const LOADER_RESERVED_MEMORY_NODE: &str = "sel4-kernel-loader";
+
+/// Edits to make to the DTB passed to the kernel.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DtbEdits {
+    pub(crate) bootargs: Option<String>,
+    pub(crate) rng_seed: Option<Seed>,
+    pub(crate) kaslr_seed: Option<Seed>,
+    /// Add a `/reserved-memory` node covering the loader, to be filled in at boot time.
+    pub(crate) reserve_loader: bool,
+    /// Physical address ranges to remove from `/memory` nodes.
+    pub(crate) trim_memory: Vec<Range<u64>>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Seed {
+    Value(Vec<u8>),
+    /// Filled in at boot time by the loader.
+    Hardware,
+}
+
+impl FromStr for Seed {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "hardware" => Self::Hardware,
+            _ => Self::Value(hex::decode(s).context("expected \"hardware\" or hex")?),
+        })
+    }
+}
+
+impl DtbEdits {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.bootargs.is_none()
+            && self.rng_seed.is_none()
+            && self.kaslr_seed.is_none()
+            && !self.reserve_loader
+            && self.trim_memory.is_empty()
+    }
+
+    /// Returns the edited DTB along with the fixups that the loader must apply to it.
+    pub(crate) fn apply(
+        &self,
+        dtb: &[u8],
+        initrd: Option<&Range<u64>>,
+    ) -> Result<(Vec<u8>, Vec<DtbFixup>)> {
+        if self.is_empty() && initrd.is_none() {
+            return Ok((dtb.to_vec(), vec![]));
+        }
+
+        let mut tree = Tree::parse(dtb).context("failed to parse DTB")?;
+
+        let address_cells = tree.root.prop_u32("#address-cells").unwrap_or(2);
+        let size_cells = tree.root.prop_u32("#size-cells").unwrap_or(1);
+
+        {
+            let chosen = tree.node_or_insert("/chosen");
+            if let Some(bootargs) = &self.bootargs {
+                chosen.set_prop_str("bootargs", bootargs);
+            }
+            if let Some(initrd) = initrd {
+                chosen.set_prop("linux,initrd-start", encode_cells(initrd.start, 2));
+                chosen.set_prop("linux,initrd-end", encode_cells(initrd.end, 2));
+            }
+            if let Some(seed) = &self.rng_seed {
+                chosen.set_prop("rng-seed", seed_placeholder(seed, HARDWARE_RNG_SEED_SIZE)?);
+            }
+            if let Some(seed) = &self.kaslr_seed {
+                let value = seed_placeholder(seed, KASLR_SEED_SIZE)?;
+                ensure!(value.len() == KASLR_SEED_SIZE, "kaslr-seed must be 8 bytes");
+                chosen.set_prop("kaslr-seed", value);
+            }
+        }
+
+        if !self.trim_memory.is_empty() {
+            trim_memory(&mut tree, &self.trim_memory, address_cells, size_cells)?;
+        }
+
+        if self.reserve_loader {
+            let reserved_memory = tree.node_or_insert("/reserved-memory");
+            reserved_memory.set_prop_u32("#address-cells", address_cells);
+            reserved_memory.set_prop_u32("#size-cells", size_cells);
+            reserved_memory.set_prop("ranges", vec![]);
+            let node = reserved_memory
+                .children
+                .iter_mut()
+                .find(|child| child.name == LOADER_RESERVED_MEMORY_NODE);
+            let node = match node {
+                Some(node) => node,
+                None => {
+                    reserved_memory.children.push(Node {
+                        name: LOADER_RESERVED_MEMORY_NODE.to_owned(),
+                        ..Default::default()
+                    });
+                    reserved_memory.children.last_mut().unwrap()
+                }
+            };
+            let mut reg = encode_cells(0, address_cells);
+            reg.extend(encode_cells(0, size_cells));
+            node.set_prop("reg", reg);
+            node.set_prop("no-map", vec![]);
+        }
+
+        let (bytes, offsets) = tree.to_bytes();
+        let offset = |path: &str, prop: &str| offsets[&(path.to_owned(), prop.to_owned())];
+
+        let mut fixups = vec![];
+        if matches!(self.rng_seed, Some(Seed::Hardware)) {
+            fixups.push(DtbFixup::Random {
+                offset: offset("/chosen", "rng-seed"),
+            });
+        }
+        if matches!(self.kaslr_seed, Some(Seed::Hardware)) {
+            fixups.push(DtbFixup::Random {
+                offset: offset("/chosen", "kaslr-seed"),
+            });
+        }
+        if self.reserve_loader {
+            fixups.push(DtbFixup::LoaderFootprint {
+                offset: offset(
+                    &format!("/reserved-memory/{LOADER_RESERVED_MEMORY_NODE}"),
+                    "reg",
+                ),
+                address_cells,
+                size_cells,
+            });
+        }
+
+        Ok((bytes, fixups))
+    }
+}
+
+fn seed_placeholder(seed: &Seed, hardware_size: usize) -> Result<Vec<u8>> {
+    Ok(match seed {
+        Seed::Value(value) => {
+            ensure!(!value.is_empty(), "seed must not be empty");
+            value.clone()
+        }
+        Seed::Hardware => vec![0; hardware_size],
+    })
+}
+
+fn trim_memory(
+    tree: &mut Tree,
+    trim: &[Range<u64>],
+    address_cells: u32,
+    size_cells: u32,
+) -> Result<()> {
+    let mut emptied = vec![];
+    for node in tree.root.children.iter_mut() {
+        let is_memory = node.prop("device_type") == Some(b"memory\0")
+            || node.name == "memory"
+            || node.name.starts_with("memory@");
+        if !is_memory {
+            continue;
+        }
+        let Some(reg) = node.prop("reg") else {
+            continue;
+        };
+        let mut ranges = decode_reg(reg, address_cells, size_cells)
+            .with_context(|| format!("/{}", node.name))?
+            .into_iter()
+            .map(|(start, size)| start..start + size)
+            .collect::<Vec<_>>();
+        for hole in trim {
+            ranges = ranges
+                .into_iter()
+                .flat_map(|range| subtract(range, hole))
+                .collect();
+        }
+        if ranges.is_empty() {
+            emptied.push(node.name.clone());
+            continue;
+        }
+        let mut reg = vec![];
+        for range in ranges {
+            reg.extend(encode_cells(range.start, address_cells));
+            reg.extend(encode_cells(range.end - range.start, size_cells));
+        }
+        node.set_prop("reg", reg);
+    }
+    tree.root
+        .children
+        .retain(|child| !emptied.contains(&child.name));
+    Ok(())
+}
+
+fn subtract(range: Range<u64>, hole: &Range<u64>) -> impl Iterator<Item = Range<u64>> {
+    let below = range.start..range.end.min(hole.start);
+    let above = range.start.max(hole.end)..range.end;
+    [below, above].into_iter().filter(|r| !r.is_empty())
+}
+
+/// Parses `<start>..<end>`, where each bound is a hex or decimal integer.
+pub(crate) fn parse_range(s: &str) -> Result<Range<u64>> {
+    let Some((start, end)) = s.split_once("..") else {
+        bail!("expected <start>..<end>");
+    };
+    let range = parse_int(start)?..parse_int(end)?;
+    ensure!(range.start < range.end, "empty range");
+    Ok(range)
+}
+
+fn parse_int(s: &str) -> Result<u64> {
+    Ok(match s.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16)?,
+        None => s.parse()?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn example() -> Vec<u8> {
+        let mut root = Node::default();
+        root.set_prop_u32("#address-cells", 2);
+        root.set_prop_u32("#size-cells", 2);
+        let mut memory = Node {
+            name: "memory@40000000".to_owned(),
+            ..Default::default()
+        };
+        memory.set_prop_str("device_type", "memory");
+        let mut reg = encode_cells(0x4000_0000, 2);
+        reg.extend(encode_cells(0x8000_0000, 2));
+        memory.set_prop("reg", reg);
+        root.children.push(memory);
+        Tree {
+            boot_cpuid_phys: 0,
+            mem_reserve: vec![(0x1000, 0x1000)],
+            root,
+        }
+        .to_bytes()
+        .0
+    }
+
+    #[test]
+    fn round_trip() {
+        let bytes = example();
+        assert_eq!(Tree::parse(&bytes).unwrap().to_bytes().0, bytes);
+    }
+
+    #[test]
+    fn edits() {
+        let edits = DtbEdits {
+            bootargs: Some("console=ttyAMA0".to_owned()),
+            rng_seed: Some(Seed::Hardware),
+            kaslr_seed: Some("0102030405060708".parse().unwrap()),
+            reserve_loader: true,
+            trim_memory: vec![parse_range("0x50000000..0x60000000").unwrap()],
+        };
+        let (bytes, fixups) = edits
+            .apply(&example(), Some(&(0x4800_0000..0x4810_0000)))
+            .unwrap();
+        let tree = Tree::parse(&bytes).unwrap();
+
+        let chosen = tree.node("/chosen").unwrap();
+        assert_eq!(chosen.prop("bootargs"), Some(&b"console=ttyAMA0\0"[..]));
+        assert_eq!(chosen.prop("rng-seed").unwrap().len(), 64);
+        assert_eq!(
+            chosen.prop("kaslr-seed"),
+            Some(&[1, 2, 3, 4, 5, 6, 7, 8][..])
+        );
+        assert_eq!(
+            chosen.prop("linux,initrd-start"),
+            Some(&encode_cells(0x4800_0000, 2)[..])
+        );
+
+        let memory = tree.node("/memory@40000000").unwrap();
+        assert_eq!(
+            decode_reg(memory.prop("reg").unwrap(), 2, 2).unwrap(),
+            vec![(0x4000_0000, 0x1000_0000), (0x6000_0000, 0x6000_0000)]
+        );
+
+        assert!(tree.node("/reserved-memory/sel4-kernel-loader").is_some());
+
+        assert_eq!(fixups.len(), 2);
+        for fixup in fixups {
+            let offset = match fixup {
+                DtbFixup::Random { offset } => offset,
+                DtbFixup::LoaderFootprint { offset, .. } => offset,
+            };
+            let offset = usize::try_from(offset).unwrap();
+            assert_eq!(bytes[offset..offset + 4], 3u32.to_be_bytes());
+        }
+    }
+}
@@ -0,0 +1,366 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+// A minimal editable representation of a flattened device tree, sufficient for round-tripping the
+// DTBs that the loader passes to the kernel.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Result, bail, ensure};
+
+const FDT_MAGIC: u32 = 0xd00d_feed;
+const FDT_BEGIN_NODE: u32 = 0x1;
+const FDT_END_NODE: u32 = 0x2;
+const FDT_PROP: u32 = 0x3;
+const FDT_NOP: u32 = 0x4;
+const FDT_END: u32 = 0x9;
+
+const HEADER_SIZE: usize = 40;
+const VERSION: u32 = 17;
+const LAST_COMP_VERSION: u32 = 16;
+
+#[derive(Debug, Clone)]
+pub(crate) struct Tree {
+    pub(crate) boot_cpuid_phys: u32,
+    pub(crate) mem_reserve: Vec<(u64, u64)>,
+    pub(crate) root: Node,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Node {
+    pub(crate) name: String,
+    pub(crate) props: Vec<Prop>,
+    pub(crate) children: Vec<Node>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Prop {
+    pub(crate) name: String,
+    pub(crate) value: Vec<u8>,
+}
+
+/// Offsets of the `FDT_PROP` tokens of properties in a serialized tree, keyed by node path and
+/// property name.
+pub(crate) type PropOffsets = BTreeMap<(String, String), u32>;
+
+impl Tree {
+    pub(crate) fn parse(bytes: &[u8]) -> Result<Self> {
+        let header = |i: usize| be_u32(bytes, i * 4);
+        ensure!(header(0)? == FDT_MAGIC, "bad DTB magic");
+        let total_size = usize::try_from(header(1)?)?;
+        let off_struct = usize::try_from(header(2)?)?;
+        let off_strings = usize::try_from(header(3)?)?;
+        let off_mem_rsvmap = usize::try_from(header(4)?)?;
+        ensure!(header(6)? <= VERSION, "unsupported DTB version");
+        let boot_cpuid_phys = header(7)?;
+        ensure!(total_size <= bytes.len(), "truncated DTB");
+        let bytes = &bytes[..total_size];
+
+        let mut mem_reserve = vec![];
+        let mut i = off_mem_rsvmap;
+        loop {
+            let entry = (be_u64(bytes, i)?, be_u64(bytes, i + 8)?);
+            if entry == (0, 0) {
+                break;
+            }
+            mem_reserve.push(entry);
+            i += 16;
+        }
+
+        let mut parser = Parser {
+            bytes,
+            strings: bytes.get(off_strings..).unwrap_or_default(),
+            pos: off_struct,
+        };
+        ensure!(parser.next_token()? == FDT_BEGIN_NODE, "expected root node");
+        let root = parser.node()?;
+        ensure!(
+            parser.next_token()? == FDT_END,
+            "expected end of structure block"
+        );
+
+        Ok(Self {
+            boot_cpuid_phys,
+            mem_reserve,
+            root,
+        })
+    }
+
+    pub(crate) fn to_bytes(&self) -> (Vec<u8>, PropOffsets) {
+        let mut struct_block = vec![];
+        let mut strings = Strings::default();
+        let mut offsets = PropOffsets::new();
+        let struct_offset = HEADER_SIZE + (self.mem_reserve.len() + 1) * 16;
+        write_node(
+            &self.root,
+            "",
+            &mut struct_block,
+            &mut strings,
+            &mut offsets,
+            struct_offset,
+        );
+        push_u32(&mut struct_block, FDT_END);
+
+        let strings_offset = struct_offset + struct_block.len();
+        let total_size = strings_offset + strings.block.len();
+
+        let mut bytes = vec![];
+        for word in [
+            FDT_MAGIC as usize,
+            total_size,
+            struct_offset,
+            strings_offset,
+            HEADER_SIZE,
+            VERSION as usize,
+            LAST_COMP_VERSION as usize,
+            self.boot_cpuid_phys as usize,
+            strings.block.len(),
+            struct_block.len(),
+        ]
+        .map(|word| u32::try_from(word).unwrap())
+        {
+            push_u32(&mut bytes, word);
+        }
+        for (addr, size) in self.mem_reserve.iter().chain([&(0, 0)]) {
+            bytes.extend_from_slice(&addr.to_be_bytes());
+            bytes.extend_from_slice(&size.to_be_bytes());
+        }
+        bytes.extend_from_slice(&struct_block);
+        bytes.extend_from_slice(&strings.block);
+        (bytes, offsets)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn node(&self, path: &str) -> Option<&Node> {
+        path_components(path).try_fold(&self.root, |node, name| node.child(name))
+    }
+
+    /// Returns the node at `path`, creating it and any missing ancestors.
+    pub(crate) fn node_or_insert(&mut self, path: &str) -> &mut Node {
+        path_components(path).fold(&mut self.root, |node, name| {
+            if node.child(name).is_none() {
+                node.children.push(Node {
+                    name: name.to_owned(),
+                    ..Default::default()
+                });
+            }
+            node.child_mut(name).unwrap()
+        })
+    }
+}
+
+impl Node {
+    pub(crate) fn child(&self, name: &str) -> Option<&Node> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    pub(crate) fn child_mut(&mut self, name: &str) -> Option<&mut Node> {
+        self.children.iter_mut().find(|child| child.name == name)
+    }
+
+    pub(crate) fn prop(&self, name: &str) -> Option<&[u8]> {
+        self.props
+            .iter()
+            .find(|prop| prop.name == name)
+            .map(|prop| prop.value.as_slice())
+    }
+
+    pub(crate) fn prop_u32(&self, name: &str) -> Option<u32> {
+        Some(u32::from_be_bytes(self.prop(name)?.try_into().ok()?))
+    }
+
+    pub(crate) fn set_prop(&mut self, name: &str, value: Vec<u8>) {
+        match self.props.iter_mut().find(|prop| prop.name == name) {
+            Some(prop) => prop.value = value,
+            None => self.props.push(Prop {
+                name: name.to_owned(),
+                value,
+            }),
+        }
+    }
+
+    pub(crate) fn set_prop_str(&mut self, name: &str, value: &str) {
+        let mut bytes = value.as_bytes().to_vec();
+        bytes.push(0);
+        self.set_prop(name, bytes)
+    }
+
+    pub(crate) fn set_prop_u32(&mut self, name: &str, value: u32) {
+        self.set_prop(name, value.to_be_bytes().to_vec())
+    }
+}
+
+fn path_components(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|component| !component.is_empty())
+}
+
+/// Encodes `value` as `cells` big-endian 32-bit cells.
+pub(crate) fn encode_cells(value: u64, cells: u32) -> Vec<u8> {
+    let mut bytes = vec![];
+    for i in (0..cells).rev() {
+        let cell = if i < 2 { (value >> (32 * i)) as u32 } else { 0 };
+        bytes.extend_from_slice(&cell.to_be_bytes());
+    }
+    bytes
+}
+
+/// Decodes `(address, size)` pairs from a `reg` property.
+pub(crate) fn decode_reg(
+    value: &[u8],
+    address_cells: u32,
+    size_cells: u32,
+) -> Result<Vec<(u64, u64)>> {
+    let entry_size = usize::try_from(4 * (address_cells + size_cells))?;
+    ensure!(
+        entry_size > 0 && value.len().is_multiple_of(entry_size),
+        "malformed reg property"
+    );
+    value
+        .chunks(entry_size)
+        .map(|entry| {
+            let (address, size) = entry.split_at(usize::try_from(4 * address_cells)?);
+            Ok((decode_cells(address)?, decode_cells(size)?))
+        })
+        .collect()
+}
+
+fn decode_cells(bytes: &[u8]) -> Result<u64> {
+    let mut value: u64 = 0;
+    for cell in bytes.chunks(4) {
+        ensure!(value >> 32 == 0, "value does not fit in 64 bits");
+        value = (value << 32) | u64::from(u32::from_be_bytes(cell.try_into()?));
+    }
+    Ok(value)
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    strings: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn next_token(&mut self) -> Result<u32> {
+        loop {
+            let token = self.u32()?;
+            if token != FDT_NOP {
+                return Ok(token);
+            }
+        }
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let value = be_u32(self.bytes, self.pos)?;
+        self.pos += 4;
+        Ok(value)
+    }
+
+    fn node(&mut self) -> Result<Node> {
+        let name = c_str(self.bytes, self.pos)?;
+        self.pos = (self.pos + name.len() + 1).next_multiple_of(4);
+        let mut node = Node {
+            name: name.to_owned(),
+            ..Default::default()
+        };
+        loop {
+            match self.next_token()? {
+                FDT_PROP => {
+                    let len = usize::try_from(self.u32()?)?;
+                    let name_offset = usize::try_from(self.u32()?)?;
+                    let Some(value) = self.bytes.get(self.pos..self.pos + len) else {
+                        bail!("truncated property");
+                    };
+                    node.props.push(Prop {
+                        name: c_str(self.strings, name_offset)?.to_owned(),
+                        value: value.to_vec(),
+                    });
+                    self.pos = (self.pos + len).next_multiple_of(4);
+                }
+                FDT_BEGIN_NODE => node.children.push(self.node()?),
+                FDT_END_NODE => return Ok(node),
+                token => bail!("unexpected token {token:#x} at offset {:#x}", self.pos - 4),
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct Strings {
+    block: Vec<u8>,
+    offsets: BTreeMap<String, u32>,
+}
+
+impl Strings {
+    fn offset(&mut self, s: &str) -> u32 {
+        *self.offsets.entry(s.to_owned()).or_insert_with(|| {
+            let offset = self.block.len().try_into().unwrap();
+            self.block.extend_from_slice(s.as_bytes());
+            self.block.push(0);
+            offset
+        })
+    }
+}
+
+fn write_node(
+    node: &Node,
+    path: &str,
+    out: &mut Vec<u8>,
+    strings: &mut Strings,
+    offsets: &mut PropOffsets,
+    base: usize,
+) {
+    push_u32(out, FDT_BEGIN_NODE);
+    out.extend_from_slice(node.name.as_bytes());
+    out.push(0);
+    pad(out);
+    for prop in &node.props {
+        let offset = (base + out.len()).try_into().unwrap();
+        offsets.insert((path_or_root(path), prop.name.clone()), offset);
+        push_u32(out, FDT_PROP);
+        push_u32(out, prop.value.len().try_into().unwrap());
+        push_u32(out, strings.offset(&prop.name));
+        out.extend_from_slice(&prop.value);
+        pad(out);
+    }
+    for child in &node.children {
+        let child_path = format!("{path}/{}", child.name);
+        write_node(child, &child_path, out, strings, offsets, base);
+    }
+    push_u32(out, FDT_END_NODE);
+}
+
+fn path_or_root(path: &str) -> String {
+    if path.is_empty() { "/" } else { path }.to_owned()
+}
+
+fn push_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+fn pad(out: &mut Vec<u8>) {
+    out.resize(out.len().next_multiple_of(4), 0);
+}
+
+fn be_u32(bytes: &[u8], offset: usize) -> Result<u32> {
+    match bytes.get(offset..offset + 4) {
+        Some(word) => Ok(u32::from_be_bytes(word.try_into().unwrap())),
+        None => bail!("truncated DTB"),
+    }
+}
+
+fn be_u64(bytes: &[u8], offset: usize) -> Result<u64> {
+    Ok((u64::from(be_u32(bytes, offset)?) << 32) | u64::from(be_u32(bytes, offset + 4)?))
+}
+
+fn c_str(bytes: &[u8], offset: usize) -> Result<&str> {
+    let Some(rest) = bytes.get(offset..) else {
+        bail!("string offset out of bounds");
+    };
+    let Some(len) = rest.iter().position(|b| *b == 0) else {
+        bail!("unterminated string");
+    };
+    Ok(str::from_utf8(&rest[..len])?)
+}
@@ -5,25 +5,30 @@
 //
 
 use std::fs::{self, File};
+use std::ops::Range;
 
-use anyhow::Result;
-use clap::Parser;
+use anyhow::{Context, Result, anyhow, bail};
+use clap::{Parser, ValueEnum};
 use object::elf::{FileHeader32, FileHeader64};
 use object::read::elf::{ElfFile, FileHeader, ProgramHeader};
 use object::{Endianness, ReadRef};
 use rkyv::util::AlignedVec;
 
 use sel4_config_types::Configuration;
+use sel4_kernel_loader_payload_types::{Compression, ImageSelection, PrimaryImage};
 use sel4_patch_elf::{FileHeaderExt, Patching};
 use sel4_phdrs_constants::PT_SEL4_KERNEL_LOADER_PAYLOAD;
 use sel4_platform_info_types::OwnedPlatformInfo;
 
+mod dtb;
 mod maps;
 mod page_tables;
 mod serialize_payload;
 mod utils;
 
+use crate::dtb::{DtbEdits, Seed, parse_range};
 use crate::page_tables::Scheme;
+use crate::serialize_payload::{BootProtocol, PayloadOptions};
 use crate::utils::{virt_footprint, with_elf};
 
 type ArchiveAlignedVec = AlignedVec;
@@ -34,30 +39,89 @@ struct Cli {
     sel4_prefix: Option<String>,
     #[arg(long)]
     sel4_config: Option<String>,
+    /// May be given once, or once per `--app`.
     #[arg(long)]
-    kernel: Option<String>,
+    kernel: Vec<String>,
     #[arg(long)]
     dtb: Option<String>,
     #[arg(long)]
     platform_info: Option<String>,
     #[arg(long)]
     loader: String,
+    /// Each occurrence adds an image to the payload.
+    #[arg(long, required = true)]
+    app: Vec<String>,
+    /// Index of the image to boot.
+    #[arg(long, default_value_t = 0)]
+    boot_image: u32,
+    /// Boot the image given by this `u32` property of `/chosen` in the device tree passed by the
+    /// bootloader, or `--boot-image` if the property is absent.
     #[arg(long)]
-    app: String,
+    boot_image_chosen_property: Option<String>,
+    /// Image to boot if the selected image is missing or fails verification. Implies
+    /// `--verify-images`.
+    #[arg(long)]
+    fallback_image: Option<u32>,
+    /// Embed a digest of each image, which the loader checks before booting it.
+    #[arg(long)]
+    verify_images: bool,
+    /// File containing a hex-encoded Ed25519 secret key with which to sign each image. A loader
+    /// built with the corresponding public key only boots signed images.
+    #[arg(long)]
+    signing_key: Option<String>,
+    /// Compression for payload regions, which the loader decompresses directly into place.
+    #[arg(long, value_enum, default_value_t = CompressionArg::None)]
+    compression: CompressionArg,
+    /// Add this initial ramdisk to the payload, and point `/chosen/linux,initrd-{start,end}` at it.
+    #[arg(long)]
+    initrd: Option<String>,
+    /// Set `/chosen/bootargs`.
+    #[arg(long)]
+    dtb_bootargs: Option<String>,
+    /// Set `/chosen/rng-seed` to this hex value, or to 64 bytes from a hardware random number
+    /// generator at boot time if the value is "hardware".
+    #[arg(long)]
+    dtb_rng_seed: Option<Seed>,
+    /// Set `/chosen/kaslr-seed`, as with `--dtb-rng-seed`.
+    #[arg(long)]
+    dtb_kaslr_seed: Option<Seed>,
+    /// Add a `/reserved-memory` node covering the loader's own footprint.
+    #[arg(long)]
+    dtb_reserve_loader: bool,
+    /// Remove a physical address range (<start>..<end>) from the `/memory` nodes.
+    #[arg(long, value_parser = parse_range)]
+    dtb_trim_memory: Vec<Range<u64>>,
     #[arg(long, short = 'o')]
     out_file: String,
     #[arg(long, short = 'v')]
     verbose: bool,
 }
 
+#[derive(ValueEnum, Debug, Copy, Clone)]
+enum CompressionArg {
+    None,
+    Deflate,
+    Lz4,
+}
+
+impl From<CompressionArg> for Compression {
+    fn from(arg: CompressionArg) -> Self {
+        match arg {
+            CompressionArg::None => Self::None,
+            CompressionArg::Deflate => Self::Deflate,
+            CompressionArg::Lz4 => Self::Lz4,
+        }
+    }
+}
+
 #[derive(Debug)]
 struct Paths {
     sel4_config_path: String,
-    kernel_path: String,
-    dtb_path: String,
-    platform_info_path: String,
+    kernel_paths: Vec<String>,
+    dtb_path: Option<String>,
+    platform_info_path: Option<String>,
     loader_path: String,
-    app_path: String,
+    app_paths: Vec<String>,
     out_file_path: String,
 }
 
@@ -72,26 +136,27 @@ impl Paths {
                 .or(sel4_prefix
                     .map(|prefix| format!("{prefix}/libsel4/include/kernel/gen_config.json")))
                 .unwrap(),
-            kernel_path: cli
-                .kernel
-                .as_ref()
-                .map(ToOwned::to_owned)
-                .or(sel4_prefix.map(|prefix| format!("{prefix}/bin/kernel.elf")))
-                .unwrap(),
+            kernel_paths: if cli.kernel.is_empty() {
+                vec![
+                    sel4_prefix
+                        .map(|prefix| format!("{prefix}/bin/kernel.elf"))
+                        .unwrap(),
+                ]
+            } else {
+                cli.kernel.clone()
+            },
             dtb_path: cli
                 .dtb
                 .as_ref()
                 .map(ToOwned::to_owned)
-                .or(sel4_prefix.map(|prefix| format!("{prefix}/support/kernel.dtb")))
-                .unwrap(),
+                .or(sel4_prefix.map(|prefix| format!("{prefix}/support/kernel.dtb"))),
             platform_info_path: cli
                 .platform_info
                 .as_ref()
                 .map(ToOwned::to_owned)
-                .or(sel4_prefix.map(|prefix| format!("{prefix}/support/platform_gen.yaml")))
-                .unwrap(),
+                .or(sel4_prefix.map(|prefix| format!("{prefix}/support/platform_gen.yaml"))),
             loader_path: cli.loader.to_owned(),
-            app_path: cli.app.to_owned(),
+            app_paths: cli.app.clone(),
             out_file_path: cli.out_file.to_owned(),
         }
     }
@@ -106,39 +171,125 @@ fn main() -> Result<()> {
 
     let paths = Paths::get(&cli);
 
+    if paths.kernel_paths.len() != 1 && paths.kernel_paths.len() != paths.app_paths.len() {
+        bail!("expected either one kernel or one kernel per app");
+    }
+
+    let num_images = u32::try_from(paths.app_paths.len()).unwrap();
+    for index in [Some(cli.boot_image), cli.fallback_image]
+        .into_iter()
+        .flatten()
+    {
+        if index >= num_images {
+            bail!("image index {index} out of range (payload has {num_images} images)");
+        }
+    }
+
+    let selection = ImageSelection {
+        primary: match &cli.boot_image_chosen_property {
+            Some(property) => PrimaryImage::DtbChosen {
+                property: property.clone(),
+                default: cli.boot_image,
+            },
+            None => PrimaryImage::Fixed(cli.boot_image),
+        },
+        fallback: cli.fallback_image,
+    };
+
+    let options = PayloadOptions {
+        selection,
+        compression: cli.compression.into(),
+        verify: cli.verify_images || cli.fallback_image.is_some(),
+        signing_key: cli
+            .signing_key
+            .as_ref()
+            .map(|path| read_signing_key(path))
+            .transpose()?,
+        initrd_path: cli.initrd.clone(),
+        dtb_edits: DtbEdits {
+            bootargs: cli.dtb_bootargs.clone(),
+            rng_seed: cli.dtb_rng_seed.clone(),
+            kaslr_seed: cli.dtb_kaslr_seed.clone(),
+            reserve_loader: cli.dtb_reserve_loader,
+            trim_memory: cli.dtb_trim_memory.clone(),
+        },
+    };
+
     let kernel_config: Configuration =
         serde_json::from_reader(File::open(&paths.sel4_config_path).unwrap()).unwrap();
 
     match kernel_config.get("WORD_SIZE").unwrap().as_str().unwrap() {
-        "32" => continue_with_type::<FileHeader32<Endianness>>(&paths, &kernel_config),
-        "64" => continue_with_type::<FileHeader64<Endianness>>(&paths, &kernel_config),
+        "32" => continue_with_type::<FileHeader32<Endianness>>(&paths, &kernel_config, options),
+        "64" => continue_with_type::<FileHeader64<Endianness>>(&paths, &kernel_config, options),
         _ => {
             panic!()
         }
     }
 }
 
-fn continue_with_type<T>(paths: &Paths, kernel_config: &Configuration) -> Result<()>
+fn continue_with_type<T>(
+    paths: &Paths,
+    kernel_config: &Configuration,
+    options: PayloadOptions,
+) -> Result<()>
 where
     T: FileHeaderExt,
 {
-    let platform_info: OwnedPlatformInfo =
-        serde_yaml::from_reader(fs::File::open(&paths.platform_info_path).unwrap()).unwrap();
-
-    let payload = serialize_payload::serialize_payload::<T>(
-        &paths.kernel_path,
-        &paths.app_path,
-        &paths.dtb_path,
-        &platform_info,
-    );
-
-    let payload_data: AlignedVec = payload.to_bytes().unwrap();
-
     let orig_elf_bytes = fs::read(&paths.loader_path)?;
     let orig_elf = ElfFile::<T>::parse(&orig_elf_bytes).unwrap();
 
     let mut patching = Patching::new(&orig_elf);
 
+    let payload = if kernel_config.get("ARCH_X86").unwrap().as_bool().unwrap() {
+        // The kernel sets up its own address space, and the x86_64 kernel is converted to ELF32
+        // for the benefit of multiboot bootloaders.
+        serialize_payload::serialize_payload::<T, FileHeader32<Endianness>>(
+            &paths.kernel_paths,
+            &paths.app_paths,
+            &BootProtocol::Multiboot,
+            options,
+        )?
+    } else {
+        let platform_info_path = paths
+            .platform_info_path
+            .as_ref()
+            .context("--platform-info or --sel4-prefix is required")?;
+        let dtb_path = paths
+            .dtb_path
+            .clone()
+            .context("--dtb or --sel4-prefix is required")?;
+        let platform_info: OwnedPlatformInfo =
+            serde_yaml::from_reader(fs::File::open(platform_info_path).unwrap()).unwrap();
+        add_maps::<T>(paths, kernel_config, &platform_info, &mut patching)?;
+        serialize_payload::serialize_payload::<T, T>(
+            &paths.kernel_paths,
+            &paths.app_paths,
+            &BootProtocol::Dtb {
+                dtb_path,
+                platform_info,
+            },
+            options,
+        )?
+    };
+
+    let payload_data: AlignedVec = payload.to_bytes().unwrap();
+
+    patching.add_data_segment_with_meta_phdr(
+        PT_SEL4_KERNEL_LOADER_PAYLOAD,
+        ArchiveAlignedVec::ALIGNMENT.try_into().unwrap(),
+        &payload_data,
+    );
+
+    fs::write(&paths.out_file_path, patching.finalize())?;
+    Ok(())
+}
+
+fn add_maps<T: FileHeaderExt>(
+    paths: &Paths,
+    kernel_config: &Configuration,
+    platform_info: &OwnedPlatformInfo,
+    patching: &mut Patching<'_, T>,
+) -> Result<()> {
     let scheme = Scheme::from_config(kernel_config);
 
     let smp = kernel_config
@@ -159,7 +310,7 @@ where
     if kernel_config.get("ARCH_ARM").unwrap().as_bool().unwrap() {
         let mut addr_slot = None;
         patching.add_data_segment(min_level_align, |vaddr| {
-            let (bytes, root_vaddr) = maps::mk_loader_map(&scheme, smp, vaddr, &platform_info);
+            let (bytes, root_vaddr) = maps::mk_loader_map(&scheme, smp, vaddr, platform_info);
             addr_slot = Some(root_vaddr);
             bytes
         });
@@ -168,38 +319,54 @@ where
     }
 
     {
+        // All kernels in the payload share one boot-time mapping
+        let mut phys_to_virt_offset = None;
+        let mut masked_virt_addr_range: Option<Range<u64>> = None;
+        for kernel_path in &paths.kernel_paths {
+            let (this_phys_to_virt_offset, virt_range) = with_elf::<T, _, _>(kernel_path, |elf| {
+                (
+                    kernel_phys_to_virt_offset(elf, scheme.vaddr_mask()),
+                    virt_footprint(elf),
+                )
+            });
+            if phys_to_virt_offset
+                .replace(this_phys_to_virt_offset)
+                .is_some_and(|offset| offset != this_phys_to_virt_offset)
+            {
+                bail!("kernels have different physical-to-virtual offsets");
+            }
+            let masked =
+                virt_range.start & scheme.vaddr_mask()..virt_range.end & scheme.vaddr_mask();
+            masked_virt_addr_range = Some(match masked_virt_addr_range {
+                Some(range) => range.start.min(masked.start)..range.end.max(masked.end),
+                None => masked,
+            });
+        }
         let mut addr_slot = None;
         patching.add_data_segment(min_level_align, |vaddr| {
-            with_elf::<T, _, _>(&paths.kernel_path, |elf| {
-                let phys_to_virt_offset = kernel_phys_to_virt_offset(elf, scheme.vaddr_mask());
-                let virt_range = virt_footprint(elf);
-                let masked_virt_addr_range =
-                    virt_range.start & scheme.vaddr_mask()..virt_range.end & scheme.vaddr_mask();
-                let (bytes, root_vaddr) = maps::mk_kernel_map(
-                    &scheme,
-                    smp,
-                    vaddr,
-                    masked_virt_addr_range,
-                    phys_to_virt_offset,
-                );
-                addr_slot = Some(root_vaddr);
-                bytes
-            })
+            let (bytes, root_vaddr) = maps::mk_kernel_map(
+                &scheme,
+                smp,
+                vaddr,
+                masked_virt_addr_range.unwrap(),
+                phys_to_virt_offset.unwrap(),
+            );
+            addr_slot = Some(root_vaddr);
+            bytes
         });
         let addr = addr_slot.unwrap().try_into().unwrap();
         patching.patch_word("kernel_boot_level_0_table", addr);
     }
 
-    patching.add_data_segment_with_meta_phdr(
-        PT_SEL4_KERNEL_LOADER_PAYLOAD,
-        ArchiveAlignedVec::ALIGNMENT.try_into().unwrap(),
-        &payload_data,
-    );
-
-    fs::write(&paths.out_file_path, patching.finalize())?;
     Ok(())
 }
 
+fn read_signing_key(path: &str) -> Result<[u8; 32]> {
+    let encoded = fs::read_to_string(path)?;
+    let bytes = hex::decode(encoded.trim()).with_context(|| format!("{path}: invalid key"))?;
+    <[u8; 32]>::try_from(bytes).map_err(|_| anyhow!("{path}: key must be 32 bytes"))
+}
+
 fn kernel_phys_to_virt_offset<'a, T: FileHeader, R: ReadRef<'a>>(
     elf: &ElfFile<'a, T, R>,
     vaddr_mask: u64,
@@ -8,79 +8,289 @@ use std::fs;
 use std::ops::Range;
 use std::path::Path;
 
+use anyhow::{Result, ensure};
 use num::Integer;
+use object::elf::SHF_ALLOC;
 use object::read::elf::{ElfFile, FileHeader, ProgramHeader};
-use object::{Object, ObjectSegment, ReadRef};
+use object::{Object, ObjectSection, ObjectSegment, ReadRef, SectionFlags};
 
+use sel4_kernel_loader_log_buffer::{HEADER_SIZE, SECTION_NAME as LOADER_LOG_SECTION_NAME};
 use sel4_kernel_loader_payload_types::{
-    DtbInfo, Payload, PayloadInfo, Region, UserImageInfo, Word,
+    BootModule, Compression, DtbInfo, Image, ImageSelection, LoaderLogInfo, Payload, PayloadInfo,
+    Region, UserImageInfo, Word,
 };
 use sel4_platform_info_types::OwnedPlatformInfo;
 
+use crate::dtb::DtbEdits;
 use crate::utils::{loadable_segments, virt_footprint, with_elf};
 
 const PAGE_SIZE: u64 = 4096;
 
-pub(crate) fn serialize_payload<T: FileHeader>(
-    kernel_path: impl AsRef<Path>,
-    app_path: impl AsRef<Path>,
+pub(crate) struct PayloadOptions {
+    pub(crate) selection: ImageSelection,
+    pub(crate) compression: Compression,
+    /// Embed image digests.
+    pub(crate) verify: bool,
+    /// Embed image digests and signatures.
+    pub(crate) signing_key: Option<[u8; 32]>,
+    pub(crate) initrd_path: Option<String>,
+    pub(crate) dtb_edits: DtbEdits,
+}
+
+/// How the kernel is given its root task and a description of the platform.
+pub(crate) enum BootProtocol {
+    /// The loader places the root task in memory and passes a DTB.
+    Dtb {
+        dtb_path: String,
+        platform_info: OwnedPlatformInfo,
+    },
+    /// The kernel loads the root task from the first multiboot module, and discovers the platform
+    /// for itself.
+    Multiboot,
+}
+
+/// `T` is the ELF header type of apps, and `K` of kernels.
+pub(crate) fn serialize_payload<T: FileHeader, K: FileHeader>(
+    kernel_paths: &[impl AsRef<Path>],
+    app_paths: &[impl AsRef<Path>],
+    boot_protocol: &BootProtocol,
+    options: PayloadOptions,
+) -> Result<Payload> {
+    assert!(kernel_paths.len() == 1 || kernel_paths.len() == app_paths.len());
+
+    let mut builder = Builder::new();
+
+    let kernels = kernel_paths
+        .iter()
+        .map(|kernel_path| {
+            with_elf::<K, _, _>(kernel_path, |elf| {
+                let regions = builder.add_segments(elf, |phdr| phdr.p_paddr(elf.endian()).into());
+                (Word(elf.entry()), regions)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let images = match boot_protocol {
+        BootProtocol::Dtb {
+            dtb_path,
+            platform_info,
+        } => dtb_images::<T>(
+            &mut builder,
+            &kernels,
+            app_paths,
+            dtb_path,
+            platform_info,
+            &options,
+        )?,
+        BootProtocol::Multiboot => {
+            ensure!(
+                options.initrd_path.is_none() && options.dtb_edits.is_empty(),
+                "DTB options are not supported with multiboot"
+            );
+            multiboot_images(&mut builder, &kernels, app_paths)?
+        }
+    };
+
+    let mut payload = Payload {
+        images,
+        regions: builder.regions,
+        selection: options.selection,
+    };
+
+    for region in &mut payload.regions {
+        region.compress(options.compression);
+    }
+
+    if let Some(signing_key) = &options.signing_key {
+        payload.sign_images(signing_key);
+    } else if options.verify {
+        for i in 0..payload.images.len() {
+            payload.images[i].digest = Some(payload.image_digest(&payload.images[i]));
+        }
+    }
+
+    Ok(payload)
+}
+
+type Kernel = (Word, Vec<u32>);
+
+fn kernel_for_image(kernels: &[Kernel], i: usize) -> &Kernel {
+    &kernels[if kernels.len() == 1 { 0 } else { i }]
+}
+
+fn dtb_images<T: FileHeader>(
+    builder: &mut Builder,
+    kernels: &[Kernel],
+    app_paths: &[impl AsRef<Path>],
     dtb_path: impl AsRef<Path>,
     platform_info: &OwnedPlatformInfo,
-) -> Payload {
-    let mut builder = Builder::new();
+    options: &PayloadOptions,
+) -> Result<Vec<Image>> {
+    let apps = app_paths
+        .iter()
+        .map(|app_path| {
+            with_elf::<T, _, _>(app_path, |elf| {
+                let coarse_virt_footprint = coarsen_footprint(&virt_footprint(elf), PAGE_SIZE);
+                let coarse_footprint_size = coarse_virt_footprint
+                    .end
+                    .strict_sub(coarse_virt_footprint.start);
+                let ui_p_reg_end = platform_info
+                    .memory
+                    .last()
+                    .unwrap()
+                    .end
+                    .prev_multiple_of(&PAGE_SIZE);
+                let ui_p_reg_start = ui_p_reg_end.strict_sub(coarse_footprint_size);
+                let pv_offset = ui_p_reg_start.wrapping_sub(coarse_virt_footprint.start);
 
-    let kernel_entry = with_elf::<T, _, _>(&kernel_path, |elf| {
-        builder.add_segments(elf, |phdr| phdr.p_paddr(elf.endian()).into());
-        Word(elf.entry())
-    });
-
-    let (user_image, user_image_start) = with_elf::<T, _, _>(&app_path, |elf| {
-        let coarse_virt_footprint = coarsen_footprint(&virt_footprint(elf), PAGE_SIZE);
-        let coarse_footprint_size = coarse_virt_footprint
-            .end
-            .strict_sub(coarse_virt_footprint.start);
-        let ui_p_reg_end = platform_info
-            .memory
-            .last()
-            .unwrap()
-            .end
-            .prev_multiple_of(&PAGE_SIZE);
-        let ui_p_reg_start = ui_p_reg_end.strict_sub(coarse_footprint_size);
-        let pv_offset = ui_p_reg_start.wrapping_sub(coarse_virt_footprint.start);
-
-        builder.add_segments(elf, |phdr| {
-            let vaddr = phdr.p_vaddr(elf.endian()).into();
-            pv_offset.wrapping_add(vaddr)
-        });
+                let regions = builder.add_segments(elf, |phdr| {
+                    let vaddr = phdr.p_vaddr(elf.endian()).into();
+                    pv_offset.wrapping_add(vaddr)
+                });
 
-        let info = UserImageInfo {
-            ui_p_reg_start: Word(ui_p_reg_start),
-            ui_p_reg_end: Word(ui_p_reg_end),
-            pv_offset: Word(truncate_word::<T>(pv_offset)),
-            v_entry: Word(elf.entry()),
-        };
-        (info, ui_p_reg_start)
-    });
+                let loader_log = match elf.section_by_name(LOADER_LOG_SECTION_NAME) {
+                    Some(section) => {
+                        ensure!(
+                            section.size() >= u64::try_from(HEADER_SIZE).unwrap(),
+                            "loader log buffer is too small"
+                        );
+                        ensure!(
+                            matches!(
+                                section.flags(),
+                                SectionFlags::Elf { sh_flags } if sh_flags & u64::from(SHF_ALLOC) != 0
+                            ),
+                            "loader log buffer section {LOADER_LOG_SECTION_NAME} is not allocated"
+                        );
+                        let addr_p = pv_offset.wrapping_add(section.address());
+                        ensure!(
+                            ui_p_reg_start <= addr_p
+                                && addr_p
+                                    .checked_add(section.size())
+                                    .is_some_and(|end| end <= ui_p_reg_end),
+                            "loader log buffer section {LOADER_LOG_SECTION_NAME} lies outside of the user image"
+                        );
+                        Some(LoaderLogInfo {
+                            addr_p: Word(addr_p),
+                            size: Word(section.size()),
+                        })
+                    }
+                    None => None,
+                };
+
+                let info = UserImageInfo {
+                    ui_p_reg_start: Word(ui_p_reg_start),
+                    ui_p_reg_end: Word(ui_p_reg_end),
+                    pv_offset: Word(truncate_word::<T>(pv_offset)),
+                    v_entry: Word(elf.entry()),
+                };
+                Ok((info, regions, loader_log))
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Shared by all images, so they must sit below every user image
+    let mut next_paddr = apps
+        .iter()
+        .map(|(info, ..)| info.ui_p_reg_start.0)
+        .min()
+        .unwrap();
+    let mut shared_regions = vec![];
+
+    let initrd = match &options.initrd_path {
+        Some(initrd_path) => {
+            let data = fs::read(initrd_path)?;
+            let size: u64 = data.len().try_into().unwrap();
+            next_paddr -= size.next_multiple_of(PAGE_SIZE);
+            shared_regions.push(builder.add_region(next_paddr, size, data));
+            Some(next_paddr..next_paddr + size)
+        }
+        None => None,
+    };
 
-    let dtb = {
-        let data = fs::read(dtb_path).unwrap();
+    let (dtb, dtb_fixups) = {
+        let (data, fixups) = options
+            .dtb_edits
+            .apply(&fs::read(dtb_path)?, initrd.as_ref())?;
         let size: u64 = data.len().try_into().unwrap();
-        let paddr = user_image_start - size.next_multiple_of(PAGE_SIZE);
-        builder.add_region(paddr, size, data);
-        Some(DtbInfo {
-            addr_p: Word(paddr),
+        next_paddr -= size.next_multiple_of(PAGE_SIZE);
+        shared_regions.push(builder.add_region(next_paddr, size, data));
+        let info = DtbInfo {
+            addr_p: Word(next_paddr),
             size: Word(size),
-        })
+        };
+        (Some(info), fixups)
     };
 
-    Payload {
-        info: PayloadInfo {
-            kernel_entry,
-            user_image,
-            dtb,
-        },
-        data: builder.regions,
-    }
+    Ok(apps
+        .into_iter()
+        .enumerate()
+        .map(|(i, (user_image, app_regions, loader_log))| {
+            let (kernel_entry, kernel_regions) = kernel_for_image(kernels, i);
+            Image {
+                info: PayloadInfo {
+                    kernel_entry: *kernel_entry,
+                    user_image,
+                    dtb: dtb.clone(),
+                },
+                regions: kernel_regions
+                    .iter()
+                    .chain(&app_regions)
+                    .chain(&shared_regions)
+                    .copied()
+                    .collect(),
+                digest: None,
+                signature: None,
+                dtb_fixups: dtb_fixups.clone(),
+                boot_modules: vec![],
+                loader_log,
+            }
+        })
+        .collect())
+}
+
+fn multiboot_images(
+    builder: &mut Builder,
+    kernels: &[Kernel],
+    app_paths: &[impl AsRef<Path>],
+) -> Result<Vec<Image>> {
+    app_paths
+        .iter()
+        .enumerate()
+        .map(|(i, app_path)| {
+            let app_path = app_path.as_ref();
+            let data = fs::read(app_path)?;
+            let size = data.len().try_into().unwrap();
+            // The loader chooses where to place modules
+            let region = builder.add_region(0, size, data);
+            let (kernel_entry, kernel_regions) = kernel_for_image(kernels, i);
+            Ok(Image {
+                info: PayloadInfo {
+                    kernel_entry: *kernel_entry,
+                    user_image: UserImageInfo {
+                        ui_p_reg_start: Word(0),
+                        ui_p_reg_end: Word(0),
+                        pv_offset: Word(0),
+                        v_entry: Word(0),
+                    },
+                    dtb: None,
+                },
+                regions: kernel_regions.clone(),
+                digest: None,
+                signature: None,
+                dtb_fixups: vec![],
+                boot_modules: vec![BootModule {
+                    region,
+                    cmdline: app_path
+                        .file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .into_owned(),
+                }],
+                // The kernel chooses where to place the root task
+                loader_log: None,
+            })
+        })
+        .collect()
 }
 
 //
@@ -98,19 +308,24 @@ impl Builder {
         &mut self,
         elf: &ElfFile<'a, T, R>,
         f: impl Fn(&T::ProgramHeader) -> u64,
-    ) {
-        for seg in loadable_segments(elf) {
-            let paddr = f(seg.elf_program_header());
-            self.add_region(paddr, seg.size(), seg.data().unwrap().to_vec());
-        }
+    ) -> Vec<u32> {
+        loadable_segments(elf)
+            .map(|seg| {
+                let paddr = f(seg.elf_program_header());
+                self.add_region(paddr, seg.size(), seg.data().unwrap().to_vec())
+            })
+            .collect()
     }
 
-    fn add_region(&mut self, start: u64, size: u64, data: Vec<u8>) {
+    fn add_region(&mut self, start: u64, size: u64, data: Vec<u8>) -> u32 {
+        let index = self.regions.len().try_into().unwrap();
         self.regions.push(Region {
             addr: Word(start),
             size: Word(size),
+            compression: Compression::None,
             data,
         });
+        index
     }
 }
 
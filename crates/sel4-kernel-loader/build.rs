@@ -6,8 +6,10 @@
 
 #![allow(clippy::useless_conversion)]
 
+use std::env;
 use std::fs;
 use std::ops::Range;
+use std::path::PathBuf;
 
 use object::{
     ReadRef,
@@ -17,14 +19,19 @@ use object::{
 };
 
 use sel4_build_env::{get_libsel4_include_dirs, get_with_sel4_prefix_relative_fallback};
-use sel4_config::{sel4_cfg, sel4_cfg_str};
+use sel4_config::{sel4_cfg, sel4_cfg_bool, sel4_cfg_str};
 
 pub const SEL4_KERNEL_ENV: &str = "SEL4_KERNEL";
 
-#[sel4_cfg(WORD_SIZE = "64")]
+// Path to a file containing a hex-encoded Ed25519 public key. If set, the loader only boots images
+// signed with the corresponding secret key.
+pub const PUBLIC_KEY_ENV: &str = "SEL4_KERNEL_LOADER_PUBLIC_KEY";
+
+#[sel4_cfg(all(WORD_SIZE = "64", not(ARCH_X86)))]
 type FileHeaderImpl = object::elf::FileHeader64<Endianness>;
 
-#[sel4_cfg(WORD_SIZE = "32")]
+// The x86_64 kernel is converted to ELF32 for the benefit of multiboot bootloaders
+#[sel4_cfg(any(WORD_SIZE = "32", ARCH_X86))]
 type FileHeaderImpl = object::elf::FileHeader32<Endianness>;
 
 const KERNEL_HEADROOM: u64 = 256 * 1024; // TODO: make configurable
@@ -57,6 +64,20 @@ fn main() {
         (phys_addr_range(&elf).end + KERNEL_HEADROOM).next_multiple_of(GRANULE_SIZE)
     };
 
+    {
+        println!("cargo::rerun-if-env-changed={PUBLIC_KEY_ENV}");
+        let public_key = env::var(PUBLIC_KEY_ENV).ok().map(|path| {
+            println!("cargo::rerun-if-changed={path}");
+            let encoded = fs::read_to_string(&path).unwrap();
+            let bytes = hex::decode(encoded.trim())
+                .unwrap_or_else(|err| panic!("{path}: invalid public key: {err}"));
+            <[u8; 32]>::try_from(bytes)
+                .unwrap_or_else(|_| panic!("{path}: public key must be 32 bytes"))
+        });
+        let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+        fs::write(out_dir.join("public_key.rs"), format!("{public_key:?}")).unwrap();
+    }
+
     // Note that -Ttext={} is incompatible with --no-rosegment (no error),
     // just bad output. See the "Default program headers" section of:
     // https://maskray.me/blog/2020-12-19-lld-and-gnu-linker-incompatibilities
@@ -68,6 +89,11 @@ fn main() {
     // No use in loader.
     // Remove unnecessary alignment gap between segments.
     println!("cargo::rustc-link-arg=--no-rosegment");
+
+    // The 32-bit entry code uses absolute addresses, and the loader runs where it is linked.
+    if sel4_cfg_bool!(ARCH_X86) {
+        println!("cargo::rustc-link-arg=--no-pie");
+    }
 }
 
 // // //
@@ -0,0 +1,185 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+//! The kernel loader's log, handed off to the root task.
+//!
+//! The loader's log output is otherwise only written to a UART, and is lost once the kernel
+//! starts. A root task can receive a copy of it by declaring a [`LogBuffer`] in the
+//! [`SECTION_NAME`] section. `sel4-kernel-loader-add-payload` records the location of that
+//! section in the payload, and the loader fills it in just before entering the kernel.
+//!
+//! ```rust
+//! use sel4_kernel_loader_log_buffer::LogBuffer;
+//!
+//! #[unsafe(link_section = ".sel4_kernel_loader_log")]
+//! static LOADER_LOG: LogBuffer<16384> = LogBuffer::new();
+//!
+//! if let Some(log) = LOADER_LOG.get() {
+//!     println!("{log}");
+//! }
+//! ```
+
+#![no_std]
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::mem;
+use core::ptr;
+use core::slice;
+
+pub const SECTION_NAME: &str = ".sel4_kernel_loader_log";
+
+const MAGIC: u32 = 0x5334_4c4c;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+struct Header {
+    magic: u32,
+    len: u32,
+    dropped: u32,
+    reserved: u32,
+}
+
+pub const HEADER_SIZE: usize = mem::size_of::<Header>();
+
+/// A buffer, `N` bytes long excluding its header, which the loader fills with its log.
+#[repr(C)]
+pub struct LogBuffer<const N: usize> {
+    inner: UnsafeCell<Inner<N>>,
+}
+
+#[repr(C)]
+struct Inner<const N: usize> {
+    header: Header,
+    data: [u8; N],
+}
+
+unsafe impl<const N: usize> Sync for LogBuffer<N> {}
+
+impl<const N: usize> LogBuffer<N> {
+    pub const fn new() -> Self {
+        Self {
+            inner: UnsafeCell::new(Inner {
+                header: Header {
+                    magic: 0,
+                    len: 0,
+                    dropped: 0,
+                    reserved: 0,
+                },
+                data: [0; N],
+            }),
+        }
+    }
+
+    /// Returns `None` if the loader did not fill in this buffer.
+    pub fn get(&self) -> Option<Log<'_>> {
+        unsafe { Log::from_ptr(self.inner.get().cast(), mem::size_of::<Inner<N>>()) }
+    }
+}
+
+impl<const N: usize> Default for LogBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The contents of a [`LogBuffer`].
+#[derive(Debug, Copy, Clone)]
+pub struct Log<'a> {
+    text: &'a [u8],
+    dropped: usize,
+}
+
+impl<'a> Log<'a> {
+    /// # Safety
+    ///
+    /// `buf` must be valid for reads of `size` bytes for `'a`, and 4-byte aligned.
+    unsafe fn from_ptr(buf: *const u8, size: usize) -> Option<Self> {
+        if size < HEADER_SIZE {
+            return None;
+        }
+        let header = unsafe { ptr::read_volatile(buf.cast::<Header>()) };
+        if header.magic != MAGIC {
+            return None;
+        }
+        let len = usize::try_from(header.len).unwrap().min(size - HEADER_SIZE);
+        Some(Self {
+            text: unsafe { slice::from_raw_parts(buf.add(HEADER_SIZE), len) },
+            dropped: header.dropped.try_into().unwrap(),
+        })
+    }
+
+    /// The log output, which is UTF-8 unless it was truncated in the middle of a character.
+    pub fn text(&self) -> &'a [u8] {
+        self.text
+    }
+
+    /// The number of bytes of log output which did not fit.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+}
+
+impl fmt::Display for Log<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for chunk in self.text.utf8_chunks() {
+            f.write_str(chunk.valid())?;
+            if !chunk.invalid().is_empty() {
+                f.write_str(char::REPLACEMENT_CHARACTER.encode_utf8(&mut [0; 4]))?;
+            }
+        }
+        if self.dropped != 0 {
+            writeln!(f, "[{} bytes of loader log dropped]", self.dropped)?;
+        }
+        Ok(())
+    }
+}
+
+/// Fills in the buffer at `buf` with as much of `text` as fits. `dropped` is the number of bytes
+/// which had already been dropped before `text`.
+///
+/// # Safety
+///
+/// `buf` must be valid for writes of `size` bytes, and 4-byte aligned.
+pub unsafe fn hand_off(buf: *mut u8, size: usize, text: &[u8], dropped: usize) {
+    assert!(size >= HEADER_SIZE);
+    let len = text.len().min(size - HEADER_SIZE);
+    let dropped = dropped + (text.len() - len);
+    unsafe {
+        ptr::copy_nonoverlapping(text.as_ptr(), buf.add(HEADER_SIZE), len);
+        ptr::write_volatile(
+            buf.cast::<Header>(),
+            Header {
+                magic: MAGIC,
+                len: len.try_into().unwrap(),
+                dropped: dropped.try_into().unwrap_or(u32::MAX),
+                reserved: 0,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        static BUF: LogBuffer<8> = LogBuffer::new();
+        assert!(BUF.get().is_none());
+        unsafe {
+            hand_off(
+                BUF.inner.get().cast(),
+                mem::size_of_val(&BUF),
+                b"0123456789",
+                1,
+            );
+        }
+        let log = BUF.get().unwrap();
+        assert_eq!(log.text(), b"01234567");
+        assert_eq!(log.dropped(), 3);
+    }
+}
@@ -10,6 +10,16 @@ mk {
   package.name = "sel4-kernel-loader-payload-types";
   dependencies = {
     rkyv = { version = versions.rkyv; default-features = false; features = [ "alloc" "pointer_width_32" ]; };
+    miniz_oxide = { version = versions.miniz_oxide; default-features = false; features = [ "with-alloc" ]; optional = true; };
+    lz4_flex = { version = versions.lz4_flex; default-features = false; features = [ "safe-decode" "safe-encode" ]; optional = true; };
+    sha2 = { version = versions.sha2; default-features = false; optional = true; };
+    ed25519-dalek = { version = versions.ed25519-dalek; default-features = false; optional = true; };
     inherit (localCrates) sel4-platform-info-types;
   };
+  features = {
+    deflate = [ "dep:miniz_oxide" ];
+    digest = [ "dep:sha2" ];
+    lz4 = [ "dep:lz4_flex" ];
+    signature = [ "digest" "dep:ed25519-dalek" ];
+  };
 }
@@ -0,0 +1,141 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use alloc::vec::Vec;
+
+use crate::{ArchivedCompression, Compression, Region};
+
+impl Compression {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Deflate => "deflate",
+            Self::Lz4 => "lz4",
+        }
+    }
+}
+
+impl ArchivedCompression {
+    pub fn unarchive(&self) -> Compression {
+        match self {
+            Self::None => Compression::None,
+            Self::Deflate => Compression::Deflate,
+            Self::Lz4 => Compression::Lz4,
+        }
+    }
+}
+
+impl Region {
+    /// Compresses [`Region::data`], unless it is already compressed.
+    ///
+    /// Panics if the feature for `compression` is not enabled.
+    pub fn compress(&mut self, compression: Compression) {
+        if self.compression != Compression::None {
+            return;
+        }
+        self.data = match compression {
+            Compression::None => return,
+            Compression::Deflate => deflate(&self.data),
+            Compression::Lz4 => lz4_compress(&self.data),
+        };
+        self.compression = compression;
+    }
+}
+
+#[cfg(feature = "deflate")]
+fn deflate(src: &[u8]) -> Vec<u8> {
+    miniz_oxide::deflate::compress_to_vec(src, 10)
+}
+
+#[cfg(not(feature = "deflate"))]
+fn deflate(_src: &[u8]) -> Vec<u8> {
+    panic!("\"deflate\" feature is not enabled")
+}
+
+#[cfg(feature = "lz4")]
+fn lz4_compress(src: &[u8]) -> Vec<u8> {
+    lz4_flex::block::compress(src)
+}
+
+#[cfg(not(feature = "lz4"))]
+fn lz4_compress(_src: &[u8]) -> Vec<u8> {
+    panic!("\"lz4\" feature is not enabled")
+}
+
+/// Decodes `src` into the start of `dst`, returning the number of bytes written.
+pub(crate) fn decompress_into(compression: Compression, src: &[u8], dst: &mut [u8]) -> usize {
+    match compression {
+        Compression::None => {
+            dst[..src.len()].copy_from_slice(src);
+            src.len()
+        }
+        Compression::Deflate => inflate_into(src, dst),
+        Compression::Lz4 => lz4_decompress_into(src, dst),
+    }
+}
+
+#[cfg(feature = "deflate")]
+fn inflate_into(src: &[u8], dst: &mut [u8]) -> usize {
+    miniz_oxide::inflate::decompress_slice_iter_to_slice(
+        dst,
+        core::iter::once(src),
+        false, // zlib_header
+        true,  // ignore_adler32
+    )
+    .unwrap()
+}
+
+#[cfg(not(feature = "deflate"))]
+fn inflate_into(_src: &[u8], _dst: &mut [u8]) -> usize {
+    panic!("found deflated region but \"deflate\" feature is not enabled")
+}
+
+#[cfg(feature = "lz4")]
+fn lz4_decompress_into(src: &[u8], dst: &mut [u8]) -> usize {
+    lz4_flex::block::decompress_into(src, dst).unwrap()
+}
+
+#[cfg(not(feature = "lz4"))]
+fn lz4_decompress_into(_src: &[u8], _dst: &mut [u8]) -> usize {
+    panic!("found lz4 region but \"lz4\" feature is not enabled")
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+
+    use super::*;
+    use crate::Word;
+
+    fn enabled() -> Vec<Compression> {
+        let mut compressions = vec![Compression::None];
+        if cfg!(feature = "deflate") {
+            compressions.push(Compression::Deflate);
+        }
+        if cfg!(feature = "lz4") {
+            compressions.push(Compression::Lz4);
+        }
+        compressions
+    }
+
+    #[test]
+    fn round_trip() {
+        let raw = (0..10000u32).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        for compression in enabled() {
+            let mut region = Region {
+                addr: Word(0),
+                size: Word(0x4000),
+                compression: Compression::None,
+                data: raw.clone(),
+            };
+            region.compress(compression);
+            assert_eq!(region.compression, compression);
+            let mut dst = vec![0xff; 0x4000];
+            let n = decompress_into(region.compression, &region.data, &mut dst);
+            assert_eq!(&dst[..n], raw.as_slice());
+        }
+    }
+}
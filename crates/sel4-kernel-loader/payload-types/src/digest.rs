@@ -0,0 +1,189 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use sha2::{Digest as _, Sha256};
+
+use crate::{
+    ArchivedDtbFixup, ArchivedImage, ArchivedPayload, ArchivedRegion, Compression, Digest,
+    DtbFixup, Image, Payload, Region,
+};
+
+impl Payload {
+    pub fn image_digest(&self, image: &Image) -> Digest {
+        let info = &image.info;
+        digest(
+            [
+                info.kernel_entry.0,
+                info.user_image.ui_p_reg_start.0,
+                info.user_image.ui_p_reg_end.0,
+                info.user_image.pv_offset.0,
+                info.user_image.v_entry.0,
+            ],
+            info.dtb.as_ref().map(|dtb| (dtb.addr_p.0, dtb.size.0)),
+            image
+                .loader_log
+                .as_ref()
+                .map(|log| (log.addr_p.0, log.size.0)),
+            image
+                .regions
+                .iter()
+                .map(|i| region_fields(&self.regions[usize::try_from(*i).unwrap()])),
+            image.dtb_fixups.iter().map(|fixup| match fixup {
+                DtbFixup::Random { offset } => [0, (*offset).into(), 0, 0],
+                DtbFixup::LoaderFootprint {
+                    offset,
+                    address_cells,
+                    size_cells,
+                } => [
+                    1,
+                    (*offset).into(),
+                    (*address_cells).into(),
+                    (*size_cells).into(),
+                ],
+            }),
+            image.boot_modules.iter().map(|module| {
+                (
+                    region_fields(&self.regions[usize::try_from(module.region).unwrap()]),
+                    module.cmdline.as_str(),
+                )
+            }),
+        )
+    }
+}
+
+fn region_fields(region: &Region) -> (u64, u64, u8, &[u8]) {
+    (
+        region.addr.0,
+        region.size.0,
+        compression_tag(region.compression),
+        region.data.as_slice(),
+    )
+}
+
+impl ArchivedPayload {
+    pub fn image_digest(&self, image: &ArchivedImage) -> Digest {
+        let info = &image.info;
+        digest(
+            [
+                info.kernel_entry.0.to_native(),
+                info.user_image.ui_p_reg_start.0.to_native(),
+                info.user_image.ui_p_reg_end.0.to_native(),
+                info.user_image.pv_offset.0.to_native(),
+                info.user_image.v_entry.0.to_native(),
+            ],
+            info.dtb
+                .as_ref()
+                .map(|dtb| (dtb.addr_p.0.to_native(), dtb.size.0.to_native())),
+            image
+                .loader_log
+                .as_ref()
+                .map(|log| (log.addr_p.0.to_native(), log.size.0.to_native())),
+            image.regions.iter().map(|i| {
+                archived_region_fields(&self.regions[usize::try_from(i.to_native()).unwrap()])
+            }),
+            image.dtb_fixups.iter().map(|fixup| match fixup {
+                ArchivedDtbFixup::Random { offset } => [0, offset.to_native().into(), 0, 0],
+                ArchivedDtbFixup::LoaderFootprint {
+                    offset,
+                    address_cells,
+                    size_cells,
+                } => [
+                    1,
+                    offset.to_native().into(),
+                    address_cells.to_native().into(),
+                    size_cells.to_native().into(),
+                ],
+            }),
+            image.boot_modules.iter().map(|module| {
+                (
+                    archived_region_fields(self.boot_module_region(module)),
+                    module.cmdline.as_str(),
+                )
+            }),
+        )
+    }
+
+    /// Returns `false` if `image` has a digest which does not match its contents.
+    pub fn verify_image(&self, image: &ArchivedImage) -> bool {
+        image
+            .digest
+            .as_ref()
+            .is_none_or(|expected| *expected == self.image_digest(image))
+    }
+}
+
+fn archived_region_fields(region: &ArchivedRegion) -> (u64, u64, u8, &[u8]) {
+    (
+        region.addr.0.to_native(),
+        region.size.0.to_native(),
+        compression_tag(region.compression.unarchive()),
+        region.data.as_slice(),
+    )
+}
+
+// The host and the loader must feed exactly the same sequence into the hash.
+fn digest<'a>(
+    info: [u64; 5],
+    dtb: Option<(u64, u64)>,
+    loader_log: Option<(u64, u64)>,
+    regions: impl ExactSizeIterator<Item = (u64, u64, u8, &'a [u8])>,
+    dtb_fixups: impl ExactSizeIterator<Item = [u64; 4]>,
+    boot_modules: impl ExactSizeIterator<Item = ((u64, u64, u8, &'a [u8]), &'a str)>,
+) -> Digest {
+    let mut hasher = Sha256::new();
+    for word in info {
+        hasher.update(word.to_le_bytes());
+    }
+    update_with_range(&mut hasher, dtb);
+    update_with_range(&mut hasher, loader_log);
+    hasher.update(u64::try_from(regions.len()).unwrap().to_le_bytes());
+    for region in regions {
+        update_with_region(&mut hasher, region);
+    }
+    hasher.update(u64::try_from(dtb_fixups.len()).unwrap().to_le_bytes());
+    for fixup in dtb_fixups {
+        for word in fixup {
+            hasher.update(word.to_le_bytes());
+        }
+    }
+    hasher.update(u64::try_from(boot_modules.len()).unwrap().to_le_bytes());
+    for (region, cmdline) in boot_modules {
+        update_with_region(&mut hasher, region);
+        update_with_bytes(&mut hasher, cmdline.as_bytes());
+    }
+    hasher.finalize().into()
+}
+
+fn update_with_range(hasher: &mut Sha256, range: Option<(u64, u64)>) {
+    match range {
+        Some((addr_p, size)) => {
+            hasher.update([1]);
+            hasher.update(addr_p.to_le_bytes());
+            hasher.update(size.to_le_bytes());
+        }
+        None => hasher.update([0]),
+    }
+}
+
+fn update_with_region(hasher: &mut Sha256, (addr, size, compression, data): (u64, u64, u8, &[u8])) {
+    hasher.update(addr.to_le_bytes());
+    hasher.update(size.to_le_bytes());
+    hasher.update([compression]);
+    update_with_bytes(hasher, data);
+}
+
+fn update_with_bytes(hasher: &mut Sha256, bytes: &[u8]) {
+    hasher.update(u64::try_from(bytes.len()).unwrap().to_le_bytes());
+    hasher.update(bytes);
+}
+
+fn compression_tag(compression: Compression) -> u8 {
+    match compression {
+        Compression::None => 0,
+        Compression::Deflate => 1,
+        Compression::Lz4 => 2,
+    }
+}
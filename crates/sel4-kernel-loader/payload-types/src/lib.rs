@@ -8,6 +8,7 @@
 
 extern crate alloc;
 
+use alloc::string::String;
 use alloc::vec::Vec;
 use core::cmp;
 use core::fmt;
@@ -21,6 +22,17 @@ use rkyv::util::AlignedVec;
 
 use sel4_platform_info_types::PlatformInfo;
 
+mod compression;
+
+#[cfg(feature = "digest")]
+mod digest;
+
+#[cfg(feature = "signature")]
+mod signature;
+
+#[cfg(feature = "signature")]
+pub use signature::{SignatureError, derive_public_key};
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, rkyv::Archive, rkyv::Deserialize, rkyv::Serialize)]
 #[rkyv(derive(Copy, Clone, Eq, PartialEq))]
 pub struct Word(pub u64);
@@ -39,8 +51,53 @@ impl fmt::Debug for ArchivedWord {
 
 #[derive(rkyv::Archive, rkyv::Serialize)]
 pub struct Payload {
+    pub images: Vec<Image>,
+    pub regions: Vec<Region>,
+    pub selection: ImageSelection,
+}
+
+/// A kernel and user image which the loader can boot.
+#[derive(Debug, rkyv::Archive, rkyv::Serialize)]
+pub struct Image {
     pub info: PayloadInfo,
-    pub data: Vec<Region>,
+    /// Indices into [`Payload::regions`]. Images may share regions (e.g. a common kernel).
+    pub regions: Vec<u32>,
+    /// If present, the loader only boots this image if its digest matches.
+    pub digest: Option<Digest>,
+    /// Ed25519 signature of [`Image::digest`]. A loader with a public key only boots images with
+    /// valid signatures.
+    pub signature: Option<Signature>,
+    /// Edits to the image's DTB which the loader makes after copying it into place.
+    pub dtb_fixups: Vec<DtbFixup>,
+    /// Files passed to the kernel as multiboot modules. On x86, the kernel loads the root task
+    /// from the first module, rather than the loader placing it according to `info.user_image`.
+    pub boot_modules: Vec<BootModule>,
+    /// Where the loader hands off its log to the root task, if the root task asks for it.
+    pub loader_log: Option<LoaderLogInfo>,
+}
+
+pub type Digest = [u8; 32];
+
+pub type Signature = [u8; 64];
+
+pub type PublicKey = [u8; 32];
+
+#[derive(Debug, Clone, rkyv::Archive, rkyv::Serialize)]
+pub struct ImageSelection {
+    pub primary: PrimaryImage,
+    /// Image to boot if the primary image does not exist or fails verification.
+    pub fallback: Option<u32>,
+}
+
+#[derive(Debug, Clone, rkyv::Archive, rkyv::Serialize)]
+pub enum PrimaryImage {
+    Fixed(u32),
+    /// Given by a `u32` property of `/chosen` in the device tree passed by the bootloader, or
+    /// `default` if there is no such property.
+    DtbChosen {
+        property: String,
+        default: u32,
+    },
 }
 
 #[derive(Debug, Clone, rkyv::Archive, rkyv::Serialize)]
@@ -60,6 +117,37 @@ pub struct UserImageInfo {
     pub v_entry: Word,
 }
 
+/// Offsets are of `FDT_PROP` tokens, relative to the start of the DTB.
+#[derive(Debug, Clone, rkyv::Archive, rkyv::Serialize)]
+#[rkyv(derive(Debug))]
+pub enum DtbFixup {
+    /// Fill the value of this property with bytes from a hardware random number generator, or, if
+    /// there is none, replace the property with `FDT_NOP` tokens.
+    Random { offset: u32 },
+    /// Set this `reg` property to the physical footprint of the loader.
+    LoaderFootprint {
+        offset: u32,
+        address_cells: u32,
+        size_cells: u32,
+    },
+}
+
+#[derive(Debug, Clone, rkyv::Archive, rkyv::Serialize)]
+pub struct BootModule {
+    /// Index into [`Payload::regions`]. The region's `addr` is ignored, as the loader chooses where
+    /// to place modules at boot time.
+    pub region: u32,
+    pub cmdline: String,
+}
+
+/// The physical address range of a `sel4_kernel_loader_log_buffer::LogBuffer` in the user image.
+#[derive(Debug, Clone, rkyv::Archive, rkyv::Serialize)]
+#[rkyv(derive(Debug, Clone))]
+pub struct LoaderLogInfo {
+    pub addr_p: Word,
+    pub size: Word,
+}
+
 #[derive(Debug, Clone, rkyv::Archive, rkyv::Serialize)]
 #[rkyv(derive(Debug, Clone))]
 pub struct DtbInfo {
@@ -71,13 +159,35 @@ pub struct DtbInfo {
 pub struct Region {
     pub addr: Word,
     pub size: Word,
+    pub compression: Compression,
+    /// Decompresses to at most `size` bytes. The rest of the region is zeroed.
     pub data: Vec<u8>,
 }
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq, rkyv::Archive, rkyv::Serialize)]
+#[rkyv(derive(Debug, Copy, Clone, Eq, PartialEq))]
+pub enum Compression {
+    None,
+    Deflate,
+    Lz4,
+}
+
 impl ArchivedRegion {
     fn addr_range(&self) -> Range<usize> {
         self.addr.to_usize()..self.addr.to_usize().strict_add(self.size.to_usize())
     }
+
+    #[allow(clippy::missing_safety_doc)]
+    pub unsafe fn copy_data_out_to(&self, addr: usize) {
+        let dst = unsafe { slice::from_raw_parts_mut(addr as *mut _, self.size.to_usize()) };
+        let n = compression::decompress_into(self.compression.unarchive(), &self.data, dst);
+        let dst_zero = &mut dst[n..];
+        // NOTE slice::fill is too slow
+        // TODO(nspin) is that still true?
+        unsafe {
+            ptr::write_bytes(dst_zero.as_mut_ptr(), 0, dst_zero.len());
+        }
+    }
 }
 
 impl Payload {
@@ -92,28 +202,43 @@ impl Payload {
 }
 
 impl ArchivedPayload {
+    pub fn image(&self, index: u32) -> Option<&ArchivedImage> {
+        self.images.get(usize::try_from(index).ok()?)
+    }
+
+    pub fn image_regions<'a>(
+        &'a self,
+        image: &'a ArchivedImage,
+    ) -> impl Iterator<Item = &'a ArchivedRegion> {
+        image
+            .regions
+            .iter()
+            .map(|i| &self.regions[usize::try_from(i.to_native()).unwrap()])
+    }
+
+    pub fn boot_module_region(&self, module: &ArchivedBootModule) -> &ArchivedRegion {
+        &self.regions[usize::try_from(module.region.to_native()).unwrap()]
+    }
+
     #[allow(clippy::missing_safety_doc)]
-    pub unsafe fn copy_data_out(&self) {
-        for region in self.data.iter() {
-            let src = &region.data;
-            let dst = unsafe {
-                slice::from_raw_parts_mut(region.addr.to_usize() as *mut _, region.size.to_usize())
-            };
-            let (dst_data, dst_zero) = dst.split_at_mut(src.len());
-            dst_data.copy_from_slice(src);
-            // NOTE slice::fill is too slow
-            // TODO(nspin) is that still true?
+    pub unsafe fn copy_data_out(&self, image: &ArchivedImage) {
+        for region in self.image_regions(image) {
             unsafe {
-                ptr::write_bytes(dst_zero.as_mut_ptr(), 0, dst_zero.len());
+                region.copy_data_out_to(region.addr.to_usize());
             }
         }
     }
 
-    pub fn sanity_check(&self, platform_info: &PlatformInfo, own_footprint: Range<usize>) {
+    pub fn sanity_check(
+        &self,
+        image: &ArchivedImage,
+        platform_info: &PlatformInfo,
+        own_footprint: Range<usize>,
+    ) {
         let memory = &platform_info.memory;
         let own_footprint_u64 = range_into(&own_footprint);
         assert!(any_range_contains(memory.iter(), &own_footprint_u64));
-        for region in self.data.iter() {
+        for region in self.image_regions(image) {
             let region_u64 = range_into(&region.addr_range());
             assert!(any_range_contains(memory.iter(), &region_u64));
             assert!(ranges_are_disjoint(&own_footprint_u64, &region_u64));
@@ -0,0 +1,175 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use core::fmt;
+
+use ed25519_dalek::{Signer as _, SigningKey, VerifyingKey};
+
+use crate::{ArchivedImage, ArchivedPayload, Digest, Image, Payload, PublicKey, Signature};
+
+impl Payload {
+    /// Sets the digest and signature of each image.
+    pub fn sign_images(&mut self, secret_key: &[u8; 32]) {
+        let signing_key = SigningKey::from_bytes(secret_key);
+        for i in 0..self.images.len() {
+            let digest = self.image_digest(&self.images[i]);
+            let image: &mut Image = &mut self.images[i];
+            image.digest = Some(digest);
+            image.signature = Some(signing_key.sign(&digest).to_bytes());
+        }
+    }
+}
+
+impl ArchivedPayload {
+    /// Checks that `image` carries a valid signature, by the holder of `public_key`, of a digest
+    /// which matches its contents.
+    pub fn verify_image_signature(
+        &self,
+        image: &ArchivedImage,
+        public_key: &PublicKey,
+    ) -> Result<(), SignatureError> {
+        let signature = image
+            .signature
+            .as_ref()
+            .ok_or(SignatureError::MissingSignature)?;
+        let digest = self.image_digest(image);
+        if image
+            .digest
+            .as_ref()
+            .is_some_and(|expected| *expected != digest)
+        {
+            return Err(SignatureError::DigestMismatch);
+        }
+        verify(public_key, &digest, signature)
+    }
+}
+
+pub fn derive_public_key(secret_key: &[u8; 32]) -> PublicKey {
+    SigningKey::from_bytes(secret_key)
+        .verifying_key()
+        .to_bytes()
+}
+
+fn verify(
+    public_key: &PublicKey,
+    digest: &Digest,
+    signature: &Signature,
+) -> Result<(), SignatureError> {
+    let verifying_key =
+        VerifyingKey::from_bytes(public_key).map_err(|_| SignatureError::InvalidPublicKey)?;
+    verifying_key
+        .verify_strict(digest, &ed25519_dalek::Signature::from_bytes(signature))
+        .map_err(|_| SignatureError::BadSignature)
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SignatureError {
+    MissingSignature,
+    DigestMismatch,
+    InvalidPublicKey,
+    BadSignature,
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingSignature => write!(f, "image is not signed"),
+            Self::DigestMismatch => write!(f, "image digest does not match its contents"),
+            Self::InvalidPublicKey => write!(f, "invalid public key"),
+            Self::BadSignature => write!(f, "image signature is not valid for this key"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+
+    use super::*;
+    use crate::{
+        Compression, ImageSelection, PayloadInfo, PrimaryImage, Region, UserImageInfo, Word,
+    };
+
+    const SECRET_KEY: [u8; 32] = [7; 32];
+
+    fn example_payload() -> Payload {
+        let info = PayloadInfo {
+            kernel_entry: Word(0x1000),
+            user_image: UserImageInfo {
+                ui_p_reg_start: Word(0x8000),
+                ui_p_reg_end: Word(0x9000),
+                pv_offset: Word(0),
+                v_entry: Word(0x8000),
+            },
+            dtb: None,
+        };
+        Payload {
+            images: vec![Image {
+                info,
+                regions: vec![0],
+                digest: None,
+                signature: None,
+                dtb_fixups: vec![],
+                boot_modules: vec![],
+                loader_log: None,
+            }],
+            regions: vec![Region {
+                addr: Word(0x8000),
+                size: Word(0x1000),
+                compression: Compression::None,
+                data: vec![1, 2, 3],
+            }],
+            selection: ImageSelection {
+                primary: PrimaryImage::Fixed(0),
+                fallback: None,
+            },
+        }
+    }
+
+    fn verify(payload: &Payload, public_key: &PublicKey) -> Result<(), SignatureError> {
+        let bytes = payload.to_bytes().unwrap();
+        let archived = unsafe { Payload::access_unchecked(&bytes) };
+        archived.verify_image_signature(&archived.images[0], public_key)
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut payload = example_payload();
+        let public_key = derive_public_key(&SECRET_KEY);
+        assert_eq!(
+            verify(&payload, &public_key),
+            Err(SignatureError::MissingSignature)
+        );
+        payload.sign_images(&SECRET_KEY);
+        assert_eq!(verify(&payload, &public_key), Ok(()));
+        assert_eq!(
+            verify(&payload, &derive_public_key(&[8; 32])),
+            Err(SignatureError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn tampering_is_detected() {
+        let public_key = derive_public_key(&SECRET_KEY);
+
+        let mut payload = example_payload();
+        payload.sign_images(&SECRET_KEY);
+        payload.regions[0].data[0] ^= 1;
+        assert_eq!(
+            verify(&payload, &public_key),
+            Err(SignatureError::DigestMismatch)
+        );
+
+        let mut payload = example_payload();
+        payload.sign_images(&SECRET_KEY);
+        payload.images[0].info.kernel_entry = Word(0x2000);
+        payload.images[0].digest = Some(payload.image_digest(&payload.images[0]));
+        assert_eq!(
+            verify(&payload, &public_key),
+            Err(SignatureError::BadSignature)
+        );
+    }
+}
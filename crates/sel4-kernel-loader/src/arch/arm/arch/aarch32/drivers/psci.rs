@@ -26,15 +26,32 @@ static CHOSEN_PSCI_FUNC: PsciFunc = if sel4_cfg_bool!(ARM_HYPERVISOR_SUPPORT) {
 
 const PSCI_FID_CPU_ON: usize = 0x84000003;
 
-unsafe fn psci_cpu_on(target_cpu: usize, entry_point: usize, context_id: usize) {
-    let ret = unsafe { CHOSEN_PSCI_FUNC(PSCI_FID_CPU_ON, target_cpu, entry_point, context_id) };
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum Conduit {
+    Smc,
+    Hvc,
+}
+
+unsafe fn psci_cpu_on(func: PsciFunc, target_cpu: usize, entry_point: usize, context_id: usize) {
+    let ret = unsafe { func(PSCI_FID_CPU_ON, target_cpu, entry_point, context_id) };
     assert_eq!(ret, 0);
 }
 
 pub(crate) fn start_secondary_core(core_id: usize, sp: usize) {
     let start = psci_secondary_entry as *const PsciSecondaryEntryFn as usize;
     unsafe {
-        psci_cpu_on(core_id, start, sp);
+        psci_cpu_on(CHOSEN_PSCI_FUNC, core_id, start, sp);
+    }
+}
+
+pub(crate) fn start_secondary_core_via(conduit: Conduit, target_cpu: usize, sp: usize) {
+    let func = match conduit {
+        Conduit::Smc => smc_psci_func as PsciFunc,
+        Conduit::Hvc => hvc_psci_func as PsciFunc,
+    };
+    let start = psci_secondary_entry as *const PsciSecondaryEntryFn as usize;
+    unsafe {
+        psci_cpu_on(func, target_cpu, start, sp);
     }
 }
 
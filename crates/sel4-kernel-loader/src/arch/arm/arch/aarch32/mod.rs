@@ -14,8 +14,8 @@ use crate::{arch::Arch, main, secondary_main};
 pub(crate) mod drivers;
 
 #[unsafe(no_mangle)]
-extern "C" fn arch_main() -> ! {
-    main(())
+extern "C" fn arch_main(bootloader_dtb: usize) -> ! {
+    main((), bootloader_dtb)
 }
 
 #[unsafe(no_mangle)]
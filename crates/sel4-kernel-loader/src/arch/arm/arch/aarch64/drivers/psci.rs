@@ -6,13 +6,25 @@
 
 use core::arch::global_asm;
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum Conduit {
+    Smc,
+    Hvc,
+}
+
 pub(crate) fn start_secondary_core(core_id: usize, sp: usize) {
+    start_secondary_core_via(Conduit::Smc, core_id, sp)
+}
+
+pub(crate) fn start_secondary_core_via(conduit: Conduit, target_cpu: usize, sp: usize) {
     let start = psci_secondary_entry as *const PsciSecondaryEntryFn as usize;
-    smccc::psci::cpu_on::<smccc::Smc>(
-        core_id.try_into().unwrap(),
-        start.try_into().unwrap(),
-        sp.try_into().unwrap(),
-    )
+    let target_cpu = target_cpu.try_into().unwrap();
+    let start = start.try_into().unwrap();
+    let sp = sp.try_into().unwrap();
+    match conduit {
+        Conduit::Smc => smccc::psci::cpu_on::<smccc::Smc>(target_cpu, start, sp),
+        Conduit::Hvc => smccc::psci::cpu_on::<smccc::Hvc>(target_cpu, start, sp),
+    }
     .unwrap();
 }
 
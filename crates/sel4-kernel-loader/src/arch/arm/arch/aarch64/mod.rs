@@ -21,8 +21,8 @@ unsafe extern "C" {
 }
 
 #[unsafe(no_mangle)]
-extern "C" fn arch_main() -> ! {
-    main(())
+extern "C" fn arch_main(bootloader_dtb: usize) -> ! {
+    main((), bootloader_dtb)
 }
 
 #[unsafe(no_mangle)]
@@ -43,6 +43,19 @@ impl Arch for ArchImpl {
         }
     }
 
+    fn fill_random(buf: &mut [u8]) -> bool {
+        if !has_rndr() {
+            return false;
+        }
+        for chunk in buf.chunks_mut(8) {
+            let Some(word) = rndr() else {
+                return false;
+            };
+            chunk.copy_from_slice(&word.to_ne_bytes()[..chunk.len()]);
+        }
+        true
+    }
+
     fn enter_kernel(
         core_id: usize,
         payload_info: &ArchivedPayloadInfo,
@@ -91,6 +104,36 @@ fn get_current_el() -> Option<CurrentEL::EL::Value> {
     CurrentEL.read_as_enum(CurrentEL::EL)
 }
 
+fn has_rndr() -> bool {
+    let isar0: u64;
+    unsafe {
+        asm!("mrs {isar0}, id_aa64isar0_el1", isar0 = out(reg) isar0);
+    }
+    (isar0 >> 60) & 0xf != 0
+}
+
+// RNDR may transiently fail, in which case it sets PSTATE.Z
+fn rndr() -> Option<u64> {
+    const ATTEMPTS: usize = 16;
+    for _ in 0..ATTEMPTS {
+        let value: u64;
+        let ok: u64;
+        unsafe {
+            // RNDR is encoded by name so that no target feature is required
+            asm!(
+                "mrs {value}, s3_3_c2_c4_0",
+                "cset {ok}, ne",
+                value = out(reg) value,
+                ok = out(reg) ok,
+            );
+        }
+        if ok != 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
 #[inline(never)] // never inline to work around issues with optimizer
 unsafe fn set_tpidr(tpidr: usize) {
     unsafe {
@@ -4,8 +4,10 @@
 // SPDX-License-Identifier: BSD-2-Clause
 //
 
+use core::ops::Range;
+
 use sel4_config::sel4_cfg_if;
-use sel4_kernel_loader_payload_types::ArchivedPayloadInfo;
+use sel4_kernel_loader_payload_types::{ArchivedImage, ArchivedPayload, ArchivedPayloadInfo};
 
 sel4_cfg_if! {
     if #[sel4_cfg(ARCH_ARM)] {
@@ -14,6 +16,9 @@ sel4_cfg_if! {
     } else if #[sel4_cfg(ARCH_RISCV)] {
         #[path = "riscv/mod.rs"]
         mod imp;
+    } else if #[sel4_cfg(ARCH_X86)] {
+        #[path = "x86/mod.rs"]
+        mod imp;
     }
 }
 
@@ -22,16 +27,38 @@ sel4_cfg_if! {
 mod arm;
 #[cfg(false)]
 mod riscv;
+#[cfg(false)]
+mod x86;
 
 pub(crate) use imp::*;
 
 pub(crate) trait Arch {
     type PerCore;
 
+    /// Whether the loader starts secondary cores, rather than leaving that to the kernel.
+    const STARTS_SECONDARY_CORES: bool = true;
+
     fn init() {}
 
     fn idle() -> !;
 
+    /// Fills `buf` from a hardware random number generator, if one is available.
+    fn fill_random(_buf: &mut [u8]) -> bool {
+        false
+    }
+
+    /// Called on the primary core after the image has been copied into place.
+    fn prepare_boot(
+        _payload: &'static ArchivedPayload,
+        image: &'static ArchivedImage,
+        _own_footprint: &Range<usize>,
+    ) {
+        assert!(
+            image.boot_modules.is_empty(),
+            "boot modules are not supported on this architecture"
+        );
+    }
+
     fn enter_kernel(
         core_id: usize,
         payload_info: &ArchivedPayloadInfo,
@@ -0,0 +1,180 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use core::arch::asm;
+use core::ops::Range;
+use core::ptr;
+
+use spin::Once;
+
+use sel4_kernel_loader_payload_types::{ArchivedImage, ArchivedPayload, ArchivedPayloadInfo};
+use sel4_platform_info_types::PlatformInfo;
+
+use crate::{arch::Arch, main};
+
+mod multiboot;
+
+use multiboot::{BootInfo, MULTIBOOT2_BOOTLOADER_MAGIC, Module, build_multiboot2_info};
+
+const PAGE_SIZE: usize = 4096;
+
+const MAX_NUM_BOOT_MODULES: usize = 8;
+
+const KERNEL_MULTIBOOT2_INFO_SIZE: usize = 8192;
+
+static BOOT_INFO: Once<BootInfo> = Once::new();
+
+#[repr(C, align(8))]
+struct KernelMultiboot2Info([u8; KERNEL_MULTIBOOT2_INFO_SIZE]);
+
+static mut KERNEL_MULTIBOOT2_INFO: KernelMultiboot2Info =
+    KernelMultiboot2Info([0; KERNEL_MULTIBOOT2_INFO_SIZE]);
+
+unsafe extern "C" {
+    fn enter_kernel_32(entry: u32, magic: u32, info: u32) -> !;
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn arch_main(magic: u32, info: u32) -> ! {
+    BOOT_INFO.call_once(|| unsafe { BootInfo::parse(magic, info.try_into().unwrap()) });
+    // No device tree on x86
+    main((), 0)
+}
+
+pub(crate) fn platform_info() -> PlatformInfo<'static> {
+    PlatformInfo {
+        memory: boot_info().available_memory(),
+        devices: &[],
+    }
+}
+
+fn boot_info() -> &'static BootInfo {
+    BOOT_INFO.get().unwrap()
+}
+
+pub(crate) unsafe fn outb(port: u16, value: u8) {
+    unsafe {
+        asm!("out dx, al", in("dx") port, in("al") value, options(nomem, nostack, preserves_flags));
+    }
+}
+
+pub(crate) unsafe fn inb(port: u16) -> u8 {
+    let value: u8;
+    unsafe {
+        asm!("in al, dx", out("al") value, in("dx") port, options(nomem, nostack, preserves_flags));
+    }
+    value
+}
+
+pub(crate) enum ArchImpl {}
+
+impl Arch for ArchImpl {
+    type PerCore = ();
+
+    // The kernel starts application processors itself
+    const STARTS_SECONDARY_CORES: bool = false;
+
+    fn idle() -> ! {
+        loop {
+            unsafe {
+                asm!("cli; hlt");
+            }
+        }
+    }
+
+    fn prepare_boot(
+        payload: &'static ArchivedPayload,
+        image: &'static ArchivedImage,
+        own_footprint: &Range<usize>,
+    ) {
+        let boot_info = boot_info();
+
+        boot_info.log_warnings();
+
+        assert!(image.boot_modules.len() <= MAX_NUM_BOOT_MODULES);
+        let mut modules = [const { None }; MAX_NUM_BOOT_MODULES];
+        // Regions which have already been copied out, and the bootloader's data, must not be
+        // clobbered
+        let reserved = payload
+            .image_regions(image)
+            .map(|region| range_u64(&region.addr_range()))
+            .chain(boot_info.bootloader_data().iter().cloned());
+        let mut next_addr = own_footprint.end;
+        for (module, slot) in image.boot_modules.iter().zip(modules.iter_mut()) {
+            let region = payload.boot_module_region(module);
+            let size = region.size.to_usize();
+            let range = find_free_range(
+                next_addr.try_into().unwrap(),
+                size.try_into().unwrap(),
+                boot_info.available_memory(),
+                reserved.clone(),
+            )
+            .map(|range| usize::try_from(range.start).unwrap()..usize::try_from(range.end).unwrap())
+            .unwrap_or_else(|| panic!("no room for boot module of size {size:#x}"));
+            log::debug!("Boot module: {range:#x?} ({})", module.cmdline.as_str());
+            unsafe {
+                region.copy_data_out_to(range.start);
+            }
+            next_addr = range.end;
+            *slot = Some(Module {
+                range,
+                cmdline: module.cmdline.as_str(),
+            });
+        }
+
+        if !boot_info.has_rsdp() {
+            log::debug!("No ACPI RSDP from bootloader, kernel will search for it");
+        }
+
+        let buf = unsafe { &mut (*ptr::addr_of_mut!(KERNEL_MULTIBOOT2_INFO)).0 };
+        build_multiboot2_info(buf, boot_info, modules.iter().flatten());
+    }
+
+    fn enter_kernel(
+        _core_id: usize,
+        payload_info: &ArchivedPayloadInfo,
+        _per_core: Self::PerCore,
+    ) -> ! {
+        let entry = u32::try_from(payload_info.kernel_entry.to_usize()).unwrap();
+        let info = u32::try_from(ptr::addr_of!(KERNEL_MULTIBOOT2_INFO) as usize).unwrap();
+        unsafe { enter_kernel_32(entry, MULTIBOOT2_BOOTLOADER_MAGIC, info) }
+    }
+}
+
+/// Finds the lowest page-aligned range of `size` bytes at or above `start` which lies within
+/// available memory and is disjoint from each of `reserved`.
+fn find_free_range(
+    mut start: u64,
+    size: u64,
+    available: &[Range<u64>],
+    reserved: impl Iterator<Item = Range<u64>> + Clone,
+) -> Option<Range<u64>> {
+    loop {
+        start = start.next_multiple_of(PAGE_SIZE as u64);
+        let range = start..start.checked_add(size)?;
+        if let Some(overlapping) = reserved
+            .clone()
+            .find(|other| other.start.max(range.start) < other.end.min(range.end))
+        {
+            start = overlapping.end;
+        } else if available
+            .iter()
+            .any(|this| this.start <= range.start && range.end <= this.end)
+        {
+            return Some(range);
+        } else {
+            start = available
+                .iter()
+                .map(|this| this.start)
+                .filter(|&this_start| this_start > start)
+                .min()?;
+        }
+    }
+}
+
+fn range_u64(range: &Range<usize>) -> Range<u64> {
+    range.start.try_into().unwrap()..range.end.try_into().unwrap()
+}
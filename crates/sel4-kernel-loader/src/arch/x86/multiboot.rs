@@ -0,0 +1,337 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+// Parses the multiboot or multiboot2 information passed by the bootloader, and builds the
+// multiboot2 information passed to the kernel.
+
+use core::ops::Range;
+use core::ptr;
+
+pub(crate) const MULTIBOOT_BOOTLOADER_MAGIC: u32 = 0x2bad_b002;
+pub(crate) const MULTIBOOT2_BOOTLOADER_MAGIC: u32 = 0x36d7_6289;
+
+const MULTIBOOT_INFO_MEMORY: u32 = 1 << 0;
+const MULTIBOOT_INFO_CMDLINE: u32 = 1 << 2;
+const MULTIBOOT_INFO_MEM_MAP: u32 = 1 << 6;
+
+const MULTIBOOT2_TAG_END: u32 = 0;
+const MULTIBOOT2_TAG_CMDLINE: u32 = 1;
+const MULTIBOOT2_TAG_MODULE: u32 = 3;
+const MULTIBOOT2_TAG_MMAP: u32 = 6;
+const MULTIBOOT2_TAG_ACPI_OLD: u32 = 14;
+const MULTIBOOT2_TAG_ACPI_NEW: u32 = 15;
+
+const MEMORY_AVAILABLE: u32 = 1;
+
+const MMAP_ENTRY_SIZE: u32 = 24;
+
+const RSDP_V1_SIZE: usize = 20;
+const RSDP_V2_SIZE: usize = 36;
+
+const MULTIBOOT_INFO_SIZE: usize = 116;
+
+const MAX_MEMORY_MAP_ENTRIES: usize = 64;
+const MAX_CMDLINE_LEN: usize = 1024;
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct MemoryMapEntry {
+    pub(crate) base: u64,
+    pub(crate) len: u64,
+    pub(crate) ty: u32,
+}
+
+/// What the loader keeps of the information passed by the bootloader, which may lie in memory
+/// that the payload overwrites.
+pub(crate) struct BootInfo {
+    memory_map: [MemoryMapEntry; MAX_MEMORY_MAP_ENTRIES],
+    num_memory_map_entries: usize,
+    available_memory: [Range<u64>; MAX_MEMORY_MAP_ENTRIES],
+    num_available_memory_ranges: usize,
+    cmdline: [u8; MAX_CMDLINE_LEN],
+    cmdline_len: usize,
+    cmdline_truncated: bool,
+    num_dropped_memory_map_entries: usize,
+    rsdp_v1: Option<[u8; RSDP_V1_SIZE]>,
+    rsdp_v2: Option<[u8; RSDP_V2_SIZE]>,
+    bootloader_data: [Range<u64>; 2],
+}
+
+impl BootInfo {
+    /// # Safety
+    ///
+    /// `info` must point to a valid multiboot or multiboot2 information structure, according to
+    /// `magic`.
+    pub(crate) unsafe fn parse(magic: u32, info: usize) -> Self {
+        let mut this = Self {
+            memory_map: [MemoryMapEntry {
+                base: 0,
+                len: 0,
+                ty: 0,
+            }; MAX_MEMORY_MAP_ENTRIES],
+            num_memory_map_entries: 0,
+            available_memory: [const { 0..0 }; MAX_MEMORY_MAP_ENTRIES],
+            num_available_memory_ranges: 0,
+            cmdline: [0; MAX_CMDLINE_LEN],
+            cmdline_len: 0,
+            cmdline_truncated: false,
+            num_dropped_memory_map_entries: 0,
+            rsdp_v1: None,
+            rsdp_v2: None,
+            bootloader_data: [const { 0..0 }; 2],
+        };
+        match magic {
+            MULTIBOOT_BOOTLOADER_MAGIC => unsafe { this.parse_multiboot(info) },
+            MULTIBOOT2_BOOTLOADER_MAGIC => unsafe { this.parse_multiboot2(info) },
+            _ => panic!("not booted by a multiboot-compliant bootloader (magic = {magic:#x})"),
+        }
+        for entry in this.memory_map() {
+            if entry.ty == MEMORY_AVAILABLE && entry.len > 0 {
+                this.available_memory[this.num_available_memory_ranges] =
+                    entry.base..entry.base + entry.len;
+                this.num_available_memory_ranges += 1;
+            }
+        }
+        this
+    }
+
+    /// Parsing happens before logging is set up, so anything lost along the way is reported
+    /// later.
+    pub(crate) fn log_warnings(&self) {
+        if self.cmdline_truncated {
+            log::warn!("Command line truncated to {MAX_CMDLINE_LEN} bytes");
+        }
+        if self.num_dropped_memory_map_entries > 0 {
+            log::warn!(
+                "Dropped {} memory map entries, as there is only room for {MAX_MEMORY_MAP_ENTRIES}",
+                self.num_dropped_memory_map_entries
+            );
+        }
+    }
+
+    unsafe fn parse_multiboot(&mut self, info: usize) {
+        self.bootloader_data[0] = range_u64(info, MULTIBOOT_INFO_SIZE);
+        let flags = unsafe { read::<u32>(info) };
+        if flags & MULTIBOOT_INFO_CMDLINE != 0 {
+            unsafe {
+                self.set_cmdline(read::<u32>(info + 16).try_into().unwrap());
+            }
+        }
+        if flags & MULTIBOOT_INFO_MEM_MAP != 0 {
+            let len = usize::try_from(unsafe { read::<u32>(info + 44) }).unwrap();
+            let addr = usize::try_from(unsafe { read::<u32>(info + 48) }).unwrap();
+            self.bootloader_data[1] = range_u64(addr, len);
+            let mut entry = addr;
+            while entry < addr + len {
+                // The size field does not count itself
+                let size = usize::try_from(unsafe { read::<u32>(entry) }).unwrap();
+                self.push_memory_map_entry(MemoryMapEntry {
+                    base: unsafe { read(entry + 4) },
+                    len: unsafe { read(entry + 12) },
+                    ty: unsafe { read(entry + 20) },
+                });
+                entry += size + 4;
+            }
+        } else if flags & MULTIBOOT_INFO_MEMORY != 0 {
+            let mem_lower = u64::from(unsafe { read::<u32>(info + 4) });
+            let mem_upper = u64::from(unsafe { read::<u32>(info + 8) });
+            for (base, len) in [(0, mem_lower * 1024), (0x10_0000, mem_upper * 1024)] {
+                self.push_memory_map_entry(MemoryMapEntry {
+                    base,
+                    len,
+                    ty: MEMORY_AVAILABLE,
+                });
+            }
+        }
+    }
+
+    unsafe fn parse_multiboot2(&mut self, info: usize) {
+        let total_size = usize::try_from(unsafe { read::<u32>(info) }).unwrap();
+        self.bootloader_data[0] = range_u64(info, total_size);
+        let mut tag = info + 8;
+        while tag < info + total_size {
+            let ty = unsafe { read::<u32>(tag) };
+            let size = usize::try_from(unsafe { read::<u32>(tag + 4) }).unwrap();
+            match ty {
+                MULTIBOOT2_TAG_END => break,
+                MULTIBOOT2_TAG_CMDLINE => unsafe { self.set_cmdline(tag + 8) },
+                MULTIBOOT2_TAG_MMAP => {
+                    let entry_size = usize::try_from(unsafe { read::<u32>(tag + 8) }).unwrap();
+                    let mut entry = tag + 16;
+                    while entry + entry_size <= tag + size {
+                        self.push_memory_map_entry(MemoryMapEntry {
+                            base: unsafe { read(entry) },
+                            len: unsafe { read(entry + 8) },
+                            ty: unsafe { read(entry + 16) },
+                        });
+                        entry += entry_size;
+                    }
+                }
+                MULTIBOOT2_TAG_ACPI_OLD => self.rsdp_v1 = Some(unsafe { read(tag + 8) }),
+                MULTIBOOT2_TAG_ACPI_NEW => self.rsdp_v2 = Some(unsafe { read(tag + 8) }),
+                _ => {}
+            }
+            tag = (tag + size).next_multiple_of(8);
+        }
+    }
+
+    unsafe fn set_cmdline(&mut self, addr: usize) {
+        let mut len = 0;
+        loop {
+            let c = unsafe { read::<u8>(addr + len) };
+            if c == 0 {
+                break;
+            }
+            if len == MAX_CMDLINE_LEN {
+                self.cmdline_truncated = true;
+                break;
+            }
+            self.cmdline[len] = c;
+            len += 1;
+        }
+        self.cmdline_len = len;
+    }
+
+    /// Merges entries with their predecessors where possible. When the map is full, entries for
+    /// memory which is not available are dropped first, which only costs the kernel information.
+    fn push_memory_map_entry(&mut self, entry: MemoryMapEntry) {
+        if let Some(last) = self.memory_map[..self.num_memory_map_entries].last_mut()
+            && last.ty == entry.ty
+            && last.base.checked_add(last.len) == Some(entry.base)
+        {
+            last.len += entry.len;
+            return;
+        }
+        if self.num_memory_map_entries == MAX_MEMORY_MAP_ENTRIES {
+            self.num_dropped_memory_map_entries += 1;
+            let victim = match entry.ty {
+                MEMORY_AVAILABLE => self
+                    .memory_map()
+                    .iter()
+                    .position(|existing| existing.ty != MEMORY_AVAILABLE),
+                _ => None,
+            };
+            let Some(victim) = victim else {
+                return;
+            };
+            self.memory_map.copy_within(victim + 1.., victim);
+            self.num_memory_map_entries -= 1;
+        }
+        self.memory_map[self.num_memory_map_entries] = entry;
+        self.num_memory_map_entries += 1;
+    }
+
+    pub(crate) fn memory_map(&self) -> &[MemoryMapEntry] {
+        &self.memory_map[..self.num_memory_map_entries]
+    }
+
+    pub(crate) fn available_memory(&self) -> &[Range<u64>] {
+        &self.available_memory[..self.num_available_memory_ranges]
+    }
+
+    pub(crate) fn cmdline(&self) -> &[u8] {
+        &self.cmdline[..self.cmdline_len]
+    }
+
+    pub(crate) fn has_rsdp(&self) -> bool {
+        self.rsdp_v1.is_some() || self.rsdp_v2.is_some()
+    }
+
+    /// The memory holding the bootloader's information structure and memory map.
+    pub(crate) fn bootloader_data(&self) -> &[Range<u64>] {
+        &self.bootloader_data
+    }
+}
+
+pub(crate) struct Module<'a> {
+    pub(crate) range: Range<usize>,
+    pub(crate) cmdline: &'a str,
+}
+
+/// Writes the multiboot2 information for the kernel into `buf`, which must be 8-byte aligned.
+pub(crate) fn build_multiboot2_info<'a>(
+    buf: &mut [u8],
+    boot_info: &BootInfo,
+    modules: impl Iterator<Item = &'a Module<'a>>,
+) {
+    let mut w = Writer { buf, pos: 0 };
+    w.u32(0); // total_size, filled in below
+    w.u32(0);
+    if !boot_info.cmdline().is_empty() {
+        w.tag(MULTIBOOT2_TAG_CMDLINE, |w| {
+            w.bytes(boot_info.cmdline());
+            w.bytes(&[0]);
+        });
+    }
+    for module in modules {
+        w.tag(MULTIBOOT2_TAG_MODULE, |w| {
+            w.u32(module.range.start.try_into().unwrap());
+            w.u32(module.range.end.try_into().unwrap());
+            w.bytes(module.cmdline.as_bytes());
+            w.bytes(&[0]);
+        });
+    }
+    w.tag(MULTIBOOT2_TAG_MMAP, |w| {
+        w.u32(MMAP_ENTRY_SIZE);
+        w.u32(0); // entry_version
+        for entry in boot_info.memory_map() {
+            w.u64(entry.base);
+            w.u64(entry.len);
+            w.u32(entry.ty);
+            w.u32(0);
+        }
+    });
+    if let Some(rsdp) = &boot_info.rsdp_v1 {
+        w.tag(MULTIBOOT2_TAG_ACPI_OLD, |w| w.bytes(rsdp));
+    }
+    if let Some(rsdp) = &boot_info.rsdp_v2 {
+        w.tag(MULTIBOOT2_TAG_ACPI_NEW, |w| w.bytes(rsdp));
+    }
+    w.tag(MULTIBOOT2_TAG_END, |_| {});
+    let total_size = w.pos;
+    w.buf[..4].copy_from_slice(&u32::try_from(total_size).unwrap().to_le_bytes());
+}
+
+struct Writer<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl Writer<'_> {
+    fn bytes(&mut self, bytes: &[u8]) {
+        let end = self.pos + bytes.len();
+        assert!(end <= self.buf.len(), "multiboot2 information too large");
+        self.buf[self.pos..end].copy_from_slice(bytes);
+        self.pos = end;
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.bytes(&value.to_le_bytes())
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.bytes(&value.to_le_bytes())
+    }
+
+    fn tag(&mut self, ty: u32, f: impl FnOnce(&mut Self)) {
+        let padded = self.pos.next_multiple_of(8);
+        self.bytes(&[0; 8][..padded - self.pos]);
+        let start = self.pos;
+        self.u32(ty);
+        self.u32(0); // size, filled in below
+        f(self);
+        let size = u32::try_from(self.pos - start).unwrap();
+        self.buf[start + 4..start + 8].copy_from_slice(&size.to_le_bytes());
+    }
+}
+
+fn range_u64(addr: usize, len: usize) -> Range<u64> {
+    let start = u64::try_from(addr).unwrap();
+    start..start + u64::try_from(len).unwrap()
+}
+
+unsafe fn read<T>(addr: usize) -> T {
+    unsafe { ptr::read_unaligned(addr as *const T) }
+}
@@ -0,0 +1,26 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use fdt::Fdt;
+
+use crate::plat::platform_info;
+
+/// The DTB passed by the bootloader, if `dtb_addr` plausibly points to one.
+///
+/// It is only valid until the selected image is copied into place, which may overwrite it.
+pub(crate) fn get(dtb_addr: usize) -> Option<Fdt<'static>> {
+    // Firmware which does not pass a device tree may leave anything in this register
+    let plausible = dtb_addr != 0
+        && dtb_addr.is_multiple_of(8)
+        && platform_info()
+            .memory
+            .iter()
+            .any(|region| region.contains(&dtb_addr.try_into().unwrap()));
+    if !plausible {
+        return None;
+    }
+    unsafe { Fdt::from_ptr(dtb_addr as *const u8) }.ok()
+}
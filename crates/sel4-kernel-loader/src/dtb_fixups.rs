@@ -0,0 +1,81 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use core::ops::Range;
+use core::slice;
+
+use sel4_kernel_loader_payload_types::{ArchivedDtbFixup, ArchivedImage};
+
+use crate::arch::{Arch, ArchImpl};
+
+const FDT_PROP: u32 = 0x3;
+const FDT_NOP: u32 = 0x4;
+
+const PROP_HEADER_SIZE: usize = 12;
+
+pub(crate) fn apply_dtb_fixups(image: &ArchivedImage, own_footprint: &Range<usize>) {
+    if image.dtb_fixups.is_empty() {
+        return;
+    }
+
+    let dtb_info = image.info.dtb.as_ref().unwrap();
+    let dtb = unsafe {
+        slice::from_raw_parts_mut(
+            dtb_info.addr_p.to_usize() as *mut u8,
+            dtb_info.size.to_usize(),
+        )
+    };
+
+    for fixup in image.dtb_fixups.iter() {
+        match fixup {
+            ArchivedDtbFixup::Random { offset } => {
+                let offset = usize::try_from(offset.to_native()).unwrap();
+                let value = prop_value(dtb, offset);
+                if ArchImpl::fill_random(&mut dtb[value.clone()]) {
+                    log::debug!("Filled DTB property at {offset:#x} with random bytes");
+                } else {
+                    log::warn!(
+                        "No hardware random number generator, removing DTB property at {offset:#x}"
+                    );
+                    for token in dtb[offset..value.end.next_multiple_of(4)].chunks_mut(4) {
+                        token.copy_from_slice(&FDT_NOP.to_be_bytes());
+                    }
+                }
+            }
+            ArchivedDtbFixup::LoaderFootprint {
+                offset,
+                address_cells,
+                size_cells,
+            } => {
+                let offset = usize::try_from(offset.to_native()).unwrap();
+                let address_cells = usize::try_from(address_cells.to_native()).unwrap();
+                let size_cells = usize::try_from(size_cells.to_native()).unwrap();
+                let value = prop_value(dtb, offset);
+                assert_eq!(value.len(), 4 * (address_cells + size_cells));
+                let (address, size) = dtb[value].split_at_mut(4 * address_cells);
+                write_cells(address, own_footprint.start);
+                write_cells(size, own_footprint.len());
+            }
+        }
+    }
+}
+
+fn prop_value(dtb: &[u8], offset: usize) -> Range<usize> {
+    let word = |i: usize| u32::from_be_bytes(dtb[offset + i..][..4].try_into().unwrap());
+    assert_eq!(word(0), FDT_PROP);
+    let len = usize::try_from(word(4)).unwrap();
+    let start = offset + PROP_HEADER_SIZE;
+    start..start + len
+}
+
+fn write_cells(cells: &mut [u8], value: usize) {
+    let value = u64::try_from(value).unwrap().to_be_bytes();
+    cells.fill(0);
+    let n = cells.len().min(value.len());
+    assert!(value[..value.len() - n].iter().all(|b| *b == 0));
+    let dst_start = cells.len() - n;
+    cells[dst_start..].copy_from_slice(&value[value.len() - n..]);
+}
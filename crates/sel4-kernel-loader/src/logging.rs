@@ -6,16 +6,45 @@
 
 use spin::Mutex;
 
+use sel4_kernel_loader_log_buffer::hand_off;
+use sel4_kernel_loader_payload_types::ArchivedImage;
 use sel4_logging::{LevelFilter, Logger, LoggerBuilder, SynchronizedLogger};
 
 use crate::fmt::debug_print;
 
 const LOG_LEVEL: LevelFilter = LevelFilter::Debug;
 
+const LOG_BUFFER_SIZE: usize = 16 * 1024;
+
+// Retains log output for the root task
+static LOG_BUFFER: Mutex<LogBuffer> = Mutex::new(LogBuffer {
+    data: [0; LOG_BUFFER_SIZE],
+    len: 0,
+    dropped: 0,
+});
+
+struct LogBuffer {
+    data: [u8; LOG_BUFFER_SIZE],
+    len: usize,
+    dropped: usize,
+}
+
+impl LogBuffer {
+    fn push(&mut self, s: &str) {
+        let n = s.len().min(LOG_BUFFER_SIZE - self.len);
+        self.data[self.len..][..n].copy_from_slice(&s.as_bytes()[..n]);
+        self.len += n;
+        self.dropped += s.len() - n;
+    }
+}
+
 static LOGGER: SynchronizedLogger<Mutex<()>, Logger> = SynchronizedLogger::new(
     LoggerBuilder::const_default()
         .level_filter(LOG_LEVEL)
-        .write(|s| debug_print!("{}", s))
+        .write(|s| {
+            debug_print!("{}", s);
+            LOG_BUFFER.lock().push(s);
+        })
         .fmt(|record, f| {
             write!(
                 f,
@@ -31,3 +60,27 @@ pub(crate) fn set_logger() {
     log::set_max_level(LOGGER.inner().lock().level_filter);
     log::set_logger(&LOGGER).unwrap();
 }
+
+/// Copies the log so far into the buffer that the root task provided, if any. The user image must
+/// already be in place.
+pub(crate) fn hand_off_to_root_task(image: &ArchivedImage) {
+    if let Some(info) = image.loader_log.as_ref() {
+        let addr = info.addr_p.to_usize();
+        let size = info.size.to_usize();
+        let user_image = &image.info.user_image;
+        assert!(
+            user_image.ui_p_reg_start.to_usize() <= addr
+                && addr + size <= user_image.ui_p_reg_end.to_usize()
+        );
+        log::debug!("Handing off log to root task at {:#x?}", addr..addr + size);
+        let buffer = LOG_BUFFER.lock();
+        unsafe {
+            hand_off(
+                addr as *mut u8,
+                size,
+                &buffer.data[..buffer.len],
+                buffer.dropped,
+            );
+        }
+    }
+}
@@ -11,26 +11,34 @@
 use spin::RwLock;
 
 use sel4_kernel_loader_payload_types::ArchivedPayloadInfo;
-use sel4_platform_info::PLATFORM_INFO;
 
 use sel4_no_allocator as _;
 
 mod arch;
 mod barrier;
+mod bootloader_dtb;
+mod dtb_fixups;
 mod fmt;
 mod logging;
 mod plat;
 mod rt;
+mod select;
 mod this_image;
 
 use crate::{
     arch::{Arch, ArchImpl},
     barrier::Barrier,
-    plat::{Plat, PlatImpl},
+    plat::{Plat, PlatImpl, platform_info},
 };
 
 const MAX_NUM_NODES: usize = sel4_config::sel4_cfg_usize!(MAX_NUM_NODES);
 
+const NUM_LOADER_CORES: usize = if ArchImpl::STARTS_SECONDARY_CORES {
+    MAX_NUM_NODES
+} else {
+    1
+};
+
 static SECONDARY_CORE_INIT_INFO: RwLock<Option<SecondaryCoreInitInfo>> = RwLock::new(None);
 
 struct SecondaryCoreInitInfo {
@@ -40,10 +48,15 @@ struct SecondaryCoreInitInfo {
 }
 
 #[allow(clippy::reversed_empty_ranges)]
-fn main(per_core: <ArchImpl as Arch>::PerCore) -> ! {
+fn main(per_core: <ArchImpl as Arch>::PerCore, bootloader_dtb: usize) -> ! {
     ArchImpl::init();
     PlatImpl::init();
 
+    let bootloader_fdt = bootloader_dtb::get(bootloader_dtb);
+    if let Some(fdt) = &bootloader_fdt {
+        PlatImpl::init_with_bootloader_dtb(fdt);
+    }
+
     logging::set_logger();
 
     log::info!("Starting loader");
@@ -52,33 +65,46 @@ fn main(per_core: <ArchImpl as Arch>::PerCore) -> ! {
 
     let own_footprint = this_image::get_user_image_bounds();
 
-    log::debug!("Platform info: {PLATFORM_INFO:#x?}");
+    let platform_info = platform_info();
+
+    log::debug!("Platform info: {platform_info:#x?}");
     log::debug!("Loader footprint: {own_footprint:#x?}");
-    log::debug!("Payload info: {:#x?}", payload.info);
+    log::debug!("Bootloader DTB: {bootloader_dtb:#x?}");
+
+    let image = select::select_image(payload, bootloader_fdt.as_ref());
+
+    log::debug!("Payload info: {:#x?}", image.info);
     log::debug!("Payload regions:");
-    for region in payload.data.iter() {
+    for region in payload.image_regions(image) {
         log::debug!(
-            "    {:#x?} (filesz = {:#x?}, memsz = {:#x?})",
+            "    {:#x?} (memsz = {:#x?}, encoded filesz = {:#x?}, compression = {})",
             region.addr.0,
             region.size.0,
-            region.data.len()
+            region.data.len(),
+            region.compression.unarchive().name(),
         );
     }
 
-    payload.sanity_check(&PLATFORM_INFO, own_footprint.clone());
+    payload.sanity_check(image, &platform_info, own_footprint.clone());
 
     log::debug!("Copying payload data");
     unsafe {
-        payload.copy_data_out();
+        payload.copy_data_out(image);
     }
 
-    for core_id in 1..MAX_NUM_NODES {
+    PlatImpl::init_with_image(payload, image);
+
+    dtb_fixups::apply_dtb_fixups(image, &own_footprint);
+
+    ArchImpl::prepare_boot(payload, image, &own_footprint);
+
+    for core_id in 1..NUM_LOADER_CORES {
         let sp = this_image::stacks::get_secondary_stack_bottom(core_id).ptr() as usize;
         {
             let mut init_info = SECONDARY_CORE_INIT_INFO.write();
             *init_info = Some(SecondaryCoreInitInfo {
                 core_id,
-                payload_info: payload.info.clone(),
+                payload_info: image.info.clone(),
                 barrier: Barrier::new(2),
             });
         }
@@ -92,7 +118,9 @@ fn main(per_core: <ArchImpl as Arch>::PerCore) -> ! {
         log::debug!("Primary core: core {core_id} up");
     }
 
-    common_epilogue(0, &payload.info, per_core)
+    logging::hand_off_to_root_task(image);
+
+    common_epilogue(0, &image.info, per_core)
 }
 
 fn secondary_main(per_core: <ArchImpl as Arch>::PerCore) -> ! {
@@ -109,7 +137,7 @@ fn secondary_main(per_core: <ArchImpl as Arch>::PerCore) -> ! {
     common_epilogue(core_id, &payload_info, per_core)
 }
 
-static KERNEL_ENTRY_BARRIER: Barrier = Barrier::new(MAX_NUM_NODES);
+static KERNEL_ENTRY_BARRIER: Barrier = Barrier::new(NUM_LOADER_CORES);
 
 #[allow(unreachable_code)]
 fn common_epilogue(
@@ -0,0 +1,113 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use core::ptr;
+
+use embedded_hal_nb::nb;
+use embedded_hal_nb::serial::Write;
+use fdt::node::FdtNode;
+
+use sel4_bcm2835_aux_uart_driver::Driver as Bcm2835AuxUartDriver;
+use sel4_pl011_driver::Driver as Pl011Driver;
+
+/// A UART, as left configured by firmware. Only transmission is supported.
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum Console {
+    Pl011 {
+        base: usize,
+    },
+    Bcm2835Aux {
+        base: usize,
+    },
+    Ns16550 {
+        base: usize,
+        reg_shift: usize,
+        reg_io_width: usize,
+    },
+    Cadence {
+        base: usize,
+    },
+    Imx {
+        base: usize,
+    },
+}
+
+const NS16550_THR: usize = 0;
+const NS16550_LSR: usize = 5;
+const NS16550_LSR_THRE: u32 = 1 << 5;
+
+const CADENCE_SR: usize = 0x2c;
+const CADENCE_FIFO: usize = 0x30;
+const CADENCE_SR_TXFULL: u32 = 1 << 4;
+
+const IMX_UTXD: usize = 0x40;
+const IMX_UTS: usize = 0xb4;
+const IMX_UTS_TXFULL: u32 = 1 << 4;
+
+impl Console {
+    pub(crate) fn from_node(node: &FdtNode) -> Option<Self> {
+        let base = node.reg()?.next()?.starting_address as usize;
+        let usize_prop = |name| node.property(name).and_then(|prop| prop.as_usize());
+        node.compatible()?.all().find_map(|compatible| {
+            Some(match compatible {
+                "arm,pl011" => Self::Pl011 { base },
+                "brcm,bcm2835-aux-uart" => Self::Bcm2835Aux { base },
+                "ns16550" | "ns16550a" | "snps,dw-apb-uart" => Self::Ns16550 {
+                    base,
+                    reg_shift: usize_prop("reg-shift").unwrap_or(0),
+                    reg_io_width: usize_prop("reg-io-width").unwrap_or(1),
+                },
+                "cdns,uart-r1p8" | "cdns,uart-r1p12" | "xlnx,xuartps" => Self::Cadence { base },
+                "fsl,imx6q-uart" | "fsl,imx21-uart" => Self::Imx { base },
+                _ => return None,
+            })
+        })
+    }
+
+    pub(crate) fn put_char(&self, c: u8) {
+        match *self {
+            Self::Pl011 { base } => {
+                let mut driver = unsafe { Pl011Driver::new_uninit(base as *mut _) };
+                nb::block!(driver.write(c)).unwrap_or_else(|err| match err {});
+            }
+            Self::Bcm2835Aux { base } => {
+                let mut driver = unsafe { Bcm2835AuxUartDriver::new_uninit(base as *mut _) };
+                nb::block!(driver.write(c)).unwrap_or_else(|err| match err {});
+            }
+            Self::Ns16550 {
+                base,
+                reg_shift,
+                reg_io_width,
+            } => unsafe {
+                let reg = |i: usize| base + (i << reg_shift);
+                let read = |addr: usize| match reg_io_width {
+                    4 => ptr::read_volatile(addr as *const u32),
+                    _ => ptr::read_volatile(addr as *const u8).into(),
+                };
+                while read(reg(NS16550_LSR)) & NS16550_LSR_THRE == 0 {
+                    core::hint::spin_loop();
+                }
+                match reg_io_width {
+                    4 => ptr::write_volatile(reg(NS16550_THR) as *mut u32, c.into()),
+                    _ => ptr::write_volatile(reg(NS16550_THR) as *mut u8, c),
+                }
+            },
+            Self::Cadence { base } => unsafe {
+                while ptr::read_volatile((base + CADENCE_SR) as *const u32) & CADENCE_SR_TXFULL != 0
+                {
+                    core::hint::spin_loop();
+                }
+                ptr::write_volatile((base + CADENCE_FIFO) as *mut u32, c.into());
+            },
+            Self::Imx { base } => unsafe {
+                while ptr::read_volatile((base + IMX_UTS) as *const u32) & IMX_UTS_TXFULL != 0 {
+                    core::hint::spin_loop();
+                }
+                ptr::write_volatile((base + IMX_UTXD) as *mut u32, c.into());
+            },
+        }
+    }
+}
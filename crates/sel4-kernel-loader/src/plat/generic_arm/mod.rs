@@ -0,0 +1,191 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+// A platform described entirely by the DTB in the payload, which is the one passed to the kernel.
+// The console is `/chosen/stdout-path` (or the `serial0` alias), and secondary cores are started
+// according to `/psci` or their `enable-method`. Core `i` is the `i`th node under `/cpus`, so the
+// boot core must come first.
+//
+// Until the selected image has been verified and copied into place, the console is instead the one
+// described by the DTB passed by the bootloader, if any, so that failures to select an image are
+// reported. Without one, log output from before then only reaches the log buffer handed off to the
+// root task.
+
+use core::slice;
+
+use fdt::Fdt;
+use fdt::node::FdtNode;
+use spin::Once;
+use spin::lock_api::Mutex;
+
+use sel4_config::{sel4_cfg_bool, sel4_cfg_usize};
+use sel4_kernel_loader_payload_types::{ArchivedImage, ArchivedPayload};
+
+use crate::{
+    arch::{drivers::psci, drivers::spin_table, reset_cntvoff},
+    plat::Plat,
+};
+
+mod console;
+
+use console::Console;
+
+const MAX_NUM_NODES: usize = sel4_cfg_usize!(MAX_NUM_NODES);
+
+struct Platform {
+    console: Option<Console>,
+    cpu_start: CpuStart,
+}
+
+enum CpuStart {
+    Psci {
+        conduit: psci::Conduit,
+        target_cpus: [usize; MAX_NUM_NODES],
+    },
+    SpinTable {
+        release_addrs: [usize; MAX_NUM_NODES],
+    },
+    Unknown,
+}
+
+static PLATFORM: Once<Platform> = Once::new();
+
+static EARLY_CONSOLE: Once<Option<Console>> = Once::new();
+
+static SERIAL_LOCK: Mutex<()> = Mutex::new(());
+
+pub(crate) enum PlatImpl {}
+
+impl Plat for PlatImpl {
+    fn init_with_bootloader_dtb(fdt: &Fdt) {
+        EARLY_CONSOLE.call_once(|| find_console(fdt));
+    }
+
+    fn init_with_image(payload: &ArchivedPayload, image: &ArchivedImage) {
+        PLATFORM.call_once(|| {
+            let fdt = image_dtb(payload, image).expect("no usable DTB in image");
+            Platform {
+                console: find_console(&fdt),
+                cpu_start: find_cpu_start(&fdt),
+            }
+        });
+    }
+
+    fn init_per_core() {
+        if sel4_cfg_bool!(ARM_HYPERVISOR_SUPPORT) {
+            unsafe {
+                reset_cntvoff();
+            }
+        }
+    }
+
+    fn put_char(c: u8) {
+        let _guard = SERIAL_LOCK.lock();
+        Self::put_char_without_synchronization(c)
+    }
+
+    fn put_char_without_synchronization(c: u8) {
+        let console = match PLATFORM.get() {
+            Some(platform) => platform.console.as_ref(),
+            None => EARLY_CONSOLE.get().and_then(Option::as_ref),
+        };
+        if let Some(console) = console {
+            console.put_char(c)
+        }
+    }
+
+    fn start_secondary_core(core_id: usize, sp: usize) {
+        match &PLATFORM.get().unwrap().cpu_start {
+            CpuStart::Psci {
+                conduit,
+                target_cpus,
+            } => psci::start_secondary_core_via(*conduit, target_cpus[core_id], sp),
+            CpuStart::SpinTable { release_addrs } => {
+                spin_table::start_secondary_core(release_addrs, core_id, sp)
+            }
+            CpuStart::Unknown => panic!("DTB does not say how to start core {core_id}"),
+        }
+    }
+}
+
+/// The selected image's DTB, in place. Like the rest of the image, it has already been verified,
+/// checked to be in memory and clear of the loader, and copied out of the payload.
+fn image_dtb(payload: &ArchivedPayload, image: &ArchivedImage) -> Option<Fdt<'static>> {
+    let dtb = image.info.dtb.as_ref()?;
+    let start = dtb.addr_p.to_usize();
+    let end = start.checked_add(dtb.size.to_usize())?;
+    let within_image = payload.image_regions(image).any(|region| {
+        let region_start = region.addr.to_usize();
+        region_start <= start && end <= region_start + region.size.to_usize()
+    });
+    if !within_image {
+        return None;
+    }
+    Fdt::new(unsafe { slice::from_raw_parts(start as *const u8, end - start) }).ok()
+}
+
+fn find_console(fdt: &Fdt) -> Option<Console> {
+    let path = fdt
+        .find_node("/chosen")
+        .and_then(|chosen| chosen.property("stdout-path"))
+        .and_then(|prop| prop.as_str())
+        .map(|path| path.split(':').next().unwrap())
+        .unwrap_or("serial0");
+    let path = if path.starts_with('/') {
+        path
+    } else {
+        fdt.find_node("/aliases")?.property(path)?.as_str()?
+    };
+    Console::from_node(&fdt.find_node(path)?)
+}
+
+fn find_cpu_start(fdt: &Fdt<'static>) -> CpuStart {
+    let mut cpus = [None; MAX_NUM_NODES];
+    if let Some(node) = fdt.find_node("/cpus") {
+        for (slot, cpu) in cpus.iter_mut().zip(
+            node.children()
+                .filter(|child| prop_str(child, "device_type") == Some("cpu")),
+        ) {
+            *slot = Some(cpu);
+        }
+    }
+
+    if let Some(psci) = fdt.find_node("/psci") {
+        let conduit = match prop_str(&psci, "method") {
+            Some("smc") => Some(psci::Conduit::Smc),
+            Some("hvc") => Some(psci::Conduit::Hvc),
+            _ => None,
+        };
+        if let Some(conduit) = conduit {
+            return CpuStart::Psci {
+                conduit,
+                target_cpus: cpus.map(|cpu| {
+                    cpu.and_then(|cpu| cpu.property("reg")?.as_usize())
+                        .unwrap_or(usize::MAX)
+                }),
+            };
+        }
+    }
+
+    let spin_table = cpus
+        .iter()
+        .flatten()
+        .any(|cpu| prop_str(cpu, "enable-method") == Some("spin-table"));
+    if spin_table {
+        return CpuStart::SpinTable {
+            release_addrs: cpus.map(|cpu| {
+                cpu.and_then(|cpu| cpu.property("cpu-release-addr")?.as_usize())
+                    .unwrap_or(0)
+            }),
+        };
+    }
+
+    CpuStart::Unknown
+}
+
+fn prop_str<'a>(node: &FdtNode<'_, 'a>, name: &str) -> Option<&'a str> {
+    node.property(name)?.as_str()
+}
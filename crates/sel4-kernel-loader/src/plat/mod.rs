@@ -4,7 +4,11 @@
 // SPDX-License-Identifier: BSD-2-Clause
 //
 
+use fdt::Fdt;
+
 use sel4_config::sel4_cfg_if;
+use sel4_kernel_loader_payload_types::{ArchivedImage, ArchivedPayload};
+use sel4_platform_info_types::PlatformInfo;
 
 sel4_cfg_if! {
     if #[sel4_cfg(all(ARCH_ARM, PLAT_QEMU_ARM_VIRT))] {
@@ -13,9 +17,15 @@ sel4_cfg_if! {
     } else if #[sel4_cfg(all(ARCH_ARM, PLAT_BCM2711))] {
         #[path = "bcm2711/mod.rs"]
         mod imp;
+    } else if #[sel4_cfg(ARCH_ARM)] {
+        #[path = "generic_arm/mod.rs"]
+        mod imp;
     } else if #[sel4_cfg(all(ARCH_RISCV, any(PLAT_SPIKE, PLAT_QEMU_RISCV_VIRT, PLAT_HIFIVE)))] {
         #[path = "riscv_generic/mod.rs"]
         mod imp;
+    } else if #[sel4_cfg(all(ARCH_X86, PLAT_PC99))] {
+        #[path = "pc99/mod.rs"]
+        mod imp;
     }
 }
 
@@ -23,6 +33,10 @@ sel4_cfg_if! {
 #[cfg(false)]
 mod bcm2711;
 #[cfg(false)]
+mod generic_arm;
+#[cfg(false)]
+mod pc99;
+#[cfg(false)]
 mod qemu_arm_virt;
 #[cfg(false)]
 mod riscv_generic;
@@ -30,9 +44,26 @@ mod riscv_generic;
 #[allow(unused_imports)]
 pub(crate) use imp::*;
 
+sel4_cfg_if! {
+    if #[sel4_cfg(ARCH_X86)] {
+        // Discovered at boot time from the multiboot memory map
+        pub(crate) use crate::arch::platform_info;
+    } else {
+        pub(crate) fn platform_info() -> PlatformInfo<'static> {
+            sel4_platform_info::PLATFORM_INFO
+        }
+    }
+}
+
 pub(crate) trait Plat {
     fn init() {}
 
+    /// Called before an image is selected, if the bootloader passed a DTB.
+    fn init_with_bootloader_dtb(_fdt: &Fdt) {}
+
+    /// Called once the selected image has been verified and copied into place.
+    fn init_with_image(_payload: &ArchivedPayload, _image: &ArchivedImage) {}
+
     fn init_per_core() {}
 
     fn put_char(c: u8);
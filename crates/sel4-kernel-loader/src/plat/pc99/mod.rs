@@ -0,0 +1,65 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use spin::Mutex;
+
+use crate::{
+    arch::{inb, outb},
+    plat::Plat,
+};
+
+// 16550 UART at COM1
+const SERIAL_PORT_BASE: u16 = 0x3f8;
+
+const THR: u16 = 0;
+const IER: u16 = 1;
+const DLL: u16 = 0;
+const DLM: u16 = 1;
+const FCR: u16 = 2;
+const LCR: u16 = 3;
+const MCR: u16 = 4;
+const LSR: u16 = 5;
+
+const LCR_DLAB: u8 = 1 << 7;
+const LCR_8N1: u8 = 0x03;
+const LSR_THRE: u8 = 1 << 5;
+
+const BAUD_DIVISOR: u16 = 1; // 115200
+
+static SERIAL_LOCK: Mutex<()> = Mutex::new(());
+
+pub(crate) enum PlatImpl {}
+
+impl Plat for PlatImpl {
+    fn init() {
+        let _guard = SERIAL_LOCK.lock();
+        unsafe {
+            outb(SERIAL_PORT_BASE + IER, 0);
+            outb(SERIAL_PORT_BASE + LCR, LCR_DLAB);
+            outb(SERIAL_PORT_BASE + DLL, BAUD_DIVISOR as u8);
+            outb(SERIAL_PORT_BASE + DLM, (BAUD_DIVISOR >> 8) as u8);
+            outb(SERIAL_PORT_BASE + LCR, LCR_8N1);
+            outb(SERIAL_PORT_BASE + FCR, 0x07); // enable and clear FIFOs
+            outb(SERIAL_PORT_BASE + MCR, 0x03); // DTR, RTS
+        }
+    }
+
+    fn put_char(c: u8) {
+        let _guard = SERIAL_LOCK.lock();
+        Self::put_char_without_synchronization(c)
+    }
+
+    fn put_char_without_synchronization(c: u8) {
+        unsafe {
+            while inb(SERIAL_PORT_BASE + LSR) & LSR_THRE == 0 {}
+            outb(SERIAL_PORT_BASE + THR, c);
+        }
+    }
+
+    fn start_secondary_core(_core_id: usize, _sp: usize) {
+        unreachable!("the kernel starts secondary cores itself on x86")
+    }
+}
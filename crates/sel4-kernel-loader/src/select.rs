@@ -0,0 +1,84 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use fdt::Fdt;
+
+use sel4_kernel_loader_payload_types::{
+    ArchivedImage, ArchivedPayload, ArchivedPrimaryImage, PublicKey,
+};
+
+pub(crate) fn select_image<'a>(
+    payload: &'a ArchivedPayload,
+    bootloader_dtb: Option<&Fdt>,
+) -> &'a ArchivedImage {
+    let selection = &payload.selection;
+
+    let primary = match &selection.primary {
+        ArchivedPrimaryImage::Fixed(index) => index.to_native(),
+        ArchivedPrimaryImage::DtbChosen { property, default } => {
+            match read_chosen_u32(bootloader_dtb, property.as_str()) {
+                Some(index) => {
+                    log::info!("Image {index} requested by /chosen/{}", property.as_str());
+                    index
+                }
+                None => default.to_native(),
+            }
+        }
+    };
+
+    match payload.image(primary) {
+        Some(image) if verify(payload, primary, image) => {
+            log::info!("Booting image {primary}");
+            return image;
+        }
+        Some(_) => {}
+        None => log::warn!("Image {primary} does not exist"),
+    }
+
+    let Some(fallback) = selection.fallback.as_ref().map(|index| index.to_native()) else {
+        panic!("Refusing to boot: no valid image");
+    };
+    let Some(image) = payload.image(fallback) else {
+        panic!("Refusing to boot: fallback image {fallback} does not exist");
+    };
+    if !verify(payload, fallback, image) {
+        panic!("Refusing to boot: fallback image {fallback} is not valid");
+    }
+    log::warn!("Booting fallback image {fallback}");
+    image
+}
+
+const PUBLIC_KEY: Option<PublicKey> = include!(concat!(env!("OUT_DIR"), "/public_key.rs"));
+
+fn verify(payload: &ArchivedPayload, index: u32, image: &ArchivedImage) -> bool {
+    match &PUBLIC_KEY {
+        Some(public_key) => match payload.verify_image_signature(image, public_key) {
+            Ok(()) => {
+                log::debug!("Image {index} signature verified");
+                true
+            }
+            Err(err) => {
+                log::error!("Image {index} failed signature verification: {err}");
+                false
+            }
+        },
+        None => {
+            let ok = payload.verify_image(image);
+            if !ok {
+                log::error!("Image {index} digest does not match its contents");
+            }
+            ok
+        }
+    }
+}
+
+fn read_chosen_u32(dtb: Option<&Fdt>, property: &str) -> Option<u32> {
+    dtb?.find_node("/chosen")?
+        .property(property)?
+        .as_usize()?
+        .try_into()
+        .ok()
+}
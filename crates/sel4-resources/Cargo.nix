@@ -0,0 +1,14 @@
+#
+# Copyright 2026, Colias Group, LLC
+#
+# SPDX-License-Identifier: BSD-2-Clause
+#
+
+{ mk, localCrates }:
+
+mk {
+  package.name = "sel4-resources";
+  dependencies = {
+    inherit (localCrates) sel4;
+  };
+}
@@ -0,0 +1,62 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// Allocates slots in the current thread's root CNode.
+///
+/// Slots are identified by their indices, which, because the CNode is resolved with the full
+/// depth of a CPtr, are also the bits of the CPtrs which refer to them.
+pub struct SlotAllocator {
+    cnode: sel4::cap::CNode,
+    fresh: Range<usize>,
+    freed: Vec<usize>,
+}
+
+impl SlotAllocator {
+    /// `slots` must be empty.
+    pub fn new(cnode: sel4::cap::CNode, slots: Range<usize>) -> Self {
+        Self {
+            cnode,
+            fresh: slots,
+            freed: Vec::new(),
+        }
+    }
+
+    /// Uses the root task's empty slots, which must not have been used yet.
+    pub fn from_bootinfo(bootinfo: &sel4::BootInfo) -> Self {
+        Self::new(
+            sel4::init_thread::slot::CNODE.cap(),
+            bootinfo.empty().range(),
+        )
+    }
+
+    pub fn cnode(&self) -> sel4::cap::CNode {
+        self.cnode
+    }
+
+    pub fn num_free(&self) -> usize {
+        self.fresh.len() + self.freed.len()
+    }
+
+    pub fn allocate(&mut self) -> Option<usize> {
+        self.freed.pop().or_else(|| self.fresh.next())
+    }
+
+    /// `slot` must be empty.
+    pub fn free(&mut self, slot: usize) {
+        self.freed.push(slot);
+    }
+
+    pub fn cap(&self, slot: usize) -> sel4::cap::Unspecified {
+        sel4::cap::Unspecified::from_bits(slot.try_into().unwrap())
+    }
+
+    pub fn absolute_cptr(&self, slot: usize) -> sel4::AbsoluteCPtr {
+        self.cnode.absolute_cptr(self.cap(slot))
+    }
+}
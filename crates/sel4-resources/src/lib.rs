@@ -0,0 +1,135 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+//! Allocation of capability slots, kernel objects, and translation tables for root tasks.
+//!
+//! [`ObjectAllocator::from_bootinfo`] takes ownership of the root task's empty slots and kernel
+//! (that is, non-device) untypeds. Objects allocated with [`ObjectAllocator`] can be freed, which
+//! revokes every capability derived from them. [`VSpaceMapper`] maps frames into an address space,
+//! allocating intermediate translation tables as they are needed.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+mod cspace;
+mod untyped;
+mod vspace;
+
+pub use cspace::SlotAllocator;
+pub use untyped::UntypedAllocator;
+pub use vspace::VSpaceMapper;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Error {
+    OutOfSlots,
+    OutOfMemory,
+    NotAllocated,
+    Sel4(sel4::Error),
+}
+
+impl From<sel4::Error> for Error {
+    fn from(err: sel4::Error) -> Self {
+        Self::Sel4(err)
+    }
+}
+
+/// Allocates kernel objects, each from its own untyped so that it can be freed independently.
+pub struct ObjectAllocator {
+    slots: SlotAllocator,
+    untyped: UntypedAllocator,
+    /// The untyped backing each allocated object, and its size in bits, indexed by the object's
+    /// slot.
+    allocated: BTreeMap<usize, (sel4::cap::Untyped, usize)>,
+}
+
+impl ObjectAllocator {
+    pub fn new(slots: SlotAllocator, untyped: UntypedAllocator) -> Self {
+        Self {
+            slots,
+            untyped,
+            allocated: BTreeMap::new(),
+        }
+    }
+
+    /// Uses the empty slots and kernel untypeds described by `bootinfo`, which must not have been
+    /// used yet.
+    pub fn from_bootinfo(bootinfo: &sel4::BootInfo) -> Self {
+        Self::new(
+            SlotAllocator::from_bootinfo(bootinfo),
+            UntypedAllocator::from_bootinfo(bootinfo),
+        )
+    }
+
+    pub fn slots(&mut self) -> &mut SlotAllocator {
+        &mut self.slots
+    }
+
+    pub fn untyped(&mut self) -> &mut UntypedAllocator {
+        &mut self.untyped
+    }
+
+    pub fn allocate(
+        &mut self,
+        blueprint: sel4::ObjectBlueprint,
+    ) -> Result<sel4::cap::Unspecified, Error> {
+        let size_bits = blueprint.physical_size_bits();
+        let ut = self.untyped.allocate(&mut self.slots, size_bits)?;
+        let result = self
+            .slots
+            .allocate()
+            .ok_or(Error::OutOfSlots)
+            .and_then(|slot| {
+                ut.untyped_retype(
+                    &blueprint,
+                    &self.slots.cnode().absolute_cptr_for_self(),
+                    slot,
+                    1,
+                )
+                .map(|_| slot)
+                .map_err(|err| {
+                    self.slots.free(slot);
+                    err.into()
+                })
+            });
+        match result {
+            Ok(slot) => {
+                self.allocated.insert(slot, (ut, size_bits));
+                Ok(self.slots.cap(slot))
+            }
+            Err(err) => {
+                self.untyped.free(&mut self.slots, ut, size_bits)?;
+                Err(err)
+            }
+        }
+    }
+
+    pub fn allocate_fixed_sized<T: sel4::CapTypeForObjectOfFixedSize>(
+        &mut self,
+    ) -> Result<sel4::Cap<T>, Error> {
+        Ok(self.allocate(T::object_blueprint())?.cast())
+    }
+
+    pub fn allocate_variable_sized<T: sel4::CapTypeForObjectOfVariableSize>(
+        &mut self,
+        size_bits: usize,
+    ) -> Result<sel4::Cap<T>, Error> {
+        Ok(self.allocate(T::object_blueprint(size_bits))?.cast())
+    }
+
+    /// Destroys an object allocated by this allocator, along with every capability derived from
+    /// it, and returns its memory and slot.
+    pub fn free<T: sel4::CapType>(&mut self, cap: sel4::Cap<T>) -> Result<(), Error> {
+        let slot = usize::try_from(cap.bits()).unwrap();
+        let (ut, size_bits) = self.allocated.remove(&slot).ok_or(Error::NotAllocated)?;
+        // Revoking the untyped deletes the object's capability.
+        self.untyped.free(&mut self.slots, ut, size_bits)?;
+        self.slots.free(slot);
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{Error, SlotAllocator};
+
+/// Allocates untypeds of exact power-of-two sizes from a pool of unused untypeds.
+///
+/// Free untypeds are kept in one list per size. A request is served from the smallest available
+/// untyped that is large enough, which is split in half repeatedly until it is of the requested
+/// size, with the unused halves going to their respective lists. A freed untyped whose buddy is
+/// also free is coalesced with it back into their parent, which is then freed in turn.
+pub struct UntypedAllocator {
+    /// Indexed by size in bits.
+    free: Vec<Vec<sel4::cap::Untyped>>,
+    /// The parent and buddy of each half of a split untyped, indexed by the half's slot.
+    splits: BTreeMap<usize, (sel4::cap::Untyped, sel4::cap::Untyped)>,
+}
+
+impl UntypedAllocator {
+    pub fn new() -> Self {
+        Self {
+            free: vec![Vec::new(); sel4::WORD_SIZE],
+            splits: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the root task's kernel (that is, non-device) untypeds, which must not have been used
+    /// yet.
+    pub fn from_bootinfo(bootinfo: &sel4::BootInfo) -> Self {
+        let mut this = Self::new();
+        for (i, desc) in bootinfo.untyped_list().iter().enumerate() {
+            if !desc.is_device() {
+                this.add(bootinfo.untyped().index(i).cap(), desc.size_bits());
+            }
+        }
+        this
+    }
+
+    /// `ut` must have no children.
+    pub fn add(&mut self, ut: sel4::cap::Untyped, size_bits: usize) {
+        self.free[size_bits].push(ut);
+    }
+
+    /// The total size in bytes of the free untypeds.
+    pub fn num_free_bytes(&self) -> usize {
+        self.free
+            .iter()
+            .enumerate()
+            .map(|(size_bits, uts)| uts.len() << size_bits)
+            .sum()
+    }
+
+    /// New untypeds, including the one returned, are placed in slots from `slots`.
+    pub fn allocate(
+        &mut self,
+        slots: &mut SlotAllocator,
+        size_bits: usize,
+    ) -> Result<sel4::cap::Untyped, Error> {
+        let mut current_size_bits = (size_bits..self.free.len())
+            .find(|i| !self.free[*i].is_empty())
+            .ok_or(Error::OutOfMemory)?;
+        let mut ut = self.free[current_size_bits].pop().unwrap();
+        while current_size_bits > size_bits {
+            let (lower, upper) = match split(ut, current_size_bits, slots) {
+                Ok(halves) => halves,
+                Err(err) => {
+                    self.add(ut, current_size_bits);
+                    return Err(err);
+                }
+            };
+            current_size_bits -= 1;
+            self.splits.insert(slot_of(lower), (ut, upper));
+            self.splits.insert(slot_of(upper), (ut, lower));
+            self.add(upper, current_size_bits);
+            ut = lower;
+        }
+        Ok(ut)
+    }
+
+    /// Revokes `ut`, which destroys all objects retyped from it, and makes it available again.
+    ///
+    /// `ut` must have been obtained from this allocator, with the given size.
+    pub fn free(
+        &mut self,
+        slots: &mut SlotAllocator,
+        mut ut: sel4::cap::Untyped,
+        mut size_bits: usize,
+    ) -> Result<(), Error> {
+        slots.cnode().absolute_cptr(ut).revoke()?;
+        while let Some(&(parent, buddy)) = self.splits.get(&slot_of(ut)) {
+            let Some(i) = self.free[size_bits]
+                .iter()
+                .position(|free| free.bits() == buddy.bits())
+            else {
+                break;
+            };
+            // Revoking the parent deletes both halves.
+            if let Err(err) = slots.cnode().absolute_cptr(parent).revoke() {
+                self.add(ut, size_bits);
+                return Err(err.into());
+            }
+            self.free[size_bits].swap_remove(i);
+            for half in [ut, buddy] {
+                self.splits.remove(&slot_of(half));
+                slots.free(slot_of(half));
+            }
+            ut = parent;
+            size_bits += 1;
+        }
+        self.add(ut, size_bits);
+        Ok(())
+    }
+}
+
+impl Default for UntypedAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The parent is left exhausted until both halves are coalesced back into it.
+fn split(
+    ut: sel4::cap::Untyped,
+    size_bits: usize,
+    slots: &mut SlotAllocator,
+) -> Result<(sel4::cap::Untyped, sel4::cap::Untyped), Error> {
+    let blueprint = sel4::ObjectBlueprint::Untyped {
+        size_bits: size_bits - 1,
+    };
+    let first = slots.allocate().ok_or(Error::OutOfSlots)?;
+    let Some(second) = slots.allocate() else {
+        slots.free(first);
+        return Err(Error::OutOfSlots);
+    };
+    let halves = [first, second];
+    let dst = slots.cnode().absolute_cptr_for_self();
+    for (i, slot) in halves.iter().enumerate() {
+        if let Err(err) = ut.untyped_retype(&blueprint, &dst, *slot, 1) {
+            // Undo the first half, if it was created, so that the parent can be reused whole.
+            if i > 0 {
+                slots.cnode().absolute_cptr(ut).revoke()?;
+            }
+            halves.iter().for_each(|slot| slots.free(*slot));
+            return Err(err.into());
+        }
+    }
+    Ok((slots.cap(halves[0]).cast(), slots.cap(halves[1]).cast()))
+}
+
+fn slot_of(ut: sel4::cap::Untyped) -> usize {
+    ut.bits().try_into().unwrap()
+}
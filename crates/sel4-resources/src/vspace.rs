@@ -0,0 +1,110 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::{Error, ObjectAllocator};
+
+/// Maps frames into an address space, allocating intermediate translation tables as needed.
+///
+/// The address space may already contain translation tables which were not allocated by this
+/// mapper, as is the case for the root task's own address space.
+pub struct VSpaceMapper {
+    vspace: sel4::cap::VSpace,
+    /// The levels and base addresses of the translation tables known to be present.
+    tables: BTreeSet<(usize, usize)>,
+    allocated: Vec<sel4::cap::Unspecified>,
+}
+
+impl VSpaceMapper {
+    pub fn new(vspace: sel4::cap::VSpace) -> Self {
+        Self {
+            vspace,
+            tables: BTreeSet::new(),
+            allocated: Vec::new(),
+        }
+    }
+
+    pub fn vspace(&self) -> sel4::cap::VSpace {
+        self.vspace
+    }
+
+    pub fn map_frame<T: sel4::CapTypeForFrameObject>(
+        &mut self,
+        allocator: &mut ObjectAllocator,
+        frame: sel4::Cap<T>,
+        frame_type: sel4::FrameObjectType,
+        vaddr: usize,
+        rights: sel4::CapRights,
+        attrs: sel4::VmAttributes,
+    ) -> Result<(), Error> {
+        assert_eq!(vaddr % frame_type.bytes(), 0);
+        self.ensure_translation_tables(allocator, vaddr, frame_type)?;
+        frame.frame_map(self.vspace, vaddr, rights, attrs)?;
+        Ok(())
+    }
+
+    pub fn map_fixed_sized_frame<T: sel4::CapTypeForFrameObjectOfFixedSize>(
+        &mut self,
+        allocator: &mut ObjectAllocator,
+        frame: sel4::Cap<T>,
+        vaddr: usize,
+        rights: sel4::CapRights,
+        attrs: sel4::VmAttributes,
+    ) -> Result<(), Error> {
+        self.map_frame(allocator, frame, T::FRAME_OBJECT_TYPE, vaddr, rights, attrs)
+    }
+
+    /// Ensures that the translation tables required to map a frame of type `frame_type` at
+    /// `vaddr` are present.
+    pub fn ensure_translation_tables(
+        &mut self,
+        allocator: &mut ObjectAllocator,
+        vaddr: usize,
+        frame_type: sel4::FrameObjectType,
+    ) -> Result<(), Error> {
+        for level in 1..sel4::vspace_levels::NUM_LEVELS {
+            let span_bits = sel4::vspace_levels::span_bits(level);
+            if span_bits <= frame_type.bits() {
+                break;
+            }
+            let base = vaddr & !((1 << span_bits) - 1);
+            if self.tables.contains(&(level, base)) {
+                continue;
+            }
+            let ty = sel4::TranslationTableObjectType::from_level(level).unwrap();
+            let table = allocator.allocate(ty.blueprint())?;
+            match table
+                .cast::<sel4::cap_type::UnspecifiedIntermediateTranslationTable>()
+                .generic_intermediate_translation_table_map(
+                    ty,
+                    self.vspace,
+                    base,
+                    sel4::VmAttributes::default(),
+                ) {
+                Ok(()) => self.allocated.push(table),
+                // A table which this mapper did not allocate is already present.
+                Err(sel4::Error::DeleteFirst) => allocator.free(table)?,
+                Err(err) => {
+                    allocator.free(table)?;
+                    return Err(err.into());
+                }
+            }
+            self.tables.insert((level, base));
+        }
+        Ok(())
+    }
+
+    /// Frees the translation tables allocated by this mapper, which unmaps everything mapped
+    /// through them.
+    pub fn free(self, allocator: &mut ObjectAllocator) -> Result<(), Error> {
+        for table in self.allocated.into_iter().rev() {
+            allocator.free(table)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+#
+# Copyright 2026, Colias Group, LLC
+#
+# SPDX-License-Identifier: BSD-2-Clause
+#
+
+{ mk, versions, localCrates }:
+
+mk {
+  package.name = "sel4-spawn";
+  dependencies = {
+    object = { version = versions.object; default-features = false; features = [ "read" ]; };
+    inherit (localCrates)
+      sel4
+      sel4-initialize-tls
+      sel4-resources
+    ;
+  };
+}
@@ -0,0 +1,432 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+//! Spawning of processes from ELF images.
+//!
+//! [`spawn`] gives a process its own CSpace, VSpace, and thread, and starts it. The process's
+//! address space contains, in order:
+//!
+//! - the loadable segments of its image;
+//! - its IPC buffer, in the first page after the image;
+//! - an unmapped guard page, followed by its stack;
+//! - an unmapped guard page, followed by its TLS region, if its image has a `PT_TLS` segment.
+//!
+//! The process's thread starts at the image's entry point, with its stack pointer at the top of its
+//! stack, its thread pointer set, and the address of its IPC buffer as its first argument.
+//!
+//! The process's CNode is resolved such that each slot's index is also the CPtr which refers to
+//! it. It contains capabilities for the process's own objects at the indices in [`slot`],
+//! followed by the capabilities in [`Config::grants`].
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use object::elf::{PF_W, PT_LOAD, PT_TLS};
+use object::read::elf::{FileHeader, ProgramHeader};
+
+use sel4_resources::{ObjectAllocator, VSpaceMapper};
+
+mod load;
+
+use load::{Chunk, GRANULE_SIZE, TlsReservation, intersection, load};
+
+#[cfg(target_pointer_width = "32")]
+type ElfFile<'a> = object::read::elf::ElfFile32<'a>;
+
+#[cfg(target_pointer_width = "64")]
+type ElfFile<'a> = object::read::elf::ElfFile64<'a>;
+
+/// Indices of slots in a process's CNode.
+pub mod slot {
+    pub const NULL: usize = 0;
+    pub const TCB: usize = 1;
+    pub const CNODE: usize = 2;
+    pub const VSPACE: usize = 3;
+    /// Empty with `KERNEL_MCS`, where a thread's fault endpoint is not looked up in its CSpace.
+    pub const FAULT_ENDPOINT: usize = 4;
+    /// Granted capabilities occupy consecutive slots starting here.
+    pub const FIRST_GRANTED: usize = 5;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Error {
+    InvalidImage(object::Error),
+    InvalidSegment,
+    InvalidTlsImage,
+    CNodeTooSmall,
+    Resources(sel4_resources::Error),
+    Sel4(sel4::Error),
+}
+
+impl From<object::Error> for Error {
+    fn from(err: object::Error) -> Self {
+        Self::InvalidImage(err)
+    }
+}
+
+impl From<sel4_resources::Error> for Error {
+    fn from(err: sel4_resources::Error) -> Self {
+        Self::Resources(err)
+    }
+}
+
+impl From<sel4::Error> for Error {
+    fn from(err: sel4::Error) -> Self {
+        Self::Sel4(err)
+    }
+}
+
+/// A capability to be minted into a process's CNode.
+#[derive(Debug, Clone)]
+pub struct Grant {
+    pub cap: sel4::cap::Unspecified,
+    pub rights: sel4::CapRights,
+    pub badge: sel4::Badge,
+}
+
+#[sel4::sel4_cfg_struct]
+pub struct Config<'a> {
+    pub asid_pool: sel4::cap::AsidPool,
+    /// Authority for the process's thread's priority.
+    pub authority: sel4::cap::Tcb,
+    pub priority: sel4::Word,
+    /// Must already be configured, and is not freed along with the process.
+    #[sel4_cfg(KERNEL_MCS)]
+    pub sched_context: sel4::cap::SchedContext,
+    /// The process's faults are delivered to this endpoint with this badge.
+    pub fault_endpoint: Option<(sel4::cap::Endpoint, sel4::Badge)>,
+    pub cnode_size_bits: usize,
+    pub grants: &'a [Grant],
+    pub stack_size: usize,
+    /// The caller's address space.
+    pub vspace: sel4::cap::VSpace,
+    /// A granule-aligned address in the caller's address space at which nothing is mapped, but
+    /// at which a granule can be mapped without any additional translation tables. The process's
+    /// pages are mapped here, one at a time, to be initialized.
+    pub scratch_page_addr: usize,
+}
+
+/// A running process, whose resources are freed by [`Process::destroy`].
+pub struct Process {
+    tcb: sel4::cap::Tcb,
+    cnode: sel4::cap::CNode,
+    vspace: sel4::cap::VSpace,
+    resources: Resources,
+}
+
+impl Process {
+    pub fn tcb(&self) -> sel4::cap::Tcb {
+        self.tcb
+    }
+
+    pub fn cnode(&self) -> sel4::cap::CNode {
+        self.cnode
+    }
+
+    pub fn vspace(&self) -> sel4::cap::VSpace {
+        self.vspace
+    }
+
+    /// Stops the process and frees the objects allocated for it, along with every capability
+    /// derived from them.
+    pub fn destroy(self, allocator: &mut ObjectAllocator) -> Result<(), Error> {
+        self.tcb.tcb_suspend()?;
+        self.resources.free(allocator)
+    }
+}
+
+/// Everything allocated for a process.
+struct Resources {
+    mapper: VSpaceMapper,
+    objects: Vec<sel4::cap::Unspecified>,
+    /// A slot in the caller's CNode holding a badged copy of the fault endpoint.
+    fault_endpoint_slot: Option<usize>,
+}
+
+impl Resources {
+    fn allocate_fixed_sized<T: sel4::CapTypeForObjectOfFixedSize>(
+        &mut self,
+        allocator: &mut ObjectAllocator,
+    ) -> Result<sel4::Cap<T>, Error> {
+        let cap = allocator.allocate_fixed_sized::<T>()?;
+        self.objects.push(cap.cast());
+        Ok(cap)
+    }
+
+    fn allocate_variable_sized<T: sel4::CapTypeForObjectOfVariableSize>(
+        &mut self,
+        allocator: &mut ObjectAllocator,
+        size_bits: usize,
+    ) -> Result<sel4::Cap<T>, Error> {
+        let cap = allocator.allocate_variable_sized::<T>(size_bits)?;
+        self.objects.push(cap.cast());
+        Ok(cap)
+    }
+
+    fn free(self, allocator: &mut ObjectAllocator) -> Result<(), Error> {
+        if let Some(slot) = self.fault_endpoint_slot {
+            allocator.slots().absolute_cptr(slot).delete()?;
+            allocator.slots().free(slot);
+        }
+        for object in self.objects.into_iter().rev() {
+            allocator.free(object)?;
+        }
+        self.mapper.free(allocator)?;
+        Ok(())
+    }
+}
+
+/// Creates a process from `image`, which must be an ELF file for this architecture, and starts
+/// it.
+pub fn spawn(
+    allocator: &mut ObjectAllocator,
+    image: &[u8],
+    config: &Config,
+) -> Result<Process, Error> {
+    if slot::FIRST_GRANTED + config.grants.len() > 1 << config.cnode_size_bits {
+        return Err(Error::CNodeTooSmall);
+    }
+    let elf = ElfFile::parse(image)?;
+    let vspace = allocator.allocate_fixed_sized::<sel4::cap_type::VSpace>()?;
+    let mut resources = Resources {
+        mapper: VSpaceMapper::new(vspace),
+        objects: Vec::from([vspace.cast()]),
+        fault_endpoint_slot: None,
+    };
+    match build(allocator, &mut resources, &elf, config) {
+        Ok((tcb, cnode)) => Ok(Process {
+            tcb,
+            cnode,
+            vspace,
+            resources,
+        }),
+        Err(err) => {
+            resources.free(allocator)?;
+            Err(err)
+        }
+    }
+}
+
+fn build(
+    allocator: &mut ObjectAllocator,
+    resources: &mut Resources,
+    elf: &ElfFile,
+    config: &Config,
+) -> Result<(sel4::cap::Tcb, sel4::cap::CNode), Error> {
+    let vspace = resources.mapper.vspace();
+    config.asid_pool.asid_pool_assign(vspace)?;
+
+    let endian = elf.endian();
+    let mut segments = Vec::new();
+    let mut tls_segment = None;
+    for phdr in elf.elf_program_headers() {
+        let data = phdr
+            .data(endian, elf.data())
+            .map_err(|_| Error::InvalidSegment)?;
+        let chunk = Chunk {
+            vaddr: to_usize(phdr.p_vaddr(endian))?,
+            memsz: to_usize(phdr.p_memsz(endian))?,
+            data,
+            writable: phdr.p_flags(endian) & PF_W != 0,
+        }
+        .check()?;
+        match phdr.p_type(endian) {
+            PT_LOAD => segments.push(chunk),
+            PT_TLS => tls_segment = Some((chunk, to_usize(phdr.p_align(endian))?)),
+            _ => {}
+        }
+    }
+
+    let image_end = segments
+        .iter()
+        .map(|chunk| chunk.range().end)
+        .max()
+        .ok_or(Error::InvalidSegment)?;
+    let ipc_buffer_addr = image_end
+        .checked_next_multiple_of(GRANULE_SIZE)
+        .ok_or(Error::InvalidSegment)?;
+    let stack_bottom = ipc_buffer_addr
+        .checked_add(2 * GRANULE_SIZE)
+        .ok_or(Error::InvalidSegment)?;
+    let stack_top = config
+        .stack_size
+        .checked_next_multiple_of(GRANULE_SIZE)
+        .and_then(|stack_size| stack_bottom.checked_add(stack_size))
+        .ok_or(Error::InvalidSegment)?;
+    let (tls, end) = match tls_segment {
+        Some((tls_segment, align)) => {
+            let reservation = TlsReservation::new(&tls_segment, align)?;
+            let start = stack_top
+                .checked_add(GRANULE_SIZE)
+                .and_then(|addr| addr.checked_next_multiple_of(reservation.align()))
+                .ok_or(Error::InvalidSegment)?;
+            let contents = reservation.contents_at(start);
+            let end = start
+                .checked_add(contents.len())
+                .ok_or(Error::InvalidSegment)?;
+            (Some((reservation, start, contents)), end)
+        }
+        None => (None, stack_top),
+    };
+
+    // The scratch page is where the caller initializes the process's pages, so none of them may
+    // cover it, and no segment may cover the pages following the image.
+    let scratch_page = config.scratch_page_addr..(config.scratch_page_addr + GRANULE_SIZE);
+    for chunk in &segments {
+        let range = chunk.range();
+        let pages = (range.start - range.start % GRANULE_SIZE)
+            ..range
+                .end
+                .checked_next_multiple_of(GRANULE_SIZE)
+                .ok_or(Error::InvalidSegment)?;
+        if !intersection(&pages, &scratch_page).is_empty()
+            || !intersection(&pages, &(ipc_buffer_addr..end)).is_empty()
+        {
+            return Err(Error::InvalidSegment);
+        }
+    }
+    if !intersection(&(ipc_buffer_addr..end), &scratch_page).is_empty() {
+        return Err(Error::InvalidSegment);
+    }
+
+    load(allocator, resources, config, &segments)?;
+
+    let ipc_buffer = resources.allocate_fixed_sized::<sel4::cap_type::Granule>(allocator)?;
+    resources.mapper.map_fixed_sized_frame(
+        allocator,
+        ipc_buffer,
+        ipc_buffer_addr,
+        sel4::CapRights::read_write(),
+        sel4::VmAttributes::default(),
+    )?;
+
+    load(
+        allocator,
+        resources,
+        config,
+        &[Chunk {
+            vaddr: stack_bottom,
+            memsz: stack_top - stack_bottom,
+            data: &[],
+            writable: true,
+        }],
+    )?;
+
+    let thread_pointer = match tls {
+        Some((reservation, start, contents)) => {
+            load(
+                allocator,
+                resources,
+                config,
+                &[Chunk {
+                    vaddr: start,
+                    memsz: contents.len(),
+                    data: &contents,
+                    writable: true,
+                }],
+            )?;
+            Some(reservation.thread_pointer_at(start))
+        }
+        None => None,
+    };
+
+    let cnode = resources
+        .allocate_variable_sized::<sel4::cap_type::CNode>(allocator, config.cnode_size_bits)?;
+    let tcb = resources.allocate_fixed_sized::<sel4::cap_type::Tcb>(allocator)?;
+
+    let caller_cnode = allocator.slots().cnode();
+    let mint = |slot: usize, cap: sel4::cap::Unspecified, rights, badge| {
+        cnode
+            .absolute_cptr_from_bits_with_depth(slot.try_into().unwrap(), config.cnode_size_bits)
+            .mint(&caller_cnode.absolute_cptr(cap), rights, badge)
+    };
+    mint(slot::TCB, tcb.cast(), sel4::CapRights::all(), 0)?;
+    mint(slot::CNODE, cnode.cast(), sel4::CapRights::all(), 0)?;
+    mint(slot::VSPACE, vspace.cast(), sel4::CapRights::all(), 0)?;
+    for (i, grant) in config.grants.iter().enumerate() {
+        mint(
+            slot::FIRST_GRANTED + i,
+            grant.cap,
+            grant.rights.clone(),
+            grant.badge,
+        )?;
+    }
+
+    let cnode_cap_data = sel4::CNodeCapData::new(0, sel4::WORD_SIZE - config.cnode_size_bits);
+
+    sel4::sel4_cfg_if! {
+        if #[sel4_cfg(KERNEL_MCS)] {
+            tcb.tcb_configure(
+                cnode,
+                cnode_cap_data,
+                vspace,
+                ipc_buffer_addr.try_into().unwrap(),
+                ipc_buffer,
+            )?;
+            let fault_endpoint = match config.fault_endpoint {
+                Some((endpoint, badge)) => {
+                    let slot = allocator
+                        .slots()
+                        .allocate()
+                        .ok_or(sel4_resources::Error::OutOfSlots)?;
+                    resources.fault_endpoint_slot = Some(slot);
+                    allocator.slots().absolute_cptr(slot).mint(
+                        &caller_cnode.absolute_cptr(endpoint),
+                        sel4::CapRights::all(),
+                        badge,
+                    )?;
+                    allocator.slots().cap(slot).cast()
+                }
+                None => sel4::init_thread::slot::NULL.cap().cast(),
+            };
+            tcb.tcb_set_sched_params(
+                config.authority,
+                0,
+                config.priority,
+                config.sched_context,
+                fault_endpoint,
+            )?;
+        } else {
+            let fault_endpoint = match config.fault_endpoint {
+                Some((endpoint, badge)) => {
+                    mint(slot::FAULT_ENDPOINT, endpoint.cast(), sel4::CapRights::all(), badge)?;
+                    sel4::CPtr::from_bits(slot::FAULT_ENDPOINT.try_into().unwrap())
+                }
+                None => sel4::init_thread::slot::NULL.cptr(),
+            };
+            tcb.tcb_configure(
+                fault_endpoint,
+                cnode,
+                cnode_cap_data,
+                vspace,
+                ipc_buffer_addr.try_into().unwrap(),
+                ipc_buffer,
+            )?;
+            tcb.tcb_set_sched_params(config.authority, 0, config.priority)?;
+        }
+    }
+
+    if let Some(thread_pointer) = thread_pointer {
+        tcb.tcb_set_tls_base(thread_pointer.try_into().unwrap())?;
+    }
+
+    let mut ctx = sel4::UserContext::default();
+    *ctx.pc_mut() = to_usize(elf.elf_header().e_entry(endian))?
+        .try_into()
+        .unwrap();
+    *ctx.sp_mut() = stack_top.try_into().unwrap();
+    *ctx.c_param_mut(0) = ipc_buffer_addr.try_into().unwrap();
+    tcb.tcb_write_all_registers(true, &mut ctx)?;
+
+    Ok((tcb, cnode))
+}
+
+fn to_usize(x: impl Into<u64>) -> Result<usize, Error> {
+    x.into().try_into().map_err(|_| Error::InvalidSegment)
+}
@@ -0,0 +1,171 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
+use core::ops::Range;
+
+use sel4_initialize_tls::{Region, TlsReservationLayout, UncheckedTlsImage};
+use sel4_resources::ObjectAllocator;
+
+use crate::{Config, Error, Resources};
+
+pub(crate) const GRANULE_SIZE: usize = sel4::FrameObjectType::GRANULE.bytes();
+
+/// A region of a process's address space, with its initial contents, which are zero beyond
+/// `data`.
+pub(crate) struct Chunk<'a> {
+    pub(crate) vaddr: usize,
+    pub(crate) memsz: usize,
+    pub(crate) data: &'a [u8],
+    pub(crate) writable: bool,
+}
+
+impl Chunk<'_> {
+    /// Rejects chunks with more data than memory, or which wrap around the end of the address
+    /// space.
+    pub(crate) fn check(self) -> Result<Self, Error> {
+        if self.data.len() > self.memsz || self.vaddr.checked_add(self.memsz).is_none() {
+            return Err(Error::InvalidSegment);
+        }
+        Ok(self)
+    }
+
+    pub(crate) fn range(&self) -> Range<usize> {
+        self.vaddr..(self.vaddr + self.memsz)
+    }
+
+    fn data_range(&self) -> Range<usize> {
+        self.vaddr..(self.vaddr + self.data.len())
+    }
+}
+
+/// Maps fresh pages into the process's address space covering `chunks`, which are readable and,
+/// if any chunk sharing a page is writable, writable.
+///
+/// `chunks` must not wrap around the end of the address space.
+pub(crate) fn load(
+    allocator: &mut ObjectAllocator,
+    resources: &mut Resources,
+    config: &Config,
+    chunks: &[Chunk],
+) -> Result<(), Error> {
+    let Some(start) = chunks.iter().map(|chunk| chunk.vaddr).min() else {
+        return Ok(());
+    };
+    let end = chunks
+        .iter()
+        .map(|chunk| chunk.range().end)
+        .max()
+        .unwrap()
+        .checked_next_multiple_of(GRANULE_SIZE)
+        .ok_or(Error::InvalidSegment)?;
+    let start = start - start % GRANULE_SIZE;
+    for page_addr in (start..end).step_by(GRANULE_SIZE) {
+        let page = page_addr..(page_addr + GRANULE_SIZE);
+        let mut present = false;
+        let mut writable = false;
+        for chunk in chunks {
+            if !intersection(&chunk.range(), &page).is_empty() {
+                present = true;
+                writable |= chunk.writable;
+            }
+        }
+        if !present {
+            continue;
+        }
+        let frame = resources.allocate_fixed_sized::<sel4::cap_type::Granule>(allocator)?;
+        let mut mapped_at_scratch = false;
+        for chunk in chunks {
+            let to_copy = intersection(&chunk.data_range(), &page);
+            if to_copy.is_empty() {
+                continue;
+            }
+            if !mapped_at_scratch {
+                frame.frame_map(
+                    config.vspace,
+                    config.scratch_page_addr,
+                    sel4::CapRights::read_write(),
+                    sel4::VmAttributes::default(),
+                )?;
+                mapped_at_scratch = true;
+            }
+            let src = &chunk.data[(to_copy.start - chunk.vaddr)..][..to_copy.len()];
+            let dst = (config.scratch_page_addr + (to_copy.start - page_addr)) as *mut u8;
+            unsafe {
+                dst.copy_from_nonoverlapping(src.as_ptr(), src.len());
+            }
+        }
+        if mapped_at_scratch {
+            frame.frame_unmap()?;
+        }
+        let rights = if writable {
+            sel4::CapRights::read_write()
+        } else {
+            sel4::CapRights::read_only()
+        };
+        resources.mapper.map_fixed_sized_frame(
+            allocator,
+            frame,
+            page_addr,
+            rights,
+            sel4::VmAttributes::default(),
+        )?;
+    }
+    Ok(())
+}
+
+pub(crate) fn intersection(a: &Range<usize>, b: &Range<usize>) -> Range<usize> {
+    a.start.max(b.start)..a.end.min(b.end)
+}
+
+/// A TLS reservation for a process, initialized in this address space.
+pub(crate) struct TlsReservation {
+    layout: TlsReservationLayout,
+    contents: Vec<u8>,
+}
+
+impl TlsReservation {
+    pub(crate) fn new(segment: &Chunk, align: usize) -> Result<Self, Error> {
+        let image = UncheckedTlsImage {
+            vaddr: segment.data.as_ptr() as usize,
+            filesz: segment.data.len(),
+            memsz: segment.memsz,
+            align,
+        }
+        .check()
+        .map_err(|_| Error::InvalidTlsImage)?;
+        let layout = image.reservation_layout();
+        let footprint = layout.footprint();
+        let mut buf = vec![0; footprint.size() + footprint.align()];
+        let region = Region::new(buf.as_mut_ptr(), buf.len());
+        let thread_pointer = unsafe { image.initialize_inexact_reservation_region(&region) }
+            .map_err(|_| Error::InvalidTlsImage)?;
+        let start = thread_pointer - layout.thread_pointer_offset() - buf.as_ptr() as usize;
+        let contents = buf[start..][..footprint.size()].to_vec();
+        Ok(Self { layout, contents })
+    }
+
+    pub(crate) fn align(&self) -> usize {
+        self.layout.footprint().align()
+    }
+
+    pub(crate) fn thread_pointer_at(&self, start: usize) -> usize {
+        start + self.layout.thread_pointer_offset()
+    }
+
+    /// The contents of the reservation for when it is located at `start`.
+    pub(crate) fn contents_at(&self, start: usize) -> Vec<u8> {
+        let mut contents = self.contents.clone();
+        if cfg!(target_arch = "x86_64") {
+            // The TCB begins with a pointer to itself.
+            contents[self.layout.thread_pointer_offset()..][..mem::size_of::<usize>()]
+                .copy_from_slice(&self.thread_pointer_at(start).to_ne_bytes());
+        }
+        contents
+    }
+}
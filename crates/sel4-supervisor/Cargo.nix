@@ -0,0 +1,23 @@
+#
+# Copyright 2026, Colias Group, LLC
+#
+# SPDX-License-Identifier: BSD-2-Clause
+#
+
+{ mk, versions, localCrates }:
+
+mk {
+  package.name = "sel4-supervisor";
+  dependencies = {
+    object = { version = versions.object; default-features = false; features = [ "read" ]; };
+    addr2line = { version = versions.addr2line; default-features = false; features = [ "rustc-demangle" "cpp_demangle" "fallible-iterator" "smallvec" ]; optional = true; };
+    sel4-backtrace-symbolize = localCrates.sel4-backtrace-symbolize // { optional = true; };
+    inherit (localCrates) sel4;
+  };
+  features = {
+    symbolize = [
+      "addr2line"
+      "sel4-backtrace-symbolize"
+    ];
+  };
+}
@@ -0,0 +1,177 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+//! Supervision of threads through their fault endpoints.
+//!
+//! A [`Supervisor`] receives fault messages on an endpoint from which each supervised thread's
+//! fault endpoint is minted with a distinct badge. Each fault is decoded into a [`FaultReport`],
+//! and the [`Policy`] of the thread with that badge determines what happens to the thread next.
+//!
+//! Restarting a thread relies on its image linking `sel4-reset`, whose `_reset` entry point
+//! restores the image's writable memory to its initial state before jumping to `_start`.
+//! [`reset_entry`] finds the address of `_reset` in an image.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+use object::{Object, ObjectSymbol};
+
+mod report;
+
+pub use report::FaultReport;
+
+/// What to do with a thread after it faults.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Policy {
+    /// Restart the thread with fresh state.
+    ///
+    /// Only the thread's program counter and argument registers are set. Its stack pointer is
+    /// left as it was when it faulted, so this relies on `_reset` setting up its own stack, as the
+    /// one provided by `sel4-reset` does.
+    Restart(Restart),
+    /// Suspend the thread, leaving it to be inspected or destroyed.
+    Suspend,
+    /// Leave the thread blocked on its fault and hand the fault to the caller of
+    /// [`Supervisor::run`].
+    Escalate,
+}
+
+/// How to restart a thread.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Restart {
+    /// The address of the `_reset` symbol in the thread's image.
+    pub entry: sel4::Word,
+    /// The arguments with which `_start` is to be called, which should be those with which the
+    /// thread was originally started.
+    pub args: [sel4::Word; 4],
+    /// The number of times the thread may be restarted before its faults are escalated instead.
+    pub limit: Option<usize>,
+}
+
+/// Finds the address of the `_reset` entry point provided by `sel4-reset` in `image`, which must
+/// be an ELF file.
+#[allow(clippy::useless_conversion)]
+pub fn reset_entry(image: &[u8]) -> Result<Option<sel4::Word>, object::Error> {
+    let file = object::File::parse(image)?;
+    Ok(file
+        .symbol_by_name("_reset")
+        .map(|symbol| symbol.address().try_into().unwrap()))
+}
+
+/// What a [`Supervisor`] did with a faulted thread.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Action {
+    Restarted,
+    Suspended,
+    Escalated,
+}
+
+struct Thread {
+    tcb: sel4::cap::Tcb,
+    policy: Policy,
+    restarts: usize,
+}
+
+/// Applies per-thread policies to faults received on an endpoint.
+///
+/// `R` is that which conveys the authority to reply to a faulted thread, which is `()` unless
+/// the kernel is configured with `KERNEL_MCS`, in which case it is a reply object.
+pub struct Supervisor<R> {
+    endpoint: sel4::cap::Endpoint,
+    reply_authority: R,
+    threads: BTreeMap<sel4::Badge, Thread>,
+}
+
+impl<R: sel4::ConveysReplyAuthority + Copy> Supervisor<R> {
+    pub fn new(endpoint: sel4::cap::Endpoint, reply_authority: R) -> Self {
+        Self {
+            endpoint,
+            reply_authority,
+            threads: BTreeMap::new(),
+        }
+    }
+
+    pub fn endpoint(&self) -> sel4::cap::Endpoint {
+        self.endpoint
+    }
+
+    /// Supervises `tcb`, whose faults are expected to arrive with `badge`, replacing any thread
+    /// previously supervised with that badge.
+    pub fn add(&mut self, badge: sel4::Badge, tcb: sel4::cap::Tcb, policy: Policy) {
+        self.threads.insert(
+            badge,
+            Thread {
+                tcb,
+                policy,
+                restarts: 0,
+            },
+        );
+    }
+
+    /// Stops supervising the thread with `badge`, returning its TCB.
+    pub fn remove(&mut self, badge: sel4::Badge) -> Option<sel4::cap::Tcb> {
+        self.threads.remove(&badge).map(|thread| thread.tcb)
+    }
+
+    /// The number of times the thread with `badge` has been restarted.
+    pub fn restarts(&self, badge: sel4::Badge) -> Option<usize> {
+        self.threads.get(&badge).map(|thread| thread.restarts)
+    }
+
+    /// Blocks until a fault arrives.
+    pub fn recv(&self) -> FaultReport {
+        let (info, badge) = self.endpoint.recv(self.reply_authority);
+        FaultReport::recv(&info, badge)
+    }
+
+    /// Applies the policy of the thread which caused `report`. Faults from unknown threads are
+    /// escalated.
+    pub fn handle(&mut self, report: &FaultReport) -> Result<Action, sel4::Error> {
+        let Some(thread) = self.threads.get_mut(&report.badge()) else {
+            return Ok(Action::Escalated);
+        };
+        match &thread.policy {
+            Policy::Restart(restart) => {
+                if restart.limit.is_some_and(|limit| thread.restarts >= limit) {
+                    return Ok(Action::Escalated);
+                }
+                let mut ctx = thread.tcb.tcb_read_all_registers(false)?;
+                *ctx.pc_mut() = restart.entry;
+                for (i, arg) in restart.args.iter().enumerate() {
+                    *ctx.c_param_mut(i) = *arg;
+                }
+                // Resuming the thread cancels the fault IPC on which it is blocked.
+                thread.tcb.tcb_write_all_registers(true, &mut ctx)?;
+                thread.restarts += 1;
+                Ok(Action::Restarted)
+            }
+            Policy::Suspend => {
+                thread.tcb.tcb_suspend()?;
+                Ok(Action::Suspended)
+            }
+            Policy::Escalate => Ok(Action::Escalated),
+        }
+    }
+
+    /// Receives and handles faults until one is escalated, which is returned. `on_fault` is
+    /// called with each fault along with what was done about it.
+    pub fn run(
+        &mut self,
+        mut on_fault: impl FnMut(&FaultReport, Action),
+    ) -> Result<FaultReport, sel4::Error> {
+        loop {
+            let report = self.recv();
+            let action = self.handle(&report)?;
+            on_fault(&report, action);
+            if action == Action::Escalated {
+                return Ok(report);
+            }
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use core::fmt;
+
+use sel4::{Fault, Word};
+
+/// A fault, along with the badge identifying the thread which caused it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaultReport {
+    badge: sel4::Badge,
+    fault: Fault,
+}
+
+impl FaultReport {
+    pub fn new(badge: sel4::Badge, fault: Fault) -> Self {
+        Self { badge, fault }
+    }
+
+    /// Decodes the fault message which has just been received into the current thread's IPC
+    /// buffer.
+    pub fn recv(info: &sel4::MessageInfo, badge: sel4::Badge) -> Self {
+        Self::new(
+            badge,
+            sel4::with_ipc_buffer(|ipc_buffer| Fault::new(ipc_buffer, info)),
+        )
+    }
+
+    pub fn badge(&self) -> sel4::Badge {
+        self.badge
+    }
+
+    pub fn fault(&self) -> &Fault {
+        &self.fault
+    }
+
+    /// The address of the faulting instruction, if the fault message includes it.
+    pub fn pc(&self) -> Option<Word> {
+        Some(match &self.fault {
+            Fault::CapFault(f) => f.ip(),
+            Fault::UnknownSyscall(f) => f.fault_ip(),
+            Fault::UserException(f) => f.fault_ip(),
+            Fault::VmFault(f) => f.ip(),
+            _ => return None,
+        })
+    }
+
+    /// The faulting thread's stack pointer, if the fault message includes it.
+    pub fn sp(&self) -> Option<Word> {
+        Some(match &self.fault {
+            Fault::UnknownSyscall(f) => f.sp(),
+            Fault::UserException(f) => f.sp(),
+            _ => return None,
+        })
+    }
+
+    /// The address whose access caused a VM fault, or the CPtr whose lookup caused a cap fault.
+    pub fn addr(&self) -> Option<Word> {
+        Some(match &self.fault {
+            Fault::CapFault(f) => f.addr(),
+            Fault::VmFault(f) => f.addr(),
+            _ => return None,
+        })
+    }
+
+    /// Writes the source locations of [`FaultReport::pc`], if present, according to `ctx`, which
+    /// should describe the faulting thread's image.
+    #[cfg(feature = "symbolize")]
+    #[allow(clippy::useless_conversion)]
+    pub fn symbolize<R: addr2line::gimli::Reader>(
+        &self,
+        ctx: &addr2line::Context<R>,
+        w: &mut impl fmt::Write,
+    ) -> fmt::Result {
+        sel4_backtrace_symbolize::symbolize(
+            w,
+            ctx,
+            &Default::default(),
+            self.pc().into_iter().map(|pc| pc.try_into().unwrap()),
+        )
+    }
+}
+
+impl fmt::Display for FaultReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "thread with badge {:#x}: ", self.badge)?;
+        sel4::sel4_cfg_wrap_match! {
+            match &self.fault {
+                Fault::NullFault(_) => write!(f, "null fault")?,
+                Fault::CapFault(fault) => {
+                    write!(
+                        f,
+                        "cap fault on CPtr {:#x} (lookup failure type {}",
+                        fault.addr(),
+                        fault.lookup_failure_type(),
+                    )?;
+                    if fault.in_recv_phase() {
+                        write!(f, ", in receive phase")?;
+                    }
+                    write!(f, ")")?;
+                }
+                Fault::UnknownSyscall(fault) => {
+                    write!(f, "unknown syscall {}", fault.syscall() as isize)?;
+                }
+                Fault::UserException(fault) => {
+                    write!(
+                        f,
+                        "user exception {} (code {:#x})",
+                        fault.number(),
+                        fault.code(),
+                    )?;
+                }
+                Fault::VmFault(fault) => {
+                    let access = if fault.is_prefetch() {
+                        "instruction fetch"
+                    } else {
+                        "data access"
+                    };
+                    write!(
+                        f,
+                        "VM fault on {access} at {:#x} (status {:#x})",
+                        fault.addr(),
+                        fault.fsr(),
+                    )?;
+                }
+                #[sel4_cfg(KERNEL_MCS)]
+                Fault::Timeout(fault) => {
+                    write!(
+                        f,
+                        "timeout of scheduling context with badge {:#x} after {} ticks",
+                        fault.data(),
+                        fault.consumed(),
+                    )?;
+                }
+                #[allow(unreachable_patterns)]
+                fault => write!(f, "{fault:?}")?,
+            }
+        }
+        if let Some(pc) = self.pc() {
+            write!(f, ", pc = {pc:#x}")?;
+        }
+        if let Some(sp) = self.sp() {
+            write!(f, ", sp = {sp:#x}")?;
+        }
+        Ok(())
+    }
+}
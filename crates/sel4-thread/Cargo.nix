@@ -0,0 +1,20 @@
+#
+# Copyright 2026, Colias Group, LLC
+#
+# SPDX-License-Identifier: BSD-2-Clause
+#
+
+{ mk, localCrates }:
+
+mk {
+  package.name = "sel4-thread";
+  dependencies = {
+    inherit (localCrates)
+      sel4
+      sel4-panicking
+      sel4-resources
+      sel4-sync
+    ;
+    sel4-initialize-tls = localCrates.sel4-initialize-tls // { features = [ "on-heap" ]; };
+  };
+}
@@ -0,0 +1,184 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+//! A `std::thread`-like API for seL4 components.
+//!
+//! A [`Runtime`] is created once, by the component's initial thread, from an [`ObjectAllocator`]
+//! which it uses to allocate each thread's kernel objects. Each spawned thread shares its
+//! creator's CSpace and VSpace, and is given:
+//!
+//! - its own TCB, and, with `KERNEL_MCS`, its own scheduling context;
+//! - an IPC buffer, mapped at an address taken from [`Config::ipc_buffer_region`];
+//! - a stack, without a guard page, and a TLS region, both on the heap;
+//! - a notification on which it parks, and another with which it signals its exit.
+//!
+//! These are released when the thread is joined, except for the park notification, which is
+//! released when the last [`Thread`] handle to it is dropped. Released objects are freed the next
+//! time a thread is spawned or joined. Detached threads' resources, other than their park
+//! notifications, are never freed.
+//!
+//! The global allocator must be safe to use from multiple threads. For example, a root task
+//! should call `sel4_root_task::set_global_allocator_mutex_notification` before spawning any
+//! threads.
+
+#![no_std]
+#![feature(thread_local)]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::mem;
+use core::ops::Range;
+
+use sel4_initialize_tls::TlsImage;
+use sel4_resources::{ObjectAllocator, VSpaceMapper};
+use sel4_sync::{RawNotificationMutex, lock_api::Mutex};
+
+mod spawn;
+mod thread;
+
+pub use spawn::{Builder, JoinHandle, Panicked, spawn};
+pub use thread::{Thread, ThreadId, current, park, yield_now};
+
+const GRANULE_SIZE: usize = sel4::FrameObjectType::GRANULE.bytes();
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// [`Config::ipc_buffer_region`] has no room for another IPC buffer.
+    OutOfIpcBufferRegion,
+    /// [`Config::ipc_buffer_region`] is not granule-aligned.
+    MisalignedIpcBufferRegion,
+    Resources(sel4_resources::Error),
+    Sel4(sel4::Error),
+}
+
+impl From<sel4_resources::Error> for Error {
+    fn from(err: sel4_resources::Error) -> Self {
+        Self::Resources(err)
+    }
+}
+
+impl From<sel4::Error> for Error {
+    fn from(err: sel4::Error) -> Self {
+        Self::Sel4(err)
+    }
+}
+
+#[sel4::sel4_cfg_struct]
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Authority for spawned threads' priorities.
+    pub authority: sel4::cap::Tcb,
+    pub priority: sel4::Word,
+    /// Configures each spawned thread's scheduling context.
+    #[sel4_cfg(KERNEL_MCS)]
+    pub sched_control: sel4::cap::SchedControl,
+    #[sel4_cfg(KERNEL_MCS)]
+    pub budget: sel4::Time,
+    #[sel4_cfg(KERNEL_MCS)]
+    pub period: sel4::Time,
+    /// The caller's address space.
+    pub vspace: sel4::cap::VSpace,
+    /// A granule-aligned range of addresses in the caller's address space at which nothing is
+    /// mapped. Spawned threads' IPC buffers are mapped here.
+    pub ipc_buffer_region: Range<usize>,
+    /// The caller's image's TLS segment, with which each spawned thread's TLS region is
+    /// initialized.
+    pub tls_image: TlsImage,
+    /// The stack size of threads spawned without [`Builder::stack_size`]. Stacks have no guard
+    /// pages.
+    pub default_stack_size: usize,
+}
+
+/// Shared by all threads spawned from the thread which created it.
+pub struct Runtime {
+    config: Config,
+    state: Mutex<RawNotificationMutex, State>,
+}
+
+struct State {
+    allocator: ObjectAllocator,
+    mapper: VSpaceMapper,
+    free_ipc_buffer_addrs: Vec<usize>,
+    next_ipc_buffer_addr: usize,
+    reaper_queue: Vec<Garbage>,
+}
+
+/// Objects which have been released, but which have not yet been freed.
+enum Garbage {
+    ParkNotification(sel4::cap::Notification),
+    Objects(spawn::Objects),
+}
+
+impl Runtime {
+    /// Creates a runtime, which lives for the rest of the program, and registers the calling
+    /// thread with it as the thread named "main".
+    pub fn init(mut allocator: ObjectAllocator, config: Config) -> Result<&'static Self, Error> {
+        if !config.ipc_buffer_region.start.is_multiple_of(GRANULE_SIZE)
+            || !config.ipc_buffer_region.end.is_multiple_of(GRANULE_SIZE)
+        {
+            return Err(Error::MisalignedIpcBufferRegion);
+        }
+        let lock_nfn = allocator.allocate_fixed_sized::<sel4::cap_type::Notification>()?;
+        let park_nfn = allocator.allocate_fixed_sized::<sel4::cap_type::Notification>()?;
+        let state = State {
+            allocator,
+            mapper: VSpaceMapper::new(config.vspace),
+            free_ipc_buffer_addrs: Vec::new(),
+            next_ipc_buffer_addr: config.ipc_buffer_region.start,
+            reaper_queue: Vec::new(),
+        };
+        let runtime = Box::leak(Box::new(Self {
+            config,
+            state: Mutex::from_raw(RawNotificationMutex::new(lock_nfn), state),
+        }));
+        thread::set_current(Some(Thread::new(Some("main".into()), park_nfn, runtime)));
+        Ok(runtime)
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Provides access to the object allocator shared by this runtime's threads.
+    ///
+    /// `f` must not drop the last handle to a [`Thread`], which would deadlock.
+    pub fn with_allocator<R>(&self, f: impl FnOnce(&mut ObjectAllocator) -> R) -> R {
+        f(&mut self.state.lock().allocator)
+    }
+}
+
+impl State {
+    fn allocate_ipc_buffer_addr(&mut self, region: &Range<usize>) -> Option<usize> {
+        self.free_ipc_buffer_addrs.pop().or_else(|| {
+            let addr = self.next_ipc_buffer_addr;
+            (addr < region.end).then(|| {
+                self.next_ipc_buffer_addr += GRANULE_SIZE;
+                addr
+            })
+        })
+    }
+
+    fn free_ipc_buffer_addr(&mut self, addr: usize) {
+        self.free_ipc_buffer_addrs.push(addr);
+    }
+
+    fn release(&mut self, garbage: Garbage) {
+        self.reaper_queue.push(garbage);
+    }
+
+    /// Frees the objects in the reaper queue. Objects which cannot be freed are leaked, like those
+    /// of detached threads.
+    fn reap(&mut self) {
+        for garbage in mem::take(&mut self.reaper_queue) {
+            let _ = match garbage {
+                Garbage::ParkNotification(nfn) => self.allocator.free(nfn).map_err(Error::from),
+                Garbage::Objects(objects) => objects.free(self),
+            };
+        }
+    }
+}
@@ -0,0 +1,442 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cell::UnsafeCell;
+use core::mem::{self, MaybeUninit};
+use core::panic::AssertUnwindSafe;
+use core::sync::atomic::{Ordering, fence};
+
+use sel4_initialize_tls::HeapTlsReservation;
+
+use crate::thread::{Thread, current, set_current};
+use crate::{Config, Error, Garbage, Runtime, State};
+
+/// The error returned by [`JoinHandle::join`] when the joined thread panicked.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Panicked;
+
+/// Spawns a thread with the current thread's runtime and the default configuration, panicking if
+/// it cannot be spawned.
+pub fn spawn<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    Builder::new().spawn(f).unwrap()
+}
+
+/// Configures a thread to be spawned.
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    name: Option<String>,
+    stack_size: Option<usize>,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Names the thread, both for [`Thread::name`] and, with `DEBUG_BUILD`, for the kernel.
+    pub fn name(mut self, name: String) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Sets the size of the thread's stack, in bytes.
+    ///
+    /// Stacks are allocated on the heap, without guard pages, so a thread which overflows its
+    /// stack silently corrupts whatever precedes it on the heap. Choose sizes with a margin.
+    pub fn stack_size(mut self, size: usize) -> Self {
+        self.stack_size = Some(size);
+        self
+    }
+
+    /// Spawns a thread with the current thread's runtime.
+    pub fn spawn<F, T>(self, f: F) -> Result<JoinHandle<T>, Error>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        self.spawn_with(current().runtime(), f)
+    }
+
+    pub fn spawn_with<F, T>(self, runtime: &'static Runtime, f: F) -> Result<JoinHandle<T>, Error>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let config = runtime.config();
+
+        let (objects, park_nfn, cnode) = {
+            let mut state = runtime.state.lock();
+            state.reap();
+            let (objects, park_nfn) = Objects::allocate(&mut state, config)?;
+            (objects, park_nfn, state.allocator.slots().cnode())
+        };
+
+        let thread = Thread::new(self.name, park_nfn, runtime);
+
+        let resources = Resources {
+            objects,
+            stack: Stack::new(self.stack_size.unwrap_or(config.default_stack_size)),
+            tls: config.tls_image.initialize_on_heap(),
+        };
+
+        if let Err(err) = resources.configure(config, cnode, thread.name()) {
+            resources.release(runtime);
+            return Err(err);
+        }
+
+        let packet = Arc::new(Packet {
+            result: UnsafeCell::new(None),
+        });
+
+        let start = Box::new(Start {
+            thread: thread.clone(),
+            tcb: resources.objects.tcb,
+            ipc_buffer: resources.objects.ipc_buffer_addr as *mut sel4::IpcBuffer,
+            exit_nfn: resources.objects.exit_nfn,
+            main: Box::new({
+                let packet = packet.clone();
+                move || {
+                    let result = sel4_panicking::catch_unwind(AssertUnwindSafe(f));
+                    unsafe {
+                        *packet.result.get() = Some(result.map_err(|()| Panicked));
+                    }
+                }
+            }),
+        });
+
+        let start = Box::into_raw(start);
+        if let Err(err) = resources.start(start) {
+            drop(unsafe { Box::from_raw(start) });
+            resources.release(runtime);
+            return Err(err);
+        }
+
+        Ok(JoinHandle {
+            thread,
+            packet,
+            resources: Some(resources),
+        })
+    }
+}
+
+/// An owned permission to join a thread.
+///
+/// Dropping a `JoinHandle` detaches its thread, whose resources are then never freed.
+pub struct JoinHandle<T> {
+    thread: Thread,
+    packet: Arc<Packet<T>>,
+    resources: Option<Resources>,
+}
+
+impl<T> JoinHandle<T> {
+    pub fn thread(&self) -> &Thread {
+        &self.thread
+    }
+
+    /// Blocks until the thread has finished, frees its resources, and returns the result of its
+    /// closure.
+    pub fn join(mut self) -> Result<T, Panicked> {
+        let Resources {
+            objects,
+            stack,
+            tls,
+        } = self.resources.take().unwrap();
+        objects.exit_nfn.wait();
+        fence(Ordering::Acquire);
+        // The thread may not have suspended itself yet. If it cannot be suspended, then its stack
+        // and TLS region are leaked rather than freed while it may still be using them.
+        if objects.tcb.tcb_suspend().is_err() {
+            mem::forget((stack, tls));
+        }
+        let mut state = self.thread.runtime().state.lock();
+        state.release(Garbage::Objects(objects));
+        state.reap();
+        drop(state);
+        unsafe { (*self.packet.result.get()).take() }.unwrap()
+    }
+}
+
+impl<T> Drop for JoinHandle<T> {
+    fn drop(&mut self) {
+        // The thread may still be using its stack and TLS region.
+        if let Some(resources) = self.resources.take() {
+            mem::forget(resources);
+        }
+    }
+}
+
+struct Packet<T> {
+    result: UnsafeCell<Option<Result<T, Panicked>>>,
+}
+
+// The result is written by the spawned thread before it signals its exit notification, and read
+// by the joining thread only after waiting on it.
+unsafe impl<T: Send> Sync for Packet<T> {}
+
+#[sel4::sel4_cfg_struct]
+pub(crate) struct Objects {
+    tcb: sel4::cap::Tcb,
+    #[sel4_cfg(KERNEL_MCS)]
+    sched_context: sel4::cap::SchedContext,
+    ipc_buffer: sel4::cap::Granule,
+    ipc_buffer_addr: usize,
+    exit_nfn: sel4::cap::Notification,
+}
+
+impl Objects {
+    /// Also allocates the thread's park notification, which is freed along with its [`Thread`]
+    /// rather than with its other objects.
+    fn allocate(
+        state: &mut State,
+        config: &Config,
+    ) -> Result<(Self, sel4::cap::Notification), Error> {
+        let ipc_buffer_addr = state
+            .allocate_ipc_buffer_addr(&config.ipc_buffer_region)
+            .ok_or(Error::OutOfIpcBufferRegion)?;
+        let mut allocated = Vec::new();
+        let result = Self::allocate_inner(state, config, ipc_buffer_addr, &mut allocated);
+        if result.is_err() {
+            for cap in allocated.into_iter().rev() {
+                state.allocator.free(cap)?;
+            }
+            state.free_ipc_buffer_addr(ipc_buffer_addr);
+        }
+        result
+    }
+
+    fn allocate_inner(
+        state: &mut State,
+        config: &Config,
+        ipc_buffer_addr: usize,
+        allocated: &mut Vec<sel4::cap::Unspecified>,
+    ) -> Result<(Self, sel4::cap::Notification), Error> {
+        let allocator = &mut state.allocator;
+
+        let tcb = allocator.allocate_fixed_sized::<sel4::cap_type::Tcb>()?;
+        allocated.push(tcb.cast());
+
+        let ipc_buffer = allocator.allocate_fixed_sized::<sel4::cap_type::Granule>()?;
+        allocated.push(ipc_buffer.cast());
+        state.mapper.map_fixed_sized_frame(
+            allocator,
+            ipc_buffer,
+            ipc_buffer_addr,
+            sel4::CapRights::read_write(),
+            sel4::VmAttributes::default(),
+        )?;
+
+        let exit_nfn = allocator.allocate_fixed_sized::<sel4::cap_type::Notification>()?;
+        allocated.push(exit_nfn.cast());
+
+        let park_nfn = allocator.allocate_fixed_sized::<sel4::cap_type::Notification>()?;
+        allocated.push(park_nfn.cast());
+
+        sel4::sel4_cfg_if! {
+            if #[sel4_cfg(KERNEL_MCS)] {
+                let sched_context = allocator
+                    .allocate_variable_sized::<sel4::cap_type::SchedContext>(
+                        usize::try_from(sel4::sys::seL4_MinSchedContextBits).unwrap(),
+                    )?;
+                allocated.push(sched_context.cast());
+                config.sched_control.sched_control_configure_flags(
+                    sched_context,
+                    config.budget,
+                    config.period,
+                    0,
+                    0,
+                    0,
+                )?;
+            } else {
+                let _ = config;
+            }
+        }
+
+        sel4::sel4_cfg_if! {
+            if #[sel4_cfg(KERNEL_MCS)] {
+                let objects = Self {
+                    tcb,
+                    sched_context,
+                    ipc_buffer,
+                    ipc_buffer_addr,
+                    exit_nfn,
+                };
+            } else {
+                let objects = Self {
+                    tcb,
+                    ipc_buffer,
+                    ipc_buffer_addr,
+                    exit_nfn,
+                };
+            }
+        }
+
+        Ok((objects, park_nfn))
+    }
+
+    pub(crate) fn free(self, state: &mut State) -> Result<(), Error> {
+        state.allocator.free(self.tcb)?;
+        sel4::sel4_cfg_if! {
+            if #[sel4_cfg(KERNEL_MCS)] {
+                state.allocator.free(self.sched_context)?;
+            }
+        }
+        // Freeing the frame unmaps it.
+        state.allocator.free(self.ipc_buffer)?;
+        state.allocator.free(self.exit_nfn)?;
+        state.free_ipc_buffer_addr(self.ipc_buffer_addr);
+        Ok(())
+    }
+}
+
+struct Resources {
+    objects: Objects,
+    stack: Stack,
+    tls: HeapTlsReservation,
+}
+
+// The stack and TLS region are not accessed through this type.
+unsafe impl Send for Resources {}
+
+impl Resources {
+    fn configure(
+        &self,
+        config: &Config,
+        cnode: sel4::cap::CNode,
+        name: Option<&str>,
+    ) -> Result<(), Error> {
+        let tcb = self.objects.tcb;
+
+        sel4::sel4_cfg_if! {
+            if #[sel4_cfg(KERNEL_MCS)] {
+                tcb.tcb_configure(
+                    cnode,
+                    sel4::CNodeCapData::new(0, 0),
+                    config.vspace,
+                    self.objects.ipc_buffer_addr.try_into().unwrap(),
+                    self.objects.ipc_buffer,
+                )?;
+                tcb.tcb_set_sched_params(
+                    config.authority,
+                    0,
+                    config.priority,
+                    self.objects.sched_context,
+                    sel4::init_thread::slot::NULL.cap().cast(),
+                )?;
+            } else {
+                tcb.tcb_configure(
+                    sel4::init_thread::slot::NULL.cptr(),
+                    cnode,
+                    sel4::CNodeCapData::new(0, 0),
+                    config.vspace,
+                    self.objects.ipc_buffer_addr.try_into().unwrap(),
+                    self.objects.ipc_buffer,
+                )?;
+                tcb.tcb_set_sched_params(config.authority, 0, config.priority)?;
+            }
+        }
+
+        tcb.tcb_set_tls_base(self.tls.thread_pointer().try_into().unwrap())?;
+
+        sel4::sel4_cfg_if! {
+            if #[sel4_cfg(DEBUG_BUILD)] {
+                if let Some(name) = name {
+                    tcb.debug_name(name.as_bytes());
+                }
+            } else {
+                let _ = name;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn start(&self, start: *mut Start) -> Result<(), Error> {
+        let tcb = self.objects.tcb;
+        let mut ctx = tcb.tcb_read_all_registers(false)?;
+        *ctx.pc_mut() = (entry as *const () as usize).try_into().unwrap();
+        *ctx.sp_mut() = self.stack.initial_sp().try_into().unwrap();
+        *ctx.c_param_mut(0) = (start as usize).try_into().unwrap();
+        #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+        {
+            ctx.inner_mut().gp = riscv_get_gp();
+        }
+        tcb.tcb_write_all_registers(true, &mut ctx)?;
+        Ok(())
+    }
+
+    /// Frees the thread's stack and TLS region, and releases its objects to be freed the next
+    /// time a thread is spawned or joined.
+    fn release(self, runtime: &Runtime) {
+        runtime.state.lock().release(Garbage::Objects(self.objects));
+    }
+}
+
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+fn riscv_get_gp() -> sel4::Word {
+    let val: sel4::Word;
+    unsafe {
+        core::arch::asm!("mv {}, gp", out(reg) val);
+    }
+    val
+}
+
+#[repr(C, align(16))]
+struct StackUnit([u8; 16]);
+
+struct Stack(Box<[MaybeUninit<StackUnit>]>);
+
+impl Stack {
+    fn new(size: usize) -> Self {
+        Self(Box::new_uninit_slice(size.div_ceil(size_of::<StackUnit>())))
+    }
+
+    fn initial_sp(&self) -> usize {
+        let top = self.0.as_ptr_range().end as usize;
+        // Entering a function with a 16-byte aligned stack pointer is like entering it by a call
+        // from a function whose stack pointer was 16-byte aligned, except on x86_64, where such a
+        // call would have pushed a return address.
+        if cfg!(target_arch = "x86_64") {
+            top - size_of::<usize>()
+        } else {
+            top
+        }
+    }
+}
+
+struct Start {
+    thread: Thread,
+    tcb: sel4::cap::Tcb,
+    ipc_buffer: *mut sel4::IpcBuffer,
+    exit_nfn: sel4::cap::Notification,
+    main: Box<dyn FnOnce()>,
+}
+
+unsafe extern "C" fn entry(arg: sel4::Word) -> ! {
+    let start = unsafe { Box::from_raw(arg as usize as *mut Start) };
+    sel4::set_ipc_buffer(unsafe { start.ipc_buffer.as_mut() }.unwrap());
+    let Start {
+        thread,
+        tcb,
+        exit_nfn,
+        main,
+        ..
+    } = *start;
+    set_current(Some(thread));
+    main();
+    drop(set_current(None));
+    fence(Ordering::Release);
+    exit_nfn.signal();
+    tcb.tcb_suspend().unwrap();
+    unreachable!()
+}
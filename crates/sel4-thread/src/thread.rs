@@ -0,0 +1,125 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use core::cell::RefCell;
+use core::fmt;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{Garbage, Runtime};
+
+#[thread_local]
+static CURRENT: RefCell<Option<Thread>> = RefCell::new(None);
+
+pub(crate) fn set_current(thread: Option<Thread>) -> Option<Thread> {
+    CURRENT.replace(thread)
+}
+
+/// A handle to the current thread.
+///
+/// Panics if the current thread was neither spawned by this crate nor registered by
+/// [`Runtime::init`].
+pub fn current() -> Thread {
+    CURRENT
+        .borrow()
+        .clone()
+        .expect("current thread is not managed by sel4-thread")
+}
+
+/// Blocks until the current thread's token is made available by [`Thread::unpark`], consuming
+/// it.
+///
+/// As with `std::thread::park`, callers should not rely on there being no spurious wakeups.
+pub fn park() {
+    current().inner.park_nfn.wait();
+}
+
+pub fn yield_now() {
+    sel4::r#yield();
+}
+
+/// A unique identifier for a thread.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ThreadId(usize);
+
+impl ThreadId {
+    fn new() -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+
+    pub fn as_usize(&self) -> usize {
+        self.0
+    }
+}
+
+/// A handle to a thread.
+#[derive(Clone)]
+pub struct Thread {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    id: ThreadId,
+    name: Option<String>,
+    park_nfn: sel4::cap::Notification,
+    runtime: &'static Runtime,
+}
+
+impl Thread {
+    pub(crate) fn new(
+        name: Option<String>,
+        park_nfn: sel4::cap::Notification,
+        runtime: &'static Runtime,
+    ) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                id: ThreadId::new(),
+                name,
+                park_nfn,
+                runtime,
+            }),
+        }
+    }
+
+    pub fn id(&self) -> ThreadId {
+        self.inner.id
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.inner.name.as_deref()
+    }
+
+    /// The runtime with which this thread was spawned.
+    pub fn runtime(&self) -> &'static Runtime {
+        self.inner.runtime
+    }
+
+    /// Makes this thread's token available, waking it if it is parked.
+    pub fn unpark(&self) {
+        self.inner.park_nfn.signal();
+    }
+}
+
+impl fmt::Debug for Thread {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Thread")
+            .field("id", &self.id())
+            .field("name", &self.name())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        // Freeing the notification could fail, which could not be reported from here.
+        self.runtime
+            .state
+            .lock()
+            .release(Garbage::ParkNotification(self.park_nfn));
+    }
+}
@@ -19,9 +19,11 @@ mk rec {
       sel4-config
       sel4-sys
     ;
+    sel4-benchmark-log = localCrates.sel4-benchmark-log // { optional = true; };
   };
   features = {
     default = [ "state" ];
+    benchmark-log = [ "dep:sel4-benchmark-log" ];
     state = [];
     exposed-state = [];
     extern-state = [];
@@ -0,0 +1,11 @@
+#
+# Copyright 2026, Colias Group, LLC
+#
+# SPDX-License-Identifier: BSD-2-Clause
+#
+
+{ mk }:
+
+mk {
+  package.name = "sel4-benchmark-log";
+}
@@ -0,0 +1,16 @@
+#
+# Copyright 2026, Colias Group, LLC
+#
+# SPDX-License-Identifier: BSD-2-Clause
+#
+
+{ mk, localCrates, versions }:
+
+mk {
+  package.name = "sel4-benchmark-log-cli";
+  dependencies = {
+    inherit (versions) anyhow;
+    clap = { version = versions.clap; features = [ "derive" ]; };
+    inherit (localCrates) sel4-benchmark-log;
+  };
+}
@@ -0,0 +1,122 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+use clap::{Parser, ValueEnum};
+
+use sel4_benchmark_log::{EntryType, Histogram, kernel_entries, tracepoints};
+
+/// Prints a histogram of durations for each syscall, other kind of kernel entry, or tracepoint
+/// in a dumped kernel log buffer.
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Cli {
+    in_file_path: PathBuf,
+    #[arg(long, value_enum, default_value_t = Format::KernelEntries)]
+    format: Format,
+    /// Number of entries, as returned by `seL4_BenchmarkFinalizeLog` (defaults to as many as fit
+    /// in the file)
+    #[arg(short = 'n')]
+    num_entries: Option<usize>,
+    /// Size of a word on the target in bytes
+    #[arg(long, default_value_t = 8)]
+    word_size: usize,
+    /// Width of the longest bar
+    #[arg(long, default_value_t = 50)]
+    width: usize,
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone)]
+enum Format {
+    /// `BENCHMARK_TRACK_KERNEL_ENTRIES`
+    KernelEntries,
+    /// `BENCHMARK_TRACEPOINTS`
+    Tracepoints,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Key {
+    Syscall(i32),
+    Other(EntryType),
+    Tracepoint(u64),
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Syscall(syscall) => write!(f, "syscall {syscall}"),
+            Self::Other(entry_type) => write!(f, "{entry_type:?}"),
+            Self::Tracepoint(id) => write!(f, "tracepoint {id}"),
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    if ![4, 8].contains(&cli.word_size) {
+        bail!("unsupported word size: {}", cli.word_size);
+    }
+
+    let log = fs::read(&cli.in_file_path)?;
+    let n = cli.num_entries.unwrap_or(usize::MAX);
+
+    let mut histograms = BTreeMap::<Key, Histogram>::new();
+    match cli.format {
+        Format::KernelEntries => {
+            for entry in kernel_entries(&log, n, cli.word_size) {
+                let key = match entry.entry.syscall() {
+                    Some(syscall) => Key::Syscall(syscall),
+                    None => Key::Other(entry.entry.entry_type()),
+                };
+                histograms
+                    .entry(key)
+                    .or_default()
+                    .add(entry.duration.into());
+            }
+        }
+        Format::Tracepoints => {
+            for entry in tracepoints(&log, n, cli.word_size) {
+                histograms
+                    .entry(Key::Tracepoint(entry.id))
+                    .or_default()
+                    .add(entry.duration);
+            }
+        }
+    }
+
+    for (key, histogram) in &histograms {
+        print_histogram(key, histogram, cli.width);
+    }
+
+    Ok(())
+}
+
+fn print_histogram(key: &Key, histogram: &Histogram, width: usize) {
+    println!(
+        "{key}: count = {}, min = {}, mean = {:.1}, max = {} cycles",
+        histogram.count(),
+        histogram.min().unwrap(),
+        histogram.mean().unwrap(),
+        histogram.max().unwrap(),
+    );
+    let max_count = histogram.buckets().map(|(_, count)| count).max().unwrap();
+    for (range, count) in histogram.buckets() {
+        let bar = usize::try_from(count * u64::try_from(width).unwrap() / max_count).unwrap();
+        println!(
+            "  {:>20} ..= {:<20} {count:>10} {}",
+            range.start(),
+            range.end(),
+            "#".repeat(bar.max(1)),
+        );
+    }
+    println!();
+}
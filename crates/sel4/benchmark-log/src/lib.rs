@@ -0,0 +1,391 @@
+//
+// Copyright 2026, Colias Group, LLC
+//
+// SPDX-License-Identifier: BSD-2-Clause
+//
+
+//! Decoding of the data produced by the seL4 kernel's benchmarking features.
+//!
+//! This crate does not depend on the kernel configuration, so that it can be used both by seL4
+//! programs, through `sel4::benchmark` with the `benchmark-log` feature, and by host tools which
+//! process data dumped by them. All data is little-endian.
+
+#![no_std]
+
+use core::iter;
+use core::ops::RangeInclusive;
+
+/// Corresponds to `entry_type_t`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EntryType {
+    Interrupt,
+    UnknownSyscall,
+    UserLevelFault,
+    DebugFault,
+    VmFault,
+    Syscall,
+    UnimplementedDevice,
+    /// `Entry_VCPUFault` on ARM, and `Entry_VMExit` on x86.
+    Arch,
+}
+
+impl EntryType {
+    const ALL: [Self; 8] = [
+        Self::Interrupt,
+        Self::UnknownSyscall,
+        Self::UserLevelFault,
+        Self::DebugFault,
+        Self::VmFault,
+        Self::Syscall,
+        Self::UnimplementedDevice,
+        Self::Arch,
+    ];
+
+    fn from_path(path: u32) -> Self {
+        Self::ALL[usize::try_from(path).unwrap()]
+    }
+}
+
+/// Corresponds to `kernel_entry_t`.
+///
+/// The fields other than the path are only meaningful for some entry types. For syscalls, they
+/// describe the syscall. For other entry types, [`KernelEntry::word`] holds, for example, the
+/// IRQ number or the syscall number.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct KernelEntry(u32);
+
+impl KernelEntry {
+    pub const fn from_raw(raw: u32) -> Self {
+        Self(raw)
+    }
+
+    pub const fn raw(&self) -> u32 {
+        self.0
+    }
+
+    const fn bits(&self, start: u32, width: u32) -> u32 {
+        (self.0 >> start) & ((1 << width) - 1)
+    }
+
+    pub fn entry_type(&self) -> EntryType {
+        EntryType::from_path(self.bits(0, 3))
+    }
+
+    pub const fn core(&self) -> u32 {
+        self.bits(3, 3)
+    }
+
+    pub const fn word(&self) -> u32 {
+        self.bits(6, 26)
+    }
+
+    /// The kernel records the negation of the syscall number, which is itself negative.
+    pub const fn syscall_no(&self) -> u32 {
+        self.bits(3, 4)
+    }
+
+    /// The syscall number, as in `seL4_Syscall_ID`, for syscall entries.
+    pub fn syscall(&self) -> Option<i32> {
+        (self.entry_type() == EntryType::Syscall)
+            .then(|| -i32::try_from(self.syscall_no()).unwrap())
+    }
+
+    /// The type of the capability invoked by a syscall, as in `cap_tag_t`.
+    pub const fn cap_type(&self) -> u32 {
+        self.bits(7, 5)
+    }
+
+    pub const fn is_fastpath(&self) -> bool {
+        self.bits(12, 1) != 0
+    }
+
+    /// The label of the message info of a syscall.
+    pub const fn invocation_tag(&self) -> u32 {
+        self.bits(13, 19)
+    }
+}
+
+/// Corresponds to `benchmark_track_kernel_entry_t`, the entry type of the log buffer when the
+/// kernel is configured with `BENCHMARK_TRACK_KERNEL_ENTRIES`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TrackedKernelEntry {
+    /// The cycle count at entry.
+    pub start_time: u64,
+    /// The number of cycles spent in the kernel.
+    pub duration: u32,
+    pub entry: KernelEntry,
+}
+
+impl TrackedKernelEntry {
+    /// `kernel_entry_t` is a word of `word_size` bytes, which follows the other fields at the
+    /// next multiple of `word_size`.
+    pub const fn size(word_size: usize) -> usize {
+        Self::entry_offset(word_size) + word_size
+    }
+
+    const fn entry_offset(word_size: usize) -> usize {
+        12usize.next_multiple_of(word_size)
+    }
+
+    /// `bytes` must be of length [`TrackedKernelEntry::size`].
+    pub fn from_bytes(bytes: &[u8], word_size: usize) -> Self {
+        assert_eq!(bytes.len(), Self::size(word_size));
+        let (start_time, rest) = bytes.split_first_chunk().unwrap();
+        let (duration, _) = rest.split_first_chunk().unwrap();
+        // The fields of `kernel_entry_t` occupy the low 32 bits of the word, the rest of which is
+        // padding.
+        let (entry, _) = bytes[Self::entry_offset(word_size)..]
+            .split_first_chunk()
+            .unwrap();
+        Self {
+            start_time: u64::from_le_bytes(*start_time),
+            duration: u32::from_le_bytes(*duration),
+            entry: KernelEntry::from_raw(u32::from_le_bytes(*entry)),
+        }
+    }
+}
+
+/// Corresponds to `benchmark_tracepoint_log_entry_t`, the entry type of the log buffer when the
+/// kernel is configured with `BENCHMARK_TRACEPOINTS`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TracepointEntry {
+    pub id: u64,
+    /// The number of cycles between the start and end of the tracepoint.
+    pub duration: u64,
+}
+
+impl TracepointEntry {
+    /// The fields of this entry type are words of `word_size` bytes.
+    pub const fn size(word_size: usize) -> usize {
+        2 * word_size
+    }
+
+    /// `bytes` must be of length [`TracepointEntry::size`].
+    pub fn from_bytes(bytes: &[u8], word_size: usize) -> Self {
+        assert_eq!(bytes.len(), Self::size(word_size));
+        let (id, duration) = bytes.split_at(word_size);
+        Self {
+            id: word_from_le_bytes(id),
+            duration: word_from_le_bytes(duration),
+        }
+    }
+}
+
+fn word_from_le_bytes(bytes: &[u8]) -> u64 {
+    match bytes.len() {
+        4 => u32::from_le_bytes(bytes.try_into().unwrap()).into(),
+        8 => u64::from_le_bytes(bytes.try_into().unwrap()),
+        _ => panic!("unsupported word size: {}", bytes.len()),
+    }
+}
+
+/// Decodes the first `n` entries of a log buffer of [`TrackedKernelEntry`]s.
+pub fn kernel_entries(
+    log: &[u8],
+    n: usize,
+    word_size: usize,
+) -> impl Iterator<Item = TrackedKernelEntry> + '_ {
+    log.chunks_exact(TrackedKernelEntry::size(word_size))
+        .take(n)
+        .map(move |chunk| TrackedKernelEntry::from_bytes(chunk, word_size))
+}
+
+/// Decodes the first `n` entries of a log buffer of [`TracepointEntry`]s.
+pub fn tracepoints(
+    log: &[u8],
+    n: usize,
+    word_size: usize,
+) -> impl Iterator<Item = TracepointEntry> + '_ {
+    log.chunks_exact(TracepointEntry::size(word_size))
+        .take(n)
+        .map(move |chunk| TracepointEntry::from_bytes(chunk, word_size))
+}
+
+/// The values written to the start of the IPC buffer by `seL4_BenchmarkGetThreadUtilisation`,
+/// which are in cycles unless otherwise noted.
+///
+/// Corresponds to `benchmark_track_util_ipc_index`.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct ThreadUtilisation {
+    /// Time spent running the thread.
+    pub thread: u64,
+    /// Time spent in the idle thread of the current core.
+    pub idle_local_core: u64,
+    /// Time spent in the idle thread of the thread's core.
+    pub idle_thread_core: u64,
+    /// Time since utilisation tracking was last reset.
+    pub total: u64,
+    /// The number of times the thread has been scheduled.
+    pub schedules: u64,
+    /// Time spent in the kernel on behalf of the thread.
+    pub kernel: u64,
+    /// The number of times the thread has entered the kernel.
+    pub kernel_entries: u64,
+}
+
+impl ThreadUtilisation {
+    pub const SIZE: usize = 7 * 8;
+
+    pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Self {
+        let mut values = bytes
+            .chunks_exact(8)
+            .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()));
+        let mut next = || values.next().unwrap();
+        Self {
+            thread: next(),
+            idle_local_core: next(),
+            idle_thread_core: next(),
+            total: next(),
+            schedules: next(),
+            kernel: next(),
+            kernel_entries: next(),
+        }
+    }
+
+    /// The fraction of [`ThreadUtilisation::total`] spent running the thread.
+    pub fn fraction(&self) -> Option<f64> {
+        (self.total != 0).then(|| self.thread as f64 / self.total as f64)
+    }
+}
+
+/// Summary statistics of a set of durations, along with a histogram with power-of-two bucket
+/// boundaries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    /// Bucket `i` counts durations `d` with `d.ilog2() == i - 1`, with bucket `0` counting zeros.
+    buckets: [u64; 65],
+    count: u64,
+    sum: u128,
+    min: u64,
+    max: u64,
+}
+
+impl Histogram {
+    pub const fn new() -> Self {
+        Self {
+            buckets: [0; 65],
+            count: 0,
+            sum: 0,
+            min: u64::MAX,
+            max: 0,
+        }
+    }
+
+    pub fn add(&mut self, duration: u64) {
+        let bucket = match duration.checked_ilog2() {
+            Some(log) => usize::try_from(log).unwrap() + 1,
+            None => 0,
+        };
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.sum += u128::from(duration);
+        self.min = self.min.min(duration);
+        self.max = self.max.max(duration);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn min(&self) -> Option<u64> {
+        (self.count != 0).then_some(self.min)
+    }
+
+    pub fn max(&self) -> Option<u64> {
+        (self.count != 0).then_some(self.max)
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        (self.count != 0).then(|| self.sum as f64 / self.count as f64)
+    }
+
+    /// The non-empty buckets, as ranges of durations along with their counts.
+    pub fn buckets(&self) -> impl Iterator<Item = (RangeInclusive<u64>, u64)> + '_ {
+        iter::zip(0.., self.buckets.iter()).filter_map(|(i, count)| {
+            let range = match i {
+                0 => 0..=0,
+                _ => {
+                    let start = 1 << (i - 1);
+                    start..=(start - 1 + start)
+                }
+            };
+            (*count != 0).then_some((range, *count))
+        })
+    }
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // path = Entry_Syscall, syscall_no = 1, cap_type = 4, is_fastpath, invocation_tag = 3
+    const RAW_SYSCALL_ENTRY: u32 = 5 | (1 << 3) | (4 << 7) | (1 << 12) | (3 << 13);
+
+    fn check_syscall_entry(entry: &TrackedKernelEntry) {
+        assert_eq!(entry.start_time, 1234);
+        assert_eq!(entry.duration, 56);
+        assert_eq!(entry.entry.entry_type(), EntryType::Syscall);
+        assert_eq!(entry.entry.syscall(), Some(-1));
+        assert_eq!(entry.entry.cap_type(), 4);
+        assert!(entry.entry.is_fastpath());
+        assert_eq!(entry.entry.invocation_tag(), 3);
+    }
+
+    #[test]
+    fn kernel_entry_32() {
+        assert_eq!(TrackedKernelEntry::size(4), 16);
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&1234u64.to_le_bytes());
+        bytes[8..12].copy_from_slice(&56u32.to_le_bytes());
+        bytes[12..].copy_from_slice(&RAW_SYSCALL_ENTRY.to_le_bytes());
+        check_syscall_entry(&TrackedKernelEntry::from_bytes(&bytes, 4));
+    }
+
+    #[test]
+    fn kernel_entry_64() {
+        assert_eq!(TrackedKernelEntry::size(8), 24);
+        let mut bytes = [0; 24];
+        bytes[..8].copy_from_slice(&1234u64.to_le_bytes());
+        bytes[8..12].copy_from_slice(&56u32.to_le_bytes());
+        bytes[12..16].fill(0xff); // padding before the word-aligned kernel_entry_t
+        bytes[16..].copy_from_slice(&u64::from(RAW_SYSCALL_ENTRY).to_le_bytes());
+        check_syscall_entry(&TrackedKernelEntry::from_bytes(&bytes, 8));
+    }
+
+    #[test]
+    fn kernel_entries_64() {
+        let mut log = [0; 2 * 24 + 8];
+        for (i, chunk) in log.chunks_exact_mut(24).enumerate() {
+            chunk[..8].copy_from_slice(&u64::try_from(i).unwrap().to_le_bytes());
+            chunk[16..].copy_from_slice(&u64::from(RAW_SYSCALL_ENTRY).to_le_bytes());
+        }
+        assert!(
+            kernel_entries(&log, usize::MAX, 8)
+                .map(|entry| entry.start_time)
+                .eq([0, 1])
+        );
+        assert_eq!(kernel_entries(&log, 1, 8).count(), 1);
+    }
+
+    #[test]
+    fn histogram() {
+        let mut histogram = Histogram::new();
+        for duration in [0, 1, 5, 6, 7, 8] {
+            histogram.add(duration);
+        }
+        assert_eq!(histogram.min(), Some(0));
+        assert_eq!(histogram.max(), Some(8));
+        assert_eq!(histogram.mean(), Some(27.0 / 6.0));
+        assert!(
+            histogram
+                .buckets()
+                .eq([(0..=0, 1), (1..=1, 1), (4..=7, 3), (8..=15, 1)])
+        );
+    }
+}
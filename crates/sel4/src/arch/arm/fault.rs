@@ -88,10 +88,6 @@ impl Fault {
     }
 }
 
-impl CapFault {
-    // TODO
-}
-
 impl UnknownSyscall {
     pub fn fault_ip(&self) -> Word {
         self.inner().get_FaultIP()
@@ -111,7 +107,21 @@ impl UnknownSyscall {
 }
 
 impl UserException {
-    // TODO
+    pub fn fault_ip(&self) -> Word {
+        self.inner().get_FaultIP()
+    }
+
+    pub fn sp(&self) -> Word {
+        self.inner().get_Stack()
+    }
+
+    pub fn number(&self) -> Word {
+        self.inner().get_Number()
+    }
+
+    pub fn code(&self) -> Word {
+        self.inner().get_Code()
+    }
 }
 
 impl VmFault {
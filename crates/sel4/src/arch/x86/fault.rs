@@ -6,7 +6,7 @@
 
 use sel4_config::{sel4_cfg, sel4_cfg_enum, sel4_cfg_wrap_match};
 
-use crate::{declare_fault_newtype, sys};
+use crate::{Word, declare_fault_newtype, sys};
 
 declare_fault_newtype!(NullFault, seL4_Fault_NullFault);
 declare_fault_newtype!(CapFault, seL4_Fault_CapFault);
@@ -57,3 +57,53 @@ impl Fault {
         }
     }
 }
+
+impl UnknownSyscall {
+    pub fn fault_ip(&self) -> Word {
+        self.inner().get_FaultIP()
+    }
+
+    pub fn sp(&self) -> Word {
+        self.inner().get_RSP()
+    }
+
+    pub fn syscall(&self) -> Word {
+        self.inner().get_Syscall()
+    }
+}
+
+impl UserException {
+    pub fn fault_ip(&self) -> Word {
+        self.inner().get_FaultIP()
+    }
+
+    pub fn sp(&self) -> Word {
+        self.inner().get_Stack()
+    }
+
+    pub fn number(&self) -> Word {
+        self.inner().get_Number()
+    }
+
+    pub fn code(&self) -> Word {
+        self.inner().get_Code()
+    }
+}
+
+impl VmFault {
+    pub fn ip(&self) -> Word {
+        self.inner().get_IP()
+    }
+
+    pub fn addr(&self) -> Word {
+        self.inner().get_Addr()
+    }
+
+    pub fn is_prefetch(&self) -> bool {
+        self.inner().get_PrefetchFault() != 0
+    }
+
+    pub fn fsr(&self) -> Word {
+        self.inner().get_FSR()
+    }
+}
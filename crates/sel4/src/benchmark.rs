@@ -5,10 +5,18 @@
 // SPDX-License-Identifier: MIT
 //
 
+//! Benchmarking syscalls, and, with the `benchmark-log` feature, decoding of the data which they
+//! produce.
+
 use sel4_config::sel4_cfg_if;
 
 use crate::{sys, Error, Result, cap, Word};
 
+#[cfg(feature = "benchmark-log")]
+pub use sel4_benchmark_log::{
+    EntryType, Histogram, KernelEntry, ThreadUtilisation, TracepointEntry, TrackedKernelEntry,
+};
+
 pub fn benchmark_reset_log() -> Result<()> {
     Error::wrap(sys::seL4_BenchmarkResetLog())
 }
@@ -21,8 +29,33 @@ pub fn benchmark_set_log_buffer(frame: cap::UnspecifiedPage) -> Result<()> {
     Error::wrap(sys::seL4_BenchmarkSetLogBuffer(frame.bits()))
 }
 
+#[cfg(feature = "benchmark-log")]
+sel4_cfg_if! {
+    if #[sel4_cfg(BENCHMARK_TRACK_KERNEL_ENTRIES)] {
+        /// Decodes the first `n` entries of the log buffer `log`, where `n` is as returned by
+        /// [`benchmark_finalize_log`].
+        pub fn benchmark_log_entries(
+            log: &[u8],
+            n: Word,
+        ) -> impl Iterator<Item = TrackedKernelEntry> + '_ {
+            sel4_benchmark_log::kernel_entries(log, n.try_into().unwrap(), crate::WORD_SIZE / 8)
+        }
+    } else if #[sel4_cfg(BENCHMARK_TRACEPOINTS)] {
+        /// Decodes the first `n` entries of the log buffer `log`, where `n` is as returned by
+        /// [`benchmark_finalize_log`].
+        pub fn benchmark_log_entries(
+            log: &[u8],
+            n: Word,
+        ) -> impl Iterator<Item = TracepointEntry> + '_ {
+            sel4_benchmark_log::tracepoints(log, n.try_into().unwrap(), crate::WORD_SIZE / 8)
+        }
+    }
+}
+
 sel4_cfg_if! {
     if #[sel4_cfg(BENCHMARK_TRACK_UTILISATION)] {
+        /// The results are written to the IPC buffer, from which they can be read with
+        /// [`IpcBuffer::benchmark_thread_utilisation`].
         pub fn benchmark_get_thread_utilisation(tcb: cap::Tcb) {
             sys::seL4_BenchmarkGetThreadUtilisation(tcb.bits())
         }
@@ -31,6 +64,16 @@ sel4_cfg_if! {
             sys::seL4_BenchmarkResetThreadUtilisation(tcb.bits())
         }
 
+        #[cfg(feature = "benchmark-log")]
+        impl crate::IpcBuffer {
+            /// Decodes the results of [`benchmark_get_thread_utilisation`].
+            pub fn benchmark_thread_utilisation(&self) -> ThreadUtilisation {
+                ThreadUtilisation::from_bytes(
+                    self.msg_bytes()[..ThreadUtilisation::SIZE].try_into().unwrap(),
+                )
+            }
+        }
+
         sel4_cfg_if! {
             if #[sel4_cfg(DEBUG_BUILD)] {
                 pub fn benchmark_dump_all_thread_utilisation() {
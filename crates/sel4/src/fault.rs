@@ -6,7 +6,9 @@
 
 //! Fault types.
 
-use crate::{IpcBuffer, MessageInfo, sys};
+use sel4_config::sel4_cfg;
+
+use crate::{IpcBuffer, MessageInfo, Word, sys};
 
 pub use crate::arch::fault::*;
 
@@ -18,3 +20,32 @@ impl Fault {
         ))
     }
 }
+
+impl CapFault {
+    pub fn ip(&self) -> Word {
+        self.inner().get_IP()
+    }
+
+    pub fn addr(&self) -> Word {
+        self.inner().get_Addr()
+    }
+
+    pub fn in_recv_phase(&self) -> bool {
+        self.inner().get_InRecvPhase() != 0
+    }
+
+    pub fn lookup_failure_type(&self) -> Word {
+        self.inner().get_LookupFailureType()
+    }
+}
+
+#[sel4_cfg(KERNEL_MCS)]
+impl Timeout {
+    pub fn data(&self) -> Word {
+        self.inner().get_data()
+    }
+
+    pub fn consumed(&self) -> Word {
+        self.inner().get_consumed()
+    }
+}
@@ -173,7 +173,7 @@ sel4_cfg_if! {
 
 sel4_cfg_if! {
     if #[sel4_cfg(ENABLE_BENCHMARKS)] {
-        mod benchmark;
+        pub mod benchmark;
 
         pub use benchmark::{
             benchmark_reset_log,
@@ -181,6 +181,10 @@ sel4_cfg_if! {
             benchmark_set_log_buffer,
         };
 
+        #[cfg(feature = "benchmark-log")]
+        #[sel4_cfg(any(BENCHMARK_TRACK_KERNEL_ENTRIES, BENCHMARK_TRACEPOINTS))]
+        pub use benchmark::benchmark_log_entries;
+
         sel4_cfg_if! {
             if #[sel4_cfg(BENCHMARK_TRACK_UTILISATION)] {
                 pub use benchmark::{
@@ -17,8 +17,8 @@
 { cdl
 , fill
 , embedFrames ? true
-, deflate ? true
-, alloc ? true
+, codec ? "deflate" # one of "none", "deflate", "lz4", "zstd"
+, measurementLog ? null # name of a frame to receive the initializer's measurement log
 }:
 
 let
@@ -27,7 +27,8 @@ let
   };
 
   initializer = sel4-capdl-initializer.override {
-    inherit deflate alloc;
+    inherit codec;
+    measurement = measurementLog != null;
   };
 
 in lib.fix (self: runCommand "sel4-capdl-initializer-with-spec" {
@@ -53,6 +54,7 @@ in lib.fix (self: runCommand "sel4-capdl-initializer-with-spec" {
     -d ${fill} \
     --object-names-level 2 \
     ${lib.optionalString (!embedFrames) "--no-embed-frames"} \
-    ${lib.optionalString (!deflate) "--no-deflate"} \
+    --codec ${codec} \
+    ${lib.optionalString (measurementLog != null) "--measurement-log ${measurementLog}"} \
     -o $out
 '')
@@ -13,8 +13,8 @@
 , seL4Modifications
 , mkSeL4RustTargetTriple
 
-, deflate ? true
-, alloc ? true
+, codec ? "deflate"
+, measurement ? false
 }:
 
 mkTask {
@@ -26,7 +26,8 @@ mkTask {
   release = true;
 
   noDefaultFeatures = true;
-  features = lib.optional deflate "deflate" ++ lib.optional deflate "alloc";
+  features = lib.optional (codec != "none") codec ++ lib.optional (codec == "deflate") "alloc"
+    ++ lib.optional measurement "measurement";
 
   # layers = [
   #   crateUtils.defaultIntermediateLayer
@@ -152,15 +152,14 @@ self: with self;
     , specAttrs ? spec.specAttrs
     , embedFrames ? true
     , deflate ? true
-    , alloc ? true
+    , codec ? (if deflate then "deflate" else "none")
     , extraDebuggingLinks ? []
     }:
 
     lib.fix (self:
       mkCapDLInitializerWithSpec (spec.specAttrs // {
         inherit embedFrames;
-        inherit deflate;
-        inherit alloc;
+        inherit codec;
       }) // {
         inherit spec;
         debuggingLinks = [
@@ -88,6 +88,9 @@ in rec {
     tests.root-task.loader
     tests.root-task.config
     tests.root-task.tls
+    tests.root-task.thread
+    tests.root-task.resources
+    tests.root-task.supervisor
     tests.root-task.backtrace
     tests.root-task.panicking
     tests.root-task.c
@@ -161,6 +164,37 @@ in rec {
         };
       });
 
+      thread = maybe (haveFullRuntime && haveUnwindingSupport) (mkInstance {
+        rootTask = mkTask {
+          rootCrate = crates.tests-root-task-thread;
+          targetTriple = mkSeL4RustTargetTriple { unwind = true; }; # for the panicking thread
+          release = false;
+        };
+        extraPlatformArgs = lib.optionalAttrs canSimulate {
+          canAutomateSimply = true;
+        };
+      });
+
+      resources = maybe haveFullRuntime (mkInstance {
+        rootTask = mkTask {
+          rootCrate = crates.tests-root-task-resources;
+          release = false;
+        };
+        extraPlatformArgs = lib.optionalAttrs canSimulate {
+          canAutomateSimply = true;
+        };
+      });
+
+      supervisor = maybe haveFullRuntime (mkInstance {
+        rootTask = mkTask {
+          rootCrate = crates.tests-root-task-supervisor;
+          release = false;
+        };
+        extraPlatformArgs = lib.optionalAttrs canSimulate {
+          canAutomateSimply = true;
+        };
+      });
+
       reset =
         let
           origRootTask = mkTask {
@@ -408,7 +442,6 @@ in rec {
           };
           embedFrames = false;
           deflate = false;
-          alloc = false;
         };
         extraPlatformArgs = lib.optionalAttrs canSimulate {
           canAutomateSimply = true;